@@ -6,34 +6,34 @@
 //! Run with: cargo bench --bench lmsr_bench
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_decimal_macros::dec;
 
 use polymarket_lmsr_bot::domain::lmsr::LmsrModel;
-use polymarket_lmsr_bot::domain::kelly::KellyCriterion;
+use polymarket_lmsr_bot::domain::kelly::KellySizer;
 use polymarket_lmsr_bot::domain::fees::FeeCalculator;
 use polymarket_lmsr_bot::domain::bayesian::BayesianEstimator;
 
 /// Benchmark LMSR price computation for a binary market.
 fn bench_lmsr_price(c: &mut Criterion) {
-    let model = LmsrModel::new(100.0);
+    let model = LmsrModel::new(dec!(100.0));
 
     c.bench_function("lmsr_price_binary", |b| {
         b.iter(|| {
-            let _price = model.price(black_box(60.0), black_box(40.0));
+            let _price = model.price_yes(black_box(dec!(60.0)), black_box(dec!(40.0)));
         });
     });
 }
 
 /// Benchmark LMSR cost function (buy 10 shares).
 fn bench_lmsr_cost(c: &mut Criterion) {
-    let model = LmsrModel::new(100.0);
+    let model = LmsrModel::new(dec!(100.0));
 
     c.bench_function("lmsr_cost_10_shares", |b| {
         b.iter(|| {
-            let _cost = model.cost(
-                black_box(60.0),
-                black_box(40.0),
-                black_box(10.0),
-                black_box(true),
+            let _cost = model.cost_to_buy_yes(
+                black_box(dec!(60.0)),
+                black_box(dec!(40.0)),
+                black_box(dec!(10.0)),
             );
         });
     });
@@ -41,7 +41,7 @@ fn bench_lmsr_cost(c: &mut Criterion) {
 
 /// Benchmark Kelly criterion position sizing.
 fn bench_kelly_size(c: &mut Criterion) {
-    let kelly = KellyCriterion::new(0.25, 0.20);
+    let kelly = KellySizer::new(0.25);
 
     c.bench_function("kelly_quarter_size", |b| {
         b.iter(|| {
@@ -56,11 +56,11 @@ fn bench_kelly_size(c: &mut Criterion) {
 
 /// Benchmark fee calculation at various probability points.
 fn bench_fee_calc(c: &mut Criterion) {
-    let fee_calc = FeeCalculator::new_taker();
+    let fee_calc = FeeCalculator::standard();
 
     c.bench_function("fee_calc_taker", |b| {
         b.iter(|| {
-            let _fee = fee_calc.calculate_fee(black_box(0.50));
+            let _fee = fee_calc.taker_fee_f64(black_box(0.50), black_box(10.0));
         });
     });
 }
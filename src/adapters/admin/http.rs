@@ -0,0 +1,215 @@
+//! Admin HTTP Routes - Authenticated Halt / Resume / Cancel-All
+//!
+//! Mounted on the :9090 health server only when the secret backend
+//! has an `admin_token` (`ADMIN_TOKEN` with the env backend). Every
+//! request must carry `Authorization: Bearer <token>`.
+//!
+//! - `GET  /admin/status`     — trading state, halt record, breaker
+//! - `POST /admin/halt`       — `{"reason": "..."}`; halts + cancels all
+//! - `POST /admin/resume`     — lifts the halt
+//! - `POST /admin/cancel-all` — cancels resting orders, keeps quoting
+//! - `GET  /admin/wallet`     — gas, hot/cold allocation, queued sweep
+//! - `POST /admin/wallet/approve` — sends the queued sweep (live mode)
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::warn;
+
+use crate::ports::chain_client::ChainClient;
+use crate::ports::execution::OrderExecution;
+use crate::ports::repository::HaltSource;
+use crate::ports::secrets::Secret;
+use crate::usecases::trading_control::TradingControl;
+use crate::usecases::wallet_monitor::WalletMonitor;
+
+/// Shared state for admin handlers.
+struct AdminState<E: OrderExecution> {
+    /// Operator controls.
+    control: Arc<TradingControl<E>>,
+    /// Expected bearer token.
+    token: Secret,
+}
+
+/// Shared state for wallet handlers.
+struct WalletState<C: ChainClient, E: OrderExecution> {
+    /// Wallet monitor.
+    monitor: Arc<WalletMonitor<C, E>>,
+    /// Expected bearer token.
+    token: Secret,
+}
+
+/// Body of `POST /admin/halt`.
+#[derive(Debug, Deserialize)]
+struct HaltRequest {
+    /// Why trading is being halted (shown in status and logs).
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// Build the `/admin/*` router.
+pub fn admin_router<E: OrderExecution>(
+    control: Arc<TradingControl<E>>,
+    token: Secret,
+) -> Router {
+    let state = Arc::new(AdminState { control, token });
+    Router::new()
+        .route("/admin/status", get(status::<E>))
+        .route("/admin/halt", post(halt::<E>))
+        .route("/admin/resume", post(resume::<E>))
+        .route("/admin/cancel-all", post(cancel_all::<E>))
+        .with_state(state)
+}
+
+/// Build the `/admin/wallet*` router (merged into `admin_router`'s).
+pub fn wallet_router<C: ChainClient, E: OrderExecution>(
+    monitor: Arc<WalletMonitor<C, E>>,
+    token: Secret,
+) -> Router {
+    let state = Arc::new(WalletState { monitor, token });
+    Router::new()
+        .route("/admin/wallet", get(wallet_status::<C, E>))
+        .route("/admin/wallet/approve", post(approve_sweep::<C, E>))
+        .with_state(state)
+}
+
+/// `GET /admin/status`
+async fn status<E: OrderExecution>(
+    State(state): State<Arc<AdminState<E>>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(resp) = authorize(&headers, state.token.expose()) {
+        return resp;
+    }
+    Json(state.control.status().await).into_response()
+}
+
+/// `POST /admin/halt`
+async fn halt<E: OrderExecution>(
+    State(state): State<Arc<AdminState<E>>>,
+    headers: HeaderMap,
+    body: Option<Json<HaltRequest>>,
+) -> Response {
+    if let Err(resp) = authorize(&headers, state.token.expose()) {
+        return resp;
+    }
+    let reason = body
+        .and_then(|Json(b)| b.reason)
+        .filter(|r| !r.trim().is_empty())
+        .unwrap_or_else(|| "admin halt".to_string());
+
+    match state.control.halt(&reason, HaltSource::Admin).await {
+        Ok(cancelled) => Json(json!({
+            "halted": true,
+            "reason": reason,
+            "cancelled": cancelled,
+        }))
+        .into_response(),
+        Err(e) => (
+            StatusCode::BAD_GATEWAY,
+            Json(json!({
+                "halted": true,
+                "reason": reason,
+                "error": format!("cancel-all failed: {e}"),
+            })),
+        )
+            .into_response(),
+    }
+}
+
+/// `POST /admin/resume`
+async fn resume<E: OrderExecution>(
+    State(state): State<Arc<AdminState<E>>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(resp) = authorize(&headers, state.token.expose()) {
+        return resp;
+    }
+    let lifted = state.control.resume().await;
+    Json(json!({ "resumed": lifted.is_some(), "lifted": lifted })).into_response()
+}
+
+/// `POST /admin/cancel-all`
+async fn cancel_all<E: OrderExecution>(
+    State(state): State<Arc<AdminState<E>>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(resp) = authorize(&headers, state.token.expose()) {
+        return resp;
+    }
+    match state.control.cancel_all().await {
+        Ok(cancelled) => Json(json!({ "cancelled": cancelled })).into_response(),
+        Err(e) => (
+            StatusCode::BAD_GATEWAY,
+            Json(json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+/// `GET /admin/wallet`
+async fn wallet_status<C: ChainClient, E: OrderExecution>(
+    State(state): State<Arc<WalletState<C, E>>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(resp) = authorize(&headers, state.token.expose()) {
+        return resp;
+    }
+    Json(json!({ "status": state.monitor.status().await })).into_response()
+}
+
+/// `POST /admin/wallet/approve`
+async fn approve_sweep<C: ChainClient, E: OrderExecution>(
+    State(state): State<Arc<WalletState<C, E>>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(resp) = authorize(&headers, state.token.expose()) {
+        return resp;
+    }
+    match state.monitor.approve().await {
+        Ok(transfer) => Json(json!({
+            "swept": transfer.amount,
+            "tx_hash": transfer.tx_hash,
+        }))
+        .into_response(),
+        Err(e) => (
+            StatusCode::CONFLICT,
+            Json(json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+/// Check the bearer token; returns a 401 response on failure.
+#[allow(clippy::result_large_err)]
+fn authorize(headers: &HeaderMap, expected: &str) -> Result<(), Response> {
+    let provided = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or_default();
+
+    if token_matches(provided, expected) {
+        Ok(())
+    } else {
+        warn!("Rejected admin request with missing or invalid token");
+        Err(StatusCode::UNAUTHORIZED.into_response())
+    }
+}
+
+/// Constant-time token comparison (no early exit on first mismatch).
+fn token_matches(provided: &str, expected: &str) -> bool {
+    !expected.is_empty()
+        && provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
@@ -0,0 +1,87 @@
+//! Kill Switch - File-Based Trading Halt
+//!
+//! Polls for a kill file (default `data/KILL`). While the file
+//! exists trading is halted and resting orders are cancelled; the
+//! first line of the file, if any, is recorded as the halt reason.
+//! Removing the file resumes trading, but only if the halt came
+//! from the kill switch — an admin halt is never lifted by ops
+//! deleting a file.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+use tracing::{error, info, instrument, warn};
+
+use crate::ports::execution::OrderExecution;
+use crate::ports::repository::HaltSource;
+use crate::usecases::trading_control::TradingControl;
+
+/// Default halt reason when the kill file is empty.
+const DEFAULT_REASON: &str = "kill switch file present";
+
+/// Polls a kill file and halts/resumes trading accordingly.
+pub struct KillSwitch<E: OrderExecution> {
+    /// Operator controls.
+    control: Arc<TradingControl<E>>,
+    /// Path of the kill file.
+    path: PathBuf,
+    /// Poll interval.
+    interval: Duration,
+}
+
+impl<E: OrderExecution> KillSwitch<E> {
+    /// Create a new kill switch watching `path`.
+    pub fn new(control: Arc<TradingControl<E>>, path: impl Into<PathBuf>, interval: Duration) -> Self {
+        Self {
+            control,
+            path: path.into(),
+            interval,
+        }
+    }
+
+    /// Poll the kill file until shutdown.
+    #[instrument(skip(self, shutdown_rx), fields(path = %self.path.display()))]
+    pub async fn run(self, mut shutdown_rx: broadcast::Receiver<()>) {
+        info!("Kill switch armed");
+        let mut ticker = tokio::time::interval(self.interval);
+        loop {
+            tokio::select! {
+                biased;
+                _ = shutdown_rx.recv() => break,
+                _ = ticker.tick() => self.check().await,
+            }
+        }
+    }
+
+    /// Apply the current kill file state once.
+    pub async fn check(&self) {
+        let present = tokio::fs::try_exists(&self.path).await.unwrap_or(false);
+        let source = self.control.halt_source().await;
+
+        match (present, source) {
+            (true, None) => {
+                let reason = self.read_reason().await;
+                warn!(reason = %reason, "Kill file detected — halting trading");
+                if let Err(e) = self.control.halt(&reason, HaltSource::KillSwitch).await {
+                    error!(error = %e, "Kill switch halt could not cancel all orders");
+                }
+            }
+            (false, Some(HaltSource::KillSwitch)) => {
+                info!("Kill file removed — resuming trading");
+                self.control.resume().await;
+            }
+            _ => {}
+        }
+    }
+
+    /// First non-empty line of the kill file, or a default reason.
+    async fn read_reason(&self) -> String {
+        tokio::fs::read_to_string(&self.path)
+            .await
+            .ok()
+            .and_then(|s| s.lines().map(str::trim).find(|l| !l.is_empty()).map(String::from))
+            .unwrap_or_else(|| DEFAULT_REASON.to_string())
+    }
+}
@@ -0,0 +1,11 @@
+//! Admin Adapters - Operator Controls
+//!
+//! Drives `TradingControl` from outside the process:
+//! - `http`: Authenticated `/admin/*` routes merged into the :9090 server
+//! - `kill_switch`: File-based halt for ops (touch to halt, rm to resume)
+
+pub mod http;
+pub mod kill_switch;
+
+pub use http::{admin_router, wallet_router};
+pub use kill_switch::KillSwitch;
@@ -1,15 +1,18 @@
 //! CLOB Authentication — HMAC-SHA256 Request Signing
 //!
 //! Signs every CLOB API request using HMAC-SHA256 per the Polymarket
-//! CLOB specification. Credentials come from environment variables
-//! (POLY_API_KEY, POLY_API_SECRET, POLY_PASSPHRASE).
+//! CLOB specification. Credentials come from the secret backend
+//! (`SecretStore::clob_auth`) and stay wrapped in `Secret`, so the
+//! HMAC key and passphrase never show up in `Debug` output.
 
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use base64::Engine;
 
+use crate::ports::secrets::Secret;
+
 /// Thread-safe nonce generator: timestamp_seed + atomic counter.
 ///
 /// Guarantees unique nonces even for concurrent requests within
@@ -23,51 +26,44 @@ static NONCE_COUNTER: AtomicU64 = AtomicU64::new(0);
 /// Used by `ClobClient` to attach auth headers to requests.
 #[derive(Debug, Clone)]
 pub struct ClobCredentials {
-    /// API key from POLY_API_KEY env var.
+    /// API key (`poly_api_key`).
     pub api_key: String,
-    /// Passphrase from POLY_PASSPHRASE env var.
-    pub api_passphrase: String,
+    /// Passphrase (`poly_passphrase`); redacted in `Debug`.
+    pub api_passphrase: Secret,
 }
 
 /// CLOB API authentication handler.
 ///
-/// Manages API key, secret, and passphrase loaded from env vars.
+/// Manages API key, secret, and passphrase from the secret backend.
 /// Signs requests using HMAC-SHA256 as required by Polymarket CLOB.
 pub struct ClobAuth {
-    /// API key from POLY_API_KEY env var.
+    /// API key (`poly_api_key`).
     api_key: String,
-    /// API secret from POLY_API_SECRET env var (never sent in headers).
-    api_secret: String,
-    /// Passphrase from POLY_PASSPHRASE env var.
-    passphrase: String,
+    /// API secret (`poly_api_secret`, never sent in headers).
+    api_secret: Secret,
+    /// Passphrase (`poly_passphrase`).
+    passphrase: Secret,
     /// Timestamp seed set at construction for nonce generation.
     nonce_seed: u64,
 }
 
 impl ClobAuth {
-    /// Load credentials from environment variables.
+    /// Create from the three CLOB credentials.
     ///
-    /// Required env vars: POLY_API_KEY, POLY_API_SECRET, POLY_PASSPHRASE.
-    /// These MUST be set in `.env` (never committed to git).
-    pub fn from_env() -> Result<Self> {
-        let api_key = std::env::var("POLY_API_KEY")
-            .context("POLY_API_KEY not set")?;
-        let api_secret = std::env::var("POLY_API_SECRET")
-            .context("POLY_API_SECRET not set")?;
-        let passphrase = std::env::var("POLY_PASSPHRASE")
-            .context("POLY_PASSPHRASE not set")?;
-
+    /// Loaded by `SecretStore::clob_auth` (env, file or Vault backend);
+    /// never from config files.
+    pub fn new(api_key: &Secret, api_secret: Secret, passphrase: Secret) -> Self {
         let nonce_seed = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis() as u64;
 
-        Ok(Self {
-            api_key,
+        Self {
+            api_key: api_key.expose().to_string(),
             api_secret,
             passphrase,
             nonce_seed,
-        })
+        }
     }
 
     /// Get the API key for request headers.
@@ -77,7 +73,7 @@ impl ClobAuth {
 
     /// Get the passphrase for request headers.
     pub fn passphrase(&self) -> &str {
-        &self.passphrase
+        self.passphrase.expose()
     }
 
     /// Return non-secret credentials bundle for auth headers.
@@ -123,10 +119,10 @@ impl ClobAuth {
         path: &str,
         body: &str,
     ) -> String {
-        let message = format!("{}{}{}{}", timestamp, method, path, body);
+        let message = format!("{timestamp}{method}{path}{body}");
         let mac = hmac_sha256::HMAC::mac(
             message.as_bytes(),
-            self.api_secret.as_bytes(),
+            self.api_secret.expose().as_bytes(),
         );
         base64::engine::general_purpose::STANDARD.encode(mac)
     }
@@ -161,7 +157,7 @@ impl ClobAuth {
             self.api_key.clone(),
             timestamp,
             signature,
-            self.passphrase.clone(),
+            self.passphrase.expose().to_string(),
         )
     }
 }
@@ -10,7 +10,7 @@ use anyhow::{Context, Result};
 use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use tokio::sync::Semaphore;
 use tokio::time::sleep;
-use tracing::{debug, error, warn};
+use tracing::{debug, warn};
 
 use super::auth::ClobAuth;
 use super::types::RateLimitInfo;
@@ -142,11 +142,11 @@ impl ClobClient {
             if let Some(creds) = self.auth.credentials() {
                 req = req
                     .header("POLY_API_KEY", &creds.api_key)
-                    .header("POLY_PASSPHRASE", &creds.api_passphrase)
+                    .header("POLY_PASSPHRASE", sensitive_header(creds.api_passphrase.expose())?)
                     .header("POLY_TIMESTAMP", &timestamp);
 
                 if let Ok(sig) = self.auth.sign_request(&timestamp, method, path, body) {
-                    req = req.header("POLY_SIGNATURE", sig);
+                    req = req.header("POLY_SIGNATURE", sensitive_header(&sig)?);
                 }
             }
 
@@ -161,12 +161,10 @@ impl ClobClient {
                             warn!("Rate limited by CLOB API, backing off");
                             sleep(Duration::from_secs(2)).await;
                             last_error = Some(anyhow::anyhow!("Rate limited"));
-                            continue;
                         }
                         status if status.is_server_error() => {
                             warn!(status = %status, "Server error, retrying");
                             last_error = Some(anyhow::anyhow!("Server error: {status}"));
-                            continue;
                         }
                         status => {
                             let body = response.text().await.unwrap_or_default();
@@ -179,7 +177,6 @@ impl ClobClient {
                 Err(e) => {
                     warn!(error = %e, attempt, "Request failed");
                     last_error = Some(e.into());
-                    continue;
                 }
             }
         }
@@ -236,3 +233,11 @@ impl ClobClient {
         self.get("/time").await.is_ok()
     }
 }
+
+/// Header value flagged sensitive, so `Debug` on the request hides it.
+fn sensitive_header(value: &str) -> Result<reqwest::header::HeaderValue> {
+    let mut header = reqwest::header::HeaderValue::from_str(value)
+        .context("Credential is not a valid header value")?;
+    header.set_sensitive(true);
+    Ok(header)
+}
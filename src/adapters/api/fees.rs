@@ -0,0 +1,134 @@
+//! CLOB Fee Source — Fee Rates and Trade History
+//!
+//! Implements the `FeeSource` port on the shared `ClobClient`:
+//! - `GET /fee-rate?token_id=` for a token's taker `base_fee`
+//! - `GET /data/trades?after=&before=` (paginated) for the account's
+//!   fills; as taker a trade is one fill, as maker each of our resting
+//!   orders it matched (found by API key) is one fill
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tracing::debug;
+
+use super::client::ClobClient;
+use super::types::{FeeRateResponse, TradeInfo, TradesPage};
+use crate::domain::trade::TradeSide;
+use crate::ports::fees::{FeeSource, TradeFill};
+
+/// Cursor the CLOB returns after the last page.
+const END_CURSOR: &str = "LTE=";
+
+/// Upper bound on pages fetched per query.
+const MAX_PAGES: usize = 100;
+
+/// Fee source backed by the shared authenticated client.
+pub struct ClobFeeSource {
+    /// Shared CLOB client with auth + retry.
+    client: Arc<ClobClient>,
+}
+
+impl ClobFeeSource {
+    /// Create a fee source on the shared client.
+    pub fn new(client: Arc<ClobClient>) -> Self {
+        Self { client }
+    }
+
+    /// Our fills in one trade.
+    fn fills(&self, trade: &TradeInfo) -> Vec<TradeFill> {
+        let timestamp_ms = trade.match_time.parse::<u64>().unwrap_or(0) * 1000;
+        let fee_rate_bps = trade.fee_rate_bps.parse::<u32>().unwrap_or(0);
+
+        if trade.trader_side != "MAKER" {
+            return vec![TradeFill {
+                trade_id: trade.id.clone(),
+                token_id: trade.asset_id.clone(),
+                side: parse_side(&trade.side),
+                price: trade.price.parse().unwrap_or(0.0),
+                size: trade.size.parse().unwrap_or(0.0),
+                is_maker: false,
+                fee_rate_bps,
+                timestamp_ms,
+            }];
+        }
+
+        let api_key = self.client.auth().api_key();
+        trade
+            .maker_orders
+            .iter()
+            .filter(|o| o.owner == api_key)
+            .map(|o| TradeFill {
+                trade_id: format!("{}:{}", trade.id, o.order_id),
+                token_id: o.asset_id.clone(),
+                side: parse_side(&o.side),
+                price: o.price.parse().unwrap_or(0.0),
+                size: o.matched_amount.parse().unwrap_or(0.0),
+                is_maker: true,
+                fee_rate_bps,
+                timestamp_ms,
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl FeeSource for ClobFeeSource {
+    async fn fee_rate_bps(&self, token_id: &str) -> Result<u32> {
+        let path = format!("/fee-rate?token_id={token_id}");
+        let rate: FeeRateResponse = self
+            .client
+            .get(&path)
+            .await
+            .context("Failed to fetch fee rate")?
+            .json()
+            .await
+            .context("Failed to parse fee rate response")?;
+        Ok(rate.base_fee)
+    }
+
+    async fn trades(&self, from_ms: u64, to_ms: u64) -> Result<Vec<TradeFill>> {
+        let mut fills = Vec::new();
+        let mut cursor = String::new();
+
+        for _ in 0..MAX_PAGES {
+            let path = format!(
+                "/data/trades?after={}&before={}&next_cursor={cursor}",
+                from_ms / 1000,
+                to_ms.div_ceil(1000)
+            );
+            let page: TradesPage = self
+                .client
+                .get(&path)
+                .await
+                .context("Failed to fetch trades")?
+                .json()
+                .await
+                .context("Failed to parse trades response")?;
+
+            fills.extend(
+                page.data
+                    .iter()
+                    .flat_map(|t| self.fills(t))
+                    .filter(|f| (from_ms..to_ms).contains(&f.timestamp_ms)),
+            );
+
+            if page.next_cursor.is_empty() || page.next_cursor == END_CURSOR {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+
+        debug!(from_ms, to_ms, fills = fills.len(), "Trade history fetched");
+        Ok(fills)
+    }
+}
+
+/// Parse a CLOB side string.
+fn parse_side(side: &str) -> TradeSide {
+    if side.eq_ignore_ascii_case("SELL") {
+        TradeSide::Sell
+    } else {
+        TradeSide::Buy
+    }
+}
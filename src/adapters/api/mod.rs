@@ -1,10 +1,11 @@
 //! CLOB API Adapters — HTTP and WebSocket clients for Polymarket.
 //!
 //! Contains the authenticated HTTP client, order executor, order book
-//! queries, and request/response types.
+//! queries, fee rates / trade history, and request/response types.
 
 pub mod auth;
 pub mod client;
+pub mod fees;
 pub mod orderbook;
 pub mod orders;
 pub mod types;
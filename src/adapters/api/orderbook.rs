@@ -12,6 +12,9 @@ use tracing::{debug, warn};
 use super::client::ClobClient;
 use super::types::OrderBookResponse;
 
+/// Price levels as (price, size), best first.
+type Levels = Vec<(f64, f64)>;
+
 /// Order book adapter that wraps the CLOB HTTP client.
 pub struct OrderBookAdapter {
     client: Arc<ClobClient>,
@@ -28,7 +31,7 @@ impl OrderBookAdapter {
     /// Calls GET /book?token_id={token_id} on the CLOB API
     /// and parses the response into bid/ask levels.
     pub async fn get_order_book(&self, token_id: &str) -> Result<OrderBookResponse> {
-        let path = format!("/book?token_id={}", token_id);
+        let path = format!("/book?token_id={token_id}");
         let response = self
             .client
             .get(&path)
@@ -65,12 +68,11 @@ impl OrderBookAdapter {
             .first()
             .and_then(|l| l.price.parse::<f64>().ok());
 
-        match (best_bid, best_ask) {
-            (Some(bid), Some(ask)) => Ok(Some((bid + ask) / 2.0)),
-            _ => {
-                warn!(token_id, "Incomplete order book, cannot compute mid-price");
-                Ok(None)
-            }
+        if let (Some(bid), Some(ask)) = (best_bid, best_ask) {
+            Ok(Some(f64::midpoint(bid, ask)))
+        } else {
+            warn!(token_id, "Incomplete order book, cannot compute mid-price");
+            Ok(None)
         }
     }
 
@@ -108,7 +110,7 @@ impl OrderBookAdapter {
             let client = Arc::clone(&self.client);
             let tid = token_id.to_string();
             handles.push(tokio::spawn(async move {
-                let path = format!("/book?token_id={}", tid);
+                let path = format!("/book?token_id={tid}");
                 let response = client
                     .get(&path)
                     .await
@@ -134,8 +136,8 @@ impl OrderBookAdapter {
     /// Parse order book levels into sorted (price, size) tuples.
     ///
     /// Bids are sorted descending by price, asks ascending.
-    pub fn parse_levels(book: &OrderBookResponse) -> (Vec<(f64, f64)>, Vec<(f64, f64)>) {
-        let mut bids: Vec<(f64, f64)> = book
+    pub fn parse_levels(book: &OrderBookResponse) -> (Levels, Levels) {
+        let mut bids: Levels = book
             .bids
             .iter()
             .filter_map(|l| {
@@ -145,7 +147,7 @@ impl OrderBookAdapter {
             })
             .collect();
 
-        let mut asks: Vec<(f64, f64)> = book
+        let mut asks: Levels = book
             .asks
             .iter()
             .filter_map(|l| {
@@ -166,7 +168,7 @@ impl OrderBookAdapter {
     pub fn spread_bps(book: &OrderBookResponse) -> Option<f64> {
         let best_bid = book.bids.first()?.price.parse::<f64>().ok()?;
         let best_ask = book.asks.first()?.price.parse::<f64>().ok()?;
-        let mid = (best_bid + best_ask) / 2.0;
+        let mid = f64::midpoint(best_bid, best_ask);
         if mid > 0.0 {
             Some((best_ask - best_bid) / mid * 10_000.0)
         } else {
@@ -1,8 +1,14 @@
 //! CLOB Order Executor — Adapter for Order Placement
 //!
 //! Implements the `OrderExecution` port using the shared `ClobClient`
-//! for authenticated requests. All orders use maker-first strategy
-//! (GTC + post-only) for 0% fees + rebates.
+//! for authenticated requests. Quoting uses maker-first strategy
+//! (GTC + post-only) for 0% fees + rebates; FOK taker orders are
+//! only sent for complete-set arbitrage legs.
+//!
+//! Every order carries a fresh client-generated `salt`. With an
+//! `OrderJournal` attached, it is journaled (fsync'd) under that salt
+//! before it is sent, and its outcome after; a send that fails midway
+//! is resolved at once against the open orders.
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
@@ -10,14 +16,16 @@ use std::time::Instant;
 
 use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
-use tracing::{debug, info, instrument, warn};
+use tracing::{info, instrument, warn};
 
 use super::client::ClobClient;
 use super::orderbook::OrderBookAdapter;
-use crate::domain::trade::{Order, OrderId, TokenId, TradeSide};
+use crate::domain::time::now_ms;
+use crate::domain::trade::{Order, OrderId, OrderType, TokenId, TradeSide};
 use crate::ports::execution::{
     OrderCancellation, OrderExecution, OrderPlacement, OrderStatus,
 };
+use crate::ports::order_journal::{IntentOutcome, OrderJournal};
 
 /// Maximum slippage tolerance before skipping trade (checklist: 2%).
 const MAX_SLIPPAGE_PCT: f64 = 2.0;
@@ -35,6 +43,8 @@ pub struct ClobOrderExecutor {
     orders_this_minute: AtomicU32,
     /// Last minute reset timestamp.
     minute_reset: std::sync::Mutex<Instant>,
+    /// Write-ahead journal of order intents, if attached.
+    journal: Option<Arc<dyn OrderJournal>>,
 }
 
 impl ClobOrderExecutor {
@@ -45,9 +55,16 @@ impl ClobOrderExecutor {
             client,
             orders_this_minute: AtomicU32::new(0),
             minute_reset: std::sync::Mutex::new(Instant::now()),
+            journal: None,
         }
     }
 
+    /// Journal every order intent before it is sent.
+    pub fn with_journal(mut self, journal: Arc<dyn OrderJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
     /// Reset the per-minute order counter if a minute has elapsed.
     fn maybe_reset_minute_counter(&self) {
         let mut reset = self.minute_reset.lock().unwrap();
@@ -121,6 +138,54 @@ impl ClobOrderExecutor {
 
         Ok(avg_fill)
     }
+
+    /// POST an order payload and parse the CLOB's answer.
+    async fn send_order(&self, body: &str) -> Result<serde_json::Value> {
+        self.client
+            .post("/order", body)
+            .await
+            .context("Failed to place order via CLOB")?
+            .json()
+            .await
+            .context("Failed to parse place_order response")
+    }
+
+    /// Settle a journaled intent whose send failed midway.
+    ///
+    /// The order may have reached the CLOB anyway: if it is open under
+    /// our salt it counts as placed, otherwise the intent is marked
+    /// rejected and the send error returned (a FOK fill is caught by
+    /// the chain balance check). Should the lookup fail too, the intent
+    /// stays unresolved for the startup replay.
+    async fn resolve_failed_send(&self, salt: &str, error: anyhow::Error) -> Result<OrderPlacement> {
+        let Some(journal) = &self.journal else {
+            return Err(error);
+        };
+        let open = match self.get_open_orders().await {
+            Ok(open) => open,
+            Err(e) => {
+                warn!(salt, error = %e, "Open orders unavailable, intent left for replay");
+                return Err(error);
+            }
+        };
+
+        if let Some(live) = open.into_iter().find(|o| o.salt == salt) {
+            warn!(salt, order_id = %live.id, error = %error, "Send failed but the order is open");
+            self.orders_this_minute.fetch_add(1, Ordering::Relaxed);
+            journal
+                .record_outcome(salt, IntentOutcome::Placed { order_id: live.id.clone() })
+                .await?;
+            return Ok(OrderPlacement {
+                order_id: live.id,
+                accepted: true,
+                rejection_reason: None,
+                timestamp_ms: now_ms(),
+            });
+        }
+        let reason = format!("Send failed: {error:#}");
+        journal.record_outcome(salt, IntentOutcome::Rejected { reason }).await?;
+        Err(error)
+    }
 }
 
 #[async_trait]
@@ -156,32 +221,52 @@ impl OrderExecution for ClobOrderExecutor {
             });
         }
 
-        // Build order payload — all orders are GTC + post-only (maker)
+        // Build order payload — maker orders are GTC/GTD + post-only
         let side_str = match order.side {
             TradeSide::Buy => "BUY",
             TradeSide::Sell => "SELL",
         };
+        let (type_str, expiration) = match order.order_type {
+            OrderType::Gtc => ("GTC", 0),
+            OrderType::Gtd { expiration_secs } => {
+                ("GTD", order.timestamp_ms / 1000 + expiration_secs)
+            }
+            OrderType::Fok => ("FOK", 0),
+        };
 
+        // The salt identifies the order on the CLOB before (and without)
+        // a CLOB order ID; it is the journal's client ID
+        let salt = uuid::Uuid::new_v4().simple().to_string();
         let payload = serde_json::json!({
+            "salt": salt,
             "tokenID": order.token_id,
             "price": format!("{:.2}", order.price),
             "size": format!("{:.2}", order.size),
             "side": side_str,
-            "type": "GTC",
-            "postOnly": true,
+            "type": type_str,
+            "expiration": expiration,
+            "postOnly": order.post_only && order.order_type != OrderType::Fok,
         });
 
         let body = serde_json::to_string(&payload)?;
 
+        // Journal the intent before anything reaches the CLOB
+        if let Some(journal) = &self.journal {
+            let sent = Order {
+                salt: salt.clone(),
+                ..order.clone()
+            };
+            journal
+                .record_intent(&salt, &sent)
+                .await
+                .context("Failed to journal order intent, not sending")?;
+        }
+
         // Send via ClobClient (HMAC auth + retry handled internally)
-        let response: serde_json::Value = self
-            .client
-            .post("/order", &body)
-            .await
-            .context("Failed to place order via CLOB")?
-            .json()
-            .await
-            .context("Failed to parse place_order response")?;
+        let response: serde_json::Value = match self.send_order(&body).await {
+            Ok(response) => response,
+            Err(e) => return self.resolve_failed_send(&salt, e).await,
+        };
 
         // Parse response
         let order_id = response["orderID"]
@@ -190,16 +275,13 @@ impl OrderExecution for ClobOrderExecutor {
             .to_string();
 
         let accepted = response["success"].as_bool().unwrap_or(false);
-        let rejection_reason = if !accepted {
-            response["errorMsg"].as_str().map(String::from)
-        } else {
+        let rejection_reason = if accepted {
             None
+        } else {
+            response["errorMsg"].as_str().map(String::from)
         };
 
-        let timestamp_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
+        let timestamp_ms = now_ms();
 
         if accepted {
             self.orders_this_minute.fetch_add(1, Ordering::Relaxed);
@@ -208,6 +290,23 @@ impl OrderExecution for ClobOrderExecutor {
             warn!(reason = ?rejection_reason, "Order rejected by CLOB");
         }
 
+        if let Some(journal) = &self.journal {
+            let outcome = if accepted {
+                IntentOutcome::Placed {
+                    order_id: order_id.clone(),
+                }
+            } else {
+                IntentOutcome::Rejected {
+                    reason: rejection_reason
+                        .clone()
+                        .unwrap_or_else(|| "Rejected by CLOB".to_string()),
+                }
+            };
+            if let Err(e) = journal.record_outcome(&salt, outcome).await {
+                warn!(salt = %salt, error = %e, "Failed to journal order outcome");
+            }
+        }
+
         Ok(OrderPlacement {
             order_id,
             accepted,
@@ -235,10 +334,10 @@ impl OrderExecution for ClobOrderExecutor {
         Ok(OrderCancellation {
             order_id: order_id.clone(),
             success,
-            error: if !success {
-                response["errorMsg"].as_str().map(String::from)
-            } else {
+            error: if success {
                 None
+            } else {
+                response["errorMsg"].as_str().map(String::from)
             },
         })
     }
@@ -329,7 +428,15 @@ impl OrderExecution for ClobOrderExecutor {
                     filled_size: filled,
                 }
             }
-            "CANCELLED" => OrderStatus::Cancelled,
+            // Cancelled after a partial fill: report what matched
+            "CANCELLED" => match response["filled_size"].as_f64() {
+                Some(filled) if filled > 0.0 => OrderStatus::PartiallyFilled {
+                    filled_size: filled,
+                    remaining_size: 0.0,
+                    avg_price: response["avg_price"].as_f64().unwrap_or(0.0),
+                },
+                _ => OrderStatus::Cancelled,
+            },
             _ => OrderStatus::Unknown,
         };
 
@@ -386,11 +493,10 @@ impl OrderExecution for ClobOrderExecutor {
         let reset = self
             .minute_reset
             .lock()
-            .map(|r| {
+            .map_or(0, |r| {
                 let elapsed = r.elapsed().as_millis() as u64;
                 60_000u64.saturating_sub(elapsed)
-            })
-            .unwrap_or(0);
+            });
         (remaining, reset)
     }
 }
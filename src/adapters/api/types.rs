@@ -143,6 +143,63 @@ pub struct RateLimitInfo {
   pub limit: u32,
 }
 
+/// Fee rate of a token (`GET /fee-rate`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeeRateResponse {
+  /// Taker fee rate in basis points.
+  pub base_fee: u32,
+}
+
+/// One of the resting orders matched by a trade.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MakerOrderInfo {
+  /// CLOB order ID.
+  pub order_id: String,
+  /// API key of the order's owner.
+  pub owner: String,
+  /// Token ID.
+  pub asset_id: String,
+  /// "BUY" or "SELL".
+  pub side: String,
+  /// Size matched against this order.
+  pub matched_amount: String,
+  /// Order price.
+  pub price: String,
+}
+
+/// A trade from `GET /data/trades`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TradeInfo {
+  /// Trade ID.
+  pub id: String,
+  /// Token ID of the taker order.
+  pub asset_id: String,
+  /// Taker side: "BUY" or "SELL".
+  pub side: String,
+  /// Taker size.
+  pub size: String,
+  /// Taker price.
+  pub price: String,
+  /// Rate the taker was charged, in basis points.
+  pub fee_rate_bps: String,
+  /// Match time (Unix seconds).
+  pub match_time: String,
+  /// Our role in the trade: "TAKER" or "MAKER".
+  pub trader_side: String,
+  /// Resting orders the taker matched.
+  #[serde(default)]
+  pub maker_orders: Vec<MakerOrderInfo>,
+}
+
+/// A page of trades.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TradesPage {
+  /// Trades on this page.
+  pub data: Vec<TradeInfo>,
+  /// Cursor of the next page (`LTE=` after the last one).
+  pub next_cursor: String,
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
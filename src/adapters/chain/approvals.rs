@@ -1,34 +1,36 @@
 //! ERC-20 Approval Manager - Token Spend Allowances
 //!
-//! Handles one-time ERC-20 approvals at startup:
-//! - USDCe → CTF Exchange
-//! - USDCe → Neg Risk Adapter
-//! - CTF → Exchange contracts
+//! Handles one-time approvals at startup, before live trading:
+//! - USDCe → CTF Exchange (CLOB buys)
+//! - USDCe → Neg Risk Adapter (neg-risk splits)
+//! - USDCe → ConditionalTokens (`splitPosition` collateral)
+//! - CTF → CTF Exchange (CLOB sells, `setApprovalForAll`)
+//! - CTF → Neg Risk Adapter (`convertPositions`, neg-risk merges)
 //!
-//! Approvals use max uint256 to avoid repeated transactions.
-//! Only runs on-chain if allowance is below threshold.
+//! ERC-20 approvals use max uint256 to avoid repeated transactions.
+//! Only runs on-chain if an allowance is below threshold or an
+//! operator is not yet approved.
 
 use std::sync::Arc;
 
 use alloy::primitives::{Address, U256, Bytes, keccak256};
 use alloy::rpc::types::{TransactionInput, TransactionRequest};
-use alloy::providers::Provider;
 use anyhow::{Context, Result};
 use tracing::{info, instrument, warn};
 
 use super::contracts::ContractAddresses;
-use super::gas::GasOracle;
 use super::provider::PolygonProvider;
+use super::signer::TxSender;
 
 /// Manages ERC-20 token approvals for the bot's trading wallet.
 ///
 /// At startup, checks allowances and submits approval transactions
-/// only when needed. Uses EIP-1559 gas pricing from the GasOracle.
+/// only when needed, signed by the shared `TxSender`.
 pub struct ApprovalManager {
     /// Shared Polygon provider.
     provider: Arc<PolygonProvider>,
-    /// Gas oracle for fee estimation.
-    gas_oracle: Arc<GasOracle>,
+    /// Transaction signer (also the wallet being approved from).
+    sender: Arc<TxSender>,
     /// Contract addresses from config.
     addresses: ContractAddresses,
     /// Bot wallet address.
@@ -38,49 +40,116 @@ pub struct ApprovalManager {
 /// Minimum allowance threshold before re-approval (1M USDC in 6 decimals).
 const MIN_ALLOWANCE_THRESHOLD: u128 = 1_000_000 * 1_000_000;
 
+/// How a spender is approved on a token contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalKind {
+    /// ERC-20 `approve(spender, max)` on USDCe.
+    Allowance,
+    /// ERC-1155 `setApprovalForAll(operator, true)` on ConditionalTokens.
+    Operator,
+}
+
+/// One approval the trading wallet needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Approval {
+    /// Token name, for logs.
+    pub token_name: &'static str,
+    /// Token contract.
+    pub token: Address,
+    /// Spender name, for logs.
+    pub spender_name: &'static str,
+    /// Spender (or ERC-1155 operator) contract.
+    pub spender: Address,
+    /// ERC-20 allowance or ERC-1155 operator approval.
+    pub kind: ApprovalKind,
+}
+
+/// Every approval trading needs: collateral for the exchange, splits
+/// and neg-risk baskets, outcome tokens for sells and conversions.
+pub fn required_approvals(addresses: &ContractAddresses) -> Vec<Approval> {
+    let usdce = |spender_name, spender| Approval {
+        token_name: "USDCe",
+        token: addresses.usdce,
+        spender_name,
+        spender,
+        kind: ApprovalKind::Allowance,
+    };
+    let ctf = |spender_name, spender| Approval {
+        token_name: "CTF",
+        token: addresses.conditional_tokens,
+        spender_name,
+        spender,
+        kind: ApprovalKind::Operator,
+    };
+    vec![
+        usdce("CTF Exchange", addresses.ctf_exchange),
+        usdce("NegRisk Adapter", addresses.neg_risk_adapter),
+        usdce("ConditionalTokens", addresses.conditional_tokens),
+        ctf("CTF Exchange", addresses.ctf_exchange),
+        ctf("NegRisk Adapter", addresses.neg_risk_adapter),
+    ]
+}
+
+/// Left-pad an address to a 32-byte ABI word.
+fn address_word(address: Address) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(address.as_slice());
+    word
+}
+
 impl ApprovalManager {
-    /// Create a new approval manager.
+    /// Create a new approval manager for the sender's wallet.
     pub fn new(
         provider: Arc<PolygonProvider>,
-        gas_oracle: Arc<GasOracle>,
+        sender: Arc<TxSender>,
         addresses: ContractAddresses,
-    ) -> Result<Self> {
-        let wallet_str = std::env::var("WALLET_ADDRESS")
-            .context("WALLET_ADDRESS not set")?;
-        let wallet: Address = wallet_str.parse().context("Invalid WALLET_ADDRESS")?;
-
-        Ok(Self {
+    ) -> Self {
+        let wallet = sender.address();
+        Self {
             provider,
-            gas_oracle,
+            sender,
             addresses,
             wallet,
-        })
+        }
     }
 
     /// Ensure all required approvals are in place.
     ///
-    /// Checks current allowances and only submits approval tx if below
-    /// threshold. Called once at startup per the checklist.
+    /// Checks current allowances and operator approvals and only
+    /// submits a tx where one is missing. Called once at live startup,
+    /// before any order or split.
     #[instrument(skip(self))]
     pub async fn ensure_approvals(&self) -> Result<()> {
-        let spenders = [
-            ("CTF Exchange", self.addresses.ctf_exchange),
-            ("NegRisk Adapter", self.addresses.neg_risk_adapter),
-        ];
-
-        for (name, spender) in &spenders {
-            match self.check_and_approve(self.addresses.usdce, *spender).await {
+        for approval in required_approvals(&self.addresses) {
+            let result = match approval.kind {
+                ApprovalKind::Allowance => {
+                    self.check_and_approve(approval.token, approval.spender).await
+                }
+                ApprovalKind::Operator => {
+                    self.check_and_approve_operator(approval.token, approval.spender)
+                        .await
+                }
+            };
+            match result {
                 Ok(needed) => {
                     if needed {
-                        info!(token = "USDCe", spender = name, "Approval submitted");
+                        info!(
+                            token = approval.token_name,
+                            spender = approval.spender_name,
+                            "Approval submitted"
+                        );
                     } else {
-                        info!(token = "USDCe", spender = name, "Allowance sufficient");
+                        info!(
+                            token = approval.token_name,
+                            spender = approval.spender_name,
+                            "Allowance sufficient"
+                        );
                     }
                 }
                 Err(e) => {
                     warn!(
-                        token = "USDCe",
-                        spender = name,
+                        token = approval.token_name,
+                        spender = approval.spender_name,
                         error = %e,
                         "Approval check failed"
                     );
@@ -107,14 +176,9 @@ impl ApprovalManager {
         let mut calldata = Vec::with_capacity(68);
         calldata.extend_from_slice(selector);
 
-        // Left-pad owner address to 32 bytes
-        let mut owner_padded = [0u8; 32];
-        owner_padded[12..].copy_from_slice(self.wallet.as_slice());
-        calldata.extend_from_slice(&owner_padded);
-
-        // Left-pad spender address to 32 bytes
-        let mut spender_padded = [0u8; 32];
-        spender_padded[12..].copy_from_slice(spender.as_slice());
+        // Left-pad owner and spender addresses to 32 bytes
+        let spender_padded = address_word(spender);
+        calldata.extend_from_slice(&address_word(self.wallet));
         calldata.extend_from_slice(&spender_padded);
 
         // alloy 0.9: use TransactionInput::new() for the input field
@@ -140,13 +204,97 @@ impl ApprovalManager {
             "Submitting max approval"
         );
 
-        // In production: encode approve(spender, uint256.max) and sign+send tx
-        // with EIP-1559 fees from gas_oracle (tip 30 gwei, max 50 gwei)
-        let _gas_gwei = self.gas_oracle.current_gas_gwei().await?;
+        // approve(spender, type(uint256).max)
+        let selector = &keccak256(b"approve(address,uint256)")[..4];
+        let mut calldata = Vec::with_capacity(68);
+        calldata.extend_from_slice(selector);
+        calldata.extend_from_slice(&spender_padded);
+        calldata.extend_from_slice(&U256::MAX.to_be_bytes::<32>());
 
-        // TODO: Actual tx submission requires wallet signer integration
-        warn!("Approval tx submission requires wallet signer — placeholder");
+        let receipt = self
+            .sender
+            .send(token, Bytes::from(calldata))
+            .await
+            .context("Approval transaction failed")?;
+        info!(tx_hash = %receipt.transaction_hash, "Approval confirmed");
 
         Ok(true)
     }
+    /// Check ERC-1155 operator approval and set it if missing.
+    ///
+    /// Returns `true` if an approval transaction was submitted.
+    async fn check_and_approve_operator(
+        &self,
+        token: Address,
+        operator: Address,
+    ) -> Result<bool> {
+        // isApprovedForAll(owner, operator)
+        let selector = &keccak256(b"isApprovedForAll(address,address)")[..4];
+        let mut calldata = Vec::with_capacity(68);
+        calldata.extend_from_slice(selector);
+        calldata.extend_from_slice(&address_word(self.wallet));
+        calldata.extend_from_slice(&address_word(operator));
+
+        let tx = TransactionRequest::default()
+            .to(token)
+            .input(TransactionInput::new(Bytes::from(calldata)));
+        let result = self
+            .provider
+            .inner()
+            .call(&tx)
+            .await
+            .context("Operator approval query failed")?;
+
+        if !U256::from_be_slice(&result).is_zero() {
+            return Ok(false);
+        }
+
+        info!(operator = %operator, "Submitting operator approval");
+
+        // setApprovalForAll(operator, true)
+        let selector = &keccak256(b"setApprovalForAll(address,bool)")[..4];
+        let mut calldata = Vec::with_capacity(68);
+        calldata.extend_from_slice(selector);
+        calldata.extend_from_slice(&address_word(operator));
+        calldata.extend_from_slice(&U256::from(1).to_be_bytes::<32>());
+
+        let receipt = self
+            .sender
+            .send(token, Bytes::from(calldata))
+            .await
+            .context("Operator approval transaction failed")?;
+        info!(tx_hash = %receipt.transaction_hash, "Operator approval confirmed");
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_approvals_cover_exchange_splits_and_conversions() {
+        let addresses = ContractAddresses {
+            ctf_exchange: Address::repeat_byte(1),
+            usdce: Address::repeat_byte(2),
+            neg_risk_adapter: Address::repeat_byte(3),
+            conditional_tokens: Address::repeat_byte(4),
+        };
+
+        let set: Vec<_> = required_approvals(&addresses)
+            .into_iter()
+            .map(|a| (a.token, a.spender, a.kind))
+            .collect();
+        assert_eq!(
+            set,
+            vec![
+                (addresses.usdce, addresses.ctf_exchange, ApprovalKind::Allowance),
+                (addresses.usdce, addresses.neg_risk_adapter, ApprovalKind::Allowance),
+                (addresses.usdce, addresses.conditional_tokens, ApprovalKind::Allowance),
+                (addresses.conditional_tokens, addresses.ctf_exchange, ApprovalKind::Operator),
+                (addresses.conditional_tokens, addresses.neg_risk_adapter, ApprovalKind::Operator),
+            ]
+        );
+    }
 }
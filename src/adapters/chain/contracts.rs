@@ -4,20 +4,42 @@
 //! checking condition resolution, and executing batch redemptions
 //! via the CTF contract on Polygon. Contract addresses come from
 //! `config.toml` and are validated on-chain at startup.
+//!
+//! Redemptions, splits and merges are signed by `TxSender`: standard
+//! markets call ConditionalTokens `redeemPositions` / `splitPosition` /
+//! `mergePositions`,
+//! neg-risk markets go through the NegRiskAdapter. Recovered USDC is
+//! read from the `PayoutRedemption` event in the receipt.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use alloy::primitives::{Address, U256, Bytes, keccak256};
-use alloy::providers::Provider;
+use alloy::primitives::{Address, B256, Log, U256, Bytes, keccak256};
 use alloy::rpc::types::{TransactionInput, TransactionRequest};
 use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
 use tracing::{info, instrument, warn};
 
-use crate::ports::chain_client::{ChainClient, RedemptionResult, TokenBalance};
+use crate::config::{ContractConfig, MarketConfig};
+use crate::domain::trade::TokenId;
+use crate::ports::chain_client::{
+    ChainClient, ConditionPayouts, ConvertResult, MergeResult, RedemptionResult, SplitResult, TokenBalance,
+    TransferResult,
+};
 
+use super::approvals::ApprovalManager;
 use super::gas::GasOracle;
 use super::provider::PolygonProvider;
+use super::signer::{gas_cost_native, TxSender};
+
+/// Binary outcome index sets (YES = 0b01, NO = 0b10).
+const BINARY_INDEX_SETS: [u64; 2] = [1, 2];
+
+/// Maximum token IDs per `balanceOfBatch` call.
+const BALANCE_BATCH_SIZE: usize = 100;
+
+/// Outcome tokens share the collateral's 6 decimals.
+const TOKEN_DECIMALS_SCALE: f64 = 1_000_000.0;
 
 /// CTF and ERC-20 contract addresses loaded from config.
 #[derive(Debug, Clone)]
@@ -28,6 +50,36 @@ pub struct ContractAddresses {
     pub usdce: Address,
     /// Neg Risk CTF Exchange adapter (for batch redeem).
     pub neg_risk_adapter: Address,
+    /// ConditionalTokens contract (payout vectors, ERC-1155 balances).
+    pub conditional_tokens: Address,
+}
+
+impl ContractAddresses {
+    /// Parse addresses from `[contracts]` config.
+    pub fn from_config(config: &ContractConfig) -> Result<Self> {
+        let parse = |name: &str, value: &str| -> Result<Address> {
+            value
+                .parse()
+                .context(format!("Invalid address for {name}: {value}"))
+        };
+        Ok(Self {
+            ctf_exchange: parse("ctf_exchange", &config.ctf_exchange)?,
+            usdce: parse("usdce", &config.usdce)?,
+            neg_risk_adapter: parse("neg_risk_adapter", &config.neg_risk_adapter)?,
+            conditional_tokens: parse("conditional_tokens", &config.conditional_tokens)?,
+        })
+    }
+}
+
+/// How a market's positions are redeemed.
+#[derive(Debug, Clone)]
+struct RedeemTarget {
+    /// Redeem through the NegRiskAdapter instead of ConditionalTokens.
+    neg_risk: bool,
+    /// YES outcome token (ERC-1155 position ID).
+    yes_token_id: String,
+    /// NO outcome token (ERC-1155 position ID).
+    no_token_id: String,
 }
 
 /// Implements on-chain CTF operations via alloy-rs 0.9.
@@ -40,8 +92,12 @@ pub struct CtfContracts {
     provider: Arc<PolygonProvider>,
     /// Gas oracle for EIP-1559 fee estimation.
     gas_oracle: Arc<GasOracle>,
+    /// Transaction signer for redemptions.
+    sender: Arc<TxSender>,
     /// Contract addresses from config.
     addresses: ContractAddresses,
+    /// Condition ID → redemption route.
+    markets: HashMap<String, RedeemTarget>,
 }
 
 impl CtfContracts {
@@ -53,6 +109,7 @@ impl CtfContracts {
     pub async fn new(
         provider: Arc<PolygonProvider>,
         gas_oracle: Arc<GasOracle>,
+        sender: Arc<TxSender>,
         addresses: ContractAddresses,
     ) -> Result<Self> {
         let inner = provider.inner();
@@ -61,6 +118,7 @@ impl CtfContracts {
             ("CTF Exchange", addresses.ctf_exchange),
             ("USDCe", addresses.usdce),
             ("NegRisk Adapter", addresses.neg_risk_adapter),
+            ("ConditionalTokens", addresses.conditional_tokens),
         ] {
             let code = inner
                 .get_code_at(addr)
@@ -69,8 +127,7 @@ impl CtfContracts {
 
             if code.is_empty() {
                 bail!(
-                    "Contract {name} at {} has no deployed code — check config.toml",
-                    addr
+                    "Contract {name} at {addr} has no deployed code — check config.toml"
                 );
             }
 
@@ -80,10 +137,36 @@ impl CtfContracts {
         Ok(Self {
             provider,
             gas_oracle,
+            sender,
             addresses,
+            markets: HashMap::new(),
         })
     }
 
+    /// Register configured markets (neg-risk routing, token IDs).
+    pub fn with_markets(mut self, markets: &[MarketConfig]) -> Self {
+        for market in markets {
+            self.markets.insert(
+                market.condition_id.clone(),
+                RedeemTarget {
+                    neg_risk: market.neg_risk,
+                    yes_token_id: market.yes_token_id.clone(),
+                    no_token_id: market.no_token_id.clone(),
+                },
+            );
+        }
+        self
+    }
+
+    /// Approval manager for this wallet, sharing the tx signer (nonces).
+    pub fn approvals(&self) -> ApprovalManager {
+        ApprovalManager::new(
+            Arc::clone(&self.provider),
+            Arc::clone(&self.sender),
+            self.addresses.clone(),
+        )
+    }
+
     /// Build ABI-encoded calldata for `balanceOf(address)` (ERC-20).
     fn encode_balance_of(wallet: Address) -> Bytes {
         let selector = &keccak256(b"balanceOf(address)")[..4];
@@ -95,26 +178,384 @@ impl CtfContracts {
         calldata.extend_from_slice(&padded);
         Bytes::from(calldata)
     }
-}
 
-#[async_trait]
-impl ChainClient for CtfContracts {
-    #[instrument(skip(self))]
-    async fn usdc_balance(&self) -> Result<f64> {
-        let inner = self.provider.inner();
+    /// Build ABI-encoded calldata for `transfer(address,uint256)` (ERC-20).
+    fn encode_transfer(to: Address, amount: U256) -> Bytes {
+        Self::encode_call(
+            b"transfer(address,uint256)",
+            &[Self::address_word(to), Self::uint_word(amount)],
+        )
+    }
+
+    /// Build ABI-encoded calldata: 4-byte selector followed by 32-byte words.
+    fn encode_call(signature: &[u8], words: &[[u8; 32]]) -> Bytes {
+        let selector = &keccak256(signature)[..4];
+        let mut calldata = Vec::with_capacity(4 + 32 * words.len());
+        calldata.extend_from_slice(selector);
+        for word in words {
+            calldata.extend_from_slice(word);
+        }
+        Bytes::from(calldata)
+    }
+
+    /// Parse a `0x`-prefixed condition ID into a bytes32 word.
+    fn parse_condition_id(condition_id: &str) -> Result<B256> {
+        condition_id
+            .parse::<B256>()
+            .context(format!("Invalid condition ID: {condition_id}"))
+    }
+
+    /// Call a `uint256` view on the ConditionalTokens contract.
+    async fn call_conditional_tokens(&self, calldata: Bytes, method: &str) -> Result<u128> {
+        self.call_uint(self.addresses.conditional_tokens, "ConditionalTokens", calldata, method)
+            .await
+    }
+
+    /// Call a `uint256` view on `contract` (named `name` in errors).
+    async fn call_uint(
+        &self,
+        contract: Address,
+        name: &str,
+        calldata: Bytes,
+        method: &str,
+    ) -> Result<u128> {
+        let tx = TransactionRequest::default()
+            .to(contract)
+            .input(TransactionInput::new(calldata));
+
+        let result = self
+            .provider
+            .inner()
+            .call(&tx)
+            .await
+            .context(format!("{name} {method} call failed"))?;
+
+        if result.len() < 32 {
+            bail!("{name} {method} returned {} bytes", result.len());
+        }
+        let value = U256::from_be_slice(&result[..32]);
+        u128::try_from(value).context(format!("{method} value overflows u128"))
+    }
+
+    /// Left-pad an address to a 32-byte ABI word.
+    fn address_word(address: Address) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[12..].copy_from_slice(address.as_slice());
+        word
+    }
+
+    /// Encode a uint256 as a 32-byte ABI word.
+    fn uint_word(value: U256) -> [u8; 32] {
+        value.to_be_bytes::<32>()
+    }
+
+    /// Parse an outcome token ID (decimal or `0x` hex) into its ERC-1155 position ID.
+    fn parse_position_id(token_id: &str) -> Result<U256> {
+        let parsed = token_id.strip_prefix("0x").map_or_else(
+            || U256::from_str_radix(token_id, 10),
+            |hex| U256::from_str_radix(hex, 16),
+        );
+        parsed.context(format!("Invalid token ID: {token_id}"))
+    }
+
+    /// Calldata for ConditionalTokens
+    /// `redeemPositions(address,bytes32,bytes32,uint256[])` with a
+    /// zero parent collection.
+    fn encode_redeem_positions(collateral: Address, condition_id: B256, index_sets: &[u64]) -> Bytes {
+        let mut words = vec![
+            Self::address_word(collateral),
+            [0u8; 32],
+            condition_id.0,
+            // Offset of the dynamic array: after 4 head words
+            Self::uint_word(U256::from(4 * 32)),
+            Self::uint_word(U256::from(index_sets.len())),
+        ];
+        words.extend(index_sets.iter().map(|&i| Self::uint_word(U256::from(i))));
+        Self::encode_call(b"redeemPositions(address,bytes32,bytes32,uint256[])", &words)
+    }
+
+    /// Calldata for NegRiskAdapter `redeemPositions(bytes32,uint256[])`
+    /// with the YES/NO amounts held.
+    fn encode_neg_risk_redeem(condition_id: B256, amounts: &[U256]) -> Bytes {
+        let mut words = vec![
+            condition_id.0,
+            // Offset of the dynamic array: after 2 head words
+            Self::uint_word(U256::from(2 * 32)),
+            Self::uint_word(U256::from(amounts.len())),
+        ];
+        words.extend(amounts.iter().map(|&a| Self::uint_word(a)));
+        Self::encode_call(b"redeemPositions(bytes32,uint256[])", &words)
+    }
+
+    /// Calldata for ConditionalTokens
+    /// `mergePositions(address,bytes32,bytes32,uint256[],uint256)` over
+    /// the binary YES/NO partition with a zero parent collection.
+    fn encode_merge_positions(collateral: Address, condition_id: B256, amount: U256) -> Bytes {
+        Self::encode_partition_call(
+            b"mergePositions(address,bytes32,bytes32,uint256[],uint256)",
+            collateral,
+            condition_id,
+            amount,
+        )
+    }
 
-        let wallet_str = std::env::var("WALLET_ADDRESS")
-            .context("WALLET_ADDRESS not set")?;
-        let wallet_addr: Address = wallet_str.parse().context("Invalid wallet address")?;
+    /// Calldata for ConditionalTokens
+    /// `splitPosition(address,bytes32,bytes32,uint256[],uint256)`.
+    fn encode_split_position(collateral: Address, condition_id: B256, amount: U256) -> Bytes {
+        Self::encode_partition_call(
+            b"splitPosition(address,bytes32,bytes32,uint256[],uint256)",
+            collateral,
+            condition_id,
+            amount,
+        )
+    }
+
+    /// Shared `(collateral, parent, condition, partition, amount)` layout
+    /// of `splitPosition` / `mergePositions`.
+    fn encode_partition_call(
+        signature: &[u8],
+        collateral: Address,
+        condition_id: B256,
+        amount: U256,
+    ) -> Bytes {
+        let mut words = vec![
+            Self::address_word(collateral),
+            [0u8; 32],
+            condition_id.0,
+            // Offset of the partition array: after 5 head words
+            Self::uint_word(U256::from(5 * 32)),
+            Self::uint_word(amount),
+            Self::uint_word(U256::from(BINARY_INDEX_SETS.len())),
+        ];
+        words.extend(BINARY_INDEX_SETS.iter().map(|&i| Self::uint_word(U256::from(i))));
+        Self::encode_call(signature, &words)
+    }
+
+    /// Calldata for NegRiskAdapter `mergePositions(bytes32,uint256)`.
+    fn encode_neg_risk_merge(condition_id: B256, amount: U256) -> Bytes {
+        Self::encode_call(
+            b"mergePositions(bytes32,uint256)",
+            &[condition_id.0, Self::uint_word(amount)],
+        )
+    }
+
+    /// Calldata for NegRiskAdapter `splitPosition(bytes32,uint256)`.
+    fn encode_neg_risk_split(condition_id: B256, amount: U256) -> Bytes {
+        Self::encode_call(
+            b"splitPosition(bytes32,uint256)",
+            &[condition_id.0, Self::uint_word(amount)],
+        )
+    }
+
+    /// Calldata for NegRiskAdapter `convertPositions(bytes32,uint256,uint256)`.
+    fn encode_neg_risk_convert(market_id: B256, index_set: U256, amount: U256) -> Bytes {
+        Self::encode_call(
+            b"convertPositions(bytes32,uint256,uint256)",
+            &[market_id.0, Self::uint_word(index_set), Self::uint_word(amount)],
+        )
+    }
+
+    /// Bitmask of neg-risk question indices (bit i = question i).
+    fn question_index_set(question_indices: &[u32]) -> Result<U256> {
+        question_indices.iter().try_fold(U256::ZERO, |set, &i| {
+            if i >= 256 {
+                bail!("Question index {i} out of range");
+            }
+            Ok(set | (U256::from(1u8) << i as usize))
+        })
+    }
+
+    /// Target and calldata to split (`split = true`) or merge complete
+    /// sets of a condition, routed through the NegRiskAdapter when the
+    /// market is neg-risk.
+    fn complete_set_call(
+        &self,
+        condition_id: &str,
+        amount_raw: u128,
+        split: bool,
+    ) -> Result<(Address, Bytes)> {
+        let id = Self::parse_condition_id(condition_id)?;
+        let amount = U256::from(amount_raw);
+        let neg_risk = self.markets.get(condition_id).is_some_and(|t| t.neg_risk);
+        Ok(match (neg_risk, split) {
+            (true, true) => (self.addresses.neg_risk_adapter, Self::encode_neg_risk_split(id, amount)),
+            (true, false) => (self.addresses.neg_risk_adapter, Self::encode_neg_risk_merge(id, amount)),
+            (false, true) => (
+                self.addresses.conditional_tokens,
+                Self::encode_split_position(self.addresses.usdce, id, amount),
+            ),
+            (false, false) => (
+                self.addresses.conditional_tokens,
+                Self::encode_merge_positions(self.addresses.usdce, id, amount),
+            ),
+        })
+    }
+
+    /// Fail if gas is above the on-chain operation threshold.
+    async fn ensure_gas_acceptable(&self, operation: &str) -> Result<f64> {
+        let gas_gwei = self.gas_oracle.current_gas_gwei().await?;
+        let threshold = self.gas_oracle.redeem_threshold_gwei();
+        if gas_gwei > threshold {
+            warn!(gas_gwei, threshold, operation, "Gas too high");
+            bail!("Gas price {gas_gwei} gwei exceeds {threshold} gwei threshold");
+        }
+        Ok(gas_gwei)
+    }
+
+    /// Sum the payout of `PayoutRedemption` events emitted for `redeemer`.
+    ///
+    /// Handles both the ConditionalTokens event (payout is the third
+    /// data word) and the NegRiskAdapter event (second data word).
+    /// Events with another redeemer — e.g. the adapter redeeming on
+    /// our behalf — are ignored to avoid double counting.
+    fn decode_payout_redemption<'a>(logs: impl IntoIterator<Item = &'a Log>, redeemer: Address) -> U256 {
+        let ctf_topic = keccak256(b"PayoutRedemption(address,address,bytes32,bytes32,uint256[],uint256)");
+        let adapter_topic = keccak256(b"PayoutRedemption(address,bytes32,uint256[],uint256)");
+        let redeemer_topic = B256::from(Self::address_word(redeemer));
+
+        logs.into_iter()
+            .filter_map(|log| {
+                let topics = log.topics();
+                if topics.get(1) != Some(&redeemer_topic) {
+                    return None;
+                }
+                let word = match topics.first() {
+                    Some(t) if *t == ctf_topic => 2,
+                    Some(t) if *t == adapter_topic => 1,
+                    _ => return None,
+                };
+                let data = &log.data.data;
+                data.get(word * 32..(word + 1) * 32).map(U256::from_be_slice)
+            })
+            .fold(U256::ZERO, U256::saturating_add)
+    }
+
+    /// Calldata for ERC-1155 `balanceOfBatch(address[],uint256[])`
+    /// with the same owner repeated for every ID.
+    fn encode_balance_of_batch(owner: Address, position_ids: &[U256]) -> Bytes {
+        let n = position_ids.len();
+        let owner_word = Self::address_word(owner);
+        let mut words = Vec::with_capacity(4 + 2 * n);
+        // Offsets of the two dynamic arrays, measured from the head
+        words.push(Self::uint_word(U256::from(2 * 32)));
+        words.push(Self::uint_word(U256::from((3 + n) * 32)));
+        words.push(Self::uint_word(U256::from(n)));
+        words.extend(std::iter::repeat_n(owner_word, n));
+        words.push(Self::uint_word(U256::from(n)));
+        words.extend(position_ids.iter().map(|&id| Self::uint_word(id)));
+        Self::encode_call(b"balanceOfBatch(address[],uint256[])", &words)
+    }
+
+    /// Decode an ABI-encoded `uint256[]` return value.
+    fn decode_uint_array(data: &[u8]) -> Result<Vec<u128>> {
+        let word = |i: usize| -> Result<U256> {
+            data.get(i..i + 32)
+                .map(U256::from_be_slice)
+                .context("Truncated uint256[] return data")
+        };
+        let offset: usize = word(0)?.try_into().context("Array offset overflows usize")?;
+        let len: usize = word(offset)?.try_into().context("Array length overflows usize")?;
+        (0..len)
+            .map(|i| {
+                let value = word(offset + 32 * (i + 1))?;
+                u128::try_from(value).context("Balance overflows u128")
+            })
+            .collect()
+    }
 
-        let calldata = Self::encode_balance_of(wallet_addr);
+    /// Scale a raw outcome token balance to whole tokens.
+    fn token_balance_from_raw(token_id: &str, balance_raw: u128) -> TokenBalance {
+        TokenBalance {
+            token_id: token_id.to_string(),
+            balance_raw,
+            balance: balance_raw as f64 / TOKEN_DECIMALS_SCALE,
+        }
+    }
+
+    /// ERC-1155 `balanceOf(address,uint256)` on ConditionalTokens.
+    async fn position_balance(&self, owner: Address, position_id: U256) -> Result<u128> {
+        let calldata = Self::encode_call(
+            b"balanceOf(address,uint256)",
+            &[Self::address_word(owner), Self::uint_word(position_id)],
+        );
+        self.call_conditional_tokens(calldata, "balanceOf").await
+    }
+
+    /// Build the redemption call for one condition.
+    ///
+    /// Returns `None` for neg-risk markets where nothing is held.
+    async fn redemption_call(&self, condition_id: &str) -> Result<Option<(Address, Bytes)>> {
+        let id = Self::parse_condition_id(condition_id)?;
+        let Some(target) = self.markets.get(condition_id).filter(|t| t.neg_risk) else {
+            return Ok(Some((
+                self.addresses.conditional_tokens,
+                Self::encode_redeem_positions(self.addresses.usdce, id, &BINARY_INDEX_SETS),
+            )));
+        };
+
+        let owner = self.sender.address();
+        let yes = self
+            .position_balance(owner, Self::parse_position_id(&target.yes_token_id)?)
+            .await?;
+        let no = self
+            .position_balance(owner, Self::parse_position_id(&target.no_token_id)?)
+            .await?;
+        if yes == 0 && no == 0 {
+            return Ok(None);
+        }
+        Ok(Some((
+            self.addresses.neg_risk_adapter,
+            Self::encode_neg_risk_redeem(id, &[U256::from(yes), U256::from(no)]),
+        )))
+    }
+
+    /// `payoutDenominator(bytes32)` — zero until the oracle reports.
+    async fn payout_denominator(&self, condition_id: B256) -> Result<u128> {
+        let calldata = Self::encode_call(b"payoutDenominator(bytes32)", &[condition_id.0]);
+        self.call_conditional_tokens(calldata, "payoutDenominator").await
+    }
+
+    /// `getOutcomeSlotCount(bytes32)` — zero if the condition was never prepared.
+    async fn outcome_slot_count(&self, condition_id: B256) -> Result<u128> {
+        let calldata = Self::encode_call(b"getOutcomeSlotCount(bytes32)", &[condition_id.0]);
+        self.call_conditional_tokens(calldata, "getOutcomeSlotCount").await
+    }
+
+    /// `payoutNumerators(bytes32, uint256)` for one outcome slot.
+    async fn payout_numerator(&self, condition_id: B256, index: u128) -> Result<u128> {
+        let index = U256::from(index).to_be_bytes::<32>();
+        let calldata = Self::encode_call(
+            b"payoutNumerators(bytes32,uint256)",
+            &[condition_id.0, index],
+        );
+        self.call_conditional_tokens(calldata, "payoutNumerators").await
+    }
+
+    /// NegRiskAdapter `getFeeBips(bytes32)` — conversion fee of an event.
+    async fn neg_risk_fee_bips(&self, market_id: B256) -> Result<u128> {
+        let calldata = Self::encode_call(b"getFeeBips(bytes32)", &[market_id.0]);
+        self.call_uint(self.addresses.neg_risk_adapter, "NegRiskAdapter", calldata, "getFeeBips")
+            .await
+    }
+
+    /// Amount left after the NegRiskAdapter fee, rounded like the
+    /// adapter (`amount - amount * feeBips / 10_000`).
+    fn after_convert_fee(amount_raw: u128, fee_bips: u128) -> u128 {
+        amount_raw - amount_raw.saturating_mul(fee_bips) / 10_000
+    }
+
+    /// USDCe balance of `owner`.
+    async fn usdc_balance_at(&self, owner: Address) -> Result<f64> {
+        let calldata = Self::encode_balance_of(owner);
 
         // alloy 0.9: use TransactionInput::new() for the input field
         let tx = TransactionRequest::default()
             .to(self.addresses.usdce)
             .input(TransactionInput::new(calldata));
 
-        let result = inner
+        let result = self
+            .provider
+            .inner()
             .call(&tx)
             .await
             .context("USDCe balanceOf call failed")?;
@@ -125,21 +566,100 @@ impl ChainClient for CtfContracts {
 
         Ok(balance)
     }
+}
 
-    #[instrument(skip(self), fields(token_id = %token_id))]
-    async fn token_balance(&self, token_id: &str) -> Result<TokenBalance> {
-        // CTF Exchange balanceOf(address, tokenId) — ERC-1155 style
-        // TODO: Implement full ERC-1155 ABI encoding
-        let _wallet = std::env::var("WALLET_ADDRESS")
-            .context("WALLET_ADDRESS not set")?;
+#[async_trait]
+impl ChainClient for CtfContracts {
+    #[instrument(skip(self))]
+    async fn usdc_balance(&self) -> Result<f64> {
+        self.usdc_balance_at(self.sender.address()).await
+    }
 
-        Ok(TokenBalance {
-            token_id: token_id.to_string(),
-            balance_raw: 0,
-            balance: 0.0,
+    #[instrument(skip(self), fields(address = %address))]
+    async fn usdc_balance_of(&self, address: &str) -> Result<f64> {
+        let owner: Address = address
+            .parse()
+            .context(format!("Invalid address: {address}"))?;
+        self.usdc_balance_at(owner).await
+    }
+
+    #[instrument(skip(self))]
+    async fn native_balance(&self) -> Result<f64> {
+        let wei = self
+            .provider
+            .inner()
+            .get_balance(self.sender.address())
+            .await
+            .context("Failed to query native balance")?;
+        Ok(wei.to::<u128>() as f64 / 1e18)
+    }
+
+    #[instrument(skip(self), fields(to = %to))]
+    async fn transfer_usdc(&self, to: &str, amount_raw: u128) -> Result<TransferResult> {
+        let recipient: Address = to.parse().context(format!("Invalid address: {to}"))?;
+        if recipient == self.sender.address() {
+            bail!("Refusing to transfer USDC to the bot's own wallet");
+        }
+        let calldata = Self::encode_transfer(recipient, U256::from(amount_raw));
+        let gas_gwei = self.ensure_gas_acceptable("transfer").await?;
+
+        info!(amount_raw, gas_gwei, "Submitting USDC transfer");
+        let receipt = self.sender.send(self.addresses.usdce, calldata).await?;
+        Ok(TransferResult {
+            tx_hash: receipt.transaction_hash.to_string(),
+            amount: amount_raw as f64 / TOKEN_DECIMALS_SCALE,
+            gas_cost_matic: gas_cost_native(&receipt),
         })
     }
 
+    #[instrument(skip(self), fields(token_id = %token_id))]
+    async fn token_balance(&self, token_id: &TokenId) -> Result<TokenBalance> {
+        let position_id = Self::parse_position_id(token_id)?;
+        let balance_raw = self
+            .position_balance(self.sender.address(), position_id)
+            .await?;
+        Ok(Self::token_balance_from_raw(token_id, balance_raw))
+    }
+
+    #[instrument(skip(self), fields(count = token_ids.len()))]
+    async fn token_balances(&self, token_ids: &[String]) -> Result<Vec<TokenBalance>> {
+        let owner = self.sender.address();
+        let mut balances = Vec::with_capacity(token_ids.len());
+
+        for chunk in token_ids.chunks(BALANCE_BATCH_SIZE) {
+            let position_ids = chunk
+                .iter()
+                .map(|id| Self::parse_position_id(id))
+                .collect::<Result<Vec<_>>>()?;
+
+            let tx = TransactionRequest::default()
+                .to(self.addresses.conditional_tokens)
+                .input(TransactionInput::new(Self::encode_balance_of_batch(owner, &position_ids)));
+            let result = self
+                .provider
+                .inner()
+                .call(&tx)
+                .await
+                .context("ConditionalTokens balanceOfBatch call failed")?;
+
+            let raw = Self::decode_uint_array(&result)?;
+            if raw.len() != chunk.len() {
+                bail!("balanceOfBatch returned {} balances for {} IDs", raw.len(), chunk.len());
+            }
+            balances.extend(
+                chunk
+                    .iter()
+                    .zip(raw)
+                    .map(|(id, raw)| Self::token_balance_from_raw(id, raw)),
+            );
+        }
+
+        Ok(balances)
+    }
+
+    /// Redeem each condition in `token_ids` (condition IDs) with its
+    /// own transaction. Conditions that fail are logged and skipped;
+    /// the call errors only if none could be redeemed.
     #[instrument(skip(self), fields(batch_size = token_ids.len()))]
     async fn batch_redeem(&self, token_ids: &[String]) -> Result<RedemptionResult> {
         if token_ids.is_empty() {
@@ -152,14 +672,7 @@ impl ChainClient for CtfContracts {
         }
 
         // Check gas before submitting on-chain tx
-        let gas_gwei = self.gas_oracle.current_gas_gwei().await?;
-        if gas_gwei > 35.0 {
-            warn!(
-                gas_gwei,
-                "Gas too high for batch redeem (threshold: 35 gwei)"
-            );
-            bail!("Gas price {gas_gwei} gwei exceeds 35 gwei threshold");
-        }
+        let gas_gwei = self.ensure_gas_acceptable("batch redeem").await?;
 
         info!(
             batch_size = token_ids.len(),
@@ -167,23 +680,172 @@ impl ChainClient for CtfContracts {
             "Submitting batch redemption"
         );
 
-        // TODO: Actual tx submission requires full ABI + signer setup
-        // In production: encode redeemPositions() calldata, sign with
-        // EIP-1559 (tip 30 gwei, max 50 gwei), and submit.
+        let redeemer = self.sender.address();
+        let mut tx_hashes = Vec::new();
+        let mut payout_raw = U256::ZERO;
+        let mut gas_cost_matic = 0.0;
+        let mut last_error = None;
+
+        for condition_id in token_ids {
+            let call = match self.redemption_call(condition_id).await {
+                Ok(Some(call)) => call,
+                Ok(None) => {
+                    info!(condition_id = %condition_id, "Nothing held — skipping redemption");
+                    continue;
+                }
+                Err(e) => {
+                    warn!(condition_id = %condition_id, error = %e, "Failed to build redemption");
+                    last_error = Some(e);
+                    continue;
+                }
+            };
+
+            match self.sender.send(call.0, call.1).await {
+                Ok(receipt) => {
+                    let payout = Self::decode_payout_redemption(
+                        receipt.inner.logs().iter().map(|l| &l.inner),
+                        redeemer,
+                    );
+                    info!(
+                        condition_id = %condition_id,
+                        tx_hash = %receipt.transaction_hash,
+                        payout = %payout,
+                        "Positions redeemed"
+                    );
+                    tx_hashes.push(receipt.transaction_hash.to_string());
+                    payout_raw = payout_raw.saturating_add(payout);
+                    gas_cost_matic += gas_cost_native(&receipt);
+                }
+                Err(e) => {
+                    warn!(condition_id = %condition_id, error = %e, "Redemption failed");
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        if tx_hashes.is_empty() {
+            if let Some(e) = last_error {
+                return Err(e.context("No redemption succeeded"));
+            }
+        }
+
+        // USDCe has 6 decimals
+        let usdc_recovered = payout_raw.saturating_to::<u128>() as f64 / 1_000_000.0;
         Ok(RedemptionResult {
-            tx_hash: format!("0x_pending_{}", token_ids.len()),
-            positions_redeemed: token_ids.len(),
-            usdc_recovered: 0.0,
-            gas_cost_matic: 0.0,
+            tx_hash: tx_hashes.join(","),
+            positions_redeemed: tx_hashes.len(),
+            usdc_recovered,
+            gas_cost_matic,
+        })
+    }
+
+    #[instrument(skip(self), fields(condition_id = %condition_id))]
+    async fn merge_positions(&self, condition_id: &str, amount_raw: u128) -> Result<MergeResult> {
+        let (to, calldata) = self.complete_set_call(condition_id, amount_raw, false)?;
+        let gas_gwei = self.ensure_gas_acceptable("merge").await?;
+
+        info!(amount_raw, gas_gwei, "Submitting position merge");
+        let receipt = self.sender.send(to, calldata).await?;
+
+        // One complete set returns one unit of collateral
+        let sets_merged = amount_raw as f64 / TOKEN_DECIMALS_SCALE;
+        Ok(MergeResult {
+            tx_hash: receipt.transaction_hash.to_string(),
+            sets_merged,
+            usdc_recovered: sets_merged,
+            gas_cost_matic: gas_cost_native(&receipt),
+        })
+    }
+
+    #[instrument(skip(self), fields(market_id = %neg_risk_market_id))]
+    async fn convert_positions(
+        &self,
+        neg_risk_market_id: &str,
+        question_indices: &[u32],
+        amount_raw: u128,
+    ) -> Result<ConvertResult> {
+        if question_indices.is_empty() {
+            bail!("No outcomes to convert");
+        }
+        let market_id = Self::parse_condition_id(neg_risk_market_id)?;
+        let index_set = Self::question_index_set(question_indices)?;
+        let calldata = Self::encode_neg_risk_convert(market_id, index_set, U256::from(amount_raw));
+        let fee_bips = self.neg_risk_fee_bips(market_id).await?;
+        let gas_gwei = self.ensure_gas_acceptable("convert").await?;
+
+        info!(
+            amount_raw,
+            outcomes = question_indices.len(),
+            fee_bips,
+            gas_gwei,
+            "Submitting neg-risk conversion"
+        );
+        let receipt = self
+            .sender
+            .send(self.addresses.neg_risk_adapter, calldata)
+            .await?;
+
+        // The fee comes out of the amount; k NO positions then release
+        // k − 1 USDC and one YES of every other outcome per unit left
+        let minted = Self::after_convert_fee(amount_raw, fee_bips) as f64 / TOKEN_DECIMALS_SCALE;
+        Ok(ConvertResult {
+            tx_hash: receipt.transaction_hash.to_string(),
+            amount: amount_raw as f64 / TOKEN_DECIMALS_SCALE,
+            minted,
+            usdc_recovered: minted * (question_indices.len() - 1) as f64,
+            gas_cost_matic: gas_cost_native(&receipt),
+        })
+    }
+
+    #[instrument(skip(self), fields(condition_id = %condition_id))]
+    async fn split_position(&self, condition_id: &str, amount_raw: u128) -> Result<SplitResult> {
+        let (to, calldata) = self.complete_set_call(condition_id, amount_raw, true)?;
+        let gas_gwei = self.ensure_gas_acceptable("split").await?;
+
+        info!(amount_raw, gas_gwei, "Submitting position split");
+        let receipt = self.sender.send(to, calldata).await?;
+
+        // One unit of collateral mints one YES + one NO
+        let sets_split = amount_raw as f64 / TOKEN_DECIMALS_SCALE;
+        Ok(SplitResult {
+            tx_hash: receipt.transaction_hash.to_string(),
+            sets_split,
+            usdc_spent: sets_split,
+            gas_cost_matic: gas_cost_native(&receipt),
         })
     }
 
     #[instrument(skip(self), fields(condition_id = %condition_id))]
     async fn is_condition_resolved(&self, condition_id: &str) -> Result<bool> {
-        // TODO: Query CTF Exchange payoutDenominator(conditionId)
-        // Non-zero denominator means resolved
-        let _ = condition_id;
-        Ok(false)
+        // Non-zero denominator means the oracle has reported
+        let id = Self::parse_condition_id(condition_id)?;
+        Ok(self.payout_denominator(id).await? > 0)
+    }
+
+    #[instrument(skip(self), fields(condition_id = %condition_id))]
+    async fn condition_payouts(&self, condition_id: &str) -> Result<ConditionPayouts> {
+        let id = Self::parse_condition_id(condition_id)?;
+
+        let denominator = self.payout_denominator(id).await?;
+        if denominator == 0 {
+            return Ok(ConditionPayouts::default());
+        }
+
+        let slots = self.outcome_slot_count(id).await?;
+        if slots == 0 {
+            bail!("Condition {condition_id} reported payouts but has no outcome slots");
+        }
+
+        let mut numerators = Vec::with_capacity(slots as usize);
+        for index in 0..slots {
+            numerators.push(self.payout_numerator(id, index).await?);
+        }
+
+        info!(?numerators, denominator, "Condition payouts reported");
+        Ok(ConditionPayouts {
+            numerators,
+            denominator,
+        })
     }
 
     #[instrument(skip(self))]
@@ -195,3 +857,206 @@ impl ChainClient for CtfContracts {
         self.provider.is_healthy().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::LogData;
+
+    fn word(value: u64) -> [u8; 32] {
+        CtfContracts::uint_word(U256::from(value))
+    }
+
+    #[test]
+    fn test_encode_redeem_positions_layout() {
+        let collateral = Address::repeat_byte(0xaa);
+        let condition = B256::repeat_byte(0x11);
+        let calldata = CtfContracts::encode_redeem_positions(collateral, condition, &[1, 2]);
+
+        assert_eq!(
+            &calldata[..4],
+            &keccak256(b"redeemPositions(address,bytes32,bytes32,uint256[])")[..4]
+        );
+        let words: Vec<&[u8]> = calldata[4..].chunks(32).collect();
+        assert_eq!(words.len(), 7);
+        assert_eq!(words[0], CtfContracts::address_word(collateral));
+        assert_eq!(words[1], [0u8; 32]);
+        assert_eq!(words[2], condition.as_slice());
+        assert_eq!(words[3], word(128));
+        assert_eq!(words[4], word(2));
+        assert_eq!(words[5], word(1));
+        assert_eq!(words[6], word(2));
+    }
+
+    #[test]
+    fn test_encode_neg_risk_redeem_layout() {
+        let condition = B256::repeat_byte(0x22);
+        let calldata = CtfContracts::encode_neg_risk_redeem(
+            condition,
+            &[U256::from(5_000_000u64), U256::ZERO],
+        );
+        let words: Vec<&[u8]> = calldata[4..].chunks(32).collect();
+        assert_eq!(words.len(), 5);
+        assert_eq!(words[1], word(64));
+        assert_eq!(words[2], word(2));
+        assert_eq!(words[3], word(5_000_000));
+    }
+
+    #[test]
+    fn test_decode_payout_redemption_filters_redeemer() {
+        let me = Address::repeat_byte(0x01);
+        let adapter = Address::repeat_byte(0x02);
+        let ctf_topic = keccak256(b"PayoutRedemption(address,address,bytes32,bytes32,uint256[],uint256)");
+        let adapter_topic = keccak256(b"PayoutRedemption(address,bytes32,uint256[],uint256)");
+
+        let ctf_log = |redeemer: Address, payout: u64| {
+            let data = [B256::repeat_byte(0x11).0, word(96), word(payout), word(0)].concat();
+            Log {
+                address: Address::ZERO,
+                data: LogData::new_unchecked(
+                    vec![
+                        ctf_topic,
+                        B256::from(CtfContracts::address_word(redeemer)),
+                        B256::ZERO,
+                        B256::ZERO,
+                    ],
+                    data.into(),
+                ),
+            }
+        };
+        let adapter_log = Log {
+            address: adapter,
+            data: LogData::new_unchecked(
+                vec![
+                    adapter_topic,
+                    B256::from(CtfContracts::address_word(me)),
+                    B256::repeat_byte(0x22),
+                ],
+                [word(64), word(7_000_000), word(0)].concat().into(),
+            ),
+        };
+
+        let logs = [ctf_log(me, 3_000_000), ctf_log(adapter, 9_000_000), adapter_log];
+        let payout = CtfContracts::decode_payout_redemption(logs.iter(), me);
+        assert_eq!(payout, U256::from(10_000_000u64));
+    }
+
+    #[test]
+    fn test_encode_balance_of_batch_layout() {
+        let owner = Address::repeat_byte(0x33);
+        let ids = [U256::from(7), U256::from(9)];
+        let calldata = CtfContracts::encode_balance_of_batch(owner, &ids);
+
+        assert_eq!(&calldata[..4], &keccak256(b"balanceOfBatch(address[],uint256[])")[..4]);
+        let words: Vec<&[u8]> = calldata[4..].chunks(32).collect();
+        assert_eq!(words.len(), 8);
+        assert_eq!(words[0], word(64));
+        assert_eq!(words[1], word(160));
+        assert_eq!(words[2], word(2));
+        assert_eq!(words[3], CtfContracts::address_word(owner));
+        assert_eq!(words[4], CtfContracts::address_word(owner));
+        assert_eq!(words[5], word(2));
+        assert_eq!(words[6], word(7));
+        assert_eq!(words[7], word(9));
+    }
+
+    #[test]
+    fn test_decode_uint_array_and_scaling() {
+        let data = [word(32), word(2), word(12_500_000), word(0)].concat();
+        let raw = CtfContracts::decode_uint_array(&data).unwrap();
+        assert_eq!(raw, vec![12_500_000, 0]);
+
+        let balance = CtfContracts::token_balance_from_raw("1", raw[0]);
+        assert_eq!(balance.balance_raw, 12_500_000);
+        assert_eq!(balance.balance, 12.5);
+
+        assert!(CtfContracts::decode_uint_array(&data[..64]).is_err());
+    }
+
+    #[test]
+    fn test_encode_merge_positions_layout() {
+        let collateral = Address::repeat_byte(0xaa);
+        let condition = B256::repeat_byte(0x44);
+        let calldata =
+            CtfContracts::encode_merge_positions(collateral, condition, U256::from(3_000_000u64));
+
+        assert_eq!(
+            &calldata[..4],
+            &keccak256(b"mergePositions(address,bytes32,bytes32,uint256[],uint256)")[..4]
+        );
+        let words: Vec<&[u8]> = calldata[4..].chunks(32).collect();
+        assert_eq!(words.len(), 8);
+        assert_eq!(words[2], condition.as_slice());
+        assert_eq!(words[3], word(160));
+        assert_eq!(words[4], word(3_000_000));
+        assert_eq!(words[5], word(2));
+        assert_eq!(words[6], word(1));
+        assert_eq!(words[7], word(2));
+
+        let neg_risk = CtfContracts::encode_neg_risk_merge(condition, U256::from(7u64));
+        assert_eq!(&neg_risk[..4], &keccak256(b"mergePositions(bytes32,uint256)")[..4]);
+        assert_eq!(neg_risk.len(), 4 + 64);
+    }
+
+    #[test]
+    fn test_encode_neg_risk_convert() {
+        let index_set = CtfContracts::question_index_set(&[0, 2, 3]).unwrap();
+        assert_eq!(index_set, U256::from(0b1101u64));
+        assert!(CtfContracts::question_index_set(&[256]).is_err());
+
+        let market = B256::repeat_byte(0x55);
+        let calldata =
+            CtfContracts::encode_neg_risk_convert(market, index_set, U256::from(5_000_000u64));
+        assert_eq!(
+            &calldata[..4],
+            &keccak256(b"convertPositions(bytes32,uint256,uint256)")[..4]
+        );
+        let words: Vec<&[u8]> = calldata[4..].chunks(32).collect();
+        assert_eq!(words[0], market.as_slice());
+        assert_eq!(words[1], word(0b1101));
+        assert_eq!(words[2], word(5_000_000));
+    }
+
+    #[test]
+    fn test_convert_fee_comes_out_of_amount() {
+        assert_eq!(CtfContracts::after_convert_fee(5_000_000, 0), 5_000_000);
+        // 2% of 5 tokens
+        assert_eq!(CtfContracts::after_convert_fee(5_000_000, 200), 4_900_000);
+        // The fee rounds down, as in the adapter
+        assert_eq!(CtfContracts::after_convert_fee(333, 150), 329);
+    }
+
+    #[test]
+    fn test_encode_split_position_shares_merge_layout() {
+        let collateral = Address::repeat_byte(0xaa);
+        let condition = B256::repeat_byte(0x44);
+        let amount = U256::from(2_000_000u64);
+        let split = CtfContracts::encode_split_position(collateral, condition, amount);
+        let merge = CtfContracts::encode_merge_positions(collateral, condition, amount);
+
+        assert_eq!(
+            &split[..4],
+            &keccak256(b"splitPosition(address,bytes32,bytes32,uint256[],uint256)")[..4]
+        );
+        assert_eq!(&split[4..], &merge[4..]);
+
+        let neg_risk = CtfContracts::encode_neg_risk_split(condition, amount);
+        assert_eq!(&neg_risk[..4], &keccak256(b"splitPosition(bytes32,uint256)")[..4]);
+    }
+
+    #[test]
+    fn test_encode_transfer_layout() {
+        let to: Address = "0x008AeEda4D805471dF9b2A5B0f38A0C3bCBA786b".parse().unwrap();
+        let calldata = CtfContracts::encode_transfer(to, U256::from(25_000_000u64));
+        assert_eq!(&calldata[..4], &[0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(&calldata[16..36], to.as_slice());
+        assert_eq!(&calldata[36..68], &word(25_000_000));
+    }
+
+    #[test]
+    fn test_parse_position_id() {
+        assert_eq!(CtfContracts::parse_position_id("255").unwrap(), U256::from(255));
+        assert_eq!(CtfContracts::parse_position_id("0xff").unwrap(), U256::from(255));
+        assert!(CtfContracts::parse_position_id("0x_example").is_err());
+    }
+}
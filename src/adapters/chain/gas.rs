@@ -1,13 +1,14 @@
 //! Gas Oracle - EIP-1559 Fee Estimation for Polygon
 //!
 //! Monitors gas prices on Polygon to optimize on-chain transaction
-//! timing. Batch redemptions are only executed when gas < 35 gwei.
+//! timing. Batch redemptions are only executed when gas is below
+//! `settlement.max_gas_gwei` (default 35 gwei).
 //! Uses EIP-1559 with priority fee (tip) of 30 gwei and max fee of 50 gwei.
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
-use alloy::providers::Provider;
+use alloy::eips::BlockNumberOrTag;
 use anyhow::{Context, Result};
 use tracing::{debug, instrument};
 
@@ -36,7 +37,7 @@ impl Default for GasParams {
 
 /// Gas price oracle for Polygon EIP-1559 transactions.
 ///
-/// Provides real-time gas estimates and enforces the 35 gwei
+/// Provides real-time gas estimates and enforces the redemption gas
 /// threshold for batch redemption timing (scheduled @4AM UTC).
 pub struct GasOracle {
     /// Shared Polygon provider.
@@ -57,6 +58,17 @@ impl GasOracle {
         }
     }
 
+    /// Set the gas threshold for redemptions (`settlement.max_gas_gwei`).
+    pub fn with_redeem_threshold(mut self, gwei: f64) -> Self {
+        self.redeem_threshold_gwei = gwei;
+        self
+    }
+
+    /// Gas threshold for redemptions (gwei).
+    pub fn redeem_threshold_gwei(&self) -> f64 {
+        self.redeem_threshold_gwei
+    }
+
     /// Get the current gas price in gwei from the RPC node.
     #[instrument(skip(self))]
     pub async fn current_gas_gwei(&self) -> Result<f64> {
@@ -78,6 +90,27 @@ impl GasOracle {
         Ok(gwei)
     }
 
+    /// Base fee of the next block in gwei (`eth_feeHistory`).
+    ///
+    /// `eth_gasPrice` already includes a suggested tip; EIP-1559 fees
+    /// are built on this instead.
+    #[instrument(skip(self))]
+    pub async fn base_fee_gwei(&self) -> Result<f64> {
+        let history = self
+            .provider
+            .inner()
+            .get_fee_history(1, BlockNumberOrTag::Latest, &[])
+            .await
+            .context("Failed to query fee history")?;
+        let base_fee = history
+            .next_block_base_fee()
+            .context("Fee history has no base fee")?;
+
+        let gwei = base_fee as f64 / 1_000_000_000.0;
+        debug!(base_fee_gwei = gwei, "Base fee updated");
+        Ok(gwei)
+    }
+
     /// Get cached gas price without RPC call (fast path).
     pub fn cached_gas_gwei(&self) -> f64 {
         self.cached_gas_x100.load(Ordering::Relaxed) as f64 / 100.0
@@ -94,7 +127,7 @@ impl GasOracle {
     /// Uses priority fee of 30 gwei and max fee of 50 gwei
     /// per the Space checklist requirements.
     pub async fn eip1559_params(&self) -> Result<GasParams> {
-        let base_fee = self.current_gas_gwei().await?;
+        let base_fee = self.base_fee_gwei().await?;
 
         Ok(GasParams {
             base_fee_gwei: base_fee,
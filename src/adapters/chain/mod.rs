@@ -3,7 +3,8 @@
 //! Provides on-chain access via alloy-rs 0.9 for:
 //! - RPC provider management with failover
 //! - CTF contract interactions (balance, redeem)
-//! - ERC-20 approval management (USDCe → CTF, CTF → exchanges)
+//! - Transaction signing with nonce tracking and EIP-1559 fees
+//! - Approval management (USDCe → exchange/adapter/CTF, CTF → exchange/adapter)
 //! - Gas price monitoring with EIP-1559 support
 //! - Contract validation at startup (code exists + symbol check)
 
@@ -11,10 +12,12 @@ pub mod approvals;
 pub mod contracts;
 pub mod gas;
 pub mod provider;
+pub mod signer;
 pub mod validator;
 
 pub use approvals::ApprovalManager;
 pub use contracts::CtfContracts;
 pub use gas::GasOracle;
 pub use provider::PolygonProvider;
+pub use signer::TxSender;
 pub use validator::ContractValidator;
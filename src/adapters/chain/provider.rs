@@ -36,15 +36,17 @@ impl PolygonProvider {
     /// Connect to Polygon RPC and validate the chain ID.
     ///
     /// Reads the RPC URL from config. The URL itself comes from
-    /// `config.toml` (never hardcoded). Validates chain ID = 137
-    /// (Polygon mainnet) at startup.
+    /// `config.toml` (never hardcoded). Validates the chain ID
+    /// against `api.chain_id` (137 = Polygon mainnet) at startup.
     #[instrument(skip_all)]
     pub async fn connect(config: &ApiConfig) -> Result<Self> {
         let rpc_url = config.rpc_url.clone();
 
-        // alloy 0.9: on_http() is synchronous, returns impl Provider
+        // alloy 0.9: on_http() is synchronous; box the transport so the
+        // provider fits `dyn Provider` (whose default transport is boxed)
         let provider = ProviderBuilder::new()
-            .on_http(rpc_url.parse().context("Invalid RPC URL")?);
+            .on_http(rpc_url.parse().context("Invalid RPC URL")?)
+            .boxed();
 
         // Wrap in Arc<dyn Provider> for type erasure
         let provider: Arc<dyn Provider + Send + Sync> = Arc::new(provider);
@@ -55,9 +57,10 @@ impl PolygonProvider {
             .await
             .context("Failed to query chain ID")?;
 
-        if chain_id != 137 {
+        if chain_id != config.chain_id {
             anyhow::bail!(
-                "Expected Polygon mainnet (chain_id=137), got {chain_id}"
+                "Expected chain_id={}, got {chain_id}",
+                config.chain_id
             );
         }
 
@@ -0,0 +1,212 @@
+//! Transaction Signer - EIP-1559 Signing, Nonces and Receipts
+//!
+//! Signs and submits transactions from the bot wallet:
+//! - Key from `SecretStore::signer` (encrypted keystore, or a hex
+//!   `private_key` from the secret backend)
+//! - Nonces fetched once from the pending pool, then tracked
+//!   locally; reset on any submission error
+//! - Fees from `SettlementConfig` (tip, max fee cap); submission is
+//!   refused if the next block's base fee (`eth_feeHistory`) already
+//!   exceeds the cap
+//! - Waits for the receipt and fails on reverted transactions
+//!
+//! Works against any EIP-1559 chain, including a local anvil node.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::network::{EthereumWallet, TransactionBuilder};
+use alloy::primitives::{Address, Bytes};
+use alloy::rpc::types::{TransactionReceipt, TransactionRequest};
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::{bail, Context, Result};
+use tokio::sync::Mutex;
+use tracing::{info, instrument, warn};
+
+use crate::config::SettlementConfig;
+
+use super::gas::GasOracle;
+use super::provider::PolygonProvider;
+
+/// How long to wait for a transaction receipt.
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// EIP-1559 fee pair in wei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eip1559Fees {
+    /// `maxFeePerGas` (wei).
+    pub max_fee_per_gas: u128,
+    /// `maxPriorityFeePerGas` (wei).
+    pub max_priority_fee_per_gas: u128,
+}
+
+/// Choose EIP-1559 fees from the current base fee and configured caps.
+///
+/// The tip is capped at the max fee. Fails if the base fee alone
+/// exceeds the cap, since the transaction could not be included.
+pub fn select_fees(base_fee_gwei: f64, tip_gwei: f64, max_fee_gwei: f64) -> Result<Eip1559Fees> {
+    if base_fee_gwei > max_fee_gwei {
+        bail!("Base fee {base_fee_gwei:.1} gwei exceeds max fee cap {max_fee_gwei:.1} gwei");
+    }
+    let tip = tip_gwei.clamp(0.0, max_fee_gwei);
+    Ok(Eip1559Fees {
+        max_fee_per_gas: gwei_to_wei(max_fee_gwei),
+        max_priority_fee_per_gas: gwei_to_wei(tip),
+    })
+}
+
+/// Convert gwei to wei.
+fn gwei_to_wei(gwei: f64) -> u128 {
+    (gwei * 1_000_000_000.0).round() as u128
+}
+
+/// Signs and submits transactions from the bot wallet.
+pub struct TxSender {
+    /// Shared RPC provider.
+    provider: Arc<PolygonProvider>,
+    /// Gas oracle for the next block's base fee.
+    gas_oracle: Arc<GasOracle>,
+    /// Wallet signer.
+    wallet: EthereumWallet,
+    /// Wallet address.
+    address: Address,
+    /// Chain ID for replay protection.
+    chain_id: u64,
+    /// Priority fee (gwei).
+    tip_gwei: f64,
+    /// Max fee cap (gwei).
+    max_fee_gwei: f64,
+    /// Next nonce; `None` until fetched or after an error.
+    nonce: Mutex<Option<u64>>,
+}
+
+impl TxSender {
+    /// Create a sender from an explicit signer.
+    pub fn new(
+        provider: Arc<PolygonProvider>,
+        gas_oracle: Arc<GasOracle>,
+        signer: PrivateKeySigner,
+        chain_id: u64,
+        settlement: &SettlementConfig,
+    ) -> Self {
+        let address = signer.address();
+        Self {
+            provider,
+            gas_oracle,
+            wallet: EthereumWallet::from(signer),
+            address,
+            chain_id,
+            tip_gwei: settlement.tip_gwei,
+            max_fee_gwei: settlement.max_fee_gwei,
+            nonce: Mutex::new(None),
+        }
+    }
+
+    /// Wallet address transactions are sent from.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Sign, submit and wait for a contract call.
+    ///
+    /// Returns the receipt of a successful transaction; reverted
+    /// transactions are an error.
+    #[instrument(skip(self, calldata), fields(to = %to))]
+    pub async fn send(&self, to: Address, calldata: Bytes) -> Result<TransactionReceipt> {
+        let inner = self.provider.inner();
+        let base_fee = self.gas_oracle.base_fee_gwei().await?;
+        let fees = select_fees(base_fee, self.tip_gwei, self.max_fee_gwei)?;
+
+        // Hold the nonce lock across submission so concurrent sends
+        // cannot reuse a nonce
+        let mut nonce_slot = self.nonce.lock().await;
+        let nonce = match *nonce_slot {
+            Some(n) => n,
+            None => inner
+                .get_transaction_count(self.address)
+                .pending()
+                .await
+                .context("Failed to fetch pending nonce")?,
+        };
+
+        let mut tx = TransactionRequest::default()
+            .with_from(self.address)
+            .with_to(to)
+            .with_input(calldata)
+            .with_chain_id(self.chain_id)
+            .with_nonce(nonce)
+            .with_max_fee_per_gas(fees.max_fee_per_gas)
+            .with_max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+
+        let gas_limit = match inner.estimate_gas(&tx).await {
+            Ok(gas) => gas,
+            Err(e) => {
+                *nonce_slot = None;
+                return Err(e).context("Gas estimation failed (call would revert?)");
+            }
+        };
+        // 20% headroom over the estimate
+        tx = tx.with_gas_limit(gas_limit + gas_limit / 5);
+
+        let envelope = match tx.build(&self.wallet).await {
+            Ok(envelope) => envelope,
+            Err(e) => {
+                *nonce_slot = None;
+                return Err(e).context("Failed to sign transaction");
+            }
+        };
+
+        let pending = match inner.send_tx_envelope(envelope).await {
+            Ok(pending) => pending,
+            Err(e) => {
+                *nonce_slot = None;
+                return Err(e).context("Failed to submit transaction");
+            }
+        };
+        *nonce_slot = Some(nonce + 1);
+        drop(nonce_slot);
+
+        let tx_hash = *pending.tx_hash();
+        info!(tx_hash = %tx_hash, nonce, "Transaction submitted");
+
+        let receipt = pending
+            .with_timeout(Some(RECEIPT_TIMEOUT))
+            .get_receipt()
+            .await
+            .context(format!("No receipt for {tx_hash}"))?;
+
+        if !receipt.status() {
+            warn!(tx_hash = %tx_hash, "Transaction reverted");
+            bail!("Transaction {tx_hash} reverted");
+        }
+        Ok(receipt)
+    }
+}
+
+/// Gas cost of a mined transaction in the native token (MATIC/POL).
+pub fn gas_cost_native(receipt: &TransactionReceipt) -> f64 {
+    receipt.gas_used as f64 * receipt.effective_gas_price as f64 / 1e18
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_fees_uses_configured_caps() {
+        let fees = select_fees(25.0, 30.0, 50.0).unwrap();
+        assert_eq!(fees.max_fee_per_gas, 50_000_000_000);
+        assert_eq!(fees.max_priority_fee_per_gas, 30_000_000_000);
+    }
+
+    #[test]
+    fn test_select_fees_caps_tip_at_max_fee() {
+        let fees = select_fees(10.0, 80.0, 50.0).unwrap();
+        assert_eq!(fees.max_priority_fee_per_gas, fees.max_fee_per_gas);
+    }
+
+    #[test]
+    fn test_select_fees_rejects_base_fee_above_cap() {
+        assert!(select_fees(60.0, 30.0, 50.0).is_err());
+    }
+}
@@ -59,22 +59,23 @@ impl ContractValidator {
             ("CTF Exchange", &config.ctf_exchange),
             ("USDCe", &config.usdce),
             ("Neg Risk Adapter", &config.neg_risk_adapter),
+            ("Conditional Tokens", &config.conditional_tokens),
         ];
 
         for (name, addr_str) in &contracts {
             let result = self.validate_contract(name, addr_str).await?;
 
-            if !result.has_code {
-                warn!(
+            if result.has_code {
+                info!(
                     contract = name,
                     address = addr_str,
-                    "Contract has no code — possible misconfiguration"
+                    "Contract validated: code exists on-chain"
                 );
             } else {
-                info!(
+                warn!(
                     contract = name,
                     address = addr_str,
-                    "Contract validated: code exists on-chain"
+                    "Contract has no code — possible misconfiguration"
                 );
             }
 
@@ -8,11 +8,11 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use futures_util::{SinkExt, StreamExt};
+use futures_util::StreamExt;
 use serde::Deserialize;
 use tokio::sync::{broadcast, RwLock};
 use tokio_tungstenite::connect_async;
-use tracing::{debug, error, info, instrument, warn};
+use tracing::{debug, info, instrument, warn};
 
 use crate::domain::trade::Asset;
 
@@ -58,6 +58,12 @@ pub struct BinanceFeed {
     min_delta_pct: f64,
 }
 
+impl Default for BinanceFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl BinanceFeed {
     /// Create a new Binance feed with default WebSocket endpoint.
     pub fn new() -> Self {
@@ -103,12 +103,9 @@ impl FeedBridge {
 
     /// Convert a single BinanceTick into a PriceUpdate and broadcast.
     fn handle_tick(&self, tick: &BinanceTick) {
-        let market_id = match self.asset_market_map.get(&tick.symbol) {
-            Some(id) => id.clone(),
-            None => {
-                debug!(symbol = %tick.symbol, "No market mapping for symbol");
-                return;
-            }
+        let Some(market_id) = self.asset_market_map.get(&tick.symbol).cloned() else {
+            debug!(symbol = %tick.symbol, "No market mapping for symbol");
+            return;
         };
 
         // Normalize spot price to probability-like value.
@@ -8,11 +8,11 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use futures_util::StreamExt;
+use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use tokio::sync::{broadcast, RwLock};
 use tokio_tungstenite::connect_async;
-use tracing::{debug, info, instrument, warn};
+use tracing::{info, instrument, warn};
 
 /// A price tick from Coinbase.
 #[derive(Debug, Clone)]
@@ -41,7 +41,6 @@ struct TickerMsg {
     msg_type: String,
     product_id: Option<String>,
     price: Option<String>,
-    time: Option<String>,
 }
 
 /// Coinbase real-time price feed via WebSocket.
@@ -57,6 +56,12 @@ pub struct CoinbaseFeed {
     min_delta_pct: f64,
 }
 
+impl Default for CoinbaseFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CoinbaseFeed {
     /// Create a new Coinbase feed.
     pub fn new() -> Self {
@@ -119,10 +124,8 @@ impl CoinbaseFeed {
 
         let sub_json = serde_json::to_string(&subscribe)?;
 
-        // tungstenite 0.24: Message::Text takes Utf8Bytes, use .into() for String → Utf8Bytes.
-        use futures_util::SinkExt;
         write
-            .send(tokio_tungstenite::tungstenite::Message::Text(sub_json.into()))
+            .send(tokio_tungstenite::tungstenite::Message::Text(sub_json))
             .await
             .context("Failed to send subscribe")?;
 
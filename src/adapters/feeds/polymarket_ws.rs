@@ -19,10 +19,10 @@ use futures_util::StreamExt;
 use serde::Deserialize;
 use tokio::sync::{broadcast, RwLock};
 use tokio_tungstenite::connect_async;
-use tracing::{debug, error, info, instrument, warn};
+use tracing::{debug, info, instrument, warn};
 
 use crate::config::ApiConfig;
-use crate::domain::trade::{MarketId, TokenId};
+use crate::domain::trade::TokenId;
 use crate::ports::market_feed::{MarketFeed, OrderBookSnapshot, PriceUpdate};
 
 /// Raw order book message from Polymarket CLOB WebSocket.
@@ -79,19 +79,6 @@ impl PolymarketFeed {
         }
     }
 
-    /// Ensure a token has a broadcast channel allocated.
-    async fn ensure_token(&self, token_id: &TokenId) {
-        let mut tokens = self.tokens.write().await;
-        tokens.entry(token_id.clone()).or_insert_with(|| {
-            let (tx, _) = broadcast::channel(4096);
-            TokenState {
-                tx,
-                last_mid: None,
-                last_snapshot: None,
-            }
-        });
-    }
-
     /// Run the WebSocket connection loop with auto-reconnect.
     ///
     /// Listens for order book updates and broadcasts `PriceUpdate` events.
@@ -114,7 +101,7 @@ impl PolymarketFeed {
                     // Check shutdown before sleeping
                     tokio::select! {
                         _ = shutdown_rx.recv() => return Ok(()),
-                        _ = tokio::time::sleep(tokio::time::Duration::from_secs(5)) => {},
+                        () = tokio::time::sleep(tokio::time::Duration::from_secs(5)) => {},
                     }
                 }
             }
@@ -205,7 +192,7 @@ impl PolymarketFeed {
             .and_then(|s| s.parse::<f64>().ok());
 
         let mid_price = match (best_bid, best_ask) {
-            (Some(b), Some(a)) => Some((b + a) / 2.0),
+            (Some(b), Some(a)) => Some(f64::midpoint(b, a)),
             _ => None,
         };
 
@@ -252,8 +239,8 @@ impl PolymarketFeed {
 
         state.last_snapshot = Some(OrderBookSnapshot {
             token_id: token_id.clone(),
-            bids: bids.clone(),
-            asks: asks.clone(),
+            bids,
+            asks,
             sequence: msg.timestamp,
             timestamp_ms: msg.timestamp,
         });
@@ -339,7 +326,7 @@ impl MarketFeed for PolymarketFeed {
         let best_bid = snapshot.bids.first().map(|(p, _)| *p);
         let best_ask = snapshot.asks.first().map(|(p, _)| *p);
         let mid_price = match (best_bid, best_ask) {
-            (Some(b), Some(a)) => Some((b + a) / 2.0),
+            (Some(b), Some(a)) => Some(f64::midpoint(b, a)),
             _ => None,
         };
 
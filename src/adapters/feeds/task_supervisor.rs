@@ -7,7 +7,6 @@
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use anyhow::Result;
 use tokio::sync::broadcast;
 use tracing::{error, info, instrument, warn};
 
@@ -17,8 +16,6 @@ use super::coinbase::CoinbaseFeed;
 /// Tracks the health state of a single feed task.
 #[derive(Debug)]
 struct FeedHealth {
-    /// Feed name for logging.
-    name: &'static str,
     /// Whether the feed is currently connected.
     connected: AtomicBool,
     /// Consecutive reconnection attempts.
@@ -49,12 +46,10 @@ impl FeedSupervisor {
             binance: Arc::new(BinanceFeed::new()),
             coinbase: Arc::new(CoinbaseFeed::new()),
             binance_health: Arc::new(FeedHealth {
-                name: "binance",
                 connected: AtomicBool::new(false),
                 reconnects: std::sync::atomic::AtomicU32::new(0),
             }),
             coinbase_health: Arc::new(FeedHealth {
-                name: "coinbase",
                 connected: AtomicBool::new(false),
                 reconnects: std::sync::atomic::AtomicU32::new(0),
             }),
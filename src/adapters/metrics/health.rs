@@ -25,6 +25,12 @@ pub struct HealthState {
     pub engine_running: Arc<std::sync::atomic::AtomicBool>,
 }
 
+impl Default for HealthState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl HealthState {
     /// Create a new health state (all healthy by default).
     pub fn new() -> Self {
@@ -86,11 +92,13 @@ impl HealthServer {
     }
 
     /// Liveness probe: always returns 200 if the process is running.
+    #[allow(clippy::unused_async)]
     async fn liveness() -> impl IntoResponse {
         (StatusCode::OK, "OK")
     }
 
     /// Readiness probe: returns 200 only if feeds + chain are healthy.
+    #[allow(clippy::unused_async)]
     async fn readiness(
         State(state): State<Arc<HealthState>>,
     ) -> impl IntoResponse {
@@ -15,6 +15,8 @@ use prometheus::{
 use tokio::sync::broadcast;
 use tracing::{info, instrument};
 
+use crate::ports::metrics::MetricsSink;
+
 /// Centralized Prometheus metrics for the trading bot.
 ///
 /// All metrics follow the naming convention `polymarket_bot_*` and
@@ -78,7 +80,7 @@ impl MetricsRegistry {
         let orders_rejected = IntCounterVec::new(
             Opts::new(
                 "polymarket_bot_orders_rejected_total",
-                "Total orders rejected by CLOB",
+                "Total orders rejected by the risk gate or CLOB",
             ),
             &["asset", "reason"],
         )?;
@@ -163,6 +165,17 @@ impl MetricsRegistry {
         })
     }
 
+    /// Encode all registered metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let encoder = TextEncoder::new();
+        let metric_families = self.registry.gather();
+        let mut buffer = Vec::new();
+        if encoder.encode(&metric_families, &mut buffer).is_err() {
+            return String::new();
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+
     /// Serve Prometheus metrics on the configured bind address.
     #[instrument(skip(self, shutdown_rx))]
     pub async fn serve(
@@ -175,14 +188,8 @@ impl MetricsRegistry {
         let app = Router::new().route(
             "/metrics",
             get(move || {
-                let registry = metrics_self.registry.clone();
-                async move {
-                    let encoder = TextEncoder::new();
-                    let metric_families = registry.gather();
-                    let mut buffer = Vec::new();
-                    encoder.encode(&metric_families, &mut buffer).unwrap();
-                    String::from_utf8(buffer).unwrap_or_default()
-                }
+                let metrics = Arc::clone(&metrics_self);
+                async move { metrics.render() }
             }),
         );
 
@@ -198,3 +205,14 @@ impl MetricsRegistry {
         Ok(())
     }
 }
+
+impl MetricsSink for MetricsRegistry {
+    fn order_rejected(&self, asset: &str, reason: &str) {
+        self.orders_rejected.with_label_values(&[asset, reason]).inc();
+    }
+
+    fn pnl_updated(&self, asset: &str, realized: f64, unrealized: f64) {
+        self.realized_pnl.with_label_values(&[asset]).set(realized);
+        self.unrealized_pnl.with_label_values(&[asset]).set(unrealized);
+    }
+}
@@ -5,14 +5,20 @@
 //! file I/O). Each sub-module groups adapters by infrastructure concern.
 //!
 //! Adapter categories:
+//! - `admin`: Operator halt/resume endpoints and kill switch file
 //! - `api`: Polymarket CLOB REST API client and auth
 //! - `chain`: Polygon blockchain interaction via alloy-rs
 //! - `feeds`: Real-time market data (Binance, Coinbase WebSockets)
 //! - `metrics`: Prometheus metrics export and health checks
+//! - `paper`: Simulated order execution for paper and dry-run mode
 //! - `persistence`: JSONL trade logging and state snapshots
+//! - `secrets`: Credential backends (env, file, Vault) and wallet keystore
 
+pub mod admin;
 pub mod api;
 pub mod chain;
 pub mod feeds;
 pub mod metrics;
+pub mod paper;
 pub mod persistence;
+pub mod secrets;
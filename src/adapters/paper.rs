@@ -0,0 +1,213 @@
+//! Paper Order Executor — Simulated Order Execution
+//!
+//! Implements the `OrderExecution` port without touching the CLOB,
+//! for paper and dry-run mode. FOK orders fill in full at their limit
+//! price against a simulated USDC balance; GTC/GTD orders rest until
+//! cancelled (there is no book to match them against).
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::domain::time::now_ms;
+use crate::domain::trade::{Order, OrderId, OrderType, TokenId, TradeSide};
+use crate::ports::execution::{
+    OrderCancellation, OrderExecution, OrderPlacement, OrderStatus,
+};
+
+/// Simulated orders and balance.
+#[derive(Debug, Default)]
+struct PaperBook {
+    /// Orders placed so far, for IDs.
+    placed: u64,
+    /// Simulated USDC balance.
+    balance: f64,
+    /// Every accepted order with its current status.
+    orders: HashMap<OrderId, (Order, OrderStatus)>,
+}
+
+/// Order executor that never sends an order.
+pub struct PaperOrderExecutor {
+    book: Mutex<PaperBook>,
+}
+
+impl PaperOrderExecutor {
+    /// Create a paper executor holding `balance` USDC.
+    pub fn new(balance: f64) -> Self {
+        Self {
+            book: Mutex::new(PaperBook {
+                balance,
+                ..PaperBook::default()
+            }),
+        }
+    }
+
+    /// Cancel the open orders matching `filter`.
+    fn cancel_where(&self, filter: impl Fn(&Order) -> bool) -> Vec<OrderCancellation> {
+        let mut book = self.book.lock().unwrap();
+        book.orders
+            .iter_mut()
+            .filter(|(_, (order, status))| {
+                matches!(status, OrderStatus::Open { .. }) && filter(order)
+            })
+            .map(|(id, (_, status))| {
+                *status = OrderStatus::Cancelled;
+                OrderCancellation {
+                    order_id: id.clone(),
+                    success: true,
+                    error: None,
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl OrderExecution for PaperOrderExecutor {
+    async fn place_order(&self, order: &Order) -> Result<OrderPlacement> {
+        let mut book = self.book.lock().unwrap();
+        let cost = order.price * order.size;
+        if order.side == TradeSide::Buy && cost > book.balance {
+            return Ok(OrderPlacement {
+                order_id: String::new(),
+                accepted: false,
+                rejection_reason: Some("Insufficient paper balance".to_string()),
+                timestamp_ms: now_ms(),
+            });
+        }
+
+        book.placed += 1;
+        let order_id = format!("paper-{}", book.placed);
+        let status = if order.order_type == OrderType::Fok {
+            match order.side {
+                TradeSide::Buy => book.balance -= cost,
+                TradeSide::Sell => book.balance += cost,
+            }
+            OrderStatus::Filled {
+                avg_price: order.price,
+                filled_size: order.size,
+            }
+        } else {
+            OrderStatus::Open {
+                remaining_size: order.size,
+                original_size: order.size,
+            }
+        };
+        info!(
+            order_id = %order_id,
+            token = %order.token_id,
+            side = ?order.side,
+            price = order.price,
+            size = order.size,
+            "Paper order placed"
+        );
+
+        let placed = Order {
+            id: order_id.clone(),
+            ..order.clone()
+        };
+        book.orders.insert(order_id.clone(), (placed, status));
+        Ok(OrderPlacement {
+            order_id,
+            accepted: true,
+            rejection_reason: None,
+            timestamp_ms: now_ms(),
+        })
+    }
+
+    async fn cancel_order(&self, order_id: &OrderId) -> Result<OrderCancellation> {
+        let cancelled = self.cancel_where(|order| &order.id == order_id);
+        Ok(cancelled.into_iter().next().unwrap_or_else(|| OrderCancellation {
+            order_id: order_id.clone(),
+            success: false,
+            error: Some("Order not open".to_string()),
+        }))
+    }
+
+    async fn cancel_all_orders(&self) -> Result<usize> {
+        Ok(self.cancel_where(|_| true).len())
+    }
+
+    async fn cancel_orders_for_token(
+        &self,
+        token_id: &TokenId,
+    ) -> Result<Vec<OrderCancellation>> {
+        Ok(self.cancel_where(|order| &order.token_id == token_id))
+    }
+
+    async fn get_order_status(&self, order_id: &OrderId) -> Result<OrderStatus> {
+        let book = self.book.lock().unwrap();
+        Ok(book
+            .orders
+            .get(order_id)
+            .map_or(OrderStatus::Unknown, |(_, status)| status.clone()))
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<Order>> {
+        let book = self.book.lock().unwrap();
+        Ok(book
+            .orders
+            .values()
+            .filter(|(_, status)| matches!(status, OrderStatus::Open { .. }))
+            .map(|(order, _)| order.clone())
+            .collect())
+    }
+
+    async fn available_balance(&self, _side: TradeSide) -> Result<f64> {
+        Ok(self.book.lock().unwrap().balance)
+    }
+
+    async fn is_healthy(&self) -> bool {
+        true
+    }
+
+    async fn rate_limit_status(&self) -> (u32, u64) {
+        (50, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fok(side: TradeSide, price: f64, size: f64) -> Order {
+        let mut order = Order::new_maker("tok".to_string(), side, price, size);
+        order.order_type = OrderType::Fok;
+        order.post_only = false;
+        order
+    }
+
+    #[tokio::test]
+    async fn test_fok_fills_at_limit_against_paper_balance() {
+        let exec = PaperOrderExecutor::new(100.0);
+
+        let buy = exec.place_order(&fok(TradeSide::Buy, 0.40, 100.0)).await.unwrap();
+        assert!(buy.accepted);
+        assert!(matches!(
+            exec.get_order_status(&buy.order_id).await.unwrap(),
+            OrderStatus::Filled { filled_size, .. } if filled_size == 100.0
+        ));
+        let sell = exec.place_order(&fok(TradeSide::Sell, 0.50, 100.0)).await.unwrap();
+        assert!(sell.accepted);
+        let balance = exec.available_balance(TradeSide::Buy).await.unwrap();
+        assert!((balance - 110.0).abs() < 1e-9);
+
+        let too_big = exec.place_order(&fok(TradeSide::Buy, 0.50, 1000.0)).await.unwrap();
+        assert!(!too_big.accepted);
+    }
+
+    #[tokio::test]
+    async fn test_maker_orders_rest_until_cancelled() {
+        let exec = PaperOrderExecutor::new(100.0);
+        let order = Order::new_maker("tok".to_string(), TradeSide::Buy, 0.40, 10.0);
+
+        let placed = exec.place_order(&order).await.unwrap();
+        assert_eq!(exec.get_open_orders().await.unwrap().len(), 1);
+        assert!(exec.cancel_order(&placed.order_id).await.unwrap().success);
+        assert!(exec.get_open_orders().await.unwrap().is_empty());
+        assert!(!exec.cancel_order(&placed.order_id).await.unwrap().success);
+    }
+}
@@ -0,0 +1,325 @@
+//! Order Journal - fsync'd JSONL Write-Ahead Log
+//!
+//! Implements the `OrderJournal` port on `<data_dir>/orders.wal`, one
+//! JSON record per line:
+//! - `intent`: client ID and order, appended and fsync'd before the
+//!   order is sent
+//! - `outcome`: placed (with the CLOB order ID) or rejected
+//!
+//! A torn last line (crash mid-append) is skipped on read. `compact`
+//! rewrites the file atomically (tmp → rename) without the intents
+//! resolved before the cutoff.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::domain::time::now_ms;
+use crate::domain::trade::Order;
+use crate::ports::order_journal::{IntentOutcome, JournaledOrder, OrderJournal};
+
+/// One line of the journal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum JournalRecord {
+    /// Order about to be sent.
+    Intent {
+        client_id: String,
+        order: Order,
+        timestamp_ms: u64,
+    },
+    /// What the CLOB answered.
+    Outcome {
+        client_id: String,
+        outcome: IntentOutcome,
+        timestamp_ms: u64,
+    },
+}
+
+/// A journaled intent with the time its outcome was recorded.
+struct Entry {
+    /// Intent and outcome.
+    order: JournaledOrder,
+    /// When the outcome was recorded (Unix ms).
+    resolved_ms: Option<u64>,
+}
+
+/// Append-only, fsync'd order intent journal.
+pub struct FileOrderJournal {
+    /// Path to orders.wal.
+    path: PathBuf,
+    /// Temporary path for compaction.
+    tmp_path: PathBuf,
+    /// Append handle; also serializes reads against writes.
+    file: Mutex<File>,
+}
+
+impl FileOrderJournal {
+    /// Open (or create) the journal in the given data directory.
+    pub async fn new(data_dir: &str) -> Result<Self> {
+        let dir = Path::new(data_dir);
+        fs::create_dir_all(dir)
+            .await
+            .context("Failed to create data directory")?;
+
+        let path = dir.join("orders.wal");
+        let mut file = open_append(&path).await?;
+
+        // Terminate a torn last line so the next record starts clean
+        if let Ok(content) = fs::read(&path).await {
+            if content.last().is_some_and(|b| *b != b'\n') {
+                warn!(path = %path.display(), "Order journal ends mid-record");
+                file.write_all(b"\n")
+                    .await
+                    .context("Failed to repair order journal")?;
+                file.sync_data()
+                    .await
+                    .context("Failed to fsync order journal")?;
+            }
+        }
+        Ok(Self {
+            tmp_path: dir.join("orders.wal.tmp"),
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Append one record and fsync it.
+    async fn append(&self, record: &JournalRecord) -> Result<()> {
+        let mut line = serde_json::to_string(record).context("Failed to serialize journal record")?;
+        line.push('\n');
+
+        let mut file = self.file.lock().await;
+        file.write_all(line.as_bytes())
+            .await
+            .context("Failed to append to order journal")?;
+        file.flush().await.context("Failed to flush order journal")?;
+        file.sync_data()
+            .await
+            .context("Failed to fsync order journal")?;
+        Ok(())
+    }
+
+    /// Fold the journal into intents, oldest first.
+    ///
+    /// Callers hold the file lock.
+    async fn read_entries(&self) -> Result<Vec<Entry>> {
+        let content = match fs::read_to_string(&self.path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).context("Failed to read order journal"),
+        };
+
+        let mut entries: Vec<Entry> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for (line_no, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: JournalRecord = match serde_json::from_str(line) {
+                Ok(record) => record,
+                Err(e) => {
+                    warn!(line = line_no + 1, error = %e, "Skipping malformed order journal line");
+                    continue;
+                }
+            };
+            match record {
+                JournalRecord::Intent {
+                    client_id,
+                    order,
+                    timestamp_ms,
+                } => {
+                    index.insert(client_id.clone(), entries.len());
+                    entries.push(Entry {
+                        order: JournaledOrder {
+                            client_id,
+                            order,
+                            timestamp_ms,
+                            outcome: None,
+                        },
+                        resolved_ms: None,
+                    });
+                }
+                JournalRecord::Outcome {
+                    client_id,
+                    outcome,
+                    timestamp_ms,
+                } => match index.get(&client_id) {
+                    Some(&i) => {
+                        entries[i].order.outcome = Some(outcome);
+                        entries[i].resolved_ms = Some(timestamp_ms);
+                    }
+                    None => {
+                        debug!(client_id = %client_id, "Order journal outcome without intent");
+                    }
+                },
+            }
+        }
+        Ok(entries)
+    }
+}
+
+#[async_trait]
+impl OrderJournal for FileOrderJournal {
+    async fn record_intent(&self, client_id: &str, order: &Order) -> Result<()> {
+        self.append(&JournalRecord::Intent {
+            client_id: client_id.to_string(),
+            order: order.clone(),
+            timestamp_ms: now_ms(),
+        })
+        .await
+    }
+
+    async fn record_outcome(&self, client_id: &str, outcome: IntentOutcome) -> Result<()> {
+        self.append(&JournalRecord::Outcome {
+            client_id: client_id.to_string(),
+            outcome,
+            timestamp_ms: now_ms(),
+        })
+        .await
+    }
+
+    async fn entries(&self) -> Result<Vec<JournaledOrder>> {
+        let _file = self.file.lock().await;
+        Ok(self
+            .read_entries()
+            .await?
+            .into_iter()
+            .map(|e| e.order)
+            .collect())
+    }
+
+    async fn compact(&self, before_ms: u64) -> Result<usize> {
+        let mut file = self.file.lock().await;
+        let entries = self.read_entries().await?;
+        let total = entries.len();
+
+        let kept: Vec<Entry> = entries
+            .into_iter()
+            .filter(|e| e.resolved_ms.is_none_or(|ms| ms >= before_ms))
+            .collect();
+        let dropped = total - kept.len();
+        if dropped == 0 {
+            return Ok(0);
+        }
+
+        let mut content = String::new();
+        for entry in &kept {
+            let JournaledOrder {
+                client_id,
+                order,
+                timestamp_ms,
+                outcome,
+            } = entry.order.clone();
+            let mut records = vec![JournalRecord::Intent {
+                client_id: client_id.clone(),
+                order,
+                timestamp_ms,
+            }];
+            if let (Some(outcome), Some(resolved_ms)) = (outcome, entry.resolved_ms) {
+                records.push(JournalRecord::Outcome {
+                    client_id,
+                    outcome,
+                    timestamp_ms: resolved_ms,
+                });
+            }
+            for record in records {
+                content.push_str(&serde_json::to_string(&record)?);
+                content.push('\n');
+            }
+        }
+        let mut tmp = File::create(&self.tmp_path)
+            .await
+            .context("Failed to create tmp order journal")?;
+        tmp.write_all(content.as_bytes())
+            .await
+            .context("Failed to write tmp order journal")?;
+        tmp.sync_all()
+            .await
+            .context("Failed to fsync tmp order journal")?;
+        fs::rename(&self.tmp_path, &self.path)
+            .await
+            .context("Failed to rename order journal")?;
+        *file = open_append(&self.path).await?;
+
+        info!(dropped, kept = kept.len(), "Order journal compacted");
+        Ok(dropped)
+    }
+}
+
+/// Open a file for appending, creating it if needed.
+async fn open_append(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+        .with_context(|| format!("Failed to open {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::trade::TradeSide;
+
+    #[tokio::test]
+    async fn test_intents_survive_reopen_and_compaction_keeps_pending() {
+        let dir = std::env::temp_dir().join(format!("polybot-journal-{}", std::process::id()));
+        let data_dir = dir.to_str().unwrap();
+        let order = Order::new_maker("tok".to_string(), TradeSide::Buy, 0.45, 10.0);
+
+        let journal = FileOrderJournal::new(data_dir).await.unwrap();
+        journal.record_intent("c1", &order).await.unwrap();
+        journal.record_intent("c2", &order).await.unwrap();
+        journal
+            .record_outcome(
+                "c1",
+                IntentOutcome::Placed {
+                    order_id: "ord_1".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        drop(journal);
+
+        // A crash mid-append leaves a torn line behind
+        let mut file = open_append(&dir.join("orders.wal")).await.unwrap();
+        file.write_all(b"{\"record\":\"intent\",\"client_").await.unwrap();
+        file.flush().await.unwrap();
+        drop(file);
+
+        let journal = FileOrderJournal::new(data_dir).await.unwrap();
+        let entries = journal.entries().await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].order_id(), Some("ord_1"));
+        assert!(entries[1].outcome.is_none());
+
+        assert_eq!(journal.compact(u64::MAX).await.unwrap(), 1);
+        let entries = journal.entries().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].client_id, "c2");
+
+        // The reopened handle still appends after compaction
+        journal
+            .record_outcome(
+                "c2",
+                IntentOutcome::Rejected {
+                    reason: "not enough balance".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(journal.compact(u64::MAX).await.unwrap(), 1);
+        assert!(journal.entries().await.unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
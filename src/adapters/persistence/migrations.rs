@@ -0,0 +1,118 @@
+//! State Migrations - Versioned `BotStateSnapshot` Upgrades
+//!
+//! Snapshots are upgraded as raw JSON, one schema step at a time, before
+//! they are deserialized:
+//! - v1 → v2: pre-0.6 snapshots get explicit `risk` / `portfolio` nulls
+//! - v2 → v3: each open order becomes `{ order, filled: 0 }`
+//!
+//! Snapshots without `schema_version` are v2 when they carry `risk` or
+//! `portfolio`, else v1. Snapshots from a newer schema are refused
+//! rather than silently truncated.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Map, Value};
+
+use crate::ports::repository::{BotStateSnapshot, STATE_SCHEMA_VERSION};
+
+/// Schema version of a raw snapshot.
+pub fn schema_version(state: &Value) -> u32 {
+    match state.get("schema_version").and_then(Value::as_u64) {
+        Some(version) => version as u32,
+        None if state.get("risk").is_some() || state.get("portfolio").is_some() => 2,
+        None => 1,
+    }
+}
+
+/// Upgrade a raw snapshot to the current schema and deserialize it.
+///
+/// Returns the snapshot and the schema it was read from.
+pub fn migrate(mut state: Value) -> Result<(BotStateSnapshot, u32)> {
+    let from = schema_version(&state);
+    if from > STATE_SCHEMA_VERSION {
+        bail!(
+            "State schema v{from} is newer than this build supports (v{STATE_SCHEMA_VERSION})"
+        );
+    }
+
+    let object = state
+        .as_object_mut()
+        .context("State snapshot is not a JSON object")?;
+    for version in from..STATE_SCHEMA_VERSION {
+        match version {
+            1 => v1_to_v2(object),
+            2 => v2_to_v3(object)?,
+            _ => {}
+        }
+    }
+    object.insert("schema_version".to_string(), json!(STATE_SCHEMA_VERSION));
+
+    let snapshot = serde_json::from_value(state)
+        .with_context(|| format!("Failed to parse state migrated from schema v{from}"))?;
+    Ok((snapshot, from))
+}
+
+/// v1 → v2: add the risk and portfolio sections.
+fn v1_to_v2(state: &mut Map<String, Value>) {
+    state.entry("risk").or_insert(Value::Null);
+    state.entry("portfolio").or_insert(Value::Null);
+}
+
+/// v2 → v3: wrap open orders with the size credited from them.
+fn v2_to_v3(state: &mut Map<String, Value>) -> Result<()> {
+    let orders = match state.remove("open_orders") {
+        Some(Value::Array(orders)) => orders,
+        Some(Value::Null) | None => Vec::new(),
+        Some(other) => bail!("open_orders is not an array: {other}"),
+    };
+    let wrapped = orders
+        .into_iter()
+        .map(|order| json!({ "order": order, "filled": 0.0 }))
+        .collect();
+    state.insert("open_orders".to_string(), Value::Array(wrapped));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_v1_snapshot_is_upgraded_to_current() {
+        let v1 = json!({
+            "version": "0.4.2",
+            "timestamp_ms": 1_700_000_000_000u64,
+            "open_orders": [{
+                "id": "ord_1",
+                "token_id": "tok",
+                "side": "Buy",
+                "price": 0.45,
+                "size": 10.0,
+                "order_type": "Gtc",
+                "post_only": true,
+                "timestamp_ms": 1_700_000_000_000u64
+            }],
+            "positions": [["tok", 5.0]],
+            "cumulative_pnl": 12.5,
+            "daily_loss": 1.0
+        });
+        assert_eq!(schema_version(&v1), 1);
+
+        let (state, from) = migrate(v1).unwrap();
+        assert_eq!(from, 1);
+        assert_eq!(state.schema_version, STATE_SCHEMA_VERSION);
+        assert_eq!(state.open_orders[0].order.id, "ord_1");
+        assert_eq!(state.open_orders[0].filled, 0.0);
+        assert!(state.risk.is_none() && state.portfolio.is_none());
+        assert_eq!(state.cumulative_pnl, 12.5);
+    }
+
+    #[test]
+    fn test_current_snapshot_round_trips_and_newer_is_refused() {
+        let current = serde_json::to_value(BotStateSnapshot::new()).unwrap();
+        assert_eq!(schema_version(&current), STATE_SCHEMA_VERSION);
+        assert_eq!(migrate(current).unwrap().1, STATE_SCHEMA_VERSION);
+
+        let newer = json!({ "schema_version": STATE_SCHEMA_VERSION + 1 });
+        assert!(migrate(newer).is_err());
+    }
+}
@@ -1,13 +1,27 @@
-//! Persistence Adapters - JSONL-based File Storage
+//! Persistence Adapters - JSONL Files or Embedded SQLite
 //!
-//! Implements the Repository port using append-only JSONL files
-//! for trade logs and atomic JSON snapshots for bot state.
-//! No database dependency — lightweight and crash-recoverable.
+//! Implements the Repository port twice:
+//! - `RepositoryImpl`: append-only JSONL files for trade logs and
+//!   atomic JSON snapshots for bot state (default)
+//! - `SqliteRepository`: one SQLite database with indexed tables
+//!
+//! `Storage` opens the one chosen in `[storage]`. Older state
+//! snapshots are upgraded on load by `migrations` in both.
+//!
+//! `FileOrderJournal` keeps the order intent write-ahead log
+//! (`orders.wal`) next to either backend.
 
+pub mod journal;
+pub mod migrations;
 pub mod repository_impl;
+pub mod sqlite;
 pub mod state;
+pub mod storage;
 pub mod trades;
 
+pub use journal::FileOrderJournal;
 pub use repository_impl::RepositoryImpl;
+pub use sqlite::SqliteRepository;
 pub use state::StateStore;
+pub use storage::Storage;
 pub use trades::TradeLogger;
@@ -13,7 +13,7 @@ use async_trait::async_trait;
 use super::state::StateStore;
 use super::trades::TradeLogger;
 use crate::ports::repository::{
-    BotStateSnapshot, DailyPnl, Repository, TradeRecord,
+    BotStateSnapshot, DailyPnl, FeeReconciliation, Repository, SettlementRecord, TradeRecord,
 };
 
 /// Concrete repository adapter combining state and trade persistence.
@@ -81,6 +81,22 @@ impl Repository for RepositoryImpl {
         self.trade_logger.load_daily_pnl().await
     }
 
+    async fn save_settlement_report(&self, record: &SettlementRecord) -> Result<()> {
+        self.trade_logger.save_settlement_report(record).await
+    }
+
+    async fn load_settlement_reports(&self) -> Result<Vec<SettlementRecord>> {
+        self.trade_logger.load_settlement_reports().await
+    }
+
+    async fn save_fee_reconciliation(&self, record: &FeeReconciliation) -> Result<()> {
+        self.trade_logger.save_fee_reconciliation(record).await
+    }
+
+    async fn load_fee_reconciliations(&self) -> Result<Vec<FeeReconciliation>> {
+        self.trade_logger.load_fee_reconciliations().await
+    }
+
     async fn is_healthy(&self) -> bool {
         self.state_store.is_healthy().await
             && self.trade_logger.is_healthy().await
@@ -0,0 +1,757 @@
+//! SQLite Repository - Embedded Database Backend
+//!
+//! Second `Repository` implementation, selected with
+//! `storage.backend = "sqlite"`. Everything lives in one database file
+//! (`storage.sqlite_path`, default `<data_dir>/bot.db`):
+//! - `trades`: indexed by time, market and order, so range queries
+//!   read only the matching rows instead of every log file
+//! - `orders` / `fills`: resting orders from each state snapshot; an
+//!   order's growth in credited size is recorded as a fill
+//! - `daily_pnl`, `settlements`, `fee_reconciliations`: one row per
+//!   record, nested parts stored as JSON
+//! - `state`: the latest `BotStateSnapshot`, migrated on load like
+//!   `state.json`
+//!
+//! The schema version is kept in `PRAGMA user_version`; pending
+//! `MIGRATIONS` run in one transaction when the database is opened.
+//! Queries run on the blocking pool over a single WAL-mode connection.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use async_trait::async_trait;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use tracing::{info, instrument};
+
+use super::migrations;
+use super::state::StateStore;
+use super::trades::TradeLogger;
+use crate::ports::repository::{
+    BotStateSnapshot, DailyPnl, FeeReconciliation, Repository, SettlementRecord, TradeRecord,
+};
+
+/// Schema migrations; entry `i` upgrades the database to version `i + 1`.
+const MIGRATIONS: &[&str] = &[
+    // v1: initial schema
+    "CREATE TABLE trades (
+        id TEXT PRIMARY KEY,
+        order_id TEXT NOT NULL,
+        market_id TEXT NOT NULL,
+        side TEXT NOT NULL,
+        price REAL NOT NULL,
+        size REAL NOT NULL,
+        lmsr_fair_value REAL NOT NULL,
+        edge REAL NOT NULL,
+        kelly_fraction REAL NOT NULL,
+        fees REAL NOT NULL,
+        timestamp_ms INTEGER NOT NULL
+    );
+    CREATE INDEX idx_trades_time ON trades (timestamp_ms);
+    CREATE INDEX idx_trades_market ON trades (market_id, timestamp_ms);
+    CREATE INDEX idx_trades_order ON trades (order_id);
+
+    CREATE TABLE orders (
+        id TEXT PRIMARY KEY,
+        token_id TEXT NOT NULL,
+        side TEXT NOT NULL,
+        price REAL NOT NULL,
+        size REAL NOT NULL,
+        order_type TEXT NOT NULL,
+        post_only INTEGER NOT NULL,
+        filled REAL NOT NULL DEFAULT 0,
+        status TEXT NOT NULL,
+        created_ms INTEGER NOT NULL,
+        updated_ms INTEGER NOT NULL
+    );
+    CREATE INDEX idx_orders_status ON orders (status);
+    CREATE INDEX idx_orders_token ON orders (token_id, created_ms);
+
+    CREATE TABLE fills (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        order_id TEXT NOT NULL,
+        token_id TEXT NOT NULL,
+        side TEXT NOT NULL,
+        price REAL NOT NULL,
+        size REAL NOT NULL,
+        timestamp_ms INTEGER NOT NULL
+    );
+    CREATE INDEX idx_fills_order ON fills (order_id);
+    CREATE INDEX idx_fills_time ON fills (timestamp_ms);
+
+    CREATE TABLE daily_pnl (
+        date TEXT PRIMARY KEY,
+        realized_pnl REAL NOT NULL,
+        unrealized_pnl REAL NOT NULL,
+        trade_count INTEGER NOT NULL,
+        volume REAL NOT NULL,
+        max_drawdown REAL NOT NULL,
+        fees REAL NOT NULL,
+        rebates REAL NOT NULL
+    );
+
+    CREATE TABLE settlements (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        timestamp_ms INTEGER NOT NULL,
+        total_usdc_recovered REAL NOT NULL,
+        realized_pnl REAL NOT NULL,
+        markets_settled INTEGER NOT NULL,
+        markets_failed INTEGER NOT NULL,
+        entries TEXT NOT NULL
+    );
+    CREATE INDEX idx_settlements_time ON settlements (timestamp_ms);
+
+    CREATE TABLE fee_reconciliations (
+        date TEXT PRIMARY KEY,
+        timestamp_ms INTEGER NOT NULL,
+        expected_fees REAL NOT NULL,
+        actual_fees REAL NOT NULL,
+        rebates REAL NOT NULL,
+        booked_fees REAL,
+        booked_rebates REAL,
+        markets TEXT NOT NULL
+    );
+
+    CREATE TABLE state (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        schema_version INTEGER NOT NULL,
+        timestamp_ms INTEGER NOT NULL,
+        snapshot TEXT NOT NULL
+    );",
+];
+
+/// Records copied by `SqliteRepository::import_jsonl`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Trades inserted (already present IDs are skipped).
+    pub trades: usize,
+    /// Daily PnL records written.
+    pub daily_pnl: usize,
+    /// Settlement reports inserted.
+    pub settlements: usize,
+    /// Fee reconciliations written.
+    pub fee_reconciliations: usize,
+    /// Whether `state.json` was imported (only into an empty database).
+    pub state: bool,
+}
+
+/// Repository backed by an embedded SQLite database.
+pub struct SqliteRepository {
+    /// Single connection, used from the blocking pool.
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteRepository {
+    /// Open (or create) the database file and apply pending migrations.
+    pub async fn open(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .context("Failed to create database directory")?;
+        }
+        let path = path.to_path_buf();
+        let conn = tokio::task::spawn_blocking(move || {
+            Connection::open(&path)
+                .with_context(|| format!("Failed to open SQLite database {}", path.display()))
+        })
+        .await
+        .context("SQLite open task failed")??;
+        Self::from_connection(conn)
+    }
+
+    /// In-memory database (tests and dry runs).
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    /// Configure the connection and bring the schema up to date.
+    fn from_connection(mut conn: Connection) -> Result<Self> {
+        let journal: String =
+            conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        let version = migrate_schema(&mut conn)?;
+        info!(schema_version = version, journal_mode = %journal, "SQLite repository ready");
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Run `f` on the connection in the blocking pool.
+    async fn call<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+    {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn
+                .lock()
+                .map_err(|_| anyhow!("SQLite connection lock poisoned"))?;
+            f(&mut conn)
+        })
+        .await
+        .context("SQLite task failed")?
+    }
+
+    /// Copy the JSONL logs and `state.json` of `data_dir` into the
+    /// database. Safe to re-run: trades already present are skipped,
+    /// PnL and fee records are replaced by date, settlement reports
+    /// are matched by timestamp and the state is only imported into
+    /// a database that has none.
+    #[instrument(skip(self))]
+    pub async fn import_jsonl(&self, data_dir: &str) -> Result<ImportSummary> {
+        let logger = TradeLogger::new(data_dir).await?;
+        let trades = logger.load_all_trades().await?;
+        let daily = logger.load_daily_pnl().await?;
+        let settlements = logger.load_settlement_reports().await?;
+        let fees = logger.load_fee_reconciliations().await?;
+        let state = StateStore::new(data_dir).await?.load().await?;
+
+        let summary = self
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let mut summary = ImportSummary::default();
+                for trade in &trades {
+                    summary.trades += insert_trade(&tx, trade, true)?;
+                }
+                for pnl in &daily {
+                    upsert_daily_pnl(&tx, pnl)?;
+                    summary.daily_pnl += 1;
+                }
+                for report in &settlements {
+                    let exists: bool = tx.query_row(
+                        "SELECT EXISTS (SELECT 1 FROM settlements WHERE timestamp_ms = ?1)",
+                        params![report.timestamp_ms],
+                        |row| row.get(0),
+                    )?;
+                    if !exists {
+                        insert_settlement(&tx, report)?;
+                        summary.settlements += 1;
+                    }
+                }
+                for record in &fees {
+                    upsert_fee_reconciliation(&tx, record)?;
+                    summary.fee_reconciliations += 1;
+                }
+                if let Some(state) = &state {
+                    let has_state: bool =
+                        tx.query_row("SELECT EXISTS (SELECT 1 FROM state)", [], |row| row.get(0))?;
+                    if !has_state {
+                        write_state(&tx, state)?;
+                        summary.state = true;
+                    }
+                }
+                tx.commit()?;
+                Ok(summary)
+            })
+            .await?;
+
+        info!(
+            trades = summary.trades,
+            daily_pnl = summary.daily_pnl,
+            settlements = summary.settlements,
+            fee_reconciliations = summary.fee_reconciliations,
+            state = summary.state,
+            "JSONL data imported into SQLite"
+        );
+        Ok(summary)
+    }
+}
+
+#[async_trait]
+impl Repository for SqliteRepository {
+    async fn save_trade(&self, record: &TradeRecord) -> Result<()> {
+        let record = record.clone();
+        self.call(move |conn| insert_trade(conn, &record, false).map(drop))
+            .await
+    }
+
+    async fn load_trades(&self) -> Result<Vec<TradeRecord>> {
+        self.call(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, order_id, market_id, side, price, size, lmsr_fair_value,
+                        edge, kelly_fraction, fees, timestamp_ms
+                 FROM trades ORDER BY timestamp_ms",
+            )?;
+            let trades: Vec<TradeRecord> =
+                stmt.query_map([], trade_from_row)?.collect::<Result<_, _>>()?;
+            Ok(trades)
+        })
+        .await
+    }
+
+    async fn load_trades_range(&self, from_ms: u64, to_ms: u64) -> Result<Vec<TradeRecord>> {
+        self.call(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, order_id, market_id, side, price, size, lmsr_fair_value,
+                        edge, kelly_fraction, fees, timestamp_ms
+                 FROM trades WHERE timestamp_ms BETWEEN ?1 AND ?2 ORDER BY timestamp_ms",
+            )?;
+            let trades: Vec<TradeRecord> = stmt
+                .query_map(params![from_ms, to_ms], trade_from_row)?
+                .collect::<Result<_, _>>()?;
+            Ok(trades)
+        })
+        .await
+    }
+
+    async fn save_state(&self, state: &BotStateSnapshot) -> Result<()> {
+        let state = state.clone();
+        self.call(move |conn| {
+            let tx = conn.transaction()?;
+            write_state(&tx, &state)?;
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn load_latest_state(&self) -> Result<Option<BotStateSnapshot>> {
+        let json: Option<String> = self
+            .call(|conn| {
+                Ok(conn
+                    .query_row("SELECT snapshot FROM state WHERE id = 1", [], |row| row.get(0))
+                    .optional()?)
+            })
+            .await?;
+        let Some(json) = json else {
+            info!("No state in database, starting fresh");
+            return Ok(None);
+        };
+
+        let raw: serde_json::Value =
+            serde_json::from_str(&json).context("Failed to parse stored state JSON")?;
+        let (state, from) = migrations::migrate(raw)?;
+        info!(
+            version = %state.version,
+            schema_version = state.schema_version,
+            migrated_from = from,
+            open_orders = state.open_orders.len(),
+            "State snapshot loaded"
+        );
+        Ok(Some(state))
+    }
+
+    async fn save_daily_pnl(&self, pnl: &DailyPnl) -> Result<()> {
+        let pnl = pnl.clone();
+        self.call(move |conn| upsert_daily_pnl(conn, &pnl)).await
+    }
+
+    async fn load_daily_pnl(&self) -> Result<Vec<DailyPnl>> {
+        self.call(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT date, realized_pnl, unrealized_pnl, trade_count, volume,
+                        max_drawdown, fees, rebates
+                 FROM daily_pnl ORDER BY date",
+            )?;
+            let records: Vec<DailyPnl> = stmt
+                .query_map([], |row| {
+                    Ok(DailyPnl {
+                        date: row.get(0)?,
+                        realized_pnl: row.get(1)?,
+                        unrealized_pnl: row.get(2)?,
+                        trade_count: row.get(3)?,
+                        volume: row.get(4)?,
+                        max_drawdown: row.get(5)?,
+                        fees: row.get(6)?,
+                        rebates: row.get(7)?,
+                    })
+                })?
+                .collect::<Result<_, _>>()?;
+            Ok(records)
+        })
+        .await
+    }
+
+    async fn save_settlement_report(&self, record: &SettlementRecord) -> Result<()> {
+        let record = record.clone();
+        self.call(move |conn| insert_settlement(conn, &record)).await
+    }
+
+    async fn load_settlement_reports(&self) -> Result<Vec<SettlementRecord>> {
+        self.call(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT timestamp_ms, entries, total_usdc_recovered, realized_pnl,
+                        markets_settled, markets_failed
+                 FROM settlements ORDER BY timestamp_ms, id",
+            )?;
+            let rows: Vec<(SettlementRecord, String)> = stmt
+                .query_map([], |row| {
+                    Ok((
+                        SettlementRecord {
+                            timestamp_ms: row.get(0)?,
+                            entries: Vec::new(),
+                            total_usdc_recovered: row.get(2)?,
+                            realized_pnl: row.get(3)?,
+                            markets_settled: row.get(4)?,
+                            markets_failed: row.get(5)?,
+                        },
+                        row.get(1)?,
+                    ))
+                })?
+                .collect::<Result<_, _>>()?;
+            rows.into_iter()
+                .map(|(mut record, entries)| {
+                    record.entries = serde_json::from_str(&entries)
+                        .context("Failed to parse settlement entries")?;
+                    Ok(record)
+                })
+                .collect()
+        })
+        .await
+    }
+
+    async fn save_fee_reconciliation(&self, record: &FeeReconciliation) -> Result<()> {
+        let record = record.clone();
+        self.call(move |conn| upsert_fee_reconciliation(conn, &record))
+            .await
+    }
+
+    async fn load_fee_reconciliations(&self) -> Result<Vec<FeeReconciliation>> {
+        self.call(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT date, timestamp_ms, markets, expected_fees, actual_fees, rebates,
+                        booked_fees, booked_rebates
+                 FROM fee_reconciliations ORDER BY date",
+            )?;
+            let rows: Vec<(FeeReconciliation, String)> = stmt
+                .query_map([], |row| {
+                    Ok((
+                        FeeReconciliation {
+                            date: row.get(0)?,
+                            timestamp_ms: row.get(1)?,
+                            markets: Vec::new(),
+                            expected_fees: row.get(3)?,
+                            actual_fees: row.get(4)?,
+                            rebates: row.get(5)?,
+                            booked_fees: row.get(6)?,
+                            booked_rebates: row.get(7)?,
+                        },
+                        row.get(2)?,
+                    ))
+                })?
+                .collect::<Result<_, _>>()?;
+            rows.into_iter()
+                .map(|(mut record, markets)| {
+                    record.markets = serde_json::from_str(&markets)
+                        .context("Failed to parse fee reconciliation markets")?;
+                    Ok(record)
+                })
+                .collect()
+        })
+        .await
+    }
+
+    async fn is_healthy(&self) -> bool {
+        self.call(|conn| Ok(conn.query_row("SELECT 1", [], |row| row.get::<_, i64>(0))?))
+            .await
+            .is_ok()
+    }
+}
+
+/// Apply pending migrations; returns the resulting schema version.
+fn migrate_schema(conn: &mut Connection) -> Result<usize> {
+    let current: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    ensure!(
+        current <= MIGRATIONS.len(),
+        "Database schema v{current} is newer than this build supports (v{})",
+        MIGRATIONS.len()
+    );
+    if current == MIGRATIONS.len() {
+        return Ok(current);
+    }
+
+    let tx = conn.transaction()?;
+    for (version, sql) in MIGRATIONS.iter().enumerate().skip(current) {
+        tx.execute_batch(sql)
+            .with_context(|| format!("SQLite migration to v{} failed", version + 1))?;
+    }
+    tx.pragma_update(None, "user_version", MIGRATIONS.len())?;
+    tx.commit()?;
+    info!(from = current, to = MIGRATIONS.len(), "SQLite schema migrated");
+    Ok(MIGRATIONS.len())
+}
+
+/// Insert a trade; returns rows written (0 when skipping a known ID).
+fn insert_trade(conn: &Connection, t: &TradeRecord, skip_existing: bool) -> Result<usize> {
+    let verb = if skip_existing { "INSERT OR IGNORE" } else { "INSERT" };
+    let sql = format!(
+        "{verb} INTO trades (id, order_id, market_id, side, price, size, lmsr_fair_value,
+                             edge, kelly_fraction, fees, timestamp_ms)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+    );
+    let written = conn
+        .prepare_cached(&sql)?
+        .execute(params![
+            t.id,
+            t.order_id,
+            t.market_id,
+            t.side,
+            t.price,
+            t.size,
+            t.lmsr_fair_value,
+            t.edge,
+            t.kelly_fraction,
+            t.fees,
+            t.timestamp_ms,
+        ])
+        .with_context(|| format!("Failed to insert trade {}", t.id))?;
+    Ok(written)
+}
+
+/// Trade from a `trades` row (column order of the SELECTs above).
+fn trade_from_row(row: &Row<'_>) -> rusqlite::Result<TradeRecord> {
+    Ok(TradeRecord {
+        id: row.get(0)?,
+        order_id: row.get(1)?,
+        market_id: row.get(2)?,
+        side: row.get(3)?,
+        price: row.get(4)?,
+        size: row.get(5)?,
+        lmsr_fair_value: row.get(6)?,
+        edge: row.get(7)?,
+        kelly_fraction: row.get(8)?,
+        fees: row.get(9)?,
+        timestamp_ms: row.get(10)?,
+    })
+}
+
+/// Write (or replace) the day's PnL.
+fn upsert_daily_pnl(conn: &Connection, p: &DailyPnl) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO daily_pnl (date, realized_pnl, unrealized_pnl, trade_count,
+                                           volume, max_drawdown, fees, rebates)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            p.date,
+            p.realized_pnl,
+            p.unrealized_pnl,
+            p.trade_count,
+            p.volume,
+            p.max_drawdown,
+            p.fees,
+            p.rebates,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Append a settlement report.
+fn insert_settlement(conn: &Connection, r: &SettlementRecord) -> Result<()> {
+    let entries = serde_json::to_string(&r.entries).context("Failed to serialize entries")?;
+    conn.execute(
+        "INSERT INTO settlements (timestamp_ms, total_usdc_recovered, realized_pnl,
+                                  markets_settled, markets_failed, entries)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            r.timestamp_ms,
+            r.total_usdc_recovered,
+            r.realized_pnl,
+            r.markets_settled,
+            r.markets_failed,
+            entries,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Write (or replace) the day's fee reconciliation.
+fn upsert_fee_reconciliation(conn: &Connection, r: &FeeReconciliation) -> Result<()> {
+    let markets = serde_json::to_string(&r.markets).context("Failed to serialize markets")?;
+    conn.execute(
+        "INSERT OR REPLACE INTO fee_reconciliations (date, timestamp_ms, expected_fees,
+                                                     actual_fees, rebates, booked_fees,
+                                                     booked_rebates, markets)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            r.date,
+            r.timestamp_ms,
+            r.expected_fees,
+            r.actual_fees,
+            r.rebates,
+            r.booked_fees,
+            r.booked_rebates,
+            markets,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Replace the stored snapshot and sync `orders` / `fills` with its
+/// open orders: new orders are inserted, growth in credited size is
+/// recorded as a fill, and orders no longer present are closed.
+fn write_state(conn: &Connection, state: &BotStateSnapshot) -> Result<()> {
+    let json = serde_json::to_string(state).context("Failed to serialize state")?;
+    conn.execute(
+        "INSERT OR REPLACE INTO state (id, schema_version, timestamp_ms, snapshot)
+         VALUES (1, ?1, ?2, ?3)",
+        params![state.schema_version, state.timestamp_ms, json],
+    )?;
+
+    let now = state.timestamp_ms;
+    let mut open = HashSet::new();
+    for o in state.open_orders.iter().filter(|o| !o.order.id.is_empty()) {
+        let order = &o.order;
+        open.insert(order.id.clone());
+        let previous: Option<f64> = conn
+            .query_row(
+                "SELECT filled FROM orders WHERE id = ?1",
+                params![order.id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let order_type =
+            serde_json::to_string(&order.order_type).context("Failed to serialize order type")?;
+        conn.prepare_cached(
+            "INSERT INTO orders (id, token_id, side, price, size, order_type, post_only,
+                                 filled, status, created_ms, updated_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'open', ?9, ?10)
+             ON CONFLICT (id) DO UPDATE SET
+                 filled = excluded.filled, status = 'open', updated_ms = excluded.updated_ms",
+        )?
+        .execute(params![
+            order.id,
+            order.token_id,
+            format!("{:?}", order.side),
+            order.price,
+            order.size,
+            order_type,
+            order.post_only,
+            o.filled,
+            order.timestamp_ms,
+            now,
+        ])?;
+
+        let delta = o.filled - previous.unwrap_or(0.0);
+        if delta > f64::EPSILON {
+            conn.prepare_cached(
+                "INSERT INTO fills (order_id, token_id, side, price, size, timestamp_ms)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?
+            .execute(params![
+                order.id,
+                order.token_id,
+                format!("{:?}", order.side),
+                order.price,
+                delta,
+                now,
+            ])?;
+        }
+    }
+
+    let tracked: Vec<String> = conn
+        .prepare_cached("SELECT id FROM orders WHERE status = 'open'")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    for id in tracked.iter().filter(|id| !open.contains(*id)) {
+        conn.execute(
+            "UPDATE orders SET status = 'closed', updated_ms = ?2 WHERE id = ?1",
+            params![id, now],
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::domain::trade::{Order, TradeSide};
+    use crate::ports::repository::OpenOrderState;
+
+    fn trade(id: &str, timestamp_ms: u64) -> TradeRecord {
+        TradeRecord {
+            id: id.to_string(),
+            order_id: format!("ord_{id}"),
+            market_id: "0xmarket".to_string(),
+            side: "BUY".to_string(),
+            price: 0.45,
+            size: 10.0,
+            lmsr_fair_value: 0.47,
+            edge: 0.02,
+            kelly_fraction: 0.25,
+            fees: 0.0,
+            timestamp_ms,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trade_range_and_state_round_trip() {
+        let repo = SqliteRepository::open_in_memory().unwrap();
+        for (id, ts) in [("t3", 3_000), ("t1", 1_000), ("t2", 2_000)] {
+            repo.save_trade(&trade(id, ts)).await.unwrap();
+        }
+        assert!(repo.save_trade(&trade("t1", 1_000)).await.is_err());
+
+        let range = repo.load_trades_range(1_500, 3_000).await.unwrap();
+        let ids: Vec<&str> = range.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["t2", "t3"]);
+        assert_eq!(repo.load_trades().await.unwrap()[0].id, "t1");
+
+        assert!(repo.load_latest_state().await.unwrap().is_none());
+        let mut state = BotStateSnapshot::new();
+        state.cumulative_pnl = 4.2;
+        repo.save_state(&state).await.unwrap();
+        let loaded = repo.load_latest_state().await.unwrap().unwrap();
+        assert_eq!(loaded.cumulative_pnl, 4.2);
+        assert!(repo.is_healthy().await);
+    }
+
+    #[tokio::test]
+    async fn test_snapshots_record_orders_and_fills() {
+        let repo = SqliteRepository::open_in_memory().unwrap();
+        let mut order = Order::new_maker("tok".to_string(), TradeSide::Buy, 0.40, 50.0);
+        order.id = "ord_1".to_string();
+
+        let mut state = BotStateSnapshot::new();
+        for (filled, ts) in [(0.0, 1_000), (20.0, 2_000), (20.0, 3_000)] {
+            state.timestamp_ms = ts;
+            state.open_orders = vec![OpenOrderState { order: order.clone(), filled }];
+            repo.save_state(&state).await.unwrap();
+        }
+        state.timestamp_ms = 4_000;
+        state.open_orders.clear();
+        repo.save_state(&state).await.unwrap();
+
+        let (status, filled, fills, fill_size): (String, f64, i64, f64) = repo
+            .call(|conn| {
+                Ok(conn.query_row(
+                    "SELECT o.status, o.filled, COUNT(f.id), SUM(f.size)
+                     FROM orders o JOIN fills f ON f.order_id = o.id WHERE o.id = 'ord_1'",
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+                )?)
+            })
+            .await
+            .unwrap();
+        assert_eq!(status, "closed");
+        assert_eq!((filled, fills, fill_size), (20.0, 1, 20.0));
+    }
+
+    #[tokio::test]
+    async fn test_import_jsonl_is_idempotent() {
+        let dir = std::env::temp_dir().join(format!("polybot-sqlite-{}", std::process::id()));
+        let data_dir = dir.to_str().unwrap();
+        let logger = TradeLogger::new(data_dir).await.unwrap();
+        logger.append_trade(&trade("t1", 1_000)).await.unwrap();
+        logger.append_trade(&trade("t2", 2_000)).await.unwrap();
+        StateStore::new(data_dir)
+            .await
+            .unwrap()
+            .save(&BotStateSnapshot::new())
+            .await
+            .unwrap();
+
+        let repo = SqliteRepository::open(&dir.join("bot.db")).await.unwrap();
+        let first = repo.import_jsonl(data_dir).await.unwrap();
+        assert_eq!(first.trades, 2);
+        assert!(first.state);
+        let again = repo.import_jsonl(data_dir).await.unwrap();
+        assert_eq!(again.trades, 0);
+        assert!(!again.state);
+        assert_eq!(repo.load_trades().await.unwrap().len(), 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -3,6 +3,9 @@
 //! Saves bot state snapshots to `state.json` using atomic writes
 //! (write to tmp file, then rename). This guarantees crash safety
 //! and prevents partial writes from corrupting state.
+//!
+//! Snapshots from an older schema are upgraded on load (`migrations`);
+//! the original file is kept as `state.json.v<N>.bak`.
 
 use std::path::{Path, PathBuf};
 
@@ -10,7 +13,8 @@ use anyhow::{Context, Result};
 use tokio::fs;
 use tracing::{info, instrument, warn};
 
-use crate::ports::repository::BotStateSnapshot;
+use super::migrations;
+use crate::ports::repository::{BotStateSnapshot, STATE_SCHEMA_VERSION};
 
 /// Atomic JSON state store for crash recovery.
 ///
@@ -18,6 +22,8 @@ use crate::ports::repository::BotStateSnapshot;
 /// renamed to `state.json`. This ensures the file is always
 /// either the old or new version, never a partial write.
 pub struct StateStore {
+    /// Data directory holding the state files.
+    dir: PathBuf,
     /// Path to state.json.
     state_path: PathBuf,
     /// Temporary path for atomic writes.
@@ -35,6 +41,7 @@ impl StateStore {
             .context("Failed to create data directory")?;
 
         Ok(Self {
+            dir: dir.to_path_buf(),
             state_path: dir.join("state.json"),
             tmp_path: dir.join("state.json.tmp"),
         })
@@ -82,11 +89,26 @@ impl StateStore {
             .await
             .context("Failed to read state file")?;
 
-        let state: BotStateSnapshot =
+        let raw: serde_json::Value =
             serde_json::from_str(&json).context("Failed to parse state JSON")?;
+        let (state, from) = migrations::migrate(raw)?;
+
+        if from < STATE_SCHEMA_VERSION {
+            let backup = self.dir.join(format!("state.json.v{from}.bak"));
+            if let Err(e) = fs::write(&backup, &json).await {
+                warn!(error = %e, "Failed to back up pre-migration state");
+            }
+            info!(
+                from,
+                to = STATE_SCHEMA_VERSION,
+                backup = %backup.display(),
+                "State snapshot migrated"
+            );
+        }
 
         info!(
             version = %state.version,
+            schema_version = state.schema_version,
             open_orders = state.open_orders.len(),
             "State snapshot loaded"
         );
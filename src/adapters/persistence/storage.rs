@@ -0,0 +1,140 @@
+//! Storage - Repository Backend Selected by `[storage]`
+//!
+//! `storage.backend = "jsonl"` (default) keeps `state.json` and the
+//! JSONL logs under `bot.data_dir`; `"sqlite"` uses the embedded
+//! database at `storage.sqlite_path`. Both sit behind one concrete type
+//! so the bot and the CLI stay generic over a single `Repository`.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tracing::info;
+
+use super::repository_impl::RepositoryImpl;
+use super::sqlite::SqliteRepository;
+use crate::config::{AppConfig, StorageBackend};
+use crate::ports::repository::{
+    BotStateSnapshot, DailyPnl, FeeReconciliation, Repository, SettlementRecord, TradeRecord,
+};
+
+/// The configured repository backend.
+pub enum Storage {
+    /// JSON snapshot + JSONL logs.
+    Jsonl(RepositoryImpl),
+    /// Embedded SQLite database.
+    Sqlite(SqliteRepository),
+}
+
+impl Storage {
+    /// Open the backend chosen in `[storage]`.
+    pub async fn from_config(config: &AppConfig) -> Result<Self> {
+        let data_dir = &config.bot.data_dir;
+        let storage = match config.storage.backend {
+            StorageBackend::Jsonl => Self::Jsonl(RepositoryImpl::from_data_dir(data_dir).await?),
+            StorageBackend::Sqlite => {
+                let path = config.storage.sqlite_path(data_dir);
+                Self::Sqlite(
+                    SqliteRepository::open(&path)
+                        .await
+                        .with_context(|| format!("Failed to open {}", path.display()))?,
+                )
+            }
+        };
+        info!(backend = storage.backend(), data_dir = %data_dir, "Storage opened");
+        Ok(storage)
+    }
+
+    /// Backend name for logs.
+    pub fn backend(&self) -> &'static str {
+        match self {
+            Self::Jsonl(_) => "jsonl",
+            Self::Sqlite(_) => "sqlite",
+        }
+    }
+}
+
+#[async_trait]
+impl Repository for Storage {
+    async fn save_trade(&self, record: &TradeRecord) -> Result<()> {
+        match self {
+            Self::Jsonl(repo) => repo.save_trade(record).await,
+            Self::Sqlite(repo) => repo.save_trade(record).await,
+        }
+    }
+
+    async fn load_trades(&self) -> Result<Vec<TradeRecord>> {
+        match self {
+            Self::Jsonl(repo) => repo.load_trades().await,
+            Self::Sqlite(repo) => repo.load_trades().await,
+        }
+    }
+
+    async fn load_trades_range(&self, from_ms: u64, to_ms: u64) -> Result<Vec<TradeRecord>> {
+        match self {
+            Self::Jsonl(repo) => repo.load_trades_range(from_ms, to_ms).await,
+            Self::Sqlite(repo) => repo.load_trades_range(from_ms, to_ms).await,
+        }
+    }
+
+    async fn save_state(&self, state: &BotStateSnapshot) -> Result<()> {
+        match self {
+            Self::Jsonl(repo) => repo.save_state(state).await,
+            Self::Sqlite(repo) => repo.save_state(state).await,
+        }
+    }
+
+    async fn load_latest_state(&self) -> Result<Option<BotStateSnapshot>> {
+        match self {
+            Self::Jsonl(repo) => repo.load_latest_state().await,
+            Self::Sqlite(repo) => repo.load_latest_state().await,
+        }
+    }
+
+    async fn save_daily_pnl(&self, pnl: &DailyPnl) -> Result<()> {
+        match self {
+            Self::Jsonl(repo) => repo.save_daily_pnl(pnl).await,
+            Self::Sqlite(repo) => repo.save_daily_pnl(pnl).await,
+        }
+    }
+
+    async fn load_daily_pnl(&self) -> Result<Vec<DailyPnl>> {
+        match self {
+            Self::Jsonl(repo) => repo.load_daily_pnl().await,
+            Self::Sqlite(repo) => repo.load_daily_pnl().await,
+        }
+    }
+
+    async fn save_settlement_report(&self, record: &SettlementRecord) -> Result<()> {
+        match self {
+            Self::Jsonl(repo) => repo.save_settlement_report(record).await,
+            Self::Sqlite(repo) => repo.save_settlement_report(record).await,
+        }
+    }
+
+    async fn load_settlement_reports(&self) -> Result<Vec<SettlementRecord>> {
+        match self {
+            Self::Jsonl(repo) => repo.load_settlement_reports().await,
+            Self::Sqlite(repo) => repo.load_settlement_reports().await,
+        }
+    }
+
+    async fn save_fee_reconciliation(&self, record: &FeeReconciliation) -> Result<()> {
+        match self {
+            Self::Jsonl(repo) => repo.save_fee_reconciliation(record).await,
+            Self::Sqlite(repo) => repo.save_fee_reconciliation(record).await,
+        }
+    }
+
+    async fn load_fee_reconciliations(&self) -> Result<Vec<FeeReconciliation>> {
+        match self {
+            Self::Jsonl(repo) => repo.load_fee_reconciliations().await,
+            Self::Sqlite(repo) => repo.load_fee_reconciliations().await,
+        }
+    }
+
+    async fn is_healthy(&self) -> bool {
+        match self {
+            Self::Jsonl(repo) => repo.is_healthy().await,
+            Self::Sqlite(repo) => repo.is_healthy().await,
+        }
+    }
+}
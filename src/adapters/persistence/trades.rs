@@ -7,12 +7,12 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
-use chrono::{NaiveDate, Utc};
+use chrono::Utc;
 use tokio::fs::{self, OpenOptions};
 use tokio::io::AsyncWriteExt;
 use tracing::{info, instrument};
 
-use crate::ports::repository::{DailyPnl, TradeRecord};
+use crate::ports::repository::{DailyPnl, FeeReconciliation, SettlementRecord, TradeRecord};
 
 /// Append-only JSONL trade logger with daily file rotation.
 ///
@@ -81,7 +81,7 @@ impl TradeLogger {
 
         while let Some(entry) = entries.next_entry().await? {
             let path = entry.path();
-            if path.extension().map_or(false, |ext| ext == "jsonl") {
+            if path.extension().is_some_and(|ext| ext == "jsonl") {
                 let content = fs::read_to_string(&path).await?;
                 for line in content.lines() {
                     if line.trim().is_empty() {
@@ -163,6 +163,80 @@ impl TradeLogger {
         Ok(records)
     }
 
+    /// Append a settlement sweep report to `pnl/settlements.jsonl`.
+    #[instrument(skip(self, record), fields(settled = record.markets_settled))]
+    pub async fn save_settlement_report(&self, record: &SettlementRecord) -> Result<()> {
+        let path = self.pnl_dir.join("settlements.jsonl");
+
+        let mut json = serde_json::to_string(record)
+            .context("Failed to serialize settlement report")?;
+        json.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await?;
+
+        file.write_all(json.as_bytes()).await?;
+        file.flush().await?;
+
+        Ok(())
+    }
+
+    /// Load all settlement sweep reports.
+    pub async fn load_settlement_reports(&self) -> Result<Vec<SettlementRecord>> {
+        let path = self.pnl_dir.join("settlements.jsonl");
+
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path).await?;
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str::<SettlementRecord>(line).ok())
+            .collect())
+    }
+
+    /// Append a daily fee reconciliation to `pnl/fees.jsonl`.
+    #[instrument(skip(self, record), fields(date = %record.date))]
+    pub async fn save_fee_reconciliation(&self, record: &FeeReconciliation) -> Result<()> {
+        let path = self.pnl_dir.join("fees.jsonl");
+
+        let mut json = serde_json::to_string(record)
+            .context("Failed to serialize fee reconciliation")?;
+        json.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await?;
+
+        file.write_all(json.as_bytes()).await?;
+        file.flush().await?;
+
+        Ok(())
+    }
+
+    /// Load all daily fee reconciliations.
+    pub async fn load_fee_reconciliations(&self) -> Result<Vec<FeeReconciliation>> {
+        let path = self.pnl_dir.join("fees.jsonl");
+
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path).await?;
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str::<FeeReconciliation>(line).ok())
+            .collect())
+    }
+
     /// Check if the trades directory is writable.
     pub async fn is_healthy(&self) -> bool {
         let test_path = self.trades_dir.join(".health_check");
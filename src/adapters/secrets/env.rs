@@ -0,0 +1,46 @@
+//! Environment Secrets - `[secrets]` as a `SecretProvider`
+//!
+//! The default backend: serves the values the config layers read from
+//! `POLY_API_KEY`, `PRIVATE_KEY`, `POLYBOT__SECRETS__*`, ... Anything
+//! that can dump the process environment can read these, so live
+//! wallets should use a keystore and the file or Vault backend.
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::config::SecretsConfig;
+use crate::ports::secrets::{self, Secret, SecretProvider};
+
+/// Serves secrets from the resolved `[secrets]` section.
+pub struct EnvSecretProvider {
+    /// Secrets read from the environment at startup.
+    secrets: SecretsConfig,
+}
+
+impl EnvSecretProvider {
+    /// Wrap the resolved `[secrets]`.
+    pub fn new(secrets: SecretsConfig) -> Self {
+        Self { secrets }
+    }
+}
+
+#[async_trait]
+impl SecretProvider for EnvSecretProvider {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Secret>> {
+        let s = &self.secrets;
+        let value = match key {
+            secrets::POLY_API_KEY => &s.poly_api_key,
+            secrets::POLY_API_SECRET => &s.poly_api_secret,
+            secrets::POLY_PASSPHRASE => &s.poly_passphrase,
+            secrets::PRIVATE_KEY => &s.private_key,
+            secrets::KEYSTORE_PASSWORD => &s.keystore_password,
+            secrets::ADMIN_TOKEN => &s.admin_token,
+            _ => return Ok(None),
+        };
+        Ok(value.clone())
+    }
+}
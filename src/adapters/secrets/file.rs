@@ -0,0 +1,104 @@
+//! File Secrets - JSON Secrets File as a `SecretProvider`
+//!
+//! Reads a flat JSON object (`{"poly_api_secret": "...", ...}`), e.g.
+//! a Docker / Kubernetes secret mount. The file is re-read on every
+//! lookup so rotated values are picked up, and on Unix it must not be
+//! readable by group or others.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use zeroize::Zeroize;
+
+use crate::ports::secrets::{Secret, SecretProvider};
+
+/// Serves secrets from a JSON file.
+pub struct FileSecretProvider {
+    /// Secrets file.
+    path: PathBuf,
+}
+
+impl FileSecretProvider {
+    /// Provider reading `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Refuse files other users can read.
+    #[cfg(unix)]
+    async fn check_permissions(&self) -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = tokio::fs::metadata(&self.path)
+            .await
+            .with_context(|| format!("Failed to stat secrets file {}", self.path.display()))?
+            .permissions()
+            .mode();
+        anyhow::ensure!(
+            mode.trailing_zeros() >= 6,
+            "Secrets file {} is accessible by other users (mode {:o}); chmod 600 it",
+            self.path.display(),
+            mode & 0o777
+        );
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    async fn check_permissions(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SecretProvider for FileSecretProvider {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Secret>> {
+        self.check_permissions().await?;
+        let mut content = tokio::fs::read_to_string(&self.path)
+            .await
+            .with_context(|| format!("Failed to read secrets file {}", self.path.display()))?;
+        let parsed: Result<HashMap<String, Secret>, _> = serde_json::from_str(&content);
+        content.zeroize();
+
+        let mut secrets = parsed.with_context(|| {
+            format!(
+                "Secrets file {} must be a JSON object of strings",
+                self.path.display()
+            )
+        })?;
+        Ok(secrets.remove(key).filter(|s| !s.is_empty()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reads_key_and_rejects_open_permissions() {
+        let dir = std::env::temp_dir().join(format!("polybot-secrets-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("secrets.json");
+        std::fs::write(&path, r#"{"poly_api_secret": "c2VjcmV0", "admin_token": ""}"#).unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            let err = FileSecretProvider::new(&path).get("poly_api_secret").await.unwrap_err();
+            assert!(err.to_string().contains("chmod 600"));
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        }
+
+        let provider = FileSecretProvider::new(&path);
+        let secret = provider.get("poly_api_secret").await.unwrap().unwrap();
+        assert_eq!(secret.expose(), "c2VjcmV0");
+        assert!(provider.get("admin_token").await.unwrap().is_none());
+        assert!(provider.get("private_key").await.unwrap().is_none());
+    }
+}
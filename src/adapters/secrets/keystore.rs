@@ -0,0 +1,67 @@
+//! Encrypted Keystore - Web3 Secret Storage (JSON v3) Wallet Keys
+//!
+//! Decrypts the wallet key from a keystore file as written by geth,
+//! `cast wallet import` or MetaMask exports (scrypt or PBKDF2 KDF,
+//! AES-128-CTR, keccak MAC). The file alone is useless without the
+//! passphrase, which comes from the secret backend. Decryption is
+//! deliberately slow (the KDF), so it runs on the blocking pool.
+
+use std::path::{Path, PathBuf};
+
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::{Context, Result};
+
+use crate::ports::secrets::Secret;
+
+/// Decrypt the signing key in `path` with `password`.
+pub async fn decrypt(path: &Path, password: Secret) -> Result<PrivateKeySigner> {
+    let path: PathBuf = path.to_path_buf();
+    let display = path.display().to_string();
+    tokio::task::spawn_blocking(move || {
+        PrivateKeySigner::decrypt_keystore(&path, password.expose())
+    })
+    .await
+    .context("Keystore decryption task failed")?
+    .with_context(|| format!("Failed to decrypt keystore {display} (wrong password?)"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// PBKDF2 test vector from the Web3 Secret Storage definition.
+    const TEST_KEYSTORE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    #[tokio::test]
+    async fn test_decrypts_spec_vector() {
+        let dir = std::env::temp_dir().join(format!("polybot-keystore-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("wallet.json");
+        std::fs::write(&path, TEST_KEYSTORE).unwrap();
+
+        let signer = decrypt(&path, Secret::new("testpassword".to_string())).await.unwrap();
+        assert_eq!(
+            signer.address().to_string(),
+            "0x008AeEda4D805471dF9b2A5B0f38A0C3bCBA786b"
+        );
+
+        let err = decrypt(&path, Secret::new("wrong".to_string())).await.unwrap_err();
+        assert!(err.to_string().contains("wrong password"));
+    }
+}
@@ -0,0 +1,160 @@
+//! Secret Adapters - Credential Backends and the Wallet Keystore
+//!
+//! `SecretStore` is what the rest of the binary asks for credentials:
+//! it reads from the backend chosen in `[secret_store]` and hands out
+//! ready-to-use CLOB auth, the wallet signer and the admin token.
+//! - `env`: `[secrets]` from environment variables (default)
+//! - `file`: JSON secrets file, owner-only permissions
+//! - `vault`: Vault-compatible KV endpoint (`VAULT_TOKEN` from env)
+//! - `keystore`: encrypted Web3 keystore for the signing key
+//!
+//! With `keystore_path` set, the plaintext `private_key` is never read.
+
+pub mod env;
+pub mod file;
+pub mod keystore;
+pub mod vault;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::{Context, Result};
+use tracing::{info, warn};
+
+use crate::adapters::api::auth::ClobAuth;
+use crate::config::{AppConfig, SecretBackend};
+use crate::ports::secrets::{self, Secret, SecretProvider};
+
+pub use env::EnvSecretProvider;
+pub use file::FileSecretProvider;
+pub use vault::VaultSecretProvider;
+
+/// Credentials from the configured backend.
+pub struct SecretStore {
+    /// Backend serving secrets by name.
+    provider: Arc<dyn SecretProvider>,
+    /// Encrypted wallet keystore, if configured.
+    keystore_path: Option<PathBuf>,
+}
+
+impl SecretStore {
+    /// Store over an explicit backend, without a keystore.
+    pub fn new(provider: Arc<dyn SecretProvider>) -> Self {
+        Self {
+            provider,
+            keystore_path: None,
+        }
+    }
+
+    /// Store for `[secret_store]`.
+    pub fn from_config(config: &AppConfig) -> Result<Self> {
+        let store = &config.secret_store;
+        let provider: Arc<dyn SecretProvider> = match store.provider {
+            SecretBackend::Env => Arc::new(EnvSecretProvider::new(config.secrets.clone())),
+            SecretBackend::File => Arc::new(FileSecretProvider::new(
+                store.file_path.as_deref().context("secret_store.file_path not set")?,
+            )),
+            SecretBackend::Vault => Arc::new(VaultSecretProvider::new(
+                store.vault_addr.as_deref().context("secret_store.vault_addr not set")?,
+                &store.vault_path,
+                config.secrets.vault_token.clone().context("VAULT_TOKEN not set")?,
+            )?),
+        };
+        let mut secret_store = Self::new(provider);
+        if let Some(path) = &store.keystore_path {
+            secret_store = secret_store.with_keystore(path);
+        }
+        Ok(secret_store)
+    }
+
+    /// Load the signing key from an encrypted keystore.
+    pub fn with_keystore(mut self, path: impl Into<PathBuf>) -> Self {
+        self.keystore_path = Some(path.into());
+        self
+    }
+
+    /// Backend name (`env`, `file`, `vault`).
+    pub fn backend(&self) -> &'static str {
+        self.provider.name()
+    }
+
+    /// Look up `key`; empty values count as unset.
+    pub async fn get(&self, key: &str) -> Result<Option<Secret>> {
+        Ok(self.provider.get(key).await?.filter(|s| !s.is_empty()))
+    }
+
+    /// Look up `key`, failing when it is unset.
+    pub async fn require(&self, key: &str) -> Result<Secret> {
+        self.get(key)
+            .await?
+            .with_context(|| format!("Secret '{key}' not set in the {} backend", self.backend()))
+    }
+
+    /// CLOB request signer.
+    pub async fn clob_auth(&self) -> Result<ClobAuth> {
+        Ok(ClobAuth::new(
+            &self.require(secrets::POLY_API_KEY).await?,
+            self.require(secrets::POLY_API_SECRET).await?,
+            self.require(secrets::POLY_PASSPHRASE).await?,
+        ))
+    }
+
+    /// Wallet signer: the keystore when configured, else `private_key`.
+    pub async fn signer(&self) -> Result<PrivateKeySigner> {
+        if let Some(path) = &self.keystore_path {
+            let password = self.require(secrets::KEYSTORE_PASSWORD).await?;
+            let signer = keystore::decrypt(path, password).await?;
+            info!(
+                address = %signer.address(),
+                keystore = %path.display(),
+                "Wallet key decrypted from keystore"
+            );
+            return Ok(signer);
+        }
+
+        let key = self.require(secrets::PRIVATE_KEY).await?;
+        if self.backend() == "env" {
+            warn!("Wallet key read from plaintext PRIVATE_KEY; prefer secret_store.keystore_path");
+        }
+        key.expose()
+            .trim()
+            .trim_start_matches("0x")
+            .parse()
+            .context("Invalid private_key (expected 32 bytes hex)")
+    }
+
+    /// Bearer token for `/admin`, if set.
+    pub async fn admin_token(&self) -> Result<Option<Secret>> {
+        self.get(secrets::ADMIN_TOKEN).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::SecretsConfig;
+
+    #[tokio::test]
+    async fn test_env_store_builds_signer_and_names_missing_keys() {
+        let store = SecretStore::new(Arc::new(EnvSecretProvider::new(SecretsConfig {
+            private_key: Some(Secret::new(
+                "0x7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d".to_string(),
+            )),
+            poly_api_key: Some(Secret::new("key".to_string())),
+            admin_token: Some(Secret::new(String::new())),
+            ..SecretsConfig::default()
+        })));
+
+        let signer = store.signer().await.unwrap();
+        assert_eq!(
+            signer.address().to_string(),
+            "0x008AeEda4D805471dF9b2A5B0f38A0C3bCBA786b"
+        );
+        assert!(store.admin_token().await.unwrap().is_none());
+
+        let err = store.clob_auth().await.err().unwrap();
+        assert!(err.to_string().contains("'poly_api_secret' not set in the env backend"));
+    }
+}
@@ -0,0 +1,162 @@
+//! Vault Secrets - Vault-Compatible KV Endpoint as a `SecretProvider`
+//!
+//! `GET {addr}/v1/{path}` with `X-Vault-Token`; works with HashiCorp
+//! Vault / OpenBao KV v2 (`data.data`) and KV v1 (`data`) mounts.
+//! Every lookup fetches the document, so rotated values are picked up
+//! on the next startup or CLI command.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use zeroize::Zeroize;
+
+use crate::ports::secrets::{Secret, SecretProvider};
+
+/// Request timeout for the Vault endpoint.
+const VAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// KV v2 read response.
+#[derive(Deserialize)]
+struct KvV2 {
+    /// Envelope around the secret data.
+    data: KvV2Data,
+}
+
+/// KV v2 `data` envelope.
+#[derive(Deserialize)]
+struct KvV2Data {
+    /// The key/value pairs.
+    data: HashMap<String, Secret>,
+}
+
+/// KV v1 read response.
+#[derive(Deserialize)]
+struct KvV1 {
+    /// The key/value pairs.
+    data: HashMap<String, Secret>,
+}
+
+/// Serves secrets from a Vault KV path.
+pub struct VaultSecretProvider {
+    /// HTTP client.
+    client: Client,
+    /// Full URL of the secret (`{addr}/v1/{path}`).
+    url: String,
+    /// Vault token (`VAULT_TOKEN`).
+    token: Secret,
+}
+
+impl VaultSecretProvider {
+    /// Provider for `path` on the Vault server at `addr`.
+    pub fn new(addr: &str, path: &str, token: Secret) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(VAULT_TIMEOUT)
+            .build()
+            .context("Failed to build Vault HTTP client")?;
+        Ok(Self {
+            client,
+            url: format!(
+                "{}/v1/{}",
+                addr.trim_end_matches('/'),
+                path.trim_matches('/')
+            ),
+            token,
+        })
+    }
+
+    /// Fetch and parse the whole secret document.
+    async fn fetch(&self) -> Result<HashMap<String, Secret>> {
+        let mut token = reqwest::header::HeaderValue::from_str(self.token.expose())
+            .context("VAULT_TOKEN is not a valid header value")?;
+        token.set_sensitive(true);
+
+        let response = self
+            .client
+            .get(&self.url)
+            .header("X-Vault-Token", token)
+            .send()
+            .await
+            .with_context(|| format!("Vault request to {} failed", self.url))?;
+        match response.status() {
+            StatusCode::OK => {}
+            StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED => {
+                bail!("Vault rejected the token for {}", self.url)
+            }
+            StatusCode::NOT_FOUND => bail!("No secret at {}", self.url),
+            status => bail!("Vault returned {status} for {}", self.url),
+        }
+
+        let mut body = response.text().await.context("Failed to read Vault response")?;
+        let parsed = serde_json::from_str::<KvV2>(&body)
+            .map(|kv| kv.data.data)
+            .or_else(|_| serde_json::from_str::<KvV1>(&body).map(|kv| kv.data));
+        body.zeroize();
+        parsed.with_context(|| format!("Unexpected Vault response format from {}", self.url))
+    }
+}
+
+#[async_trait]
+impl SecretProvider for VaultSecretProvider {
+    fn name(&self) -> &'static str {
+        "vault"
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Secret>> {
+        let mut secrets = self.fetch().await?;
+        Ok(secrets.remove(key).filter(|s| !s.is_empty()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::get;
+    use axum::{Json, Router};
+    use serde_json::json;
+
+    /// Local stand-in for a Vault KV v2 mount.
+    async fn stand_in_vault() -> String {
+        let app = Router::new().route(
+            "/v1/secret/data/polybot",
+            get(|headers: HeaderMap| async move {
+                if headers.get("X-Vault-Token").and_then(|v| v.to_str().ok()) != Some("s.test") {
+                    return Err(StatusCode::FORBIDDEN);
+                }
+                Ok(Json(json!({
+                    "data": {
+                        "data": { "poly_api_secret": "c2VjcmV0", "keystore_password": "pw" },
+                        "metadata": { "version": 3 }
+                    }
+                })))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn test_reads_kv_v2_and_rejects_bad_token() {
+        let addr = stand_in_vault().await;
+
+        let vault =
+            VaultSecretProvider::new(&addr, "/secret/data/polybot", Secret::new("s.test".into()))
+                .unwrap();
+        let secret = vault.get("poly_api_secret").await.unwrap().unwrap();
+        assert_eq!(secret.expose(), "c2VjcmV0");
+        assert!(vault.get("private_key").await.unwrap().is_none());
+
+        let wrong =
+            VaultSecretProvider::new(&addr, "secret/data/polybot", Secret::new("s.nope".into()))
+                .unwrap();
+        let err = wrong.get("poly_api_secret").await.unwrap_err();
+        assert!(err.to_string().contains("rejected the token"));
+    }
+}
@@ -0,0 +1,392 @@
+//! Operator Commands - One-Shot Tasks Against the Live Systems
+//!
+//! Each command uses the same resolved config and adapters as `run`,
+//! prints a plain-text report to stdout and exits; logs go to stderr.
+//! Nothing here starts the trading loop:
+//! - `validate-config`: layers + schema + strategy names
+//! - `check-contracts`: `ContractValidator` against the RPC
+//! - `balances`: `WalletManager::snapshot`, MATIC and hot/cold
+//!   allocation (needs the wallet key)
+//! - `orders list|cancel-all`: CLOB open orders (needs CLOB credentials)
+//! - `settle [--dry-run]`: `Settlement::sweep` over held outcome tokens
+//! - `pnl report`: `Repository::load_daily_pnl` from the data directory,
+//!   plus per-market PnL of the last saved portfolio ledger
+//! - `pnl fees [--date]`: saved fee reconciliations, or
+//!   `FeeReconciler::reconcile` of one day (needs CLOB credentials)
+//! - `db import [--from DIR]`: `SqliteRepository::import_jsonl` of a
+//!   JSONL data directory into `storage.sqlite_path`
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, ensure, Context, Result};
+
+use crate::adapters::api::client::{ClobClient, ClobClientConfig};
+use crate::adapters::api::fees::ClobFeeSource;
+use crate::adapters::api::orders::ClobOrderExecutor;
+use crate::adapters::chain::contracts::ContractAddresses;
+use crate::adapters::chain::provider::PolygonProvider;
+use crate::adapters::chain::{ContractValidator, CtfContracts, GasOracle, TxSender};
+use crate::adapters::persistence::{SqliteRepository, Storage};
+use crate::adapters::secrets::SecretStore;
+use crate::config::layers::ResolvedConfig;
+use crate::config::{AppConfig, StorageBackend};
+use crate::domain::time::now_ms;
+use crate::ports::execution::OrderExecution;
+use crate::ports::metrics::NoopMetrics;
+use crate::ports::repository::Repository;
+use crate::usecases::fee_reconciler::FeeReconciler;
+use crate::usecases::fee_schedule::FeeSchedules;
+use crate::usecases::portfolio_ledger::PortfolioLedger;
+use crate::usecases::settlement_scheduler::SettlementScheduler;
+use crate::usecases::strategy_registry::StrategyRegistry;
+use crate::usecases::wallet_manager::WalletManager;
+use crate::usecases::wallet_monitor::gas_state;
+
+/// Compact stderr logging for one-shot commands (default `warn`).
+pub fn init_logging() {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("warn")),
+        )
+        .with_writer(std::io::stderr)
+        .compact()
+        .init();
+}
+
+/// CLOB HTTP client authenticated from the secret backend.
+pub async fn clob_client(secrets: &SecretStore, config: &AppConfig) -> Result<Arc<ClobClient>> {
+    let auth = Arc::new(
+        secrets
+            .clob_auth()
+            .await
+            .context("Failed to load CLOB credentials")?,
+    );
+    let clob_config = ClobClientConfig {
+        base_url: config.api.clob_base_url.clone(),
+        timeout: Duration::from_millis(config.api.timeout_ms),
+        max_concurrent: 10,
+        max_retries: 3,
+        retry_base_delay: Duration::from_millis(200),
+    };
+    Ok(Arc::new(
+        ClobClient::new(auth, clob_config).context("Failed to create CLOB client")?,
+    ))
+}
+
+/// CTF contract bindings signing with the wallet key.
+pub async fn ctf_contracts(
+    polygon: &Arc<PolygonProvider>,
+    secrets: &SecretStore,
+    config: &AppConfig,
+) -> Result<Arc<CtfContracts>> {
+    let gas_oracle = Arc::new(
+        GasOracle::new(Arc::clone(polygon))
+            .with_redeem_threshold(config.settlement.max_gas_gwei),
+    );
+    let signer = secrets.signer().await.context("Failed to load wallet signer")?;
+    let sender = Arc::new(TxSender::new(
+        Arc::clone(polygon),
+        Arc::clone(&gas_oracle),
+        signer,
+        config.api.chain_id,
+        &config.settlement,
+    ));
+    Ok(Arc::new(
+        CtfContracts::new(
+            Arc::clone(polygon),
+            gas_oracle,
+            sender,
+            ContractAddresses::from_config(&config.contracts)?,
+        )
+        .await
+        .context("Failed to initialize CTF contracts")?
+        .with_markets(&config.markets),
+    ))
+}
+
+/// Connect to the configured Polygon RPC.
+async fn connect(config: &AppConfig) -> Result<Arc<PolygonProvider>> {
+    Ok(Arc::new(
+        PolygonProvider::connect(&config.api)
+            .await
+            .context("Failed to connect to Polygon RPC")?,
+    ))
+}
+
+/// `validate-config`: the config already resolved and validated;
+/// also build the strategies so unknown names fail here.
+pub fn validate_config(resolved: &ResolvedConfig) -> Result<()> {
+    let config = &resolved.config;
+    StrategyRegistry::default()
+        .build(config)
+        .context("Failed to build market strategies")?;
+
+    for layer in &resolved.layers {
+        println!("layer    {layer}");
+    }
+    println!(
+        "OK       mode={:?} dry_run={} markets={} ({} active)",
+        config.bot.mode,
+        config.bot.dry_run,
+        config.markets.len(),
+        config.markets.iter().filter(|m| m.active).count()
+    );
+    Ok(())
+}
+
+/// `check-contracts`: every configured address must hold code.
+pub async fn check_contracts(config: &AppConfig) -> Result<()> {
+    let polygon = connect(config).await?;
+    let results = ContractValidator::new(polygon.inner())
+        .validate_all(&config.contracts)
+        .await?;
+
+    for r in &results {
+        let status = if r.has_code { "ok" } else { "NO CODE" };
+        println!("{:<20} {}  {status}", r.name, r.address);
+    }
+    let missing = results.iter().filter(|r| !r.has_code).count();
+    if missing > 0 {
+        bail!("{missing} contract(s) have no deployed code on chain {}", config.api.chain_id);
+    }
+    Ok(())
+}
+
+/// `balances`: wallet USDC and outcome token balances.
+pub async fn balances(config: &AppConfig) -> Result<()> {
+    let polygon = connect(config).await?;
+    let ctf = ctf_contracts(&polygon, &SecretStore::from_config(config)?, config).await?;
+    let wallet = WalletManager::new(ctf)
+        .with_tokens(
+            config
+                .markets
+                .iter()
+                .flat_map(|m| [m.yes_token_id.clone(), m.no_token_id.clone()]),
+        )
+        .with_cold_wallet(config.wallet.cold_address.clone());
+    let snapshot = wallet.snapshot().await?;
+    let matic = wallet.native_balance().await?;
+
+    println!("USDC     {:>14.2}", snapshot.usdc_balance);
+    for market in &config.markets {
+        for (label, token_id) in [("YES", &market.yes_token_id), ("NO", &market.no_token_id)] {
+            let balance = snapshot.token_balances.get(token_id).copied().unwrap_or(0.0);
+            if balance > 0.0 {
+                println!(
+                    "{label:<8} {balance:>14.2}  {:?} {}",
+                    market.asset, market.condition_id
+                );
+            }
+        }
+    }
+    println!("TOTAL    {:>14.2}", snapshot.total_value);
+
+    let gas = gas_state(matic, config.wallet.min_matic_balance);
+    println!("MATIC    {matic:>14.4}  {gas:?} (min {})", config.wallet.min_matic_balance);
+    let policy = config.wallet.allocation_policy();
+    if let Some(a) = wallet.allocation(&policy).await? {
+        println!(
+            "COLD     {:>14.2}  hot share {:.1}% (target {:.1}%, alert above {:.1}%)",
+            a.cold_balance,
+            a.hot_share * 100.0,
+            policy.hot_fraction * 100.0,
+            policy.hot_alert_threshold * 100.0
+        );
+        if let Some(r) = a.rebalance {
+            println!("PROPOSED {:?} {:.2} USDC", r.direction, r.amount);
+        }
+    }
+    Ok(())
+}
+
+/// `orders list`: resting CLOB orders.
+pub async fn list_orders(config: &AppConfig) -> Result<()> {
+    let secrets = SecretStore::from_config(config)?;
+    let executor = ClobOrderExecutor::new(clob_client(&secrets, config).await?);
+    let orders = executor.get_open_orders().await?;
+    for o in &orders {
+        println!(
+            "{}  {:?} {:>10.2} @ {:.3}  token {}",
+            o.id, o.side, o.size, o.price, o.token_id
+        );
+    }
+    println!("{} open order(s)", orders.len());
+    Ok(())
+}
+
+/// `orders cancel-all`: cancel every resting order.
+pub async fn cancel_all_orders(config: &AppConfig) -> Result<()> {
+    let secrets = SecretStore::from_config(config)?;
+    let executor = ClobOrderExecutor::new(clob_client(&secrets, config).await?);
+    let cancelled = executor.cancel_all_orders().await?;
+    println!("Cancelled {cancelled} order(s)");
+    Ok(())
+}
+
+/// `settle [--dry-run]`: sweep held outcome tokens now.
+pub async fn settle(config: &AppConfig, dry_run: bool) -> Result<()> {
+    let polygon = connect(config).await?;
+    let ctf = ctf_contracts(&polygon, &SecretStore::from_config(config)?, config).await?;
+    let repo = Arc::new(Storage::from_config(config).await?);
+    let scheduler = SettlementScheduler::new(ctf, repo, config, Duration::from_secs(60))
+        .with_dry_run(dry_run);
+    let report = scheduler.sweep_now().await?;
+
+    for r in &report.results {
+        let outcome = match (&r.error, &r.tx_hash) {
+            (Some(e), _) => format!("FAILED {e}"),
+            (None, Some(tx)) => format!("tx {tx}"),
+            (None, None) if dry_run => "would redeem".to_string(),
+            (None, None) => "settled locally".to_string(),
+        };
+        println!(
+            "{}  {:<12} {:>10.2} USDC  {outcome}",
+            r.market_id,
+            r.resolution.label(),
+            r.usdc_recovered
+        );
+    }
+    println!(
+        "{}{} settled, {} failed, {:.2} USDC{}",
+        if dry_run { "[dry run] " } else { "" },
+        report.markets_settled,
+        report.markets_failed,
+        report.total_usdc_recovered,
+        if dry_run { " expected" } else { " recovered" }
+    );
+    if report.markets_failed > 0 {
+        bail!("{} settlement(s) failed", report.markets_failed);
+    }
+    Ok(())
+}
+
+/// `pnl report`: daily PnL from the trade logs and the saved ledger.
+pub async fn pnl_report(config: &AppConfig, days: Option<usize>) -> Result<()> {
+    let repo = Storage::from_config(config).await?;
+    let mut daily = repo.load_daily_pnl().await?;
+    daily.sort_by(|a, b| a.date.cmp(&b.date));
+    if let Some(days) = days {
+        daily = daily.split_off(daily.len().saturating_sub(days));
+    }
+
+    println!(
+        "{:<10} {:>12} {:>12} {:>7} {:>12} {:>10}",
+        "date", "realized", "unrealized", "trades", "volume", "max_dd"
+    );
+    for d in &daily {
+        println!(
+            "{:<10} {:>12.2} {:>12.2} {:>7} {:>12.2} {:>10.2}",
+            d.date, d.realized_pnl, d.unrealized_pnl, d.trade_count, d.volume, d.max_drawdown
+        );
+    }
+    println!(
+        "{:<10} {:>12.2} {:>12} {:>7} {:>12.2}",
+        "total",
+        daily.iter().map(|d| d.realized_pnl).sum::<f64>(),
+        "",
+        daily.iter().map(|d| d.trade_count).sum::<u64>(),
+        daily.iter().map(|d| d.volume).sum::<f64>()
+    );
+
+    let ledger = PortfolioLedger::new(config, Arc::new(NoopMetrics));
+    if ledger.restore(&repo).await? {
+        println!();
+        println!(
+            "{:<68} {:>5} {:>12} {:>12} {:>12} {:>10}",
+            "market", "asset", "realized", "today", "unrealized", "cost"
+        );
+        for m in ledger.by_market().await {
+            let asset = m.asset.map_or_else(|| "-".to_string(), |a| a.to_string());
+            println!(
+                "{:<68} {:>5} {:>12.2} {:>12.2} {:>12.2} {:>10.2}",
+                m.market_id, asset, m.realized, m.day_realized, m.unrealized, m.cost
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `pnl fees [--date]`: expected vs charged fees per day.
+pub async fn pnl_fees(config: &AppConfig, date: Option<&str>) -> Result<()> {
+    let repo = Arc::new(Storage::from_config(config).await?);
+    let Some(date) = date else {
+        let mut records = repo.load_fee_reconciliations().await?;
+        records.sort_by(|a, b| a.date.cmp(&b.date));
+        println!(
+            "{:<10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>7}",
+            "date", "expected", "actual", "gap", "booked", "rebates", "flagged"
+        );
+        for r in &records {
+            println!(
+                "{:<10} {:>10.4} {:>10.4} {:>10.4} {:>10} {:>10.4} {:>7}",
+                r.date,
+                r.expected_fees,
+                r.actual_fees,
+                r.fee_gap(),
+                r.booked_fees.map_or_else(|| "-".to_string(), |f| format!("{f:.4}")),
+                r.rebates,
+                r.flagged().count()
+            );
+        }
+        return Ok(());
+    };
+
+    let secrets = SecretStore::from_config(config)?;
+    let source = Arc::new(ClobFeeSource::new(clob_client(&secrets, config).await?));
+    let schedules = Arc::new(FeeSchedules::new(config));
+    schedules.refresh(source.as_ref()).await;
+    let reconciler = FeeReconciler::new(source, repo, schedules, config);
+    let record = reconciler.reconcile(date, now_ms()).await?;
+
+    println!(
+        "{:<68} {:>6} {:>10} {:>10} {:>10} {:>9}",
+        "market", "trades", "expected", "actual", "rebates", "mismatch"
+    );
+    for m in &record.markets {
+        println!(
+            "{:<68} {:>6} {:>10.4} {:>10.4} {:>10.4} {:>9}{}",
+            m.market_id,
+            m.trades,
+            m.expected_fees,
+            m.actual_fees,
+            m.rebates,
+            m.rate_mismatches,
+            if m.flagged { "  FLAGGED" } else { "" }
+        );
+    }
+    println!(
+        "{:<68} {:>6} {:>10.4} {:>10.4} {:>10.4}",
+        "total",
+        record.markets.iter().map(|m| m.trades).sum::<u64>(),
+        record.expected_fees,
+        record.actual_fees,
+        record.rebates
+    );
+    Ok(())
+}
+
+/// `db import [--from DIR]`: JSONL logs and `state.json` into SQLite.
+pub async fn db_import(config: &AppConfig, from: Option<&Path>) -> Result<()> {
+    let data_dir = from.map_or_else(|| config.bot.data_dir.clone(), |d| d.display().to_string());
+    ensure!(
+        Path::new(&data_dir).is_dir(),
+        "Data directory {data_dir} does not exist"
+    );
+    let path = config.storage.sqlite_path(&config.bot.data_dir);
+    let repo = SqliteRepository::open(&path).await?;
+    let summary = repo.import_jsonl(&data_dir).await?;
+
+    println!("Imported {data_dir} into {}:", path.display());
+    println!("  trades               {:>8}", summary.trades);
+    println!("  daily pnl            {:>8}", summary.daily_pnl);
+    println!("  settlement reports   {:>8}", summary.settlements);
+    println!("  fee reconciliations  {:>8}", summary.fee_reconciliations);
+    println!("  state snapshot       {:>8}", if summary.state { "yes" } else { "kept" });
+    if config.storage.backend != StorageBackend::Sqlite {
+        println!("Set storage.backend = \"sqlite\" to use it.");
+    }
+    Ok(())
+}
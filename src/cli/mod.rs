@@ -0,0 +1,209 @@
+//! Command-Line Interface - Global Flags and Subcommands
+//!
+//! Global flags choose the config layers (see `config::layers`):
+//! `--config` files, `--env` overlay, and the highest-precedence
+//! overrides (`--data-dir`, `--log-level`, `--set section.key=value`).
+//! Without a subcommand the bot runs; the other subcommands are
+//! one-shot operator tasks (see `commands`).
+
+pub mod commands;
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+use crate::config::layers::{ConfigSources, ResolvedConfig};
+
+/// Polymarket LMSR market-making bot.
+#[derive(Debug, Parser)]
+#[command(name = "polymarket-lmsr-bot", version, about)]
+pub struct Cli {
+    /// Config file; repeat to add files that override the previous ones.
+    #[arg(
+        short,
+        long = "config",
+        value_name = "FILE",
+        default_value = "config.toml",
+        global = true
+    )]
+    pub config: Vec<PathBuf>,
+
+    /// Deployment environment; merges `config.<ENV>.toml` over the base file.
+    #[arg(short, long = "env", value_name = "ENV", env = "POLYBOT_ENV", global = true)]
+    pub environment: Option<String>,
+
+    /// Override a config value (`section.key=value`, repeatable).
+    #[arg(long = "set", value_name = "KEY=VALUE", global = true)]
+    pub overrides: Vec<String>,
+
+    /// Directory for state, trade logs and PnL (`bot.data_dir`).
+    #[arg(long, value_name = "DIR", global = true)]
+    pub data_dir: Option<PathBuf>,
+
+    /// Log level filter (`bot.log_level`).
+    #[arg(long, value_name = "LEVEL", global = true)]
+    pub log_level: Option<String>,
+
+    /// Subcommand (default: `run`).
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Subcommands.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the bot (the default).
+    Run {
+        /// Paper trading (`bot.mode = "Paper"`).
+        #[arg(long, conflicts_with = "live")]
+        paper: bool,
+        /// Live trading with real orders (`bot.mode = "Live"`,
+        /// `bot.dry_run = false`).
+        #[arg(long)]
+        live: bool,
+    },
+    /// Resolve and validate the config, then exit.
+    ValidateConfig,
+    /// Check that every configured contract has code on-chain.
+    CheckContracts,
+    /// Show USDC and outcome token balances of the wallet.
+    Balances,
+    /// Inspect or cancel resting CLOB orders.
+    #[command(subcommand)]
+    Orders(OrdersCommand),
+    /// Redeem resolved positions now (ignores the schedule; redemptions
+    /// still refuse gas above `settlement.max_gas_gwei`).
+    Settle {
+        /// Only report what would be redeemed.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Profit and loss from the trade logs.
+    #[command(subcommand)]
+    Pnl(PnlCommand),
+    /// Inspect the configuration.
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Manage the SQLite database (`storage.sqlite_path`).
+    #[command(subcommand)]
+    Db(DbCommand),
+}
+
+/// `orders` subcommands.
+#[derive(Debug, Subcommand)]
+pub enum OrdersCommand {
+    /// List resting orders.
+    List,
+    /// Cancel every resting order.
+    CancelAll,
+}
+
+/// `pnl` subcommands.
+#[derive(Debug, Subcommand)]
+pub enum PnlCommand {
+    /// Daily PnL table with totals.
+    Report {
+        /// Only the most recent N days.
+        #[arg(long, value_name = "N")]
+        days: Option<usize>,
+    },
+    /// Saved fee reconciliations; with `--date`, reconcile that day
+    /// against the CLOB trade history now.
+    Fees {
+        /// UTC day to reconcile (YYYY-MM-DD).
+        #[arg(long, value_name = "DATE")]
+        date: Option<String>,
+    },
+}
+
+/// `db` subcommands.
+#[derive(Debug, Subcommand)]
+pub enum DbCommand {
+    /// Copy JSONL trade, PnL, settlement and fee logs and `state.json`
+    /// into the database; safe to re-run.
+    Import {
+        /// Data directory to read (default `bot.data_dir`).
+        #[arg(long, value_name = "DIR")]
+        from: Option<PathBuf>,
+    },
+}
+
+/// `config` subcommands.
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Print the config layers; with `--resolved`, the merged config
+    /// (secrets redacted).
+    Print {
+        /// Print the merged, validated config.
+        #[arg(long)]
+        resolved: bool,
+    },
+}
+
+impl Cli {
+    /// Config sources selected by the flags.
+    pub fn config_sources(&self) -> ConfigSources {
+        let mut overrides = Vec::new();
+        if let Some(dir) = &self.data_dir {
+            overrides.push(format!("bot.data_dir={}", dir.display()));
+        }
+        if let Some(level) = &self.log_level {
+            overrides.push(format!("bot.log_level={level}"));
+        }
+        match self.command {
+            Some(Command::Run { paper: true, .. }) => {
+                overrides.push("bot.mode=Paper".to_string());
+            }
+            Some(Command::Run { live: true, .. }) => {
+                overrides.push("bot.mode=Live".to_string());
+                overrides.push("bot.dry_run=false".to_string());
+            }
+            _ => {}
+        }
+        overrides.extend(self.overrides.iter().cloned());
+
+        ConfigSources {
+            files: self.config.clone(),
+            environment: self.environment.clone(),
+            overrides,
+        }
+    }
+
+    /// Whether the command starts the trading loop.
+    pub fn runs_bot(&self) -> bool {
+        matches!(self.command, None | Some(Command::Run { .. }))
+    }
+}
+
+/// `config print [--resolved]`.
+pub fn print_config(resolved: &ResolvedConfig, full: bool) {
+    println!("# Layers (lowest precedence first):");
+    for layer in &resolved.layers {
+        println!("#   {layer}");
+    }
+    if full {
+        println!();
+        print!("{}", resolved.redacted_toml());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_mode_flags_become_overrides() {
+        let cli = Cli::parse_from(["bot", "--set", "risk.min_bankroll=10", "run", "--live"]);
+        let sources = cli.config_sources();
+        assert_eq!(
+            sources.overrides,
+            vec!["bot.mode=Live", "bot.dry_run=false", "risk.min_bankroll=10"]
+        );
+        assert!(cli.runs_bot());
+
+        assert!(Cli::try_parse_from(["bot", "run", "--paper", "--live"]).is_err());
+        let cli = Cli::parse_from(["bot", "orders", "cancel-all", "--env", "staging"]);
+        assert!(matches!(cli.command, Some(Command::Orders(OrdersCommand::CancelAll))));
+        assert_eq!(cli.config_sources().environment.as_deref(), Some("staging"));
+    }
+}
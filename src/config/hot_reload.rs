@@ -1,71 +1,338 @@
-//! Config Hot-Reload — Watch config.toml for Changes Every 60s
+//! Config Hot-Reload — Event-Driven config.toml Watching
 //!
-//! Periodically re-reads config.toml and compares with the current
-//! config. If changes are detected, broadcasts the new config via
-//! a `tokio::sync::watch` channel. Consumers can subscribe to
-//! receive updated config without restarting the bot.
+//! Watches the directories of every config file (base, environment
+//! overlay, extra `--config` files) for filesystem events (inotify on
+//! Linux, FSEvents/kqueue elsewhere), debounces bursts of writes, and
+//! reloads when the contents actually changed. If no filesystem
+//! watcher can be created (e.g. inotify limits), falls back to polling
+//! every 60s.
 //!
-//! Checklist: hot-reload 60s A/B testing.
+//! Every candidate is re-resolved through all layers (`layers`) and
+//! the full `loader` validation, then is published on a `watch` channel as a numbered `ConfigUpdate`.
+//! Reloads are transactional: each component registered with
+//! `subscribe` must ack the generation; on a failure (or no ack within
+//! the timeout) the previous config is republished so components that
+//! already applied it revert.
+//!
+//! Only `RELOADABLE_SECTIONS` are applied at runtime (by the
+//! `ArbitrageEngine`, the `RiskManager` and `NegRiskArbitrage`). Edits
+//! to any other section (`[api]`, `[contracts]`, ...) are staged: the
+//! published config keeps the running values and the diff is logged
+//! until the next restart.
+//!
+//! The market set is fixed at startup: the `RiskGate`, the state
+//! checkpointer, settlement and merging key on it. Within `[[markets]]`
+//! only `RELOADABLE_MARKET_FIELDS` (`active`, `strategies`) apply at
+//! runtime; adding or removing a market or editing any other field
+//! (IDs, neg-risk event, fee class, ...) stages the whole section.
+//!
+//! Checklist: hot-reload A/B testing.
 
+use std::collections::HashSet;
+use std::fmt;
 use std::path::Path;
 use std::time::Duration;
 
-use anyhow::Result;
-use tokio::sync::{broadcast, watch};
-use tracing::{debug, info, instrument, warn};
+use anyhow::{bail, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio::time::Instant;
+use tracing::{debug, error, info, instrument, warn};
 
+use super::layers::ConfigSources;
 use super::AppConfig;
 
-/// Watches config.toml for changes and broadcasts updates.
+/// Sections applied to running components on reload.
+pub const RELOADABLE_SECTIONS: &[&str] = &[
+    "lmsr",
+    "risk",
+    "rate_limits",
+    "markets",
+    "strategy",
+    "complete_set",
+];
+
+/// `[[markets]]` fields applied at runtime; the others identify the
+/// market to components built once at startup.
+pub const RELOADABLE_MARKET_FIELDS: &[&str] = &["active", "strategies"];
+
+/// One changed leaf value between two configs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    /// Dotted path, e.g. `api.clob_base_url` or `markets[0].active`.
+    pub path: String,
+    /// Old value (TOML), `None` if added.
+    pub old: Option<String>,
+    /// New value (TOML), `None` if removed.
+    pub new: Option<String>,
+}
+
+impl ConfigChange {
+    /// Top-level section the change belongs to.
+    pub fn section(&self) -> &str {
+        self.path
+            .split(['.', '['])
+            .next()
+            .unwrap_or_default()
+    }
+
+    /// Field of a `[[markets]]` entry, e.g. `active`; `None` for a
+    /// whole market added or removed.
+    fn market_field(&self) -> Option<&str> {
+        let (_, field) = self.path.split_once("].")?;
+        field.split(['.', '[']).next()
+    }
+
+    /// Whether the change is applied at runtime.
+    pub fn is_reloadable(&self) -> bool {
+        match self.section() {
+            "markets" => self
+                .market_field()
+                .is_some_and(|field| RELOADABLE_MARKET_FIELDS.contains(&field)),
+            section => RELOADABLE_SECTIONS.contains(&section),
+        }
+    }
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |v: &Option<String>| v.clone().unwrap_or_else(|| "<unset>".to_string());
+        write!(f, "{}: {} → {}", self.path, show(&self.old), show(&self.new))
+    }
+}
+
+/// Leaf-level differences between two configs.
+pub fn diff_configs(old: &AppConfig, new: &AppConfig) -> Vec<ConfigChange> {
+    let (Ok(old), Ok(new)) = (toml::Value::try_from(old), toml::Value::try_from(new)) else {
+        return Vec::new();
+    };
+    let mut changes = Vec::new();
+    diff_values("", Some(&old), Some(&new), &mut changes);
+    changes
+}
+
+/// Recursive helper for `diff_configs`.
+fn diff_values(
+    path: &str,
+    old: Option<&toml::Value>,
+    new: Option<&toml::Value>,
+    changes: &mut Vec<ConfigChange>,
+) {
+    use toml::Value;
+
+    match (old, new) {
+        (Some(Value::Table(a)), Some(Value::Table(b))) => {
+            let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+            keys.sort_unstable();
+            keys.dedup();
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff_values(&child, a.get(key), b.get(key), changes);
+            }
+        }
+        (Some(Value::Array(a)), Some(Value::Array(b))) if a.iter().chain(b).any(Value::is_table) => {
+            for i in 0..a.len().max(b.len()) {
+                diff_values(&format!("{path}[{i}]"), a.get(i), b.get(i), changes);
+            }
+        }
+        (a, b) if a != b => changes.push(ConfigChange {
+            path: path.to_string(),
+            old: a.map(Value::to_string),
+            new: b.map(Value::to_string),
+        }),
+        _ => {}
+    }
+}
+
+/// Split a reloaded config into what can be applied now and what
+/// has to wait for a restart.
 ///
-/// Polls the config file every 60 seconds (not a filesystem watcher,
-/// which has portability issues across Linux/macOS/Docker volumes).
-/// Compares serialized TOML to detect meaningful changes.
+/// Returns `new` with every non-reloadable section reset to its value
+/// in `current`, plus the staged (non-reloadable) changes. A changed
+/// market set keeps the running `[[markets]]` as a whole.
+pub fn stage_reload(current: &AppConfig, new: AppConfig) -> (AppConfig, Vec<ConfigChange>) {
+    let changes = diff_configs(current, &new);
+    let market_set_changed = changes
+        .iter()
+        .any(|c| c.section() == "markets" && !c.is_reloadable());
+    let staged: Vec<ConfigChange> = changes
+        .into_iter()
+        .filter(|c| !c.is_reloadable() || (market_set_changed && c.section() == "markets"))
+        .collect();
+    let markets = if market_set_changed {
+        current.markets.clone()
+    } else {
+        new.markets
+    };
+    let applied = AppConfig {
+        lmsr: new.lmsr,
+        risk: new.risk,
+        rate_limits: new.rate_limits,
+        markets,
+        strategy: new.strategy,
+        complete_set: new.complete_set,
+        ..current.clone()
+    };
+    (applied, staged)
+}
+
+/// A published config and its reload generation.
+#[derive(Debug, Clone)]
+pub struct ConfigUpdate {
+    /// Increases with every publish (reloads and rollbacks).
+    pub generation: u64,
+    /// Config to apply.
+    pub config: AppConfig,
+}
+
+/// A component's report for one generation.
+#[derive(Debug)]
+struct ReloadAck {
+    /// Component name given to `subscribe`.
+    component: &'static str,
+    /// Generation the report is for.
+    generation: u64,
+    /// Why applying failed, if it did.
+    error: Option<String>,
+}
+
+/// A component's end of the reload channel.
+pub struct ReloadSubscriber {
+    /// Component name (used in acks and logs).
+    component: &'static str,
+    /// Published configs.
+    rx: watch::Receiver<ConfigUpdate>,
+    /// Acks back to the watcher.
+    acks: mpsc::UnboundedSender<ReloadAck>,
+}
+
+impl ReloadSubscriber {
+    /// Component name.
+    pub fn component(&self) -> &'static str {
+        self.component
+    }
+
+    /// Wait for the next published config; `None` once the watcher is gone.
+    pub async fn next(&mut self) -> Option<ConfigUpdate> {
+        self.rx.changed().await.ok()?;
+        Some(self.rx.borrow_and_update().clone())
+    }
+
+    /// Report whether `generation` was applied.
+    pub fn ack<T>(&self, generation: u64, result: &Result<T>) {
+        let _ = self.acks.send(ReloadAck {
+            component: self.component,
+            generation,
+            error: result.as_ref().err().map(|e| format!("{e:#}")),
+        });
+    }
+}
+
+/// Watches the config files for changes and publishes validated updates.
 pub struct ConfigWatcher {
-    /// Path to config.toml.
-    config_path: String,
+    /// Layers to re-resolve on change.
+    sources: ConfigSources,
     /// Watch channel sender for config updates.
-    config_tx: watch::Sender<AppConfig>,
-    /// Last known serialized config (for diff detection).
-    last_hash: Option<u64>,
+    config_tx: watch::Sender<ConfigUpdate>,
+    /// Sender handed to subscribers for acks.
+    acks_tx: mpsc::UnboundedSender<ReloadAck>,
+    /// Acks from subscribers.
+    acks_rx: mpsc::UnboundedReceiver<ReloadAck>,
+    /// Components that must ack every generation.
+    components: Vec<&'static str>,
+    /// Last contents of all config files (for change detection).
+    last_content: Option<String>,
+    /// Quiet period after the last filesystem event before reloading.
+    debounce: Duration,
+    /// How long to wait for component acks before rolling back.
+    ack_timeout: Duration,
+    /// Poll interval when no filesystem watcher is available.
+    poll_interval: Duration,
 }
 
 impl ConfigWatcher {
-    /// Create a new config watcher.
-    ///
-    /// Returns the watcher and a watch::Receiver that consumers
-    /// can use to get notified of config changes.
-    pub fn new(
-        config_path: &str,
-        initial_config: AppConfig,
-    ) -> (Self, watch::Receiver<AppConfig>) {
-        let (config_tx, config_rx) = watch::channel(initial_config);
-
-        let watcher = Self {
-            config_path: config_path.to_string(),
+    /// Create a new config watcher publishing `initial_config` as
+    /// generation 0.
+    pub fn new(sources: ConfigSources, initial_config: AppConfig) -> Self {
+        let (config_tx, _) = watch::channel(ConfigUpdate {
+            generation: 0,
+            config: initial_config,
+        });
+        let (acks_tx, acks_rx) = mpsc::unbounded_channel();
+
+        Self {
+            sources,
             config_tx,
-            last_hash: None,
-        };
+            acks_tx,
+            acks_rx,
+            components: Vec::new(),
+            last_content: None,
+            debounce: Duration::from_millis(500),
+            ack_timeout: Duration::from_secs(10),
+            poll_interval: Duration::from_secs(60),
+        }
+    }
 
-        (watcher, config_rx)
+    /// Set the debounce window (default 500ms).
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
     }
 
-    /// Run the config watcher loop.
+    /// Set the ack timeout (default 10s).
+    pub fn with_ack_timeout(mut self, ack_timeout: Duration) -> Self {
+        self.ack_timeout = ack_timeout;
+        self
+    }
+
+    /// Register a component that applies reloads and must ack each one.
+    pub fn subscribe(&mut self, component: &'static str) -> ReloadSubscriber {
+        self.components.push(component);
+        ReloadSubscriber {
+            component,
+            rx: self.config_tx.subscribe(),
+            acks: self.acks_tx.clone(),
+        }
+    }
+
+    /// Config currently published.
+    pub fn current(&self) -> ConfigUpdate {
+        self.config_tx.borrow().clone()
+    }
+
+    /// Run the config watcher loop until shutdown.
     ///
-    /// Checks config.toml every 60 seconds. On change, reloads
-    /// and broadcasts the new config. Runs until shutdown.
+    /// Filesystem events restart the debounce timer; the reload runs
+    /// once the file has been quiet for the debounce window.
     #[instrument(skip(self, shutdown_rx))]
     pub async fn run(
         &mut self,
         mut shutdown_rx: broadcast::Receiver<()>,
     ) -> Result<()> {
-        info!(
-            path = %self.config_path,
-            "Config watcher started — checking every 60s"
-        );
+        self.last_content = self.read_files().await.ok();
 
-        // Compute initial hash
-        self.last_hash = self.compute_hash().await;
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let fs_watcher = match self.watch_directories(event_tx) {
+            Ok(w) => {
+                info!(files = ?self.sources.paths(), "Config watcher started (filesystem events)");
+                Some(w)
+            }
+            Err(e) => {
+                warn!(
+                    error = %e,
+                    files = ?self.sources.paths(),
+                    "Filesystem watcher unavailable — polling config every 60s"
+                );
+                None
+            }
+        };
+        let mut poll = tokio::time::interval(self.poll_interval);
+        poll.tick().await;
+        let mut deadline: Option<Instant> = None;
 
         loop {
             tokio::select! {
@@ -74,53 +341,294 @@ impl ConfigWatcher {
                     info!("Config watcher shutting down");
                     return Ok(());
                 }
-                _ = tokio::time::sleep(Duration::from_secs(60)) => {
+                Some(()) = event_rx.recv() => {
+                    deadline = Some(Instant::now() + self.debounce);
+                }
+                () = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                    deadline = None;
+                    self.check_and_reload().await;
+                }
+                _ = poll.tick(), if fs_watcher.is_none() => {
                     self.check_and_reload().await;
                 }
             }
         }
     }
 
-    /// Check if config has changed and reload if so.
-    async fn check_and_reload(&mut self) {
-        let new_hash = self.compute_hash().await;
+    /// Forward filesystem events in the config files' directories.
+    ///
+    /// Directories are watched rather than files because editors and
+    /// ConfigMap mounts replace files instead of writing them in place;
+    /// unrelated events are filtered by the content check.
+    fn watch_directories(&self, events: mpsc::UnboundedSender<()>) -> notify::Result<RecommendedWatcher> {
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if res.is_ok_and(|event| !event.kind.is_access()) {
+                let _ = events.send(());
+            }
+        })?;
+        let paths = self.sources.paths();
+        let mut dirs: Vec<&Path> = paths
+            .iter()
+            .map(|p| p.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or_else(|| Path::new(".")))
+            .collect();
+        dirs.sort_unstable();
+        dirs.dedup();
+        for dir in dirs {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        Ok(watcher)
+    }
 
-        if new_hash == self.last_hash {
+    /// Contents of every config file, concatenated in merge order.
+    async fn read_files(&self) -> Result<String> {
+        let mut content = String::new();
+        for path in self.sources.paths() {
+            content.push_str(&tokio::fs::read_to_string(&path).await?);
+            content.push('\0');
+        }
+        Ok(content)
+    }
+
+    /// Reload if any file's contents changed since the last check.
+    async fn check_and_reload(&mut self) {
+        let content = match self.read_files().await {
+            Ok(content) => content,
+            Err(e) => {
+                warn!(error = %e, "Failed to read config — keeping current");
+                return;
+            }
+        };
+        if self.last_content.as_deref() == Some(content.as_str()) {
             debug!("Config unchanged");
             return;
         }
+        // Remember even a bad file so it is not retried until edited again
+        self.last_content = Some(content);
 
         info!("Config change detected, reloading...");
+        let new_config = match self.sources.resolve() {
+            Ok(resolved) => resolved.config,
+            Err(e) => {
+                warn!(error = %format!("{e:#}"), "Invalid config — keeping current");
+                return;
+            }
+        };
+        if let Err(e) = self.apply(new_config).await {
+            error!(error = %format!("{e:#}"), "Config reload failed — rolled back");
+        }
+    }
 
-        match super::loader::load_config(&self.config_path) {
-            Ok(new_config) => {
-                self.last_hash = new_hash;
-                if self.config_tx.send(new_config).is_err() {
-                    warn!("No config watchers — update dropped");
-                } else {
-                    info!("Config reloaded successfully");
+    /// Publish the reloadable part of `new_config` transactionally.
+    ///
+    /// Waits for every subscribed component to ack; if one fails or
+    /// stays silent past the ack timeout, the previous config is
+    /// republished and the error returned.
+    pub async fn apply(&mut self, new_config: AppConfig) -> Result<()> {
+        let current = self.config_tx.borrow().config.clone();
+        let (applied, staged) = stage_reload(&current, new_config);
+        for change in &staged {
+            warn!(
+                section = change.section(),
+                change = %change,
+                "Config change requires restart — staged"
+            );
+        }
+
+        let changes = diff_configs(&current, &applied);
+        if changes.is_empty() {
+            info!(staged = staged.len(), "No runtime-reloadable changes");
+            return Ok(());
+        }
+        for change in &changes {
+            info!(change = %change, "Config change");
+        }
+
+        let generation = self.publish(applied);
+        match self.collect_acks(generation).await {
+            Ok(()) => {
+                info!(
+                    generation,
+                    applied = changes.len(),
+                    staged = staged.len(),
+                    "Config reloaded successfully"
+                );
+                Ok(())
+            }
+            Err(failure) => {
+                let rollback = self.publish(current);
+                if let Err(e) = self.collect_acks(rollback).await {
+                    error!(error = %e, generation = rollback, "Config rollback not acknowledged");
                 }
+                Err(failure.context(format!("generation {generation} rolled back as {rollback}")))
             }
-            Err(e) => {
-                warn!(
-                    error = %e,
-                    "Failed to reload config — keeping current"
-                );
+        }
+    }
+
+    /// Publish `config` as the next generation.
+    fn publish(&self, config: AppConfig) -> u64 {
+        let generation = self.config_tx.borrow().generation + 1;
+        self.config_tx.send_replace(ConfigUpdate { generation, config });
+        generation
+    }
+
+    /// Wait until every component acked `generation`.
+    async fn collect_acks(&mut self, generation: u64) -> Result<()> {
+        let mut pending: HashSet<&'static str> = self.components.iter().copied().collect();
+        let mut failures = Vec::new();
+        let deadline = Instant::now() + self.ack_timeout;
+
+        while !pending.is_empty() {
+            match tokio::time::timeout_at(deadline, self.acks_rx.recv()).await {
+                Ok(Some(ack)) if ack.generation == generation => {
+                    pending.remove(ack.component);
+                    if let Some(error) = ack.error {
+                        failures.push(format!("{}: {error}", ack.component));
+                    }
+                }
+                // Ack for an earlier generation
+                Ok(Some(_)) => {}
+                Ok(None) => break,
+                Err(_) => {
+                    let mut silent: Vec<_> = pending.iter().copied().collect();
+                    silent.sort_unstable();
+                    failures.push(format!("no ack from {}", silent.join(", ")));
+                    break;
+                }
             }
         }
+
+        if !failures.is_empty() {
+            bail!("Config generation {generation} failed: {}", failures.join("; "));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MarketConfig;
+
+    fn example() -> AppConfig {
+        crate::config::loader::load_config("config.toml.example").unwrap()
+    }
+
+    #[test]
+    fn test_diff_reports_leaf_paths() {
+        let old = example();
+        let mut new = old.clone();
+        new.lmsr.min_edge = 0.05;
+        new.markets[1].active = false;
+
+        let paths: Vec<String> = diff_configs(&old, &new).into_iter().map(|c| c.path).collect();
+        assert_eq!(paths, vec!["lmsr.min_edge", "markets[1].active"]);
+    }
+
+    #[test]
+    fn test_stage_keeps_running_api_and_contracts() {
+        let current = example();
+        let mut new = current.clone();
+        new.api.clob_base_url = "https://example.invalid".to_string();
+        new.contracts.ctf_exchange = "0x0000000000000000000000000000000000000001".to_string();
+        new.risk.max_position_size *= 2.0;
+
+        let (applied, staged) = stage_reload(&current, new);
+        assert_eq!(applied.api.clob_base_url, current.api.clob_base_url);
+        assert_eq!(applied.contracts.ctf_exchange, current.contracts.ctf_exchange);
+        assert_eq!(applied.risk.max_position_size, current.risk.max_position_size * 2.0);
+
+        let sections: Vec<&str> = staged.iter().map(ConfigChange::section).collect();
+        assert_eq!(sections, vec!["api", "contracts"]);
+    }
+
+    #[test]
+    fn test_stage_keeps_running_market_set() {
+        let current = example();
+
+        // Toggling a market applies at runtime
+        let mut toggled = current.clone();
+        toggled.markets[1].active = false;
+        let (applied, staged) = stage_reload(&current, toggled);
+        assert!(!applied.markets[1].active);
+        assert!(staged.is_empty());
+
+        // A new market (or a new token ID) waits for a restart, and so
+        // does the toggle that came with it
+        let mut added = current.clone();
+        added.markets[1].active = false;
+        added.markets.push(MarketConfig {
+            condition_id: "0xnew".to_string(),
+            ..current.markets[0].clone()
+        });
+        let (applied, staged) = stage_reload(&current, added);
+        assert_eq!(applied.markets, current.markets);
+        let paths: Vec<String> = staged.into_iter().map(|c| c.path).collect();
+        assert_eq!(paths, vec!["markets[1].active".to_string(), format!("markets[{}]", current.markets.len())]);
+
+        let mut edited = current.clone();
+        edited.markets[0].yes_token_id = "123".to_string();
+        let (applied, staged) = stage_reload(&current, edited);
+        assert_eq!(applied.markets, current.markets);
+        assert_eq!(staged[0].path, "markets[0].yes_token_id");
+    }
+
+    #[tokio::test]
+    async fn test_apply_without_subscribers_publishes() {
+        let mut watcher = ConfigWatcher::new(ConfigSources::file("config.toml.example"), example());
+        let mut new = example();
+        new.lmsr.min_edge = 0.05;
+
+        watcher.apply(new).await.unwrap();
+        let current = watcher.current();
+        assert_eq!(current.generation, 1);
+        assert_eq!(current.config.lmsr.min_edge, 0.05);
     }
 
-    /// Compute a simple hash of the config file contents for diff detection.
-    async fn compute_hash(&self) -> Option<u64> {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
+    #[tokio::test]
+    async fn test_failed_ack_rolls_back() {
+        let mut watcher = ConfigWatcher::new(ConfigSources::file("config.toml.example"), example())
+            .with_ack_timeout(Duration::from_secs(1));
+        let mut sub = watcher.subscribe("engine");
+        let component = tokio::spawn(async move {
+            let mut seen = Vec::new();
+            while let Some(update) = sub.next().await {
+                let result = if update.config.lmsr.min_edge == 0.05 {
+                    Err(anyhow::anyhow!("cannot apply"))
+                } else {
+                    Ok(())
+                };
+                sub.ack(update.generation, &result);
+                seen.push((update.generation, update.config.lmsr.min_edge));
+                if seen.len() == 2 {
+                    return seen;
+                }
+            }
+            seen
+        });
+
+        let mut new = example();
+        new.lmsr.min_edge = 0.05;
+        let err = watcher.apply(new).await.unwrap_err();
+        assert!(format!("{err:#}").contains("engine: cannot apply"));
+
+        // Rolled back: generation 2 republishes the original config
+        let current = watcher.current();
+        assert_eq!(current.generation, 2);
+        assert_eq!(current.config.lmsr.min_edge, example().lmsr.min_edge);
+        let seen = component.await.unwrap();
+        assert_eq!(seen, vec![(1, 0.05), (2, example().lmsr.min_edge)]);
+    }
 
-        let content = tokio::fs::read_to_string(&self.config_path)
-            .await
-            .ok()?;
+    #[tokio::test]
+    async fn test_silent_component_rolls_back() {
+        let mut watcher = ConfigWatcher::new(ConfigSources::file("config.toml.example"), example())
+            .with_ack_timeout(Duration::from_millis(50));
+        let _sub = watcher.subscribe("sleepy");
+        let mut new = example();
+        new.risk.max_position_size = 50.0;
 
-        let mut hasher = DefaultHasher::new();
-        content.hash(&mut hasher);
-        Some(hasher.finish())
+        let err = watcher.apply(new).await.unwrap_err();
+        assert!(format!("{err:#}").contains("no ack from sleepy"));
+        assert_eq!(watcher.current().config.risk.max_position_size, example().risk.max_position_size);
     }
 }
@@ -0,0 +1,344 @@
+//! Layered Config - Defaults → Files → Environment → CLI
+//!
+//! Resolution order (later layers win):
+//! 1. Built-in defaults (`config/defaults.toml`, compiled in)
+//! 2. TOML files: the base file (`config.toml`), the environment
+//!    overlay `config.<env>.toml` when `--env` / `POLYBOT_ENV` is set,
+//!    then any extra `--config` files
+//! 3. `POLYBOT__SECTION__KEY=value` environment variables, plus the
+//!    legacy secret variables (`POLY_API_KEY`, `PRIVATE_KEY`, ...)
+//! 4. CLI flags (`--data-dir`, `--log-level`, `--set section.key=value`)
+//!
+//! Tables merge key by key; arrays (e.g. `[[markets]]`) are replaced
+//! as a whole, but single entries can be addressed by index
+//! (`POLYBOT__MARKETS__0__ACTIVE=false`). Env and CLI values are
+//! parsed as TOML (`true`, `42`, `["BTC"]`) unless the key already
+//! holds a string. `[secrets]` is only accepted from the environment:
+//! files and command lines end up in git and `ps`. Once deserialized,
+//! the plaintext copies left in the merged table are zeroized.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use toml::{Table, Value};
+use zeroize::Zeroize;
+
+use super::loader::validate_config;
+use super::AppConfig;
+
+/// Built-in defaults (lowest layer).
+const DEFAULTS: &str = include_str!("defaults.toml");
+
+/// Prefix of config environment variables.
+const ENV_PREFIX: &str = "POLYBOT__";
+
+/// Separator between path segments in environment variable names.
+const ENV_SEPARATOR: &str = "__";
+
+/// Section holding credentials (environment only).
+const SECRETS_SECTION: &str = "secrets";
+
+/// Legacy secret variables and the `[secrets]` key they fill.
+const SECRET_ENV_VARS: &[(&str, &str)] = &[
+    ("POLY_API_KEY", "poly_api_key"),
+    ("POLY_API_SECRET", "poly_api_secret"),
+    ("POLY_PASSPHRASE", "poly_passphrase"),
+    ("PRIVATE_KEY", "private_key"),
+    ("KEYSTORE_PASSWORD", "keystore_password"),
+    ("ADMIN_TOKEN", "admin_token"),
+    ("VAULT_TOKEN", "vault_token"),
+];
+
+/// Where a config is assembled from.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSources {
+    /// TOML files, lowest precedence first; the first is the base file.
+    pub files: Vec<PathBuf>,
+    /// Deployment environment (`staging`, `production`, ...).
+    pub environment: Option<String>,
+    /// `section.key=value` overrides from the command line.
+    pub overrides: Vec<String>,
+}
+
+impl ConfigSources {
+    /// Sources with a single base file.
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self {
+            files: vec![path.into()],
+            ..Self::default()
+        }
+    }
+
+    /// Files to merge, environment overlay included, in merge order.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths = self.files.clone();
+        if let (Some(base), Some(env)) = (self.files.first(), &self.environment) {
+            paths.insert(1, overlay_path(base, env));
+        }
+        paths
+    }
+
+    /// Resolve against the process environment.
+    pub fn resolve(&self) -> Result<ResolvedConfig> {
+        self.resolve_with_env(std::env::vars())
+    }
+
+    /// Resolve against the given environment variables.
+    pub fn resolve_with_env(
+        &self,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<ResolvedConfig> {
+        let mut merged: Table = DEFAULTS.parse().context("Invalid built-in defaults")?;
+        let mut layers = vec!["defaults".to_string()];
+
+        for path in self.paths() {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+            let layer: Table = content
+                .parse()
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            if layer.contains_key(SECRETS_SECTION) {
+                bail!(
+                    "{}: [secrets] must come from environment variables, not config files",
+                    path.display()
+                );
+            }
+            merge(&mut merged, layer);
+            layers.push(format!("file {}", path.display()));
+        }
+
+        let mut root = Value::Table(merged);
+        let mut vars: Vec<(String, String)> = vars.into_iter().collect();
+        vars.sort_unstable();
+        for (name, value) in &vars {
+            if let Some(&(_, key)) = SECRET_ENV_VARS.iter().find(|(var, _)| var == name) {
+                if !value.is_empty() {
+                    set_path(&mut root, &[SECRETS_SECTION.to_string(), key.to_string()], value)?;
+                    layers.push(format!("env {name}"));
+                }
+                continue;
+            }
+            let Some(key) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let path: Vec<String> = key.split(ENV_SEPARATOR).map(str::to_lowercase).collect();
+            set_path(&mut root, &path, value).with_context(|| format!("Invalid {name}"))?;
+            layers.push(format!("env {name}"));
+        }
+
+        for assignment in &self.overrides {
+            let (key, value) = assignment
+                .split_once('=')
+                .with_context(|| format!("Override '{assignment}' is not KEY=VALUE"))?;
+            let path: Vec<String> = key.trim().split('.').map(str::to_string).collect();
+            if path[0] == SECRETS_SECTION {
+                bail!("Secrets cannot be set on the command line (visible in `ps`)");
+            }
+            set_path(&mut root, &path, value).with_context(|| format!("Invalid override '{key}'"))?;
+            layers.push(format!("cli {}", key.trim()));
+        }
+
+        let config: Result<AppConfig> = root
+            .clone()
+            .try_into()
+            .context("Resolved configuration does not match the schema");
+        let Value::Table(mut merged) = root else {
+            unreachable!("config root is a table");
+        };
+        redact_secrets(&mut merged);
+        for (_, value) in &mut vars {
+            value.zeroize();
+        }
+        let config = config?;
+        validate_config(&config)?;
+
+        Ok(ResolvedConfig {
+            config,
+            layers,
+            merged,
+        })
+    }
+}
+
+/// A resolved config and where it came from.
+///
+/// Not `Debug`: keeps the table out of logs even though secret
+/// values are already redacted.
+#[derive(Clone)]
+pub struct ResolvedConfig {
+    /// Validated config.
+    pub config: AppConfig,
+    /// Layers that contributed, lowest precedence first.
+    pub layers: Vec<String>,
+    /// Merged TOML before deserialization, secrets redacted.
+    merged: Table,
+}
+
+impl ResolvedConfig {
+    /// Merged config as TOML, secret values replaced by `<redacted>`.
+    pub fn redacted_toml(&self) -> String {
+        toml::to_string(&self.merged).unwrap_or_default()
+    }
+}
+
+/// Zeroize every `[secrets]` value and replace it with `<redacted>`.
+fn redact_secrets(merged: &mut Table) {
+    if let Some(Value::Table(secrets)) = merged.get_mut(SECRETS_SECTION) {
+        for (_, value) in secrets.iter_mut() {
+            if let Value::String(plain) = value {
+                plain.zeroize();
+            }
+            *value = Value::String("<redacted>".to_string());
+        }
+    }
+}
+
+/// `config.toml` + `staging` → `config.staging.toml` (same directory).
+fn overlay_path(base: &Path, environment: &str) -> PathBuf {
+    let stem = base.file_stem().unwrap_or_default().to_string_lossy();
+    base.with_file_name(format!("{stem}.{environment}.toml"))
+}
+
+/// Deep-merge `layer` into `base`; non-table values are replaced.
+fn merge(base: &mut Table, layer: Table) {
+    for (key, value) in layer {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(layer)) => merge(base, layer),
+            (Some(slot), value) => *slot = value,
+            (None, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Set the value at `path` (table keys or array indices) from `raw`.
+fn set_path(root: &mut Value, path: &[String], raw: &str) -> Result<()> {
+    let literal = path.first().is_some_and(|s| s == SECRETS_SECTION);
+    let mut node = root;
+    for segment in path {
+        anyhow::ensure!(!segment.is_empty(), "empty key segment");
+        node = match node {
+            Value::Table(table) => table
+                .entry(segment.clone())
+                .or_insert_with(|| Value::Table(Table::new())),
+            Value::Array(items) => {
+                let index: usize = segment
+                    .parse()
+                    .with_context(|| format!("'{segment}' is not an array index"))?;
+                let len = items.len();
+                items
+                    .get_mut(index)
+                    .with_context(|| format!("index {index} out of range ({len} entries)"))?
+            }
+            _ => bail!("'{segment}' is below a non-table value"),
+        };
+    }
+    *node = if literal || node.is_str() {
+        Value::String(raw.to_string())
+    } else {
+        parse_scalar(raw)
+    };
+    Ok(())
+}
+
+/// Parse an env / CLI value as TOML, falling back to a bare string.
+fn parse_scalar(raw: &str) -> Value {
+    format!("v = {raw}")
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut t| t.remove("v"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ports::secrets::Secret;
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("polybot-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_layers_apply_in_order() {
+        let dir = scratch_dir("layers");
+        let base = dir.join("config.toml");
+        std::fs::copy("config.toml.example", &base).unwrap();
+        std::fs::write(
+            dir.join("config.staging.toml"),
+            "[bot]\nname = \"staging-bot\"\n[risk]\nmax_position_size = 40.0\n",
+        )
+        .unwrap();
+
+        let sources = ConfigSources {
+            files: vec![base],
+            environment: Some("staging".to_string()),
+            overrides: vec!["risk.max_position_size=25".to_string()],
+        };
+        let resolved = sources
+            .resolve_with_env(env(&[
+                ("POLYBOT__RISK__MAX_POSITION_SIZE", "30"),
+                ("POLYBOT__MARKETS__1__ACTIVE", "false"),
+                ("POLYBOT__BOT__DATA_DIR", "/var/lib/polybot"),
+                ("HOME", "/root"),
+            ]))
+            .unwrap();
+
+        let config = &resolved.config;
+        assert_eq!(config.bot.name, "staging-bot");
+        assert_eq!(config.risk.max_position_size, 25.0);
+        assert!(!config.markets[1].active);
+        assert_eq!(config.bot.data_dir, "/var/lib/polybot");
+        // Defaults fill what the files leave out
+        assert_eq!(config.lmsr.max_position_fraction, 0.0625);
+        assert_eq!(resolved.layers.len(), 7);
+        assert_eq!(resolved.layers.last().unwrap(), "cli risk.max_position_size");
+    }
+
+    #[test]
+    fn test_secrets_only_from_env_and_redacted() {
+        let sources = ConfigSources::file("config.toml.example");
+        let resolved = sources
+            .resolve_with_env(env(&[
+                ("POLY_API_SECRET", "c2VjcmV0"),
+                ("POLYBOT__SECRETS__ADMIN_TOKEN", "12345"),
+            ]))
+            .unwrap();
+        let secrets = &resolved.config.secrets;
+        assert_eq!(secrets.poly_api_secret.as_ref().map(Secret::expose), Some("c2VjcmV0"));
+        assert_eq!(secrets.admin_token.as_ref().map(Secret::expose), Some("12345"));
+
+        let printed = resolved.redacted_toml();
+        assert!(!printed.contains("c2VjcmV0") && !printed.contains("12345"));
+        assert!(printed.contains("poly_api_secret = \"<redacted>\""));
+        assert!(!format!("{:?}", resolved.config).contains("c2VjcmV0"));
+
+        let cli = ConfigSources {
+            overrides: vec!["secrets.private_key=0xabc".to_string()],
+            ..sources
+        };
+        assert!(cli.resolve_with_env(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_rejects_secrets_in_files() {
+        let dir = scratch_dir("secrets");
+        let base = dir.join("config.toml");
+        let mut content = std::fs::read_to_string("config.toml.example").unwrap();
+        content.push_str("\n[secrets]\nprivate_key = \"0xabc\"\n");
+        std::fs::write(&base, content).unwrap();
+
+        let Err(err) = ConfigSources::file(base).resolve_with_env(Vec::new()) else {
+            panic!("secrets in a config file must be rejected");
+        };
+        assert!(err.to_string().contains("[secrets]"));
+    }
+}
@@ -3,11 +3,22 @@
 //! Loads the TOML configuration file from the given path and
 //! deserializes it into `AppConfig`. Validates critical fields
 //! and merges with environment variable overrides.
+//!
+//! Validation covers ranges, formats (EIP-55 address checksums,
+//! condition / token ID formats, URL schemes), uniqueness of markets
+//! and cross-field constraints (e.g. `max_position_size <=
+//! max_total_exposure`, `min_edge` reachable after fees). The same
+//! checks gate every hot reload.
+
+use std::collections::HashSet;
+use std::str::FromStr;
 
+use alloy::primitives::{Address, U256};
 use anyhow::{Context, Result};
 use tracing::info;
 
-use super::AppConfig;
+use super::{AppConfig, SecretBackend, TransferMode};
+use crate::domain::fees::{FeeClass, FeeSchedule};
 
 /// Load and validate configuration from a TOML file.
 ///
@@ -17,17 +28,24 @@ pub fn load_config(path: &str) -> Result<AppConfig> {
     let content = std::fs::read_to_string(path)
         .context(format!("Failed to read config file: {path}"))?;
 
+    let config = parse_config(&content)?;
+
+    info!(path = path, "Configuration loaded successfully");
+    Ok(config)
+}
+
+/// Parse and validate config.toml contents.
+pub fn parse_config(content: &str) -> Result<AppConfig> {
     let config: AppConfig =
-        toml::from_str(&content).context("Failed to parse config.toml")?;
+        toml::from_str(content).context("Failed to parse config.toml")?;
 
     validate_config(&config)?;
-
-    info!(path = path, "Configuration loaded successfully");
     Ok(config)
 }
 
 /// Validate critical configuration fields.
-fn validate_config(config: &AppConfig) -> Result<()> {
+#[allow(clippy::too_many_lines)]
+pub fn validate_config(config: &AppConfig) -> Result<()> {
     anyhow::ensure!(
         !config.api.clob_base_url.is_empty(),
         "api.clob_base_url must not be empty"
@@ -44,6 +62,18 @@ fn validate_config(config: &AppConfig) -> Result<()> {
         config.lmsr.kelly_fraction > 0.0 && config.lmsr.kelly_fraction <= 1.0,
         "lmsr.kelly_fraction must be in (0, 1]"
     );
+    anyhow::ensure!(
+        config.lmsr.max_position_fraction > 0.0 && config.lmsr.max_position_fraction <= 1.0,
+        "lmsr.max_position_fraction must be in (0, 1]"
+    );
+    anyhow::ensure!(
+        config.lmsr.max_drawdown_fraction > 0.0 && config.lmsr.max_drawdown_fraction <= 1.0,
+        "lmsr.max_drawdown_fraction must be in (0, 1]"
+    );
+    anyhow::ensure!(
+        (0.0..=1.0).contains(&config.lmsr.min_drawdown_scale),
+        "lmsr.min_drawdown_scale must be in [0, 1]"
+    );
     anyhow::ensure!(
         config.risk.max_daily_loss_fraction > 0.0
             && config.risk.max_daily_loss_fraction <= 1.0,
@@ -53,10 +83,548 @@ fn validate_config(config: &AppConfig) -> Result<()> {
         !config.contracts.ctf_exchange.is_empty(),
         "contracts.ctf_exchange must not be empty"
     );
+    anyhow::ensure!(
+        !config.contracts.conditional_tokens.is_empty(),
+        "contracts.conditional_tokens must not be empty"
+    );
     anyhow::ensure!(
         !config.strategy.assets.is_empty(),
         "strategy.assets must contain at least one asset"
     );
+    anyhow::ensure!(
+        config.risk.half_open_size_fraction > 0.0
+            && config.risk.half_open_size_fraction <= 1.0,
+        "risk.half_open_size_fraction must be in (0, 1]"
+    );
+    anyhow::ensure!(
+        (-1.0..=1.0).contains(&config.strategy.same_asset_correlation)
+            && (-1.0..=1.0).contains(&config.strategy.cross_asset_correlation),
+        "strategy correlations must be in [-1, 1]"
+    );
+    anyhow::ensure!(
+        config.settlement.batch_redeem_hour_utc < 24,
+        "settlement.batch_redeem_hour_utc must be in [0, 23]"
+    );
+    anyhow::ensure!(
+        config.settlement.max_gas_gwei > 0.0,
+        "settlement.max_gas_gwei must be positive"
+    );
+    anyhow::ensure!(
+        config.settlement.merge_interval_secs > 0 && config.settlement.min_merge_size >= 0.0,
+        "settlement.merge_interval_secs must be positive and min_merge_size non-negative"
+    );
+    anyhow::ensure!(
+        config.complete_set.min_edge >= 0.0 && config.complete_set.max_size > 0.0,
+        "complete_set.min_edge must be non-negative, max_size positive"
+    );
+    anyhow::ensure!(
+        config.neg_risk_arb.min_edge >= 0.0 && config.neg_risk_arb.max_size > 0.0,
+        "neg_risk_arb.min_edge must be non-negative, max_size positive"
+    );
+    anyhow::ensure!(
+        config.portfolio.mark_interval_secs > 0,
+        "portfolio.mark_interval_secs must be positive"
+    );
+    anyhow::ensure!(
+        config.fees.refresh_interval_secs > 0,
+        "fees.refresh_interval_secs must be positive"
+    );
+    anyhow::ensure!(
+        (0.0..=1.0).contains(&config.fees.maker_rebate_share),
+        "fees.maker_rebate_share must be in [0, 1]"
+    );
+    anyhow::ensure!(
+        config.fees.reconcile_tolerance >= 0.0,
+        "fees.reconcile_tolerance must be non-negative"
+    );
+    anyhow::ensure!(
+        config.recovery.checkpoint_interval_secs > 0,
+        "recovery.checkpoint_interval_secs must be positive"
+    );
+    anyhow::ensure!(
+        config.recovery.position_tolerance >= 0.0,
+        "recovery.position_tolerance must be non-negative"
+    );
+    anyhow::ensure!(
+        config.storage.sqlite_path.as_ref().is_none_or(|p| !p.trim().is_empty()),
+        "storage.sqlite_path must not be empty"
+    );
+    validate_neg_risk_groups(config)?;
+    validate_risk_limits(config)?;
+    validate_endpoints(config)?;
+    validate_contracts(config)?;
+    validate_markets(config)?;
+    validate_cross_field(config)?;
+    validate_secret_store(config)?;
+    validate_wallet(config)?;
+
+    Ok(())
+}
+
+/// Validate hot/cold allocation and gas monitoring settings.
+fn validate_wallet(config: &AppConfig) -> Result<()> {
+    let w = &config.wallet;
+    anyhow::ensure!(
+        w.hot_fraction > 0.0 && w.hot_fraction <= 1.0,
+        "wallet.hot_fraction must be in (0, 1]"
+    );
+    anyhow::ensure!(
+        w.hot_alert_threshold > w.hot_fraction && w.hot_alert_threshold <= 1.0,
+        "wallet.hot_alert_threshold must be in (hot_fraction, 1]"
+    );
+    anyhow::ensure!(
+        w.min_matic_balance >= 0.0 && w.min_transfer >= 0.0,
+        "wallet.min_matic_balance and min_transfer must be non-negative"
+    );
+    anyhow::ensure!(w.check_interval_secs > 0, "wallet.check_interval_secs must be positive");
+    if let Some(cold) = &w.cold_address {
+        let address = parse_address(cold)
+            .with_context(|| format!("wallet.cold_address: invalid address '{cold}'"))?;
+        anyhow::ensure!(!address.is_zero(), "wallet.cold_address must not be the zero address");
+    }
+    anyhow::ensure!(
+        w.cold_address.is_some() || w.transfer_mode == TransferMode::Propose,
+        "wallet.transfer_mode = {:?} needs wallet.cold_address",
+        w.transfer_mode
+    );
+    Ok(())
+}
+
+/// Validate the secret backend settings.
+fn validate_secret_store(config: &AppConfig) -> Result<()> {
+    let store = &config.secret_store;
+    match store.provider {
+        SecretBackend::Env => {}
+        SecretBackend::File => anyhow::ensure!(
+            store.file_path.as_deref().is_some_and(|p| !p.is_empty()),
+            "secret_store.file_path is required with provider = \"file\""
+        ),
+        SecretBackend::Vault => {
+            let addr = store
+                .vault_addr
+                .as_deref()
+                .context("secret_store.vault_addr is required with provider = \"vault\"")?;
+            let loopback = ["http://127.0.0.1", "http://localhost"]
+                .iter()
+                .any(|prefix| addr.starts_with(prefix));
+            anyhow::ensure!(
+                addr.starts_with("https://") || loopback,
+                "secret_store.vault_addr must be https:// (plain http only on loopback), got '{addr}'"
+            );
+            anyhow::ensure!(
+                !store.vault_path.trim_matches('/').is_empty(),
+                "secret_store.vault_path must not be empty"
+            );
+        }
+    }
+    if let Some(path) = &store.keystore_path {
+        anyhow::ensure!(!path.is_empty(), "secret_store.keystore_path must not be empty");
+    }
+    Ok(())
+}
+
+/// Validate endpoint URLs and connection parameters.
+fn validate_endpoints(config: &AppConfig) -> Result<()> {
+    let api = &config.api;
+    for (name, url, schemes) in [
+        ("api.clob_base_url", &api.clob_base_url, ["https://", "http://"]),
+        ("api.rpc_url", &api.rpc_url, ["https://", "http://"]),
+        ("api.clob_ws_url", &api.clob_ws_url, ["wss://", "ws://"]),
+    ] {
+        anyhow::ensure!(
+            schemes.iter().any(|s| url.starts_with(s) && url.len() > s.len()),
+            "{name} must be a {} URL, got '{url}'",
+            schemes.join(" or ")
+        );
+    }
+    anyhow::ensure!(api.chain_id > 0, "api.chain_id must be positive");
+    anyhow::ensure!(api.timeout_ms > 0, "api.timeout_ms must be positive");
+    Ok(())
+}
+
+/// Validate contract addresses: well-formed, EIP-55 checksummed when
+/// mixed-case, non-zero and pairwise distinct.
+fn validate_contracts(config: &AppConfig) -> Result<()> {
+    let c = &config.contracts;
+    let mut seen = HashSet::new();
+    for (name, value) in [
+        ("contracts.ctf_exchange", &c.ctf_exchange),
+        ("contracts.usdce", &c.usdce),
+        ("contracts.neg_risk_adapter", &c.neg_risk_adapter),
+        ("contracts.conditional_tokens", &c.conditional_tokens),
+    ] {
+        let address = parse_address(value).with_context(|| format!("{name}: invalid address '{value}'"))?;
+        anyhow::ensure!(!address.is_zero(), "{name} must not be the zero address");
+        anyhow::ensure!(seen.insert(address), "{name} duplicates another contract address");
+    }
+    Ok(())
+}
+
+/// Parse an address, enforcing the EIP-55 checksum on mixed-case input.
+fn parse_address(value: &str) -> Result<Address> {
+    let hex = value.strip_prefix("0x").unwrap_or(value);
+    let mixed_case = hex.chars().any(|c| c.is_ascii_lowercase())
+        && hex.chars().any(|c| c.is_ascii_uppercase());
+    if mixed_case {
+        Address::parse_checksummed(value, None).context("EIP-55 checksum mismatch")
+    } else {
+        Address::from_str(value).context("expected 0x + 40 hex characters")
+    }
+}
+
+/// Whether `value` is `0x` followed by 64 hex characters (bytes32).
+fn is_bytes32(value: &str) -> bool {
+    value
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Whether `value` is an ERC-1155 position ID: a decimal uint256 or a
+/// `0x` bytes32.
+fn is_token_id(value: &str) -> bool {
+    is_bytes32(value)
+        || (!value.is_empty()
+            && value.chars().all(|c| c.is_ascii_digit())
+            && U256::from_str_radix(value, 10).is_ok())
+}
+
+/// Validate market IDs, strategies and uniqueness.
+fn validate_markets(config: &AppConfig) -> Result<()> {
+    let mut conditions = HashSet::new();
+    let mut tokens = HashSet::new();
+    for m in &config.markets {
+        let id = &m.condition_id;
+        anyhow::ensure!(is_bytes32(id), "market {id}: condition_id must be 0x + 64 hex");
+        anyhow::ensure!(conditions.insert(id.as_str()), "market {id} is listed twice");
+        for (side, token_id) in [("yes", &m.yes_token_id), ("no", &m.no_token_id)] {
+            anyhow::ensure!(
+                is_token_id(token_id),
+                "market {id}: {side}_token_id must be a decimal uint256 or 0x + 64 hex"
+            );
+            anyhow::ensure!(
+                tokens.insert(token_id.as_str()),
+                "market {id}: {side}_token_id {token_id} is used by another outcome"
+            );
+        }
+        anyhow::ensure!(!m.strategies.is_empty(), "market {id}: strategies must not be empty");
+        if let Some(share) = m.maker_rebate_share {
+            anyhow::ensure!(
+                (0.0..=1.0).contains(&share),
+                "market {id}: maker_rebate_share must be in [0, 1]"
+            );
+        }
+        if let Some(event_id) = &m.neg_risk_market_id {
+            anyhow::ensure!(is_bytes32(event_id), "market {id}: neg_risk_market_id must be 0x + 64 hex");
+        }
+    }
+    for group in &config.risk.correlation_groups {
+        for id in &group.markets {
+            anyhow::ensure!(
+                conditions.contains(id.as_str()),
+                "risk.correlation_groups[{}]: unknown market {id}",
+                group.name
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Validate constraints spanning several fields or sections.
+fn validate_cross_field(config: &AppConfig) -> Result<()> {
+    let risk = &config.risk;
+    anyhow::ensure!(
+        risk.max_position_size > 0.0 && risk.max_position_size <= risk.max_total_exposure,
+        "risk.max_position_size must be positive and <= risk.max_total_exposure"
+    );
+    anyhow::ensure!(risk.min_bankroll >= 0.0, "risk.min_bankroll must not be negative");
+    anyhow::ensure!(risk.circuit_breaker_losses > 0, "risk.circuit_breaker_losses must be positive");
+    let scoped = risk
+        .market_limits
+        .iter()
+        .map(|l| l.max_exposure)
+        .chain(risk.asset_limits.iter().map(|l| l.max_exposure))
+        .chain(risk.correlation_groups.iter().map(|g| g.max_exposure));
+    for max_exposure in scoped.flatten() {
+        anyhow::ensure!(
+            max_exposure <= risk.max_total_exposure,
+            "scoped max_exposure {max_exposure} exceeds risk.max_total_exposure"
+        );
+    }
+
+    // Maker quotes pay no fee, so the LMSR edge is gross edge
+    anyhow::ensure!(
+        config.lmsr.min_edge > 0.0 && config.lmsr.min_edge < 0.5,
+        "lmsr.min_edge must be in (0, 0.5)"
+    );
+    // Taker strategies: a two-leg bundle at the fee peak (p = 0.5) of
+    // the most expensive configured tier must still clear min_edge
+    let peak_rate = config
+        .markets
+        .iter()
+        .map(|m| m.fee_class.fee_rate())
+        .fold(FeeClass::Standard.fee_rate(), f64::max);
+    let peak_schedule = FeeSchedule {
+        taker_fee_rate: peak_rate,
+        maker_rebate_share: 0.0,
+    };
+    let peak_fees = 2.0 * peak_schedule.taker_fee(0.5, 1.0);
+    for (name, min_edge, max_size) in [
+        (
+            "complete_set",
+            config.complete_set.min_edge,
+            config.complete_set.max_size,
+        ),
+        (
+            "neg_risk_arb",
+            config.neg_risk_arb.min_edge,
+            config.neg_risk_arb.max_size,
+        ),
+    ] {
+        anyhow::ensure!(
+            min_edge < 1.0 - peak_fees,
+            "{name}.min_edge {min_edge} is unreachable after taker fees ({peak_fees:.4} per set)"
+        );
+        anyhow::ensure!(
+            max_size <= risk.max_total_exposure,
+            "{name}.max_size exceeds risk.max_total_exposure"
+        );
+    }
+
+    let limits = &config.rate_limits;
+    anyhow::ensure!(
+        (1..=60).contains(&limits.max_orders_per_minute),
+        "rate_limits.max_orders_per_minute must be in [1, 60] (CLOB hard limit)"
+    );
+    anyhow::ensure!(
+        (1..=15).contains(&limits.max_orders_per_batch),
+        "rate_limits.max_orders_per_batch must be in [1, 15]"
+    );
+    anyhow::ensure!(
+        limits.min_interval_ms <= 60_000,
+        "rate_limits.min_interval_ms must be at most 60000"
+    );
+
+    let wallet = &config.wallet;
+    anyhow::ensure!(
+        wallet.hot_fraction > 0.0
+            && wallet.hot_fraction <= 1.0
+            && wallet.hot_alert_threshold >= wallet.hot_fraction,
+        "wallet.hot_fraction must be in (0, 1] and <= wallet.hot_alert_threshold"
+    );
+    anyhow::ensure!(
+        config.settlement.tip_gwei <= config.settlement.max_fee_gwei,
+        "settlement.tip_gwei must not exceed settlement.max_fee_gwei"
+    );
+    Ok(())
+}
+
+/// Validate neg-risk event groups: every member is a neg-risk market
+/// with a unique question index, and the members cover all
+/// `outcome_count` outcomes of the event. An inactive outcome only
+/// pauses the event, so it can be toggled by a hot reload.
+fn validate_neg_risk_groups(config: &AppConfig) -> Result<()> {
+    for market in &config.markets {
+        if market.neg_risk_market_id.is_some() {
+            anyhow::ensure!(
+                market.neg_risk && market.question_index.is_some(),
+                "market {} has neg_risk_market_id but is not neg_risk or lacks question_index",
+                market.condition_id
+            );
+            anyhow::ensure!(
+                market.outcome_count.is_some_and(|n| n >= 2),
+                "market {} has neg_risk_market_id but no outcome_count (>= 2)",
+                market.condition_id
+            );
+        }
+    }
+    for (event_id, members) in config.neg_risk_groups() {
+        let outcome_count = members[0].outcome_count.unwrap_or_default();
+        anyhow::ensure!(
+            members.iter().all(|m| m.outcome_count == Some(outcome_count)),
+            "neg-risk event {event_id}: markets disagree on outcome_count"
+        );
+        let mut indices: Vec<u32> = members.iter().filter_map(|m| m.question_index).collect();
+        indices.dedup();
+        anyhow::ensure!(
+            indices.len() == members.len(),
+            "neg-risk event {event_id} has duplicate question indices"
+        );
+        // Sorted and unique, so this means exactly 0..outcome_count
+        anyhow::ensure!(
+            indices.iter().copied().eq(0..outcome_count),
+            "neg-risk event {event_id} is incomplete: {} of {outcome_count} outcomes listed \
+             (question indices {indices:?})",
+            members.len()
+        );
+    }
+    Ok(())
+}
+
+/// Validate per-market, per-asset and correlation-group limits.
+fn validate_risk_limits(config: &AppConfig) -> Result<()> {
+    let positive = |v: Option<f64>| v.is_none_or(|x| x > 0.0);
+
+    for limit in &config.risk.market_limits {
+        anyhow::ensure!(
+            config
+                .markets
+                .iter()
+                .any(|m| m.condition_id == limit.condition_id),
+            "risk.market_limits: unknown condition_id {}",
+            limit.condition_id
+        );
+        anyhow::ensure!(
+            positive(limit.max_exposure) && positive(limit.max_net_delta),
+            "risk.market_limits[{}]: limits must be positive",
+            limit.condition_id
+        );
+    }
+    for limit in &config.risk.asset_limits {
+        anyhow::ensure!(
+            positive(limit.max_exposure) && positive(limit.max_net_delta),
+            "risk.asset_limits[{}]: limits must be positive",
+            limit.asset
+        );
+    }
+    for group in &config.risk.correlation_groups {
+        anyhow::ensure!(
+            !group.assets.is_empty() || !group.markets.is_empty(),
+            "risk.correlation_groups[{}]: needs at least one asset or market",
+            group.name
+        );
+        anyhow::ensure!(
+            positive(group.max_exposure) && positive(group.max_net_delta),
+            "risk.correlation_groups[{}]: limits must be positive",
+            group.name
+        );
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MarketConfig;
+
+    fn example() -> AppConfig {
+        load_config("config.toml.example").unwrap()
+    }
+
+    fn rejects(config: &AppConfig, needle: &str) {
+        let err = validate_config(config).unwrap_err().to_string();
+        assert!(err.contains(needle), "'{err}' does not mention '{needle}'");
+    }
+
+    #[test]
+    fn test_example_config_is_valid() {
+        assert!(validate_config(&example()).is_ok());
+    }
+
+    #[test]
+    fn test_vault_requires_tls_off_loopback() {
+        let mut config = example();
+        config.secret_store.provider = SecretBackend::Vault;
+        rejects(&config, "secret_store.vault_addr");
+
+        config.secret_store.vault_addr = Some("http://vault.internal:8200".to_string());
+        rejects(&config, "https://");
+        config.secret_store.vault_addr = Some("http://127.0.0.1:8200".to_string());
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_wallet_threshold_above_target() {
+        let mut config = example();
+        config.wallet.hot_alert_threshold = 0.15;
+        rejects(&config, "wallet.hot_alert_threshold");
+
+        config.wallet.hot_alert_threshold = 0.30;
+        config.wallet.transfer_mode = TransferMode::Auto;
+        rejects(&config, "wallet.cold_address");
+        config.wallet.cold_address = Some("0x008AeEda4D805471dF9b2A5B0f38A0C3bCBA786b".to_string());
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_bad_checksum() {
+        let mut config = example();
+        // Flip the case of one checksummed letter
+        config.contracts.usdce = "0x2791bca1f2de4661ED88A30C99A7a9449Aa84174".to_string();
+        rejects(&config, "contracts.usdce");
+
+        // All-lowercase addresses carry no checksum and are accepted
+        config.contracts.usdce = config.contracts.usdce.to_lowercase();
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_malformed_and_duplicate_markets() {
+        let mut config = example();
+        config.markets[0].yes_token_id = "0x_btc_yes".to_string();
+        rejects(&config, "yes_token_id");
+
+        let mut config = example();
+        config.markets[1].condition_id = config.markets[0].condition_id.clone();
+        rejects(&config, "listed twice");
+
+        let mut config = example();
+        config.markets[1].no_token_id = config.markets[0].yes_token_id.clone();
+        rejects(&config, "used by another outcome");
+    }
+
+    #[test]
+    fn test_cross_field_constraints() {
+        let mut config = example();
+        config.risk.max_position_size = config.risk.max_total_exposure + 1.0;
+        rejects(&config, "max_position_size");
+
+        let mut config = example();
+        config.complete_set.min_edge = 0.9999;
+        rejects(&config, "unreachable after taker fees");
+
+        let mut config = example();
+        config.rate_limits.max_orders_per_minute = 120;
+        rejects(&config, "max_orders_per_minute");
+    }
+
+    #[test]
+    fn test_rejects_incomplete_neg_risk_group() {
+        let mut config = example();
+        let template = config.markets[0].clone();
+        for index in 0..3u32 {
+            config.markets.push(MarketConfig {
+                condition_id: format!("0x{:064x}", index + 1),
+                yes_token_id: format!("{}", 1000 + 2 * index),
+                no_token_id: format!("{}", 1001 + 2 * index),
+                neg_risk: true,
+                neg_risk_market_id: Some(format!("0x{:064x}", 99)),
+                question_index: Some(index),
+                outcome_count: Some(3),
+                ..template.clone()
+            });
+        }
+        assert!(validate_config(&config).is_ok());
+
+        let mut missing_count = config.clone();
+        missing_count.markets[2].outcome_count = None;
+        rejects(&missing_count, "no outcome_count");
+
+        let mut disagree = config.clone();
+        disagree.markets[2].outcome_count = Some(4);
+        rejects(&disagree, "disagree on outcome_count");
+
+        // Event has a fourth outcome that is not listed
+        for market in &mut config.markets[2..] {
+            market.outcome_count = Some(4);
+        }
+        rejects(&config, "incomplete");
+
+        // An inactive outcome pauses the event but keeps it complete
+        for market in &mut config.markets[2..] {
+            market.outcome_count = Some(3);
+        }
+        config.markets[3].active = false;
+        assert!(validate_config(&config).is_ok());
+    }
+}
@@ -1,15 +1,24 @@
 //! Configuration module — TOML-based bot configuration.
 //!
-//! All configuration comes from `config.toml` (never hardcoded).
-//! Secrets come from environment variables (never in config files).
+//! All configuration comes from `config.toml` (never hardcoded),
+//! layered over built-in defaults and under env / CLI overrides (see
+//! `layers`). Secrets come from environment variables (never in
+//! config files) or from the backend chosen in `[secret_store]`.
 
 pub mod hot_reload;
+pub mod layers;
 pub mod loader;
 
+use std::path::{Path, PathBuf};
+
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::fees::{FeeClass, FeeSchedule};
+use crate::domain::portfolio::CostMethod;
 use crate::domain::trade::{Asset, BotMode};
+use crate::domain::wallet_allocation::AllocationPolicy;
+use crate::ports::secrets::Secret;
 
 /// Top-level application configuration loaded from `config.toml`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +45,30 @@ pub struct AppConfig {
     /// Settlement parameters (batch redeem timing).
     #[serde(default)]
     pub settlement: SettlementConfig,
+    /// Complete-set (YES + NO bundle) arbitrage parameters.
+    #[serde(default)]
+    pub complete_set: CompleteSetConfig,
+    /// Neg-risk event group arbitrage parameters.
+    #[serde(default)]
+    pub neg_risk_arb: NegRiskArbConfig,
+    /// Portfolio ledger (cost basis, marks, daily PnL).
+    #[serde(default)]
+    pub portfolio: PortfolioConfig,
+    /// Per-market fee schedules, rebates and fee reconciliation.
+    #[serde(default)]
+    pub fees: FeeConfig,
+    /// State checkpointing and startup recovery checks.
+    #[serde(default)]
+    pub recovery: RecoveryConfig,
+    /// Persistence backend (JSONL files or embedded SQLite).
+    #[serde(default)]
+    pub storage: StorageConfig,
+    /// Where credentials are loaded from (env, file, Vault, keystore).
+    #[serde(default)]
+    pub secret_store: SecretStoreConfig,
+    /// Credentials from the environment; never serialized.
+    #[serde(default, skip_serializing)]
+    pub secrets: SecretsConfig,
 }
 
 /// Bot identity and operational settings.
@@ -49,6 +82,89 @@ pub struct BotConfig {
     pub dry_run: bool,
     /// Operating mode: Paper or Live.
     pub mode: BotMode,
+    /// Simulated USDC balance outside live mode.
+    #[serde(default = "default_paper_balance")]
+    pub paper_balance: f64,
+    /// Kill switch file; trading halts while it exists.
+    #[serde(default = "default_kill_switch_path")]
+    pub kill_switch_path: String,
+    /// Directory for state snapshots, trade logs and PnL.
+    #[serde(default = "default_data_dir")]
+    pub data_dir: String,
+}
+
+fn default_kill_switch_path() -> String { "data/KILL".to_string() }
+fn default_data_dir() -> String { "data".to_string() }
+fn default_paper_balance() -> f64 { 1000.0 }
+
+/// Credentials, filled only from environment variables.
+///
+/// Values are `Secret`s: zeroized on drop, `<redacted>` in `Debug`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SecretsConfig {
+    /// CLOB API key (`POLY_API_KEY`).
+    pub poly_api_key: Option<Secret>,
+    /// CLOB API secret (`POLY_API_SECRET`).
+    pub poly_api_secret: Option<Secret>,
+    /// CLOB passphrase (`POLY_PASSPHRASE`).
+    pub poly_passphrase: Option<Secret>,
+    /// Wallet signing key, hex (`PRIVATE_KEY`).
+    pub private_key: Option<Secret>,
+    /// Passphrase of `secret_store.keystore_path` (`KEYSTORE_PASSWORD`).
+    pub keystore_password: Option<Secret>,
+    /// Bearer token for `/admin` (`ADMIN_TOKEN`).
+    pub admin_token: Option<Secret>,
+    /// Token for the Vault backend (`VAULT_TOKEN`).
+    pub vault_token: Option<Secret>,
+}
+
+/// Secret backends.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretBackend {
+    /// `[secrets]`, i.e. environment variables.
+    #[default]
+    Env,
+    /// A JSON object of name → value in a file only the bot can read.
+    File,
+    /// A Vault-compatible KV endpoint (token from `VAULT_TOKEN`).
+    Vault,
+}
+
+/// Where credentials come from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretStoreConfig {
+    /// Backend for the CLOB credentials, admin token and keystore password.
+    #[serde(default)]
+    pub provider: SecretBackend,
+    /// JSON secrets file (`provider = "file"`).
+    #[serde(default)]
+    pub file_path: Option<String>,
+    /// Vault base URL, e.g. `https://vault.internal:8200` (`provider = "vault"`).
+    #[serde(default)]
+    pub vault_addr: Option<String>,
+    /// Secret path under `/v1/`, e.g. `secret/data/polybot` (KV v2) or
+    /// `secret/polybot` (KV v1).
+    #[serde(default = "default_vault_path")]
+    pub vault_path: String,
+    /// Encrypted Web3 keystore (JSON v3) holding the wallet key; when
+    /// set, `private_key` is ignored and `keystore_password` unlocks it.
+    #[serde(default)]
+    pub keystore_path: Option<String>,
+}
+
+fn default_vault_path() -> String { "secret/data/polymarket-lmsr-bot".to_string() }
+
+impl Default for SecretStoreConfig {
+    fn default() -> Self {
+        Self {
+            provider: SecretBackend::Env,
+            file_path: None,
+            vault_addr: None,
+            vault_path: default_vault_path(),
+            keystore_path: None,
+        }
+    }
 }
 
 /// Strategy configuration for multi-asset trading.
@@ -60,8 +176,17 @@ pub struct StrategyConfig {
     pub debounce_ms: u64,
     /// Minimum price delta to act on (checklist: 0.5%).
     pub min_delta_pct: f64,
+    /// Return correlation assumed between markets on the same asset.
+    #[serde(default = "default_same_asset_correlation")]
+    pub same_asset_correlation: f64,
+    /// Return correlation assumed between BTC and ETH markets.
+    #[serde(default = "default_cross_asset_correlation")]
+    pub cross_asset_correlation: f64,
 }
 
+fn default_same_asset_correlation() -> f64 { 0.9 }
+fn default_cross_asset_correlation() -> f64 { 0.6 }
+
 /// API endpoint configuration (URLs from config, secrets from env).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiConfig {
@@ -71,10 +196,15 @@ pub struct ApiConfig {
     pub clob_ws_url: String,
     /// Polygon RPC URL.
     pub rpc_url: String,
+    /// Expected chain ID (137 = Polygon mainnet, 31337 = local anvil).
+    #[serde(default = "default_chain_id")]
+    pub chain_id: u64,
     /// Request timeout in milliseconds.
     pub timeout_ms: u64,
 }
 
+fn default_chain_id() -> u64 { 137 }
+
 /// LMSR model and pricing parameters.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LmsrConfig {
@@ -86,8 +216,25 @@ pub struct LmsrConfig {
     pub min_edge: f64,
     /// Bayesian EWMA prior weight (alpha).
     pub prior_weight: Decimal,
+    /// Maximum position as fraction of bankroll (Kelly cap).
+    #[serde(default = "default_max_position_fraction")]
+    pub max_position_fraction: f64,
+    /// Drawdown (fraction of peak equity) at which sizing hits its floor.
+    #[serde(default = "default_max_drawdown_fraction")]
+    pub max_drawdown_fraction: f64,
+    /// Kelly multiplier floor once `max_drawdown_fraction` is reached.
+    #[serde(default = "default_min_drawdown_scale")]
+    pub min_drawdown_scale: f64,
+    /// Shrink Kelly by the estimator's standard error (Bayesian Kelly).
+    #[serde(default = "default_uncertainty_shrinkage")]
+    pub uncertainty_shrinkage: bool,
 }
 
+fn default_max_position_fraction() -> f64 { 0.0625 }
+fn default_max_drawdown_fraction() -> f64 { 0.20 }
+fn default_min_drawdown_scale() -> f64 { 0.25 }
+fn default_uncertainty_shrinkage() -> bool { true }
+
 /// Risk management configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskConfig {
@@ -103,8 +250,72 @@ pub struct RiskConfig {
     pub circuit_breaker_losses: u32,
     /// Cooldown period in seconds after circuit breaker.
     pub cooldown_seconds: u64,
+    /// Position limit multiplier while the breaker is half-open.
+    #[serde(default = "default_half_open_size_fraction")]
+    pub half_open_size_fraction: f64,
+    /// Per-market overrides (`[[risk.market_limits]]`).
+    #[serde(default)]
+    pub market_limits: Vec<MarketLimit>,
+    /// Per-asset limits across all markets of that asset.
+    #[serde(default)]
+    pub asset_limits: Vec<AssetLimit>,
+    /// Correlation buckets treated as a single bet.
+    #[serde(default)]
+    pub correlation_groups: Vec<CorrelationGroup>,
 }
 
+/// Limits for a single market (both outcome tokens).
+///
+/// Net delta is YES cost minus NO cost in USDC.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketLimit {
+    /// Condition ID the limit applies to.
+    pub condition_id: String,
+    /// Maximum gross exposure in USDC (positions + resting buys).
+    #[serde(default)]
+    pub max_exposure: Option<f64>,
+    /// Maximum absolute net directional delta in USDC.
+    #[serde(default)]
+    pub max_net_delta: Option<f64>,
+}
+
+/// Limits summed over every market of one asset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetLimit {
+    /// Asset the limit applies to.
+    pub asset: Asset,
+    /// Maximum gross exposure in USDC.
+    #[serde(default)]
+    pub max_exposure: Option<f64>,
+    /// Maximum absolute net directional delta in USDC.
+    #[serde(default)]
+    pub max_net_delta: Option<f64>,
+}
+
+/// A bucket of correlated markets limited as one position.
+///
+/// Members are every market whose asset is in `assets` plus any
+/// market listed in `markets` by condition ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelationGroup {
+    /// Group name (used in logs and rejection reasons).
+    pub name: String,
+    /// Assets whose markets belong to the group.
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+    /// Additional member markets by condition ID.
+    #[serde(default)]
+    pub markets: Vec<String>,
+    /// Maximum gross exposure in USDC.
+    #[serde(default)]
+    pub max_exposure: Option<f64>,
+    /// Maximum absolute net directional delta in USDC.
+    #[serde(default)]
+    pub max_net_delta: Option<f64>,
+}
+
+fn default_half_open_size_fraction() -> f64 { 0.25 }
+
 /// Rate limiting configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimitConfig {
@@ -125,10 +336,12 @@ pub struct ContractConfig {
     pub usdce: String,
     /// Neg Risk Adapter contract address.
     pub neg_risk_adapter: String,
+    /// ConditionalTokens (ERC-1155 CTF) contract address.
+    pub conditional_tokens: String,
 }
 
 /// Individual market configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MarketConfig {
     /// Unique condition ID from Polymarket.
     pub condition_id: String,
@@ -140,6 +353,50 @@ pub struct MarketConfig {
     pub asset: Asset,
     /// Whether this market is actively traded.
     pub active: bool,
+    /// Neg-risk market: redeemed through the NegRiskAdapter.
+    #[serde(default)]
+    pub neg_risk: bool,
+    /// NegRiskAdapter market ID (bytes32) of the event this outcome
+    /// belongs to; markets sharing it form one event group.
+    #[serde(default)]
+    pub neg_risk_market_id: Option<String>,
+    /// Question index of this outcome within its neg-risk event.
+    #[serde(default)]
+    pub question_index: Option<u32>,
+    /// Number of outcomes in the neg-risk event (required with
+    /// `neg_risk_market_id`); every one must be listed.
+    #[serde(default)]
+    pub outcome_count: Option<u32>,
+    /// Strategies run on this market, by registry name (default
+    /// `["lmsr_mm"]`). Accepts `strategy = "name"` or a list.
+    #[serde(
+        default = "default_strategies",
+        alias = "strategy",
+        deserialize_with = "one_or_many"
+    )]
+    pub strategies: Vec<String>,
+    /// Fee tier used until (or unless) the CLOB reports a rate.
+    #[serde(default)]
+    pub fee_class: FeeClass,
+    /// Maker rebate share overriding `fees.maker_rebate_share`.
+    #[serde(default)]
+    pub maker_rebate_share: Option<f64>,
+}
+
+fn default_strategies() -> Vec<String> { vec!["lmsr_mm".to_string()] }
+
+/// Deserialize a single string or a list of strings.
+fn one_or_many<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(name) => vec![name],
+        OneOrMany::Many(names) => names,
+    })
 }
 
 /// Wallet allocation parameters (checklist: hot 20%, cold 80%).
@@ -154,6 +411,34 @@ pub struct WalletConfig {
     /// Alert threshold: warn if hot wallet exceeds this fraction.
     #[serde(default = "default_hot_alert")]
     pub hot_alert_threshold: f64,
+    /// Cold wallet address (watch-only; its key never touches the bot).
+    #[serde(default)]
+    pub cold_address: Option<String>,
+    /// What to do with rebalancing transfers (default `propose`).
+    #[serde(default)]
+    pub transfer_mode: TransferMode,
+    /// Smallest transfer worth proposing, USDC (default 10).
+    #[serde(default = "default_min_transfer")]
+    pub min_transfer: f64,
+    /// Seconds between wallet checks (default 60).
+    #[serde(default = "default_wallet_check_interval")]
+    pub check_interval_secs: u64,
+}
+
+/// Handling of hot/cold rebalancing transfers.
+///
+/// Top-ups (cold → hot) always need the operator, since the bot has
+/// no cold key; the mode governs sweeps (hot → cold).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferMode {
+    /// Log the proposal only.
+    #[default]
+    Propose,
+    /// Queue the sweep until approved via `POST /admin/wallet/approve`.
+    Approve,
+    /// Send sweeps immediately.
+    Auto,
 }
 
 impl Default for WalletConfig {
@@ -162,6 +447,10 @@ impl Default for WalletConfig {
             hot_fraction: 0.20,
             min_matic_balance: 0.5,
             hot_alert_threshold: 0.30,
+            cold_address: None,
+            transfer_mode: TransferMode::Propose,
+            min_transfer: 10.0,
+            check_interval_secs: 60,
         }
     }
 }
@@ -169,6 +458,19 @@ impl Default for WalletConfig {
 fn default_hot_fraction() -> f64 { 0.20 }
 fn default_min_matic() -> f64 { 0.5 }
 fn default_hot_alert() -> f64 { 0.30 }
+fn default_min_transfer() -> f64 { 10.0 }
+fn default_wallet_check_interval() -> u64 { 60 }
+
+impl WalletConfig {
+    /// Hot/cold allocation policy from these settings.
+    pub fn allocation_policy(&self) -> AllocationPolicy {
+        AllocationPolicy {
+            hot_fraction: self.hot_fraction,
+            hot_alert_threshold: self.hot_alert_threshold,
+            min_transfer: self.min_transfer,
+        }
+    }
+}
 
 /// Settlement parameters for batch redemption.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -185,6 +487,12 @@ pub struct SettlementConfig {
     /// EIP-1559 max fee cap (gwei, default 50).
     #[serde(default = "default_max_fee")]
     pub max_fee_gwei: f64,
+    /// Seconds between YES/NO merge checks (default 300).
+    #[serde(default = "default_merge_interval")]
+    pub merge_interval_secs: u64,
+    /// Minimum complete sets worth merging (default 1.0).
+    #[serde(default = "default_min_merge_size")]
+    pub min_merge_size: f64,
 }
 
 impl Default for SettlementConfig {
@@ -194,7 +502,230 @@ impl Default for SettlementConfig {
             max_gas_gwei: 35.0,
             tip_gwei: 30.0,
             max_fee_gwei: 50.0,
+            merge_interval_secs: 300,
+            min_merge_size: 1.0,
+        }
+    }
+}
+
+/// Complete-set arbitrage configuration (`complete_set_arb` strategy).
+///
+/// Legs are FOK taker orders, so the edge must clear the market's
+/// taker fees (its `fee_class` or CLOB rate).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompleteSetConfig {
+    /// Minimum after-fee edge per set in USDC (default 0.005).
+    #[serde(default = "default_complete_set_min_edge")]
+    pub min_edge: f64,
+    /// Maximum complete sets per trade (default 50).
+    #[serde(default = "default_complete_set_max_size")]
+    pub max_size: f64,
+    /// Minimum time between trades on the same market (default 5000 ms).
+    #[serde(default = "default_complete_set_cooldown")]
+    pub cooldown_ms: u64,
+}
+
+impl Default for CompleteSetConfig {
+    fn default() -> Self {
+        Self {
+            min_edge: 0.005,
+            max_size: 50.0,
+            cooldown_ms: 5000,
+        }
+    }
+}
+
+fn default_complete_set_min_edge() -> f64 { 0.005 }
+fn default_complete_set_max_size() -> f64 { 50.0 }
+fn default_complete_set_cooldown() -> u64 { 5000 }
+
+/// Neg-risk event group arbitrage configuration.
+///
+/// Groups are built from `[[markets]]` sharing a `neg_risk_market_id`;
+/// the group must list every outcome of the event. Each leg pays the
+/// taker fee of its outcome's market.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NegRiskArbConfig {
+    /// Enable the strategy (live mode only, default false).
+    #[serde(default)]
+    pub enabled: bool,
+    /// Minimum after-fee edge per basket in USDC (default 0.01).
+    #[serde(default = "default_neg_risk_min_edge")]
+    pub min_edge: f64,
+    /// Maximum baskets per trade (default 50).
+    #[serde(default = "default_neg_risk_max_size")]
+    pub max_size: f64,
+    /// Minimum time between trades on the same event (default 5000 ms).
+    #[serde(default = "default_neg_risk_cooldown")]
+    pub cooldown_ms: u64,
+}
+
+impl Default for NegRiskArbConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_edge: 0.01,
+            max_size: 50.0,
+            cooldown_ms: 5000,
+        }
+    }
+}
+
+fn default_neg_risk_min_edge() -> f64 { 0.01 }
+fn default_neg_risk_max_size() -> f64 { 50.0 }
+fn default_neg_risk_cooldown() -> u64 { 5000 }
+
+/// Portfolio ledger configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioConfig {
+    /// Lot matching for sales: `fifo` (default) or `average`.
+    #[serde(default)]
+    pub cost_method: CostMethod,
+    /// How often positions are marked to the book mid (default 15 s).
+    #[serde(default = "default_mark_interval")]
+    pub mark_interval_secs: u64,
+}
+
+impl Default for PortfolioConfig {
+    fn default() -> Self {
+        Self {
+            cost_method: CostMethod::Fifo,
+            mark_interval_secs: 15,
+        }
+    }
+}
+
+fn default_mark_interval() -> u64 { 15 }
+
+/// Fee schedule and reconciliation configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeConfig {
+    /// Take each market's taker rate from the CLOB `/fee-rate` endpoint;
+    /// when off (or unreachable) the market's `fee_class` rate is used.
+    #[serde(default = "default_fetch_rates")]
+    pub fetch_rates: bool,
+    /// How often rates are refreshed and the day checked (default 1 h).
+    #[serde(default = "default_fee_refresh_interval")]
+    pub refresh_interval_secs: u64,
+    /// Share of the taker fee rebated to makers (default 0: no rebates).
+    #[serde(default)]
+    pub maker_rebate_share: f64,
+    /// Largest daily gap between expected and charged fees (USDC)
+    /// before reconciliation flags a market.
+    #[serde(default = "default_reconcile_tolerance")]
+    pub reconcile_tolerance: f64,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        Self {
+            fetch_rates: true,
+            refresh_interval_secs: 3600,
+            maker_rebate_share: 0.0,
+            reconcile_tolerance: 0.05,
+        }
+    }
+}
+
+impl FeeConfig {
+    /// Configured schedule of a market (before any CLOB rate).
+    pub fn schedule_for(&self, market: &MarketConfig) -> FeeSchedule {
+        FeeSchedule::new(
+            market.fee_class,
+            market.maker_rebate_share.unwrap_or(self.maker_rebate_share),
+        )
+    }
+}
+
+fn default_fetch_rates() -> bool { true }
+fn default_fee_refresh_interval() -> u64 { 3600 }
+fn default_reconcile_tolerance() -> f64 { 0.05 }
+
+/// State checkpointing and startup recovery configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryConfig {
+    /// How often the full bot state is checkpointed (default 30 s).
+    #[serde(default = "default_checkpoint_interval")]
+    pub checkpoint_interval_secs: u64,
+    /// Largest gap (tokens) between ledger and on-chain balances that
+    /// still counts as a match on startup.
+    #[serde(default = "default_position_tolerance")]
+    pub position_tolerance: f64,
+    /// Halt trading when restored positions disagree with the chain.
+    #[serde(default = "default_halt_on_mismatch")]
+    pub halt_on_mismatch: bool,
+    /// Cancel CLOB orders found on startup that the order journal does
+    /// not know; when false they are adopted instead.
+    #[serde(default = "default_cancel_unknown_orders")]
+    pub cancel_unknown_orders: bool,
+}
+
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self {
+            checkpoint_interval_secs: 30,
+            position_tolerance: 0.01,
+            halt_on_mismatch: true,
+            cancel_unknown_orders: true,
+        }
+    }
+}
+
+fn default_checkpoint_interval() -> u64 { 30 }
+fn default_position_tolerance() -> f64 { 0.01 }
+fn default_halt_on_mismatch() -> bool { true }
+fn default_cancel_unknown_orders() -> bool { true }
+
+/// Repository backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// `state.json` plus JSONL trade and PnL logs under `bot.data_dir`.
+    #[default]
+    Jsonl,
+    /// Embedded SQLite database with indexed tables.
+    Sqlite,
+}
+
+/// Persistence configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// Backend the bot reads and writes (default `jsonl`).
+    #[serde(default)]
+    pub backend: StorageBackend,
+    /// SQLite database file (default `<bot.data_dir>/bot.db`).
+    #[serde(default)]
+    pub sqlite_path: Option<String>,
+}
+
+impl StorageConfig {
+    /// SQLite database file for a data directory.
+    pub fn sqlite_path(&self, data_dir: &str) -> PathBuf {
+        self.sqlite_path
+            .as_ref()
+            .map_or_else(|| Path::new(data_dir).join("bot.db"), PathBuf::from)
+    }
+}
+
+impl AppConfig {
+    /// Neg-risk event groups: markets sharing a `neg_risk_market_id`,
+    /// ordered by question index. Inactive outcomes are included (an
+    /// event with one is not traded until it is re-enabled).
+    pub fn neg_risk_groups(&self) -> Vec<(String, Vec<MarketConfig>)> {
+        let mut groups: Vec<(String, Vec<MarketConfig>)> = Vec::new();
+        for market in self.markets.iter().filter(|m| m.neg_risk) {
+            let Some(event_id) = &market.neg_risk_market_id else {
+                continue;
+            };
+            match groups.iter_mut().find(|(id, _)| id == event_id) {
+                Some((_, members)) => members.push(market.clone()),
+                None => groups.push((event_id.clone(), vec![market.clone()])),
+            }
+        }
+        for (_, members) in &mut groups {
+            members.sort_by_key(|m| m.question_index);
         }
+        groups
     }
 }
 
@@ -202,3 +733,5 @@ fn default_redeem_hour() -> u32 { 4 }
 fn default_max_gas() -> f64 { 35.0 }
 fn default_tip() -> f64 { 30.0 }
 fn default_max_fee() -> f64 { 50.0 }
+fn default_merge_interval() -> u64 { 300 }
+fn default_min_merge_size() -> f64 { 1.0 }
@@ -5,14 +5,14 @@
 //! Uses exponential weighted moving average (EWMA) for feed fusion.
 //!
 //! Exposes both a multi-source Decimal API and a simplified f64 API.
+//! The f64 path also tracks an EWMA variance of observations so
+//! callers can size by how noisy the estimate is (`std_error`).
 
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
 use std::collections::HashMap;
 
-use super::trade::Asset;
-
 /// Bayesian estimator that fuses multiple price feeds into a fair probability.
 ///
 /// For 5-minute BTC/ETH markets like "Will BTC be above $X at time T?",
@@ -30,6 +30,10 @@ pub struct BayesianEstimator {
     smoothed_prob_f64: Option<f64>,
     /// Alpha as f64 for the simplified path.
     alpha_f64: f64,
+    /// EWMA variance of f64 observations around the estimate.
+    variance_f64: f64,
+    /// Number of f64 observations seen.
+    observations: u64,
 }
 
 impl BayesianEstimator {
@@ -50,6 +54,8 @@ impl BayesianEstimator {
             smoothed_price: None,
             smoothed_prob_f64: None,
             alpha_f64,
+            variance_f64: 0.0,
+            observations: 0,
         }
     }
 
@@ -65,13 +71,33 @@ impl BayesianEstimator {
     /// Returns the current smoothed probability estimate.
     pub fn update(&mut self, observation: f64) -> f64 {
         let smoothed = match self.smoothed_prob_f64 {
-            Some(prev) => prev * (1.0 - self.alpha_f64) + observation * self.alpha_f64,
+            Some(prev) => {
+                // Exponentially weighted variance (West 1979 incremental form)
+                let diff = observation - prev;
+                self.variance_f64 = (1.0 - self.alpha_f64)
+                    * (self.variance_f64 + self.alpha_f64 * diff * diff);
+                prev * (1.0 - self.alpha_f64) + observation * self.alpha_f64
+            }
             None => observation,
         };
         self.smoothed_prob_f64 = Some(smoothed);
+        self.observations += 1;
         smoothed
     }
 
+    /// Standard error of the f64 estimate.
+    ///
+    /// EWMA of i.i.d. noise with variance σ² has variance
+    /// σ²·α/(2−α). Before two observations there is no spread to
+    /// measure, so the estimate is treated as maximally uncertain (0.5).
+    pub fn std_error(&self) -> f64 {
+        if self.observations < 2 {
+            return 0.5;
+        }
+        let a = self.alpha_f64;
+        (self.variance_f64 * a / (2.0 - a)).sqrt()
+    }
+
     /// Returns the current fused price estimate (Decimal API).
     pub fn current_price(&self) -> Option<Decimal> {
         self.smoothed_price
@@ -191,6 +217,18 @@ mod tests {
     }
 
     // f64 API tests
+    #[test]
+    fn test_f64_std_error_tracks_noise() {
+        let mut steady = BayesianEstimator::new(dec!(0.3));
+        let mut noisy = BayesianEstimator::new(dec!(0.3));
+        for i in 0..50 {
+            steady.update(0.50);
+            noisy.update(if i % 2 == 0 { 0.40 } else { 0.60 });
+        }
+        assert!(steady.std_error() < 1e-9);
+        assert!(noisy.std_error() > 0.02);
+    }
+
     #[test]
     fn test_f64_update_first_observation() {
         let mut est = BayesianEstimator::default();
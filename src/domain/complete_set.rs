@@ -0,0 +1,153 @@
+//! Complete-Set Arbitrage - YES + NO Bundle Pricing
+//!
+//! One YES plus one NO of the same condition always redeem for exactly
+//! 1 USDC, and the CTF converts between USDC and complete sets at cost
+//! (gas only). The bundle is therefore mispriced whenever:
+//! - `BuyMerge`: YES ask + NO ask + taker fees < 1 → buy both legs,
+//!   then `mergePositions` back into USDC
+//! - `SplitSell`: YES bid + NO bid − taker fees > 1 → `splitPosition`
+//!   USDC into a set, then sell both legs
+//!
+//! Size is limited by the thinner top-of-book level of the two legs.
+
+use super::fees::FeeCalculator;
+
+/// Which way the bundle is mispriced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompleteSetSide {
+    /// Asks sum below 1: buy YES + NO, merge into USDC.
+    BuyMerge,
+    /// Bids sum above 1: split USDC, sell YES + NO.
+    SplitSell,
+}
+
+/// Best bid/ask of one leg.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BookTop {
+    /// Best bid price.
+    pub bid: Option<f64>,
+    /// Size at the best bid.
+    pub bid_size: Option<f64>,
+    /// Best ask price.
+    pub ask: Option<f64>,
+    /// Size at the best ask.
+    pub ask_size: Option<f64>,
+}
+
+/// A profitable complete-set trade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompleteSetOpportunity {
+    /// Direction of the trade.
+    pub side: CompleteSetSide,
+    /// YES leg price (ask for `BuyMerge`, bid for `SplitSell`).
+    pub yes_price: f64,
+    /// NO leg price.
+    pub no_price: f64,
+    /// Complete sets to trade (whole tokens, 2 decimals).
+    pub size: f64,
+    /// Profit per set after taker fees on both legs.
+    pub edge_per_set: f64,
+}
+
+impl CompleteSetOpportunity {
+    /// Expected profit in USDC, before gas.
+    pub fn expected_profit(&self) -> f64 {
+        self.edge_per_set * self.size
+    }
+}
+
+/// Evaluate both bundle directions and return the better one, if its
+/// after-fee edge per set is at least `min_edge`.
+///
+/// `fees` must be a taker calculator: both legs cross the spread.
+pub fn evaluate(
+    yes: &BookTop,
+    no: &BookTop,
+    fees: &FeeCalculator,
+    min_edge: f64,
+    max_size: f64,
+) -> Option<CompleteSetOpportunity> {
+    let leg_fees = |a: f64, b: f64| fees.taker_fee_f64(a, 1.0) + fees.taker_fee_f64(b, 1.0);
+
+    let buy = match (yes.ask, yes.ask_size, no.ask, no.ask_size) {
+        (Some(ya), Some(ys), Some(na), Some(ns)) => Some(CompleteSetOpportunity {
+            side: CompleteSetSide::BuyMerge,
+            yes_price: ya,
+            no_price: na,
+            size: ys.min(ns),
+            edge_per_set: 1.0 - ya - na - leg_fees(ya, na),
+        }),
+        _ => None,
+    };
+    let sell = match (yes.bid, yes.bid_size, no.bid, no.bid_size) {
+        (Some(yb), Some(ys), Some(nb), Some(ns)) => Some(CompleteSetOpportunity {
+            side: CompleteSetSide::SplitSell,
+            yes_price: yb,
+            no_price: nb,
+            size: ys.min(ns),
+            edge_per_set: yb + nb - 1.0 - leg_fees(yb, nb),
+        }),
+        _ => None,
+    };
+
+    [buy, sell]
+        .into_iter()
+        .flatten()
+        .filter(|o| o.edge_per_set >= min_edge && o.edge_per_set > 0.0)
+        .map(|o| CompleteSetOpportunity {
+            // CLOB sizes have 2 decimals; never round up past the book
+            size: (o.size.min(max_size) * 100.0).floor() / 100.0,
+            ..o
+        })
+        .filter(|o| o.size > 0.0)
+        .max_by(|a, b| a.edge_per_set.total_cmp(&b.edge_per_set))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn top(bid: f64, ask: f64, size: f64) -> BookTop {
+        BookTop {
+            bid: Some(bid),
+            bid_size: Some(size),
+            ask: Some(ask),
+            ask_size: Some(size),
+        }
+    }
+
+    #[test]
+    fn test_cheap_asks_buy_and_merge() {
+        let fees = FeeCalculator::standard();
+        let opp = evaluate(&top(0.44, 0.45, 50.0), &top(0.50, 0.51, 20.0), &fees, 0.01, 100.0)
+            .unwrap();
+        assert_eq!(opp.side, CompleteSetSide::BuyMerge);
+        assert_eq!(opp.size, 20.0);
+        assert!(opp.edge_per_set > 0.03 && opp.edge_per_set < 0.04);
+    }
+
+    #[test]
+    fn test_rich_bids_split_and_sell() {
+        let fees = FeeCalculator::standard();
+        let opp = evaluate(&top(0.55, 0.56, 10.0), &top(0.48, 0.49, 10.0), &fees, 0.01, 4.0)
+            .unwrap();
+        assert_eq!(opp.side, CompleteSetSide::SplitSell);
+        assert_eq!(opp.size, 4.0);
+        assert!((opp.expected_profit() - 4.0 * opp.edge_per_set).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fees_can_eat_the_edge() {
+        // 0.2 ¢ raw edge, but crypto taker fees cost ~0.3 ¢ per set
+        let fees = FeeCalculator::crypto_short_duration();
+        let opp = evaluate(&top(0.49, 0.499, 10.0), &top(0.49, 0.499, 10.0), &fees, 0.0, 100.0);
+        assert!(opp.is_none());
+    }
+
+    #[test]
+    fn test_fair_bundle_has_no_opportunity() {
+        let fees = FeeCalculator::standard();
+        assert!(evaluate(&top(0.49, 0.51, 10.0), &top(0.49, 0.51, 10.0), &fees, 0.0, 100.0).is_none());
+        assert!(evaluate(&BookTop::default(), &top(0.1, 0.2, 10.0), &fees, 0.0, 100.0).is_none());
+    }
+}
@@ -5,10 +5,15 @@
 //! Taker fees follow a parabolic curve that peaks at p=0.50.
 //!
 //! Exposes both Decimal API (precise) and f64 methods for ports/adapters.
+//!
+//! `FeeSchedule` is the per-market view: the taker rate of the market's
+//! `FeeClass` (or the rate the CLOB reports for it) plus the share of
+//! the taker fee rebated to makers.
 
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
 
 /// Fee calculator implementing Polymarket's fee structure.
 ///
@@ -140,6 +145,74 @@ impl FeeCalculator {
     }
 }
 
+/// Polymarket fee tier of a market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeClass {
+    /// Regular markets (rate 0.0025).
+    #[default]
+    Standard,
+    /// Short-duration crypto up/down markets (rate 0.025).
+    CryptoShortDuration,
+}
+
+impl FeeClass {
+    /// Taker curve rate of the tier.
+    pub fn fee_rate(self) -> f64 {
+        match self {
+            Self::Standard => 0.0025,
+            Self::CryptoShortDuration => 0.025,
+        }
+    }
+}
+
+/// Taker fee rate and maker rebate of one market.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    /// Taker curve rate (`fee_rate` of `FeeCalculator`).
+    pub taker_fee_rate: f64,
+    /// Share of the taker fee paid back to the resting maker (0 = none).
+    pub maker_rebate_share: f64,
+}
+
+impl FeeSchedule {
+    /// Schedule of a fee tier.
+    pub fn new(class: FeeClass, maker_rebate_share: f64) -> Self {
+        Self {
+            taker_fee_rate: class.fee_rate(),
+            maker_rebate_share,
+        }
+    }
+
+    /// Schedule from a CLOB `base_fee` in basis points.
+    pub fn from_bps(fee_rate_bps: u32, maker_rebate_share: f64) -> Self {
+        Self {
+            taker_fee_rate: f64::from(fee_rate_bps) / 10_000.0,
+            maker_rebate_share,
+        }
+    }
+
+    /// Taker rate in basis points, as the CLOB reports it.
+    pub fn fee_rate_bps(&self) -> u32 {
+        (self.taker_fee_rate * 10_000.0).round() as u32
+    }
+
+    /// Calculator for taker orders on this market.
+    pub fn calculator(&self) -> FeeCalculator {
+        FeeCalculator::new(Decimal::from_f64(self.taker_fee_rate).unwrap_or_default(), 2)
+    }
+
+    /// Fee a taker pays for `size` tokens at `price`.
+    pub fn taker_fee(&self, price: f64, size: f64) -> f64 {
+        self.calculator().taker_fee_f64(price, size)
+    }
+
+    /// Rebate a maker earns when `size` tokens at `price` are taken.
+    pub fn maker_rebate(&self, price: f64, size: f64) -> f64 {
+        self.maker_rebate_share * self.taker_fee(price, size)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,6 +271,25 @@ mod tests {
         assert!(edge > 0.0, "Edge should still be positive for this case");
     }
 
+    #[test]
+    fn test_schedule_matches_class_and_bps() {
+        let crypto = FeeSchedule::new(FeeClass::CryptoShortDuration, 0.0);
+        assert_eq!(crypto.fee_rate_bps(), 250);
+        assert_eq!(FeeSchedule::from_bps(250, 0.0), crypto);
+
+        let fee = crypto.taker_fee(0.50, 100.0);
+        let calc = FeeCalculator::crypto_short_duration().taker_fee_f64(0.50, 100.0);
+        assert!((fee - calc).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_maker_rebate_is_share_of_taker_fee() {
+        let schedule = FeeSchedule::new(FeeClass::CryptoShortDuration, 0.2);
+        let fee = schedule.taker_fee(0.40, 50.0);
+        assert!((schedule.maker_rebate(0.40, 50.0) - 0.2 * fee).abs() < 1e-12);
+        assert_eq!(FeeSchedule::new(FeeClass::Standard, 0.0).maker_rebate(0.40, 50.0), 0.0);
+    }
+
     #[test]
     fn test_taker_fee_f64_matches_decimal() {
         let calc = FeeCalculator::standard();
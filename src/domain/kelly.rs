@@ -5,6 +5,12 @@
 //! variance significantly while retaining ~75% of the growth rate.
 //!
 //! Exposes both `KellyCriterion` (Decimal API) and `KellySizer` (f64 API).
+//!
+//! `KellySizer::sized` additionally scales the fraction down with
+//! drawdown (`DrawdownScaler`) and shrinks it by estimate uncertainty
+//! (Bayesian Kelly: `f · e² / (e² + σ²)`). `KellySizer::apply_context`
+//! does the same for a fraction computed elsewhere (joint portfolio
+//! Kelly).
 
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
@@ -44,6 +50,26 @@ impl KellyCriterion {
         estimated_prob: Decimal,
         market_price: Decimal,
     ) -> Decimal {
+        self.scaled_fraction(estimated_prob, market_price, Decimal::ONE)
+    }
+
+    /// Fractional Kelly with an extra multiplier, capped at max position.
+    ///
+    /// The multiplier applies before the cap, so scaling down for
+    /// drawdown or uncertainty always reduces a capped bet too.
+    pub fn scaled_fraction(
+        &self,
+        estimated_prob: Decimal,
+        market_price: Decimal,
+        multiplier: Decimal,
+    ) -> Decimal {
+        let sized = self.uncapped_fraction(estimated_prob, market_price)
+            * multiplier.max(Decimal::ZERO);
+        sized.min(self.max_position_fraction)
+    }
+
+    /// Fractional Kelly before the max position cap.
+    pub fn uncapped_fraction(&self, estimated_prob: Decimal, market_price: Decimal) -> Decimal {
         if market_price <= Decimal::ZERO || market_price >= Decimal::ONE {
             return Decimal::ZERO;
         }
@@ -57,8 +83,12 @@ impl KellyCriterion {
             return Decimal::ZERO;
         }
 
-        let sized = full_kelly * self.fraction;
-        sized.min(self.max_position_fraction)
+        full_kelly * self.fraction
+    }
+
+    /// Caps a bankroll fraction at the max position.
+    pub fn capped(&self, fraction: Decimal) -> Decimal {
+        fraction.max(Decimal::ZERO).min(self.max_position_fraction)
     }
 
     /// Computes the position size in USDC given bankroll.
@@ -98,9 +128,15 @@ pub struct KellySizer {
 impl KellySizer {
     /// Create a sizer with the given Kelly fraction (e.g., 0.25 for quarter-Kelly).
     pub fn new(fraction: f64) -> Self {
+        Self::with_max_position(fraction, 0.0625)
+    }
+
+    /// Create a sizer with an explicit max position fraction of bankroll.
+    pub fn with_max_position(fraction: f64, max_position_fraction: f64) -> Self {
         let frac = Decimal::from_f64(fraction).unwrap_or(dec!(0.25));
+        let max = Decimal::from_f64(max_position_fraction).unwrap_or(dec!(0.0625));
         Self {
-            inner: KellyCriterion::new(frac, dec!(0.0625)),
+            inner: KellyCriterion::new(frac, max),
         }
     }
 
@@ -123,6 +159,52 @@ impl KellySizer {
             .unwrap_or(0.0)
     }
 
+    /// Position size in USDC after drawdown and uncertainty scaling.
+    ///
+    /// `ctx.drawdown_scale` comes from `DrawdownScaler`;
+    /// `ctx.prob_std_error` is the estimator's standard error.
+    pub fn sized(
+        &self,
+        estimated_prob: f64,
+        market_price: f64,
+        bankroll: f64,
+        ctx: &SizingContext,
+    ) -> f64 {
+        let prob = Decimal::from_f64(estimated_prob).unwrap_or(dec!(0.5));
+        let price = Decimal::from_f64(market_price).unwrap_or(dec!(0.5));
+        let fraction = self
+            .inner
+            .uncapped_fraction(prob, price)
+            .to_f64()
+            .unwrap_or(0.0);
+
+        self.apply_context(fraction, estimated_prob - market_price, bankroll, ctx)
+    }
+
+    /// Position size in USDC for an already computed Kelly `fraction`.
+    ///
+    /// Scales by drawdown and shrinks by the uncertainty of `edge`
+    /// like `sized`, then caps at max position.
+    pub fn apply_context(
+        &self,
+        fraction: f64,
+        edge: f64,
+        bankroll: f64,
+        ctx: &SizingContext,
+    ) -> f64 {
+        let shrink = uncertainty_shrinkage(edge, ctx.prob_std_error);
+        let multiplier = ctx.drawdown_scale.clamp(0.0, 1.0) * shrink;
+
+        let frac = Decimal::from_f64(fraction).unwrap_or(Decimal::ZERO);
+        let bank = Decimal::from_f64(bankroll).unwrap_or(Decimal::ZERO);
+        let mult = Decimal::from_f64(multiplier).unwrap_or(Decimal::ZERO);
+
+        (bank * self.inner.capped(frac * mult))
+            .round_dp(2)
+            .to_f64()
+            .unwrap_or(0.0)
+    }
+
     /// Compute optimal fraction (0.0 – 1.0).
     pub fn optimal_fraction(&self, estimated_prob: f64, market_price: f64) -> f64 {
         let prob = Decimal::from_f64(estimated_prob).unwrap_or(dec!(0.5));
@@ -140,10 +222,190 @@ impl KellySizer {
     }
 }
 
+// ────────────────────────────────────────────
+// Drawdown and uncertainty scaling
+// ────────────────────────────────────────────
+
+/// Per-trade inputs that shrink the Kelly fraction.
+#[derive(Debug, Clone, Copy)]
+pub struct SizingContext {
+    /// Multiplier from `DrawdownScaler` (1.0 = no drawdown).
+    pub drawdown_scale: f64,
+    /// Standard error of the probability estimate (0.0 = certain).
+    pub prob_std_error: f64,
+}
+
+impl Default for SizingContext {
+    fn default() -> Self {
+        Self {
+            drawdown_scale: 1.0,
+            prob_std_error: 0.0,
+        }
+    }
+}
+
+/// Bayesian Kelly shrinkage factor `e² / (e² + σ²)`.
+///
+/// With edge `e` known only up to standard error `σ`, the
+/// growth-optimal bet shrinks toward zero; an edge no larger than
+/// its own noise gets at most half the point-estimate size.
+pub fn uncertainty_shrinkage(edge: f64, std_error: f64) -> f64 {
+    if std_error <= 0.0 || !std_error.is_finite() {
+        return 1.0;
+    }
+    let e2 = edge * edge;
+    if e2 == 0.0 {
+        return 0.0;
+    }
+    e2 / (e2 + std_error * std_error)
+}
+
+/// Linear taper of the Kelly fraction as drawdown grows.
+///
+/// Scale is 1.0 at zero drawdown and falls linearly to `min_scale`
+/// at `max_drawdown`, staying there beyond it.
+#[derive(Debug, Clone, Copy)]
+pub struct DrawdownScaler {
+    /// Drawdown (fraction of peak) at which the floor is reached.
+    max_drawdown: f64,
+    /// Minimum multiplier.
+    min_scale: f64,
+}
+
+impl DrawdownScaler {
+    /// Create a scaler reaching `min_scale` at `max_drawdown`.
+    pub fn new(max_drawdown: f64, min_scale: f64) -> Self {
+        Self {
+            max_drawdown: max_drawdown.max(f64::EPSILON),
+            min_scale: min_scale.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Multiplier for the given drawdown (0.0 – 1.0 of peak).
+    pub fn scale(&self, drawdown: f64) -> f64 {
+        let progress = (drawdown.max(0.0) / self.max_drawdown).min(1.0);
+        1.0 - progress * (1.0 - self.min_scale)
+    }
+}
+
+/// Tracks equity peaks for intraday and peak-to-trough drawdown.
+#[derive(Debug, Clone, Default)]
+pub struct DrawdownTracker {
+    /// Highest equity seen since start.
+    peak: f64,
+    /// Highest equity seen today.
+    day_peak: f64,
+    /// Day key of `day_peak` (e.g. YYYY-MM-DD).
+    day: String,
+    /// Latest equity.
+    current: f64,
+}
+
+impl DrawdownTracker {
+    /// Record an equity observation for the given day.
+    pub fn observe(&mut self, equity: f64, day: &str) {
+        if day != self.day {
+            self.day = day.to_string();
+            self.day_peak = equity;
+        }
+        self.peak = self.peak.max(equity);
+        self.day_peak = self.day_peak.max(equity);
+        self.current = equity;
+    }
+
+    /// Larger of intraday and peak-to-trough drawdown (fraction of peak).
+    pub fn drawdown(&self) -> f64 {
+        let dd = |peak: f64| {
+            if peak > 0.0 {
+                ((peak - self.current) / peak).max(0.0)
+            } else {
+                0.0
+            }
+        };
+        dd(self.peak).max(dd(self.day_peak))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_kelly_positive_edge() {
-        let k
+        let kelly = KellyCriterion::default();
+        let f = kelly.optimal_fraction(dec!(0.60), dec!(0.50));
+        assert!(f > Decimal::ZERO);
+        assert!(f <= dec!(0.0625));
+    }
+
+    #[test]
+    fn test_kelly_no_edge_is_zero() {
+        let kelly = KellyCriterion::default();
+        assert_eq!(kelly.optimal_fraction(dec!(0.40), dec!(0.50)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_max_position_fraction_configurable() {
+        let tight = KellySizer::with_max_position(1.0, 0.01);
+        let size = tight.optimal_size(0.90, 0.50, 1000.0);
+        assert!((size - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_drawdown_scaler_tapers_to_floor() {
+        let scaler = DrawdownScaler::new(0.20, 0.25);
+        assert!((scaler.scale(0.0) - 1.0).abs() < 1e-12);
+        assert!((scaler.scale(0.10) - 0.625).abs() < 1e-12);
+        assert!((scaler.scale(0.50) - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_uncertainty_shrinkage() {
+        assert_eq!(uncertainty_shrinkage(0.05, 0.0), 1.0);
+        assert!((uncertainty_shrinkage(0.05, 0.05) - 0.5).abs() < 1e-12);
+        assert!(uncertainty_shrinkage(0.05, 0.20) < 0.1);
+    }
+
+    #[test]
+    fn test_sized_shrinks_under_drawdown_and_noise() {
+        let sizer = KellySizer::with_max_position(0.25, 1.0);
+        let full = sizer.sized(0.60, 0.50, 1000.0, &SizingContext::default());
+        let scaled = sizer.sized(
+            0.60,
+            0.50,
+            1000.0,
+            &SizingContext {
+                drawdown_scale: 0.5,
+                prob_std_error: 0.10,
+            },
+        );
+        // 0.5 (drawdown) × 0.5 (σ = edge) = a quarter of the size
+        assert!((scaled - full * 0.25).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_apply_context_scales_a_given_fraction() {
+        let sizer = KellySizer::with_max_position(0.25, 0.10);
+        let ctx = SizingContext {
+            drawdown_scale: 0.5,
+            prob_std_error: 0.0,
+        };
+        assert!((sizer.apply_context(0.08, 0.10, 1000.0, &ctx) - 40.0).abs() < 1e-9);
+        // Scaled before the cap: 0.40 × 0.5 still hits the 10% max
+        assert!((sizer.apply_context(0.40, 0.10, 1000.0, &ctx) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_drawdown_tracker_intraday_and_peak() {
+        let mut t = DrawdownTracker::default();
+        t.observe(1000.0, "2026-01-01");
+        t.observe(900.0, "2026-01-01");
+        assert!((t.drawdown() - 0.10).abs() < 1e-12);
+
+        // New day: intraday peak resets, peak-to-trough does not
+        t.observe(900.0, "2026-01-02");
+        assert!((t.drawdown() - 0.10).abs() < 1e-12);
+        t.observe(1000.0, "2026-01-02");
+        assert_eq!(t.drawdown(), 0.0);
+    }
+}
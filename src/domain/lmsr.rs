@@ -65,7 +65,7 @@ impl LmsrModel {
         let exp_no = (q_no_f64 / b_f64).exp();
         let price = exp_yes / (exp_yes + exp_no);
 
-        Decimal::from_f64(price).unwrap_or(Decimal::new(5, 1))
+        Decimal::from_f64(price).unwrap_or_else(|| Decimal::new(5, 1))
     }
 
     /// Computes the price for the NO outcome (1 - price_yes).
@@ -196,5 +196,4 @@ mod tests {
         let edge = model.detect_edge(dec!(0.40), dec!(0.50));
         assert!(edge > dec!(20.0), "Edge should be ~25%, got {edge}");
     }
-
-   
+}
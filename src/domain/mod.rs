@@ -5,10 +5,16 @@
 //! All types are serializable and testable in isolation.
 
 pub mod bayesian;
+pub mod complete_set;
 pub mod fees;
 pub mod kelly;
 pub mod lmsr;
+pub mod neg_risk;
+pub mod portfolio;
+pub mod portfolio_kelly;
+pub mod time;
 pub mod trade;
+pub mod wallet_allocation;
 
 // Re-export core types for convenience
 pub use bayesian::BayesianEstimator;
@@ -0,0 +1,285 @@
+//! Neg-Risk Event Arbitrage - Multi-Outcome Basket Pricing
+//!
+//! In a neg-risk event exactly one of n outcomes resolves YES, so one
+//! YES of every outcome is worth exactly 1 USDC. The NegRiskAdapter
+//! also converts NO positions: k NO tokens (one per outcome in a set S)
+//! become k − 1 USDC plus one YES of every outcome outside S.
+//!
+//! Three baskets are priced after the taker fees of each outcome's
+//! market:
+//! - `BuyYesBasket`: Σ YES asks < 1 → buy every YES, hold to resolution
+//! - `SplitSellYes`: Σ YES bids > 1 → split USDC on every outcome, sell
+//!   every YES, convert all NO back into n − 1 USDC
+//! - `ConvertNo`: buy NO on the outcomes where it is cheaper than the
+//!   YES bid it forgoes, convert, and sell the YES received
+
+use super::complete_set::BookTop;
+use super::fees::FeeCalculator;
+use super::trade::TradeSide;
+
+/// Which basket is mispriced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NegRiskKind {
+    /// Σ YES asks below 1.
+    BuyYesBasket,
+    /// Σ YES bids above 1.
+    SplitSellYes,
+    /// NO conversion beats the YES it replaces.
+    ConvertNo,
+}
+
+/// Books of one outcome of the event.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OutcomeBook {
+    /// YES token top of book.
+    pub yes: BookTop,
+    /// NO token top of book.
+    pub no: BookTop,
+}
+
+/// One CLOB order of a basket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BasketLeg {
+    /// Outcome position within the event group.
+    pub outcome: usize,
+    /// YES token (false = NO token).
+    pub yes: bool,
+    /// Buy or sell.
+    pub side: TradeSide,
+    /// Limit price (top of book).
+    pub price: f64,
+}
+
+/// A profitable neg-risk basket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NegRiskOpportunity {
+    /// Basket type.
+    pub kind: NegRiskKind,
+    /// CLOB legs, one per traded token.
+    pub legs: Vec<BasketLeg>,
+    /// Outcomes whose NO is converted through the adapter.
+    pub convert: Vec<usize>,
+    /// Baskets to trade (whole tokens, 2 decimals).
+    pub size: f64,
+    /// Profit per basket after taker fees.
+    pub edge_per_basket: f64,
+}
+
+impl NegRiskOpportunity {
+    /// Expected profit in USDC, before gas and adapter fees.
+    pub fn expected_profit(&self) -> f64 {
+        self.edge_per_basket * self.size
+    }
+}
+
+/// Evaluate all baskets over a complete event and return the best one
+/// whose after-fee edge is at least `min_edge`.
+///
+/// `outcomes` must list every outcome of the event; `fees[i]` is the
+/// taker calculator of outcome `i`.
+pub fn evaluate(
+    outcomes: &[OutcomeBook],
+    fees: &[FeeCalculator],
+    min_edge: f64,
+    max_size: f64,
+) -> Option<NegRiskOpportunity> {
+    if outcomes.len() < 2 || fees.len() != outcomes.len() {
+        return None;
+    }
+    let fee = |outcome: usize, price: f64| fees[outcome].taker_fee_f64(price, 1.0);
+
+    [
+        buy_yes_basket(outcomes, &fee),
+        split_sell_yes(outcomes, &fee),
+        convert_no(outcomes, &fee),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|o| o.edge_per_basket >= min_edge && o.edge_per_basket > 0.0)
+    .map(|o| NegRiskOpportunity {
+        // CLOB sizes have 2 decimals; never round up past the book
+        size: (o.size.min(max_size) * 100.0).floor() / 100.0,
+        ..o
+    })
+    .filter(|o| o.size > 0.0)
+    .max_by(|a, b| a.edge_per_basket.total_cmp(&b.edge_per_basket))
+}
+
+/// Buy one YES of every outcome.
+fn buy_yes_basket(outcomes: &[OutcomeBook], fee: &dyn Fn(usize, f64) -> f64) -> Option<NegRiskOpportunity> {
+    let mut legs = Vec::with_capacity(outcomes.len());
+    let mut cost = 0.0;
+    let mut size = f64::INFINITY;
+    for (i, o) in outcomes.iter().enumerate() {
+        let (ask, ask_size) = (o.yes.ask?, o.yes.ask_size?);
+        cost += ask + fee(i, ask);
+        size = size.min(ask_size);
+        legs.push(BasketLeg { outcome: i, yes: true, side: TradeSide::Buy, price: ask });
+    }
+    Some(NegRiskOpportunity {
+        kind: NegRiskKind::BuyYesBasket,
+        legs,
+        convert: Vec::new(),
+        size,
+        edge_per_basket: 1.0 - cost,
+    })
+}
+
+/// Split on every outcome, sell every YES, convert every NO.
+fn split_sell_yes(outcomes: &[OutcomeBook], fee: &dyn Fn(usize, f64) -> f64) -> Option<NegRiskOpportunity> {
+    let mut legs = Vec::with_capacity(outcomes.len());
+    let mut proceeds = 0.0;
+    let mut size = f64::INFINITY;
+    for (i, o) in outcomes.iter().enumerate() {
+        let (bid, bid_size) = (o.yes.bid?, o.yes.bid_size?);
+        proceeds += bid - fee(i, bid);
+        size = size.min(bid_size);
+        legs.push(BasketLeg { outcome: i, yes: true, side: TradeSide::Sell, price: bid });
+    }
+    // n USDC split, n − 1 USDC back from converting all NO
+    Some(NegRiskOpportunity {
+        kind: NegRiskKind::SplitSellYes,
+        legs,
+        convert: (0..outcomes.len()).collect(),
+        size,
+        edge_per_basket: proceeds - 1.0,
+    })
+}
+
+/// Buy NO where converting it beats the YES bid it replaces, sell the
+/// YES of the other outcomes.
+fn convert_no(outcomes: &[OutcomeBook], fee: &dyn Fn(usize, f64) -> f64) -> Option<NegRiskOpportunity> {
+    let mut legs = Vec::new();
+    let mut convert = Vec::new();
+    // Converting k NO pays k − 1: each converted outcome earns 1 − cost
+    let mut edge = -1.0;
+    let mut size = f64::INFINITY;
+    for (i, o) in outcomes.iter().enumerate() {
+        let via_no = match (o.no.ask, o.no.ask_size) {
+            (Some(ask), Some(ask_size)) => Some((1.0 - ask - fee(i, ask), ask, ask_size)),
+            _ => None,
+        };
+        let via_yes = match (o.yes.bid, o.yes.bid_size) {
+            (Some(bid), Some(bid_size)) => Some((bid - fee(i, bid), bid, bid_size)),
+            _ => None,
+        };
+        match (via_no, via_yes) {
+            (Some((gain, ask, ask_size)), yes) if yes.is_none_or(|(g, _, _)| gain > g) => {
+                edge += gain;
+                size = size.min(ask_size);
+                convert.push(i);
+                legs.push(BasketLeg { outcome: i, yes: false, side: TradeSide::Buy, price: ask });
+            }
+            (_, Some((gain, bid, bid_size))) => {
+                edge += gain;
+                size = size.min(bid_size);
+                legs.push(BasketLeg { outcome: i, yes: true, side: TradeSide::Sell, price: bid });
+            }
+            // No book for this outcome: its YES is held, valued at zero
+            _ => {}
+        }
+    }
+    if convert.is_empty() {
+        return None;
+    }
+    Some(NegRiskOpportunity {
+        kind: NegRiskKind::ConvertNo,
+        legs,
+        convert,
+        size,
+        edge_per_basket: edge,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(yes_bid: f64, yes_ask: f64, no_bid: f64, no_ask: f64) -> OutcomeBook {
+        let top = |bid, ask| BookTop {
+            bid: Some(bid),
+            bid_size: Some(100.0),
+            ask: Some(ask),
+            ask_size: Some(100.0),
+        };
+        OutcomeBook {
+            yes: top(yes_bid, yes_ask),
+            no: top(no_bid, no_ask),
+        }
+    }
+
+    fn no_fees(outcomes: usize) -> Vec<FeeCalculator> {
+        vec![FeeCalculator::new(rust_decimal::Decimal::ZERO, 2); outcomes]
+    }
+
+    #[test]
+    fn test_cheap_yes_basket() {
+        let event = [
+            outcome(0.29, 0.30, 0.69, 0.71),
+            outcome(0.29, 0.30, 0.69, 0.71),
+            outcome(0.34, 0.35, 0.64, 0.66),
+        ];
+        let opp = evaluate(&event, &no_fees(3), 0.01, 10.0).unwrap();
+        assert_eq!(opp.kind, NegRiskKind::BuyYesBasket);
+        assert!((opp.edge_per_basket - 0.05).abs() < 1e-9);
+        assert_eq!(opp.legs.len(), 3);
+        assert_eq!(opp.size, 10.0);
+    }
+
+    #[test]
+    fn test_rich_yes_bids_split_and_sell() {
+        let event = [
+            outcome(0.40, 0.41, 0.58, 0.61),
+            outcome(0.35, 0.36, 0.63, 0.66),
+            outcome(0.30, 0.31, 0.68, 0.71),
+        ];
+        let opp = evaluate(&event, &no_fees(3), 0.01, 100.0).unwrap();
+        assert_eq!(opp.kind, NegRiskKind::SplitSellYes);
+        assert!((opp.edge_per_basket - 0.05).abs() < 1e-9);
+        assert_eq!(opp.convert, vec![0, 1, 2]);
+        assert!(opp.legs.iter().all(|l| l.yes && l.side == TradeSide::Sell));
+    }
+
+    #[test]
+    fn test_cheap_no_converted() {
+        // NO on outcome 0 at 0.50 is a synthetic basket of YES 1 + YES 2,
+        // which bid 0.30 + 0.28 = 0.58 together
+        let event = [
+            outcome(0.40, 0.55, 0.45, 0.50),
+            outcome(0.30, 0.35, 0.60, 0.72),
+            outcome(0.28, 0.33, 0.62, 0.74),
+        ];
+        let opp = evaluate(&event, &no_fees(3), 0.01, 100.0).unwrap();
+        assert_eq!(opp.kind, NegRiskKind::ConvertNo);
+        assert_eq!(opp.convert, vec![0]);
+        assert!((opp.edge_per_basket - 0.08).abs() < 1e-9);
+        assert_eq!(opp.legs[0], BasketLeg { outcome: 0, yes: false, side: TradeSide::Buy, price: 0.50 });
+        assert_eq!(opp.legs.len(), 3);
+    }
+
+    #[test]
+    fn test_fair_event_has_no_opportunity() {
+        let event = [
+            outcome(0.49, 0.51, 0.49, 0.51),
+            outcome(0.49, 0.51, 0.49, 0.51),
+        ];
+        assert!(evaluate(&event, &no_fees(2), 0.0, 100.0).is_none());
+        assert!(evaluate(&event[..1], &no_fees(1), 0.0, 100.0).is_none());
+    }
+
+    #[test]
+    fn test_each_leg_pays_its_outcome_fee() {
+        // 0.3 ¢ raw edge clears 0.2 ¢ fee-free, but not once one
+        // outcome is on the crypto tier (~0.12 ¢ at p = 0.33)
+        let event = [
+            outcome(0.32, 0.333, 0.66, 0.68),
+            outcome(0.32, 0.333, 0.66, 0.68),
+            outcome(0.32, 0.331, 0.66, 0.68),
+        ];
+        assert!(evaluate(&event, &no_fees(3), 0.002, 100.0).is_some());
+
+        let mut fees = no_fees(3);
+        fees[2] = FeeCalculator::crypto_short_duration();
+        assert!(evaluate(&event, &fees, 0.002, 100.0).is_none());
+    }
+}
@@ -0,0 +1,844 @@
+//! Portfolio Ledger - Cost Basis and Mark-to-Market PnL
+//!
+//! Positions are rebuilt from what actually happened to the wallet:
+//! - `Fill`: buys open lots at price + fee per token; sells close lots
+//!   and realize proceeds − fee − cost basis
+//! - `Fee`: fees or rebates paid outside a fill (negative = rebate)
+//! - `Split`: 1 USDC per complete set, opening YES and NO lots at 0.50
+//! - `Merge`: YES + NO complete sets returned for 1 USDC each
+//! - `Convert`: neg-risk NO burned for USDC plus YES of the other
+//!   outcomes; the NO basis not covered by the USDC carries over to
+//!   the YES minted
+//! - `Redemption`: a resolved market pays each token its payout fraction
+//!
+//! Lots close first-in first-out, or against a single averaged lot
+//! with `CostMethod::Average`. Open positions are marked to the book
+//! mid; unmarked positions are carried at cost. Realized PnL is kept
+//! per market, cumulative and for the current day, and `roll_day`
+//! closes the day into a `DaySummary`. Fees paid and maker rebates
+//! earned are tallied alongside, so accruals survive restarts.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::trade::{Asset, MarketId, Position, TokenId, TradeSide};
+
+/// Sizes below this are treated as closed (float dust).
+const DUST: f64 = 1e-9;
+
+/// Which lots a sale closes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CostMethod {
+    /// Oldest lot first.
+    #[default]
+    Fifo,
+    /// One size-weighted lot per token.
+    Average,
+}
+
+/// Something that changed the wallet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LedgerEvent {
+    /// An order (partially) filled.
+    Fill {
+        /// Outcome token traded.
+        token_id: TokenId,
+        /// Buy or sell.
+        side: TradeSide,
+        /// Execution price.
+        price: f64,
+        /// Filled size in tokens.
+        size: f64,
+        /// Fee paid on this fill in USDC (0 for makers).
+        fee: f64,
+        /// Fill time (Unix ms).
+        timestamp_ms: u64,
+    },
+    /// Fee paid (positive) or rebate earned (negative) outside a fill.
+    Fee {
+        /// Market the fee belongs to.
+        market_id: MarketId,
+        /// Amount in USDC.
+        amount: f64,
+    },
+    /// USDC split into complete sets.
+    Split {
+        /// Market whose YES and NO were minted.
+        market_id: MarketId,
+        /// Complete sets split (tokens of each outcome).
+        sets: f64,
+        /// Split time (Unix ms).
+        timestamp_ms: u64,
+    },
+    /// Complete sets merged back into USDC.
+    Merge {
+        /// Market whose YES and NO were merged.
+        market_id: MarketId,
+        /// Complete sets merged (tokens of each outcome).
+        sets: f64,
+    },
+    /// Neg-risk NO converted through the NegRiskAdapter.
+    Convert {
+        /// NO tokens burned, one per converted outcome.
+        burned: Vec<TokenId>,
+        /// YES tokens minted, one per other outcome of the event.
+        minted: Vec<TokenId>,
+        /// NO burned per converted outcome.
+        amount: f64,
+        /// YES minted per other outcome (net of the adapter fee).
+        minted_amount: f64,
+        /// USDC released.
+        usdc: f64,
+        /// Conversion time (Unix ms).
+        timestamp_ms: u64,
+    },
+    /// Resolved market redeemed; every held token of it is closed.
+    Redemption {
+        /// Resolved market.
+        market_id: MarketId,
+        /// USDC paid per YES token.
+        yes_payout: f64,
+        /// USDC paid per NO token.
+        no_payout: f64,
+    },
+}
+
+/// Tokens held at one price.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Lot {
+    /// Tokens in the lot.
+    size: f64,
+    /// Cost per token including the buy fee.
+    price: f64,
+    /// When the lot was opened (Unix ms).
+    opened_ms: u64,
+}
+
+/// Open lots of one token.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Holding {
+    /// Lots, oldest first.
+    lots: VecDeque<Lot>,
+}
+
+impl Holding {
+    /// Tokens held.
+    fn size(&self) -> f64 {
+        self.lots.iter().map(|l| l.size).sum()
+    }
+
+    /// Cost basis of the tokens held.
+    fn cost(&self) -> f64 {
+        self.lots.iter().map(|l| l.size * l.price).sum()
+    }
+
+    /// Add a lot (merged into the single lot under `Average`).
+    fn open(&mut self, lot: Lot, method: CostMethod) {
+        match (method, self.lots.front_mut()) {
+            (CostMethod::Average, Some(avg)) => {
+                let size = avg.size + lot.size;
+                avg.price = (avg.size * avg.price + lot.size * lot.price) / size;
+                avg.size = size;
+            }
+            _ => self.lots.push_back(lot),
+        }
+    }
+
+    /// Remove up to `size` tokens, oldest first; returns their cost.
+    ///
+    /// Tokens beyond what is held (e.g. from a split the ledger never
+    /// saw) carry no basis.
+    #[allow(clippy::while_float)]
+    fn close(&mut self, mut size: f64) -> f64 {
+        let mut cost = 0.0;
+        while size > DUST {
+            let Some(lot) = self.lots.front_mut() else {
+                break;
+            };
+            let take = lot.size.min(size);
+            cost += take * lot.price;
+            lot.size -= take;
+            size -= take;
+            if lot.size <= DUST {
+                self.lots.pop_front();
+            }
+        }
+        cost
+    }
+}
+
+/// A registered market's tokens.
+#[derive(Debug, Clone)]
+struct MarketInfo {
+    /// Underlying asset.
+    asset: Asset,
+    /// YES outcome token.
+    yes_token_id: TokenId,
+    /// NO outcome token.
+    no_token_id: TokenId,
+}
+
+/// Counters for the current trading day.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct DayBook {
+    /// UTC day (YYYY-MM-DD); empty until the first `roll_day`.
+    date: String,
+    /// Realized PnL per market today.
+    realized: HashMap<MarketId, f64>,
+    /// Unrealized PnL when the day opened.
+    opening_unrealized: f64,
+    /// Fills today.
+    trade_count: u64,
+    /// Notional traded today (USDC).
+    volume: f64,
+    /// Highest day PnL seen.
+    peak: f64,
+    /// Largest drop from `peak`.
+    max_drawdown: f64,
+    /// Fees paid today (USDC).
+    #[serde(default)]
+    fees: f64,
+    /// Maker rebates accrued today (USDC).
+    #[serde(default)]
+    rebates: f64,
+}
+
+/// A closed trading day.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DaySummary {
+    /// UTC day (YYYY-MM-DD).
+    pub date: String,
+    /// Realized PnL booked during the day.
+    pub realized: f64,
+    /// Unrealized PnL of positions open at the close.
+    pub unrealized: f64,
+    /// Fills during the day.
+    pub trade_count: u64,
+    /// Notional traded (USDC).
+    pub volume: f64,
+    /// Largest intraday drop of the day's PnL from its high.
+    pub max_drawdown: f64,
+    /// Fees paid during the day.
+    pub fees: f64,
+    /// Maker rebates accrued during the day.
+    pub rebates: f64,
+}
+
+/// PnL of one market.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MarketPnl {
+    /// Condition ID (the token ID for unregistered tokens).
+    pub market_id: MarketId,
+    /// Underlying asset, if the market is registered.
+    pub asset: Option<Asset>,
+    /// Realized PnL since the ledger started.
+    pub realized: f64,
+    /// Realized PnL today.
+    pub day_realized: f64,
+    /// Mark-to-market PnL of the open position.
+    pub unrealized: f64,
+    /// Cost basis of the open position.
+    pub cost: f64,
+}
+
+/// PnL aggregated per asset.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AssetPnl {
+    /// Asset label (`unknown` for unregistered markets).
+    pub asset: String,
+    /// Realized PnL since the ledger started.
+    pub realized: f64,
+    /// Realized PnL today.
+    pub day_realized: f64,
+    /// Mark-to-market PnL of open positions.
+    pub unrealized: f64,
+}
+
+/// Positions, cost basis and PnL built from ledger events.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Portfolio {
+    /// Lot matching for sales.
+    method: CostMethod,
+    /// Open lots per token.
+    holdings: HashMap<TokenId, Holding>,
+    /// Realized PnL per market since the ledger started.
+    realized: HashMap<MarketId, f64>,
+    /// Current day counters.
+    day: DayBook,
+    /// Last book mid per token.
+    marks: HashMap<TokenId, f64>,
+    /// Fees paid since the ledger started.
+    #[serde(default)]
+    fees_paid: f64,
+    /// Maker rebates accrued since the ledger started.
+    #[serde(default)]
+    rebates: f64,
+    /// Registered markets (from config; not persisted).
+    #[serde(skip)]
+    markets: HashMap<MarketId, MarketInfo>,
+    /// Token → market (from config; not persisted).
+    #[serde(skip)]
+    token_markets: HashMap<TokenId, MarketId>,
+}
+
+impl Portfolio {
+    /// Empty portfolio using `method` for sales.
+    pub fn new(method: CostMethod) -> Self {
+        Self {
+            method,
+            ..Self::default()
+        }
+    }
+
+    /// Register a market so its tokens are attributed to it.
+    pub fn register_market(
+        &mut self,
+        market_id: &str,
+        asset: Asset,
+        yes_token_id: &str,
+        no_token_id: &str,
+    ) {
+        self.token_markets.insert(yes_token_id.to_string(), market_id.to_string());
+        self.token_markets.insert(no_token_id.to_string(), market_id.to_string());
+        self.markets.insert(
+            market_id.to_string(),
+            MarketInfo {
+                asset,
+                yes_token_id: yes_token_id.to_string(),
+                no_token_id: no_token_id.to_string(),
+            },
+        );
+    }
+
+    /// Take positions, PnL and day counters from a persisted
+    /// portfolio, keeping this one's method and registrations.
+    pub fn restore(&mut self, saved: Self) {
+        self.holdings = saved.holdings;
+        self.realized = saved.realized;
+        self.day = saved.day;
+        self.marks = saved.marks;
+        self.fees_paid = saved.fees_paid;
+        self.rebates = saved.rebates;
+    }
+
+    /// Apply an event; returns the PnL it realized.
+    pub fn apply(&mut self, event: &LedgerEvent) -> f64 {
+        let (market_id, realized) = match event {
+            LedgerEvent::Fill {
+                token_id,
+                side,
+                price,
+                size,
+                fee,
+                timestamp_ms,
+            } => {
+                self.day.trade_count += 1;
+                self.day.volume += price * size;
+                self.book_fee(*fee);
+                let holding = self.holdings.entry(token_id.clone()).or_default();
+                let realized = match side {
+                    TradeSide::Buy => {
+                        if *size > DUST {
+                            let lot = Lot {
+                                size: *size,
+                                price: price + fee / size,
+                                opened_ms: *timestamp_ms,
+                            };
+                            holding.open(lot, self.method);
+                        }
+                        0.0
+                    }
+                    TradeSide::Sell => price * size - fee - holding.close(*size),
+                };
+                (self.market_of(token_id), realized)
+            }
+            LedgerEvent::Fee { market_id, amount } => {
+                self.book_fee(*amount);
+                (market_id.clone(), -amount)
+            }
+            LedgerEvent::Split {
+                market_id,
+                sets,
+                timestamp_ms,
+            } => {
+                // A set costs 1 USDC; each outcome carries half of it
+                for token_id in self.outcome_tokens(market_id) {
+                    let lot = Lot {
+                        size: *sets,
+                        price: 0.5,
+                        opened_ms: *timestamp_ms,
+                    };
+                    self.holdings.entry(token_id).or_default().open(lot, self.method);
+                }
+                (market_id.clone(), 0.0)
+            }
+            LedgerEvent::Merge { market_id, sets } => {
+                let cost = self
+                    .outcome_tokens(market_id)
+                    .iter()
+                    .map(|token_id| self.holdings.entry(token_id.clone()).or_default().close(*sets))
+                    .sum::<f64>();
+                (market_id.clone(), sets - cost)
+            }
+            LedgerEvent::Convert {
+                burned,
+                minted,
+                amount,
+                minted_amount,
+                usdc,
+                timestamp_ms,
+            } => self.convert(burned, minted, *amount, *minted_amount, *usdc, *timestamp_ms),
+            LedgerEvent::Redemption {
+                market_id,
+                yes_payout,
+                no_payout,
+            } => {
+                let tokens = self.outcome_tokens(market_id);
+                let realized = tokens
+                    .iter()
+                    .zip([yes_payout, no_payout])
+                    .filter_map(|(token_id, payout)| {
+                        self.marks.remove(token_id);
+                        let holding = self.holdings.remove(token_id)?;
+                        Some(holding.size() * payout - holding.cost())
+                    })
+                    .sum::<f64>();
+                (market_id.clone(), realized)
+            }
+        };
+
+        self.holdings.retain(|_, h| h.size() > DUST);
+        *self.realized.entry(market_id.clone()).or_default() += realized;
+        *self.day.realized.entry(market_id).or_default() += realized;
+        self.track_drawdown();
+        realized
+    }
+
+    /// Burn the `burned` NO, carrying their basis net of `usdc` over to
+    /// the `minted` YES; returns the market booked and the PnL realized.
+    fn convert(
+        &mut self,
+        burned: &[TokenId],
+        minted: &[TokenId],
+        amount: f64,
+        minted_amount: f64,
+        usdc: f64,
+        timestamp_ms: u64,
+    ) -> (MarketId, f64) {
+        let cost = burned
+            .iter()
+            .map(|token_id| self.holdings.entry(token_id.clone()).or_default().close(amount))
+            .sum::<f64>();
+        let carried = if minted.is_empty() || minted_amount <= DUST {
+            0.0
+        } else {
+            (cost - usdc).max(0.0)
+        };
+        for token_id in minted {
+            let lot = Lot {
+                size: minted_amount,
+                price: carried / (minted_amount * minted.len() as f64),
+                opened_ms: timestamp_ms,
+            };
+            self.holdings.entry(token_id.clone()).or_default().open(lot, self.method);
+        }
+        let market_id = burned.first().map_or_else(MarketId::new, |t| self.market_of(t));
+        (market_id, usdc + carried - cost)
+    }
+
+    /// Record the book mid of a token.
+    pub fn mark(&mut self, token_id: &str, mid: f64) {
+        self.marks.insert(token_id.to_string(), mid);
+        self.track_drawdown();
+    }
+
+    /// Tokens currently held.
+    pub fn held_tokens(&self) -> Vec<TokenId> {
+        self.holdings.keys().cloned().collect()
+    }
+
+    /// Size held per token, sorted by token ID.
+    pub fn sizes(&self) -> Vec<(TokenId, f64)> {
+        let mut sizes: Vec<(TokenId, f64)> = self
+            .holdings
+            .iter()
+            .map(|(token_id, holding)| (token_id.clone(), holding.size()))
+            .collect();
+        sizes.sort_by(|a, b| a.0.cmp(&b.0));
+        sizes
+    }
+
+    /// Cost basis held per token (USDC), sorted by token ID.
+    pub fn costs(&self) -> Vec<(TokenId, f64)> {
+        let mut costs: Vec<(TokenId, f64)> = self
+            .holdings
+            .iter()
+            .map(|(token_id, holding)| (token_id.clone(), holding.cost()))
+            .collect();
+        costs.sort_by(|a, b| a.0.cmp(&b.0));
+        costs
+    }
+
+    /// Tokens held of `token_id`.
+    pub fn size(&self, token_id: &str) -> f64 {
+        self.holdings.get(token_id).map_or(0.0, Holding::size)
+    }
+
+    /// Average cost per token of the open position, if any.
+    pub fn avg_entry_price(&self, token_id: &str) -> Option<f64> {
+        let holding = self.holdings.get(token_id)?;
+        let size = holding.size();
+        (size > DUST).then(|| holding.cost() / size)
+    }
+
+    /// Mark-to-market PnL of one token (0 when unmarked).
+    pub fn unrealized(&self, token_id: &str) -> f64 {
+        match (self.holdings.get(token_id), self.marks.get(token_id)) {
+            (Some(holding), Some(mid)) => holding.size() * mid - holding.cost(),
+            _ => 0.0,
+        }
+    }
+
+    /// Mark-to-market PnL of all open positions.
+    pub fn total_unrealized(&self) -> f64 {
+        self.holdings.keys().map(|t| self.unrealized(t)).sum()
+    }
+
+    /// Realized PnL since the ledger started.
+    pub fn total_realized(&self) -> f64 {
+        self.realized.values().sum()
+    }
+
+    /// Fees paid since the ledger started.
+    pub fn fees_paid(&self) -> f64 {
+        self.fees_paid
+    }
+
+    /// Maker rebates accrued since the ledger started.
+    pub fn rebates(&self) -> f64 {
+        self.rebates
+    }
+
+    /// Fees paid and rebates accrued today.
+    pub fn day_fees(&self) -> (f64, f64) {
+        (self.day.fees, self.day.rebates)
+    }
+
+    /// Realized + change in unrealized since the day opened.
+    pub fn day_pnl(&self) -> f64 {
+        self.day.realized.values().sum::<f64>() + self.total_unrealized()
+            - self.day.opening_unrealized
+    }
+
+    /// Open positions of registered markets, marked to market.
+    pub fn positions(&self) -> Vec<Position> {
+        let mut positions: Vec<Position> = self
+            .holdings
+            .iter()
+            .filter_map(|(token_id, holding)| {
+                let market_id = self.token_markets.get(token_id)?;
+                let info = self.markets.get(market_id)?;
+                let size = holding.size();
+                let opened_ms = holding.lots.front().map_or(0, |l| l.opened_ms);
+                Some(Position {
+                    condition_id: market_id.clone(),
+                    token_id: token_id.clone(),
+                    asset: info.asset,
+                    size: Decimal::from_f64(size).unwrap_or_default(),
+                    avg_entry_price: Decimal::from_f64(holding.cost() / size).unwrap_or_default(),
+                    unrealized_pnl: Decimal::from_f64(self.unrealized(token_id))
+                        .unwrap_or_default(),
+                    opened_at: DateTime::<Utc>::from_timestamp_millis(opened_ms as i64)
+                        .unwrap_or_default(),
+                    resolved: false,
+                })
+            })
+            .collect();
+        positions.sort_by(|a, b| a.token_id.cmp(&b.token_id));
+        positions
+    }
+
+    /// PnL per market, sorted by market ID.
+    pub fn by_market(&self) -> Vec<MarketPnl> {
+        let new_row = |market_id: &MarketId| MarketPnl {
+            market_id: market_id.clone(),
+            asset: self.markets.get(market_id).map(|m| m.asset),
+            realized: 0.0,
+            day_realized: 0.0,
+            unrealized: 0.0,
+            cost: 0.0,
+        };
+        let mut rows: HashMap<MarketId, MarketPnl> = HashMap::new();
+        for (market_id, realized) in &self.realized {
+            rows.entry(market_id.clone())
+                .or_insert_with(|| new_row(market_id))
+                .realized += realized;
+        }
+        for (market_id, realized) in &self.day.realized {
+            rows.entry(market_id.clone())
+                .or_insert_with(|| new_row(market_id))
+                .day_realized += realized;
+        }
+        for (token_id, holding) in &self.holdings {
+            let market_id = self.market_of(token_id);
+            let row = rows
+                .entry(market_id.clone())
+                .or_insert_with(|| new_row(&market_id));
+            row.unrealized += self.unrealized(token_id);
+            row.cost += holding.cost();
+        }
+        let mut rows: Vec<MarketPnl> = rows.into_values().collect();
+        rows.sort_by(|a, b| a.market_id.cmp(&b.market_id));
+        rows
+    }
+
+    /// PnL per asset, sorted by label.
+    pub fn by_asset(&self) -> Vec<AssetPnl> {
+        let mut rows: Vec<AssetPnl> = Vec::new();
+        for market in self.by_market() {
+            let asset = market.asset.map_or_else(|| "unknown".to_string(), |a| a.to_string());
+            let row = if let Some(i) = rows.iter().position(|r| r.asset == asset) {
+                &mut rows[i]
+            } else {
+                rows.push(AssetPnl {
+                    asset,
+                    realized: 0.0,
+                    day_realized: 0.0,
+                    unrealized: 0.0,
+                });
+                rows.last_mut().expect("just pushed")
+            };
+            row.realized += market.realized;
+            row.day_realized += market.day_realized;
+            row.unrealized += market.unrealized;
+        }
+        rows.sort_by(|a, b| a.asset.cmp(&b.asset));
+        rows
+    }
+
+    /// The day counters currently run for (empty before the first roll).
+    pub fn day(&self) -> &str {
+        &self.day.date
+    }
+
+    /// Start `date`; returns the summary of the day it closes.
+    ///
+    /// The first call only sets the date, keeping anything booked
+    /// before it. Calling again with the
+    /// current date does nothing.
+    pub fn roll_day(&mut self, date: &str) -> Option<DaySummary> {
+        if self.day.date == date {
+            return None;
+        }
+        // First call: the fills booked so far belong to this day
+        if self.day.date.is_empty() {
+            if self.day.trade_count == 0 {
+                self.day.opening_unrealized = self.total_unrealized();
+            }
+            self.day.date = date.to_string();
+            return None;
+        }
+        let unrealized = self.total_unrealized();
+        let closed = (!self.day.date.is_empty()).then(|| DaySummary {
+            date: self.day.date.clone(),
+            realized: self.day.realized.values().sum(),
+            unrealized,
+            trade_count: self.day.trade_count,
+            volume: self.day.volume,
+            max_drawdown: self.day.max_drawdown,
+            fees: self.day.fees,
+            rebates: self.day.rebates,
+        });
+        self.day = DayBook {
+            date: date.to_string(),
+            opening_unrealized: unrealized,
+            ..DayBook::default()
+        };
+        closed
+    }
+
+    /// Tally a fee (positive) or rebate (negative).
+    fn book_fee(&mut self, amount: f64) {
+        if amount >= 0.0 {
+            self.fees_paid += amount;
+            self.day.fees += amount;
+        } else {
+            self.rebates -= amount;
+            self.day.rebates -= amount;
+        }
+    }
+
+    /// Market a token belongs to (itself when unregistered).
+    fn market_of(&self, token_id: &str) -> MarketId {
+        self.token_markets
+            .get(token_id)
+            .cloned()
+            .unwrap_or_else(|| token_id.to_string())
+    }
+
+    /// YES and NO tokens of a registered market.
+    fn outcome_tokens(&self, market_id: &str) -> Vec<TokenId> {
+        self.markets
+            .get(market_id)
+            .map(|m| vec![m.yes_token_id.clone(), m.no_token_id.clone()])
+            .unwrap_or_default()
+    }
+
+    /// Update the day's high-water mark and drawdown.
+    fn track_drawdown(&mut self) {
+        let pnl = self.day_pnl();
+        self.day.peak = self.day.peak.max(pnl);
+        self.day.max_drawdown = self.day.max_drawdown.max(self.day.peak - pnl);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(token_id: &str, side: TradeSide, price: f64, size: f64, fee: f64) -> LedgerEvent {
+        LedgerEvent::Fill {
+            token_id: token_id.to_string(),
+            side,
+            price,
+            size,
+            fee,
+            timestamp_ms: 1_700_000_000_000,
+        }
+    }
+
+    fn portfolio(method: CostMethod) -> Portfolio {
+        let mut p = Portfolio::new(method);
+        p.register_market("cond", Asset::BTC, "yes", "no");
+        p
+    }
+
+    #[test]
+    fn test_fifo_closes_oldest_lot_first() {
+        let mut p = portfolio(CostMethod::Fifo);
+        p.apply(&fill("yes", TradeSide::Buy, 0.40, 10.0, 0.0));
+        p.apply(&fill("yes", TradeSide::Buy, 0.60, 10.0, 0.0));
+        let realized = p.apply(&fill("yes", TradeSide::Sell, 0.55, 10.0, 0.0));
+        assert!((realized - 1.5).abs() < 1e-9);
+        assert!((p.avg_entry_price("yes").unwrap() - 0.60).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_average_cost_blends_lots_and_capitalizes_fees() {
+        let mut p = portfolio(CostMethod::Average);
+        p.apply(&fill("yes", TradeSide::Buy, 0.40, 10.0, 0.10));
+        p.apply(&fill("yes", TradeSide::Buy, 0.60, 10.0, 0.0));
+        assert!((p.avg_entry_price("yes").unwrap() - 0.505).abs() < 1e-9);
+        let realized = p.apply(&fill("yes", TradeSide::Sell, 0.55, 10.0, 0.05));
+        assert!((realized - (5.5 - 0.05 - 5.05)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_marks_merges_and_redemptions() {
+        let mut p = portfolio(CostMethod::Fifo);
+        p.apply(&fill("yes", TradeSide::Buy, 0.45, 20.0, 0.0));
+        p.apply(&fill("no", TradeSide::Buy, 0.50, 10.0, 0.0));
+        p.mark("yes", 0.50);
+        assert!((p.unrealized("yes") - 1.0).abs() < 1e-9);
+
+        // 10 sets cost 9.50 and return 10 USDC
+        let merged = p.apply(&LedgerEvent::Merge {
+            market_id: "cond".to_string(),
+            sets: 10.0,
+        });
+        assert!((merged - 0.5).abs() < 1e-9);
+        assert_eq!(p.size("no"), 0.0);
+
+        let redeemed = p.apply(&LedgerEvent::Redemption {
+            market_id: "cond".to_string(),
+            yes_payout: 1.0,
+            no_payout: 0.0,
+        });
+        assert!((redeemed - 5.5).abs() < 1e-9);
+        assert!(p.held_tokens().is_empty());
+
+        let market = &p.by_market()[0];
+        assert!((market.realized - 6.0).abs() < 1e-9);
+        assert_eq!(p.by_asset()[0].asset, "BTC");
+    }
+
+    #[test]
+    fn test_split_opens_both_outcomes_at_half_a_set() {
+        let mut p = portfolio(CostMethod::Fifo);
+        let split = p.apply(&LedgerEvent::Split {
+            market_id: "cond".to_string(),
+            sets: 10.0,
+            timestamp_ms: 1_700_000_000_000,
+        });
+        assert_eq!(split, 0.0);
+        assert_eq!(p.avg_entry_price("no"), Some(0.5));
+
+        // Selling the YES half at 0.58 realizes 0.08 per token
+        let sold = p.apply(&fill("yes", TradeSide::Sell, 0.58, 10.0, 0.0));
+        assert!((sold - 0.8).abs() < 1e-9);
+        assert_eq!(p.size("no"), 10.0);
+    }
+
+    #[test]
+    fn test_convert_carries_no_basis_to_minted_yes() {
+        let mut p = Portfolio::new(CostMethod::Fifo);
+        for q in 0..3 {
+            p.register_market(&format!("q{q}"), Asset::BTC, &format!("q{q}_yes"), &format!("q{q}_no"));
+        }
+        p.apply(&fill("q0_no", TradeSide::Buy, 0.50, 10.0, 0.0));
+        let converted = p.apply(&LedgerEvent::Convert {
+            burned: vec!["q0_no".to_string()],
+            minted: vec!["q1_yes".to_string(), "q2_yes".to_string()],
+            amount: 10.0,
+            minted_amount: 10.0,
+            usdc: 0.0,
+            timestamp_ms: 1_700_000_000_000,
+        });
+        assert_eq!(converted, 0.0);
+        assert_eq!(p.size("q0_no"), 0.0);
+        assert_eq!(p.avg_entry_price("q1_yes"), Some(0.25));
+
+        // Converting NO of every outcome mints nothing and realizes all
+        p.apply(&fill("q1_no", TradeSide::Buy, 0.40, 10.0, 0.0));
+        p.apply(&fill("q2_no", TradeSide::Buy, 0.45, 10.0, 0.0));
+        let realized = p.apply(&LedgerEvent::Convert {
+            burned: vec!["q1_no".to_string(), "q2_no".to_string()],
+            minted: Vec::new(),
+            amount: 10.0,
+            minted_amount: 10.0,
+            usdc: 10.0,
+            timestamp_ms: 1_700_000_000_000,
+        });
+        assert!((realized - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_roll_day_reports_realized_and_drawdown() {
+        let mut p = portfolio(CostMethod::Fifo);
+        assert!(p.roll_day("2026-01-01").is_none());
+        p.apply(&fill("yes", TradeSide::Buy, 0.50, 10.0, 0.0));
+        p.mark("yes", 0.60);
+        p.mark("yes", 0.45);
+        p.apply(&LedgerEvent::Fee {
+            market_id: "cond".to_string(),
+            amount: -0.25,
+        });
+
+        let day = p.roll_day("2026-01-02").unwrap();
+        assert_eq!(day.date, "2026-01-01");
+        assert!((day.realized - 0.25).abs() < 1e-9);
+        assert!((day.unrealized + 0.5).abs() < 1e-9);
+        assert_eq!(day.trade_count, 1);
+        assert!((day.max_drawdown - 1.5).abs() < 1e-9);
+        assert!((day.rebates - 0.25).abs() < 1e-9);
+        assert_eq!(day.fees, 0.0);
+        assert!((p.rebates() - 0.25).abs() < 1e-9);
+        assert!(p.roll_day("2026-01-02").is_none());
+        assert!(p.day_pnl().abs() < 1e-9);
+    }
+}
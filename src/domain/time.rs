@@ -0,0 +1,32 @@
+//! Time - Wall-Clock and UTC Day Helpers
+//!
+//! Timestamps across the bot are Unix milliseconds (`u64`); trading
+//! days are UTC calendar dates (`YYYY-MM-DD`).
+
+/// Current wall-clock time in Unix milliseconds.
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// UTC calendar day (YYYY-MM-DD) for a Unix-ms timestamp.
+pub fn utc_day(timestamp_ms: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp_ms as i64)
+        .unwrap_or_default()
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utc_day_boundaries() {
+        assert_eq!(utc_day(1_772_366_400_000), "2026-03-01");
+        assert_eq!(utc_day(1_772_409_599_999), "2026-03-01");
+        assert_eq!(utc_day(1_772_409_600_000), "2026-03-02");
+    }
+}
@@ -73,12 +73,15 @@ pub type OrderSide = TradeSide;
 ///
 /// `Gtc` is the primary maker-only type (post-only implied).
 /// `Gtd` carries an explicit expiration in seconds (90 s per checklist).
+/// `Fok` is the only taker type, reserved for complete-set arbitrage legs.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderType {
     /// Good-til-cancelled, post-only (maker). Primary order type.
     Gtc,
     /// Good-til-date with expiration. Used for time-sensitive markets.
     Gtd { expiration_secs: u64 },
+    /// Fill-or-kill taker order. Fills entirely at once or not at all.
+    Fok,
 }
 
 /// Lifecycle status of an order (domain-internal rich version).
@@ -127,6 +130,10 @@ pub struct Order {
     pub post_only: bool,
     /// Creation timestamp in Unix milliseconds.
     pub timestamp_ms: u64,
+    /// Client-generated salt sent with the order and echoed back in
+    /// the open orders (empty until submitted).
+    #[serde(default)]
+    pub salt: String,
 }
 
 impl Order {
@@ -149,6 +156,21 @@ impl Order {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_millis() as u64,
+            salt: String::new(),
+        }
+    }
+
+    /// Create a fill-or-kill taker order (pays the taker fee).
+    pub fn new_taker(
+        token_id: TokenId,
+        side: TradeSide,
+        price: f64,
+        size: f64,
+    ) -> Self {
+        Self {
+            order_type: OrderType::Fok,
+            post_only: false,
+            ..Self::new_maker(token_id, side, price, size)
         }
     }
 }
@@ -228,6 +250,7 @@ impl RichOrder {
             order_type: self.order_type,
             post_only: true,
             timestamp_ms: self.created_at.timestamp_millis() as u64,
+            salt: String::new(),
         }
     }
 }
@@ -367,6 +390,18 @@ mod tests {
         assert!(order.id.is_empty());
     }
 
+    #[test]
+    fn test_order_new_taker_is_fok() {
+        let order = Order::new_taker(
+            "token_no".to_string(),
+            TradeSide::Sell,
+            0.52,
+            5.0,
+        );
+        assert_eq!(order.order_type, OrderType::Fok);
+        assert!(!order.post_only);
+    }
+
     #[test]
     fn test_rich_order_new_maker_defaults() {
         let order = RichOrder::new_maker(
@@ -0,0 +1,163 @@
+//! Hot/Cold Wallet Allocation - Target Balance and Drift
+//!
+//! The bankroll is split between the hot trading wallet (key on the
+//! server) and a cold wallet (key offline). The hot wallet should
+//! hold `hot_fraction` of the combined USDC; `hot_alert_threshold`
+//! sets the tolerated band around that target:
+//! - hot share above the threshold → `Sweep` the excess to cold
+//! - hot share below `hot_fraction − (threshold − hot_fraction)` →
+//!   `TopUp` from cold (manual: the cold key never touches the bot)
+//!
+//! Transfers restore the target exactly and are skipped below
+//! `min_transfer` so dust never costs gas.
+
+use serde::Serialize;
+
+/// Allocation parameters (from `[wallet]`).
+#[derive(Debug, Clone, Copy)]
+pub struct AllocationPolicy {
+    /// Target share of the bankroll held hot.
+    pub hot_fraction: f64,
+    /// Hot share that triggers a drift alert (above `hot_fraction`).
+    pub hot_alert_threshold: f64,
+    /// Smallest transfer worth proposing (USDC).
+    pub min_transfer: f64,
+}
+
+/// Direction of a rebalancing transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferDirection {
+    /// Cold → hot (operator signs with the cold key).
+    TopUp,
+    /// Hot → cold (the bot can sign).
+    Sweep,
+}
+
+/// A proposed USDC transfer between the wallets.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Rebalance {
+    /// Which way the USDC moves.
+    pub direction: TransferDirection,
+    /// Amount in USDC.
+    pub amount: f64,
+}
+
+/// Where the hot wallet stands against its target.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Allocation {
+    /// Hot wallet USDC.
+    pub hot_balance: f64,
+    /// Cold wallet USDC.
+    pub cold_balance: f64,
+    /// Target hot balance (`hot_fraction` of the total).
+    pub target_hot: f64,
+    /// Current hot share of the total (0 when both are empty).
+    pub hot_share: f64,
+    /// Whether the hot share is outside the tolerated band.
+    pub drifted: bool,
+    /// Transfer that restores the target, if drifted and large enough.
+    pub rebalance: Option<Rebalance>,
+}
+
+impl Allocation {
+    /// Combined USDC across both wallets.
+    pub fn total(&self) -> f64 {
+        self.hot_balance + self.cold_balance
+    }
+}
+
+/// Compare the balances against the policy.
+pub fn evaluate(hot_balance: f64, cold_balance: f64, policy: &AllocationPolicy) -> Allocation {
+    let hot = hot_balance.max(0.0);
+    let cold = cold_balance.max(0.0);
+    let total = hot + cold;
+    let target_hot = total * policy.hot_fraction;
+    let hot_share = if total > 0.0 { hot / total } else { 0.0 };
+
+    let band = (policy.hot_alert_threshold - policy.hot_fraction).max(0.0);
+    let low = (policy.hot_fraction - band).max(0.0);
+    let direction = if total <= 0.0 {
+        None
+    } else if hot_share > policy.hot_alert_threshold {
+        Some(TransferDirection::Sweep)
+    } else if hot_share < low {
+        Some(TransferDirection::TopUp)
+    } else {
+        None
+    };
+
+    let rebalance = direction
+        .map(|direction| Rebalance {
+            direction,
+            amount: round_cents((hot - target_hot).abs()),
+        })
+        .filter(|r| r.amount >= policy.min_transfer);
+
+    Allocation {
+        hot_balance: hot,
+        cold_balance: cold,
+        target_hot,
+        hot_share,
+        drifted: direction.is_some(),
+        rebalance,
+    }
+}
+
+/// Round down to whole cents so a transfer never exceeds the balance.
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).floor() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> AllocationPolicy {
+        AllocationPolicy {
+            hot_fraction: 0.20,
+            hot_alert_threshold: 0.30,
+            min_transfer: 10.0,
+        }
+    }
+
+    #[test]
+    fn test_within_band_needs_nothing() {
+        let a = evaluate(250.0, 750.0, &policy());
+        assert_eq!(a.target_hot, 200.0);
+        assert!(!a.drifted);
+        assert!(a.rebalance.is_none());
+    }
+
+    #[test]
+    fn test_hot_above_threshold_sweeps_to_target() {
+        // 400 / 1000 = 40% hot > 30% threshold
+        let a = evaluate(400.0, 600.0, &policy());
+        assert!(a.drifted);
+        assert_eq!(
+            a.rebalance,
+            Some(Rebalance {
+                direction: TransferDirection::Sweep,
+                amount: 200.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_hot_below_band_tops_up() {
+        // Band is ±10 points: below 10% hot asks for a top-up
+        let a = evaluate(50.0, 950.0, &policy());
+        let r = a.rebalance.unwrap();
+        assert_eq!(r.direction, TransferDirection::TopUp);
+        assert_eq!(r.amount, 150.0);
+    }
+
+    #[test]
+    fn test_small_drift_below_min_transfer_only_alerts() {
+        // 16 / 50 = 32% hot, but the 6 USDC excess is below min_transfer
+        let a = evaluate(16.0, 34.0, &policy());
+        assert!(a.drifted);
+        assert!(a.rebalance.is_none());
+        assert!(!evaluate(0.0, 0.0, &policy()).drifted);
+    }
+}
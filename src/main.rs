@@ -1,21 +1,39 @@
 //! Polymarket LMSR Bot — Entry Point
 //!
-//! Initializes configuration, logging, blockchain connections,
-//! and the main arbitrage engine. Runs until SIGINT/SIGTERM.
+//! Parses the CLI and resolves the layered config, then either runs
+//! a one-shot operator command (`cli::commands`) or the bot: logging,
+//! blockchain connections and the main arbitrage engine, until
+//! SIGINT/SIGTERM.
 //!
-//! Wiring sequence:
-//!  1. Load config.toml + validate
+//! Wiring sequence (`run`):
+//!  1. Parse CLI, resolve layered config (defaults → files → env →
+//!     flags) + validate; other subcommands dispatch here
 //!  2. Init tracing (JSON structured logging)
 //!  3. Connect to Polygon RPC + validate chain ID
 //!  4. Validate contracts on-chain (code exists)
-//!  5. Load CLOB auth from env vars
-//!  6. Create ClobClient + ClobOrderExecutor (OrderExecution port)
+//!  5. Load CLOB auth from the secret backend (`SecretStore`)
+//!  6. Create ClobClient + ClobOrderExecutor (PaperOrderExecutor unless
+//!     live) wrapped in RiskGate,
+//!     fills booked in the PortfolioLedger at per-market FeeSchedules,
+//!     every order journaled (`orders.wal`) before it is sent
 //!  7. Create PolymarketFeed (MarketFeed port) + BinanceFeed + Bridge
-//!  8. Create RepositoryImpl (Repository port)
-//!  9. Spawn health server on :9090 (/live + /ready)
+//!  8. Open the `[storage]` backend (Repository port), restore risk, ledger
+//!     and tracked orders from the last snapshot (migrated if older)
+//!  9. Spawn health server on :9090 (/live + /ready + /metrics + /admin)
 //! 10. Spawn feeds (Polymarket WS + Binance WS + Bridge)
-//! 11. Spawn config hot-reload watcher (60s)
-//! 12. Spawn ArbitrageEngine main loop (event-driven tokio::select!)
+//! 11. Spawn config hot-reload watcher (fs events, acked by the engine
+//!     and neg-risk arbitrage; the market set is fixed until restart)
+//!     + risk scheduler + portfolio ledger (marks, daily PnL)
+//!     + fee reconciler (CLOB fee rates, daily fee reconciliation)
+//!     + state checkpointer (full snapshot every interval)
+//!     + settlement scheduler + position merger + wallet monitor
+//!     + neg-risk arbitrage (live mode only)
+//!     + USDCe / CTF approvals checked first (live mode only)
+//!     + recovery verified first against the CLOB, the order journal
+//!       and the chain (unknown orders cancelled, a position mismatch
+//!       halts trading)
+//! 12. Spawn ArbitrageEngine main loop running the configured
+//!     strategies (event-driven tokio::select!)
 //! 13. Wait for SIGINT → graceful shutdown (cancel→claim→save→exit)
 
 #[cfg(not(target_env = "msvc"))]
@@ -25,32 +43,89 @@ static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
+use clap::Parser;
 use tokio::signal;
-use tokio::sync::{broadcast, watch};
+use tokio::sync::{broadcast, watch, Notify, RwLock};
 use tracing::{error, info, warn};
 
-mod adapters;
-mod config;
-mod domain;
-mod ports;
-mod usecases;
+mod cli;
 
-use adapters::api::auth::ClobAuth;
-use adapters::api::client::{ClobClient, ClobClientConfig};
+use polymarket_lmsr_bot::{adapters, config, domain, ports, usecases};
+
+use adapters::admin::{admin_router, wallet_router, KillSwitch};
+use adapters::api::fees::ClobFeeSource;
 use adapters::api::orders::ClobOrderExecutor;
 use adapters::chain::provider::PolygonProvider;
 use adapters::chain::ContractValidator;
 use adapters::feeds::{BinanceFeed, FeedBridge, PolymarketFeed};
-use adapters::persistence::RepositoryImpl;
+use adapters::metrics::MetricsRegistry;
+use adapters::paper::PaperOrderExecutor;
+use adapters::persistence::{FileOrderJournal, Storage};
+use adapters::secrets::SecretStore;
+use cli::commands;
+use cli::{Cli, Command, ConfigCommand, DbCommand, OrdersCommand, PnlCommand};
+use config::layers::ConfigSources;
+use config::AppConfig;
 use config::hot_reload::ConfigWatcher;
+use domain::time::now_ms;
+use domain::trade::BotMode;
 use usecases::arbitrage_engine::ArbitrageEngine;
+use usecases::fee_reconciler::FeeReconciler;
+use usecases::fee_schedule::FeeSchedules;
+use usecases::risk_gate::RiskGate;
+use usecases::risk_manager::RiskManager;
+use usecases::risk_scheduler::RiskScheduler;
+use usecases::neg_risk_arb::NegRiskArbitrage;
+use usecases::portfolio_ledger::PortfolioLedger;
+use usecases::position_merger::PositionMerger;
+use usecases::settlement_scheduler::SettlementScheduler;
+use usecases::state_checkpoint::StateCheckpointer;
+use usecases::strategy_registry::StrategyRegistry;
+use usecases::trading_control::TradingControl;
+use usecases::wallet_manager::WalletManager;
+use usecases::wallet_monitor::WalletMonitor;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // ── 1. Load configuration from config.toml ──────────────
-    let config = config::loader::load_config("config.toml")
+    // ── 1. Resolve layered configuration ────────────────────
+    let cli = Cli::parse();
+    let config_sources = cli.config_sources();
+    let resolved = config_sources
+        .resolve()
         .context("Failed to load configuration")?;
+    if !cli.runs_bot() {
+        commands::init_logging();
+    }
+    let config = &resolved.config;
+    match &cli.command {
+        None | Some(Command::Run { .. }) => run(resolved.config, config_sources).await,
+        Some(Command::Config(ConfigCommand::Print { resolved: full })) => {
+            cli::print_config(&resolved, *full);
+            Ok(())
+        }
+        Some(Command::ValidateConfig) => commands::validate_config(&resolved),
+        Some(Command::CheckContracts) => commands::check_contracts(config).await,
+        Some(Command::Balances) => commands::balances(config).await,
+        Some(Command::Orders(OrdersCommand::List)) => commands::list_orders(config).await,
+        Some(Command::Orders(OrdersCommand::CancelAll)) => {
+            commands::cancel_all_orders(config).await
+        }
+        Some(Command::Settle { dry_run }) => commands::settle(config, *dry_run).await,
+        Some(Command::Pnl(PnlCommand::Report { days })) => {
+            commands::pnl_report(config, *days).await
+        }
+        Some(Command::Pnl(PnlCommand::Fees { date })) => {
+            commands::pnl_fees(config, date.as_deref()).await
+        }
+        Some(Command::Db(DbCommand::Import { from })) => {
+            commands::db_import(config, from.as_deref()).await
+        }
+    }
+}
 
+/// Run the bot until SIGINT.
+#[allow(clippy::too_many_lines, clippy::option_if_let_else)]
+async fn run(config: AppConfig, config_sources: ConfigSources) -> Result<()> {
     // ── 2. Initialize structured JSON logging ───────────────
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -76,9 +151,11 @@ async fn main() -> Result<()> {
     let (health_tx, health_rx) = watch::channel(true);
 
     // ── 4. Connect to Polygon RPC ───────────────────────────
-    let polygon = PolygonProvider::connect(&config.api)
-        .await
-        .context("Failed to connect to Polygon RPC")?;
+    let polygon = Arc::new(
+        PolygonProvider::connect(&config.api)
+            .await
+            .context("Failed to connect to Polygon RPC")?,
+    );
 
     // ── 5. Validate contracts on-chain (checklist) ──────────
     let validator = ContractValidator::new(polygon.inner());
@@ -88,27 +165,56 @@ async fn main() -> Result<()> {
         .context("Contract validation failed")?;
     info!("All contracts validated on-chain");
 
-    // ── 6. Load CLOB auth from env vars ─────────────────────
-    let auth = Arc::new(
-        ClobAuth::from_env().context("Failed to load CLOB credentials from env")?,
-    );
+    // ── 6-7. CLOB HTTP client with auth (secret backend) + retry ──
+    let secrets = SecretStore::from_config(&config).context("Failed to set up secret backend")?;
+    info!(backend = secrets.backend(), "Secret backend ready");
+    let clob_client = commands::clob_client(&secrets, &config).await?;
 
-    // ── 7. Create CLOB HTTP client with auth + retry ────────
-    let clob_config = ClobClientConfig {
-        base_url: config.api.clob_base_url.clone(),
-        timeout: std::time::Duration::from_millis(config.api.timeout_ms),
-        max_concurrent: 10,
-        max_retries: 3,
-        retry_base_delay: std::time::Duration::from_millis(200),
+    // ── 8. Create order executor behind the pre-trade risk gate ──
+    let metrics = Arc::new(
+        MetricsRegistry::new().context("Failed to create metrics registry")?,
+    );
+    let risk_manager = Arc::new(RwLock::new(
+        RiskManager::new(&config.risk).with_markets(&config.markets),
+    ));
+    // Configured fee tiers until the fee reconciler's first CLOB refresh
+    let fee_source = Arc::new(ClobFeeSource::new(Arc::clone(&clob_client)));
+    let fee_schedules = Arc::new(FeeSchedules::new(&config));
+    // The state checkpointer is the only writer of the state snapshot;
+    // other components ask it for a checkpoint through this
+    let checkpoints = Arc::new(Notify::new());
+    let ledger = Arc::new(
+        PortfolioLedger::new(&config, metrics.clone())
+            .with_risk(Arc::clone(&risk_manager))
+            .with_fees(Arc::clone(&fee_schedules))
+            .with_checkpoints(Arc::clone(&checkpoints)),
+    );
+    // Order intents are fsync'd here before they reach the CLOB
+    let journal = Arc::new(
+        FileOrderJournal::new(&config.bot.data_dir)
+            .await
+            .context("Failed to open order journal")?,
+    );
+    // Outside live mode no order may reach the CLOB
+    let live = !config.bot.dry_run && config.bot.mode == BotMode::Live;
+    let inner: Arc<dyn ports::execution::OrderExecution> = if live {
+        Arc::new(
+            ClobOrderExecutor::new(Arc::clone(&clob_client)).with_journal(journal.clone()),
+        )
+    } else {
+        info!(balance = config.bot.paper_balance, "Paper order executor — no orders are sent");
+        Arc::new(PaperOrderExecutor::new(config.bot.paper_balance))
     };
-    let clob_client = Arc::new(
-        ClobClient::new(Arc::clone(&auth), clob_config)
-            .context("Failed to create CLOB client")?,
+    let executor = Arc::new(
+        RiskGate::new(
+            inner,
+            Arc::clone(&risk_manager),
+            metrics.clone(),
+            &config,
+        )
+        .with_ledger(Arc::clone(&ledger)),
     );
 
-    // ── 8. Create order executor (OrderExecution port) ──────
-    let executor = Arc::new(ClobOrderExecutor::new(Arc::clone(&clob_client)));
-
     // ── 9. Create feeds ─────────────────────────────────────
     // Polymarket CLOB WebSocket feed (primary — implements MarketFeed)
     let pm_feed = Arc::new(PolymarketFeed::new(&config.api));
@@ -121,11 +227,11 @@ async fn main() -> Result<()> {
 
     // ── 10. Create repository (Repository port) ─────────────
     let repo = Arc::new(
-        RepositoryImpl::from_data_dir("data")
+        Storage::from_config(&config)
             .await
             .context("Failed to initialize repository")?,
     );
-    info!("Repository initialized in data/");
+    info!(backend = repo.backend(), data_dir = %config.bot.data_dir, "Repository initialized");
 
     // ── 11. Recover state from last run ─────────────────────
     {
@@ -133,6 +239,7 @@ async fn main() -> Result<()> {
         if let Some(state) = repo.load_latest_state().await? {
             info!(
                 version = %state.version,
+                schema_version = state.schema_version,
                 open_orders = state.open_orders.len(),
                 cumulative_pnl = state.cumulative_pnl,
                 "Recovered state from previous run"
@@ -141,9 +248,97 @@ async fn main() -> Result<()> {
             info!("No previous state found — fresh start");
         }
     }
-
-    // ── 12. Spawn health/metrics server on :9090 ────────────
-    let health_handle = tokio::spawn(serve_health(health_rx, config.clone()));
+    let mut risk_scheduler = RiskScheduler::new(
+        Arc::clone(&risk_manager),
+        Arc::clone(&repo),
+        std::time::Duration::from_secs(5),
+    )
+    .with_checkpoints(Arc::clone(&checkpoints));
+    risk_scheduler
+        .restore()
+        .await
+        .context("Failed to restore risk state")?;
+    ledger
+        .restore(repo.as_ref())
+        .await
+        .context("Failed to restore portfolio ledger")?;
+    let checkpointer = Arc::new(
+        StateCheckpointer::new(
+            Arc::clone(&executor),
+            Arc::clone(&risk_manager),
+            Arc::clone(&ledger),
+            Arc::clone(&repo),
+            &config,
+        )
+        .with_journal(journal)
+        .with_requests(checkpoints),
+    );
+    checkpointer
+        .restore()
+        .await
+        .context("Failed to restore tracked orders")?;
+
+    // ── 12. Spawn health/metrics/admin server on :9090 ──────
+    let control = Arc::new(TradingControl::new(
+        Arc::clone(&risk_manager),
+        Arc::clone(&executor),
+    ));
+    let ctf = if live {
+        let ctf = commands::ctf_contracts(&polygon, &secrets, &config).await?;
+        // Orders, splits and conversions revert without these
+        ctf.approvals()
+            .ensure_approvals()
+            .await
+            .context("Failed to set token approvals")?;
+        Some(ctf)
+    } else {
+        None
+    };
+    // Restored orders and positions must match the CLOB and chain
+    // before the engine trades; an unverifiable restart halts too.
+    {
+        use crate::ports::chain_client::ChainClient;
+        let chain = ctf.as_deref().map(|ctf| ctf as &dyn ChainClient);
+        if let Err(e) = checkpointer.verify(chain, now_ms()).await {
+            warn!(error = %e, "Startup recovery check failed");
+            risk_manager.write().await.halt(
+                &format!("Startup recovery check failed: {e}"),
+                crate::ports::repository::HaltSource::Recovery,
+                now_ms(),
+            );
+        }
+    }
+    let wallet_monitor = ctf.as_ref().map(|ctf| {
+        let wallet = WalletManager::new(Arc::clone(ctf))
+            .with_cold_wallet(config.wallet.cold_address.clone());
+        Arc::new(WalletMonitor::new(wallet, Arc::clone(&control), &config.wallet))
+    });
+    let admin = secrets.admin_token().await?.map_or_else(
+        || {
+            warn!("admin_token not set — /admin endpoints disabled");
+            None
+        },
+        |token| {
+            let mut router = admin_router(Arc::clone(&control), token.clone());
+            if let Some(monitor) = &wallet_monitor {
+                router = router.merge(wallet_router(Arc::clone(monitor), token));
+            }
+            Some(router)
+        },
+    );
+    let health_handle = tokio::spawn(serve_health(
+        health_rx,
+        Arc::clone(&metrics),
+        admin,
+        config.clone(),
+    ));
+
+    let kill_switch = KillSwitch::new(
+        Arc::clone(&control),
+        config.bot.kill_switch_path.clone(),
+        std::time::Duration::from_secs(1),
+    );
+    let kill_handle = tokio::spawn(kill_switch.run(shutdown_tx.subscribe()));
 
     // ── 13. Spawn Polymarket CLOB WebSocket feed ────────────
     let pm_shutdown = shutdown_tx.subscribe();
@@ -163,28 +358,96 @@ async fn main() -> Result<()> {
         }
     });
 
-    // ── 15. Spawn config hot-reload watcher (60s) ───────────
+    // ── 15. Spawn config hot-reload watcher (fs events) ─────
     let reload_shutdown = shutdown_tx.subscribe();
-    let (mut config_watcher, _config_rx) =
-        ConfigWatcher::new("config.toml", config.clone());
+    let mut config_watcher = ConfigWatcher::new(config_sources, config.clone());
+    // Components must subscribe before the watcher starts
+    let engine_reload = config_watcher.subscribe("arbitrage_engine");
+    let neg_risk_reload = (ctf.is_some() && config.neg_risk_arb.enabled)
+        .then(|| config_watcher.subscribe("neg_risk_arb"));
     let reload_handle = tokio::spawn(async move {
         if let Err(e) = config_watcher.run(reload_shutdown).await {
             error!(error = %e, "Config watcher failed");
         }
     });
 
-    // ── 16. Spawn ArbitrageEngine (event-driven main loop) ──
-    let engine_shutdown = shutdown_tx.subscribe();
-    let engine_config = config.clone();
-    let engine_feed = Arc::clone(&pm_feed);
-    let engine_executor = Arc::clone(&executor);
+    // ── 16. Spawn risk gate reconciler + risk scheduler ─────
+    let gate_handle = tokio::spawn(Arc::clone(&executor).run_reconciler(
+        std::time::Duration::from_secs(5),
+        shutdown_tx.subscribe(),
+    ));
+    let risk_handle = tokio::spawn(risk_scheduler.run(shutdown_tx.subscribe()));
+    let ledger_handle = tokio::spawn(Arc::clone(&ledger).run(
+        Arc::clone(&pm_feed),
+        Arc::clone(&repo),
+        std::time::Duration::from_secs(config.portfolio.mark_interval_secs),
+        shutdown_tx.subscribe(),
+    ));
+    let fee_reconciler =
+        FeeReconciler::new(fee_source, Arc::clone(&repo), Arc::clone(&fee_schedules), &config);
+    let fee_handle = tokio::spawn(fee_reconciler.run(shutdown_tx.subscribe()));
+    let checkpoint_handle =
+        tokio::spawn(Arc::clone(&checkpointer).run(shutdown_tx.subscribe()));
+
+    let (onchain_handles, engine_chain) = if let Some(ctf) = ctf {
+        let scheduler = SettlementScheduler::new(
+            Arc::clone(&ctf),
+            Arc::clone(&repo),
+            &config,
+            std::time::Duration::from_secs(60),
+        )
+        .with_ledger(Arc::clone(&ledger));
+        let merger = PositionMerger::new(Arc::clone(&ctf), Arc::clone(&repo), &config)
+            .with_ledger(Arc::clone(&ledger));
+        let mut handles = vec![
+            tokio::spawn(scheduler.run(shutdown_tx.subscribe())),
+            tokio::spawn(merger.run(shutdown_tx.subscribe())),
+        ];
+        if let Some(monitor) = wallet_monitor {
+            handles.push(tokio::spawn(monitor.run(shutdown_tx.subscribe())));
+        }
+        if let Some(reload) = neg_risk_reload {
+            let mut arb = NegRiskArbitrage::new(
+                Arc::clone(&pm_feed),
+                Arc::clone(&executor),
+                Arc::clone(&ctf),
+                Arc::clone(&ledger),
+                Arc::clone(&risk_manager),
+                &config,
+            )
+            .with_fees(Arc::clone(&fee_schedules))
+            .with_config_updates(reload);
+            let arb_shutdown = shutdown_tx.subscribe();
+            handles.push(tokio::spawn(async move {
+                if let Err(e) = arb.run(arb_shutdown).await {
+                    error!(error = %e, "Neg-risk arbitrage failed");
+                }
+            }));
+        }
+        let chain: Arc<dyn crate::ports::chain_client::ChainClient> = ctf;
+        (handles, Some(chain))
+    } else {
+        info!("Paper/dry-run mode — settlement, merger and wallet monitor disabled");
+        (Vec::new(), None)
+    };
+
+    // ── 17. Spawn ArbitrageEngine (event-driven main loop) ──
+    let mut engine = ArbitrageEngine::new(
+        Arc::clone(&pm_feed),
+        Arc::clone(&executor),
+        Arc::clone(&risk_manager),
+        config.clone(),
+        shutdown_tx.subscribe(),
+        &StrategyRegistry::default(),
+    )
+    .context("Failed to build market strategies")?
+    .with_config_updates(engine_reload)
+    .with_ledger(Arc::clone(&ledger))
+    .with_fees(Arc::clone(&fee_schedules));
+    if let Some(chain) = engine_chain {
+        engine = engine.with_chain(chain);
+    }
     let engine_handle = tokio::spawn(async move {
-        let mut engine = ArbitrageEngine::new(
-            engine_feed,
-            engine_executor,
-            engine_config,
-            engine_shutdown,
-        );
         if let Err(e) = engine.run().await {
             error!(error = %e, "Arbitrage engine failed");
         }
@@ -192,7 +455,7 @@ async fn main() -> Result<()> {
 
     info!("All tasks spawned — bot is running");
 
-    // ── 17. Wait for SIGINT or SIGTERM ──────────────────────
+    // ── 18. Wait for SIGINT or SIGTERM ──────────────────────
     tokio::select! {
         _ = signal::ctrl_c() => {
             info!("SIGINT received, initiating graceful shutdown");
@@ -209,33 +472,26 @@ async fn main() -> Result<()> {
     let _ = health_tx.send(false);
 
     // 3. Cancel all open orders
-    info!("Cancelling all open orders...");
-    match executor.cancel_all_orders().await {
-        Ok(n) => info!(cancelled = n, "Open orders cancelled"),
-        Err(e) => warn!(error = %e, "Failed to cancel some orders"),
-    }
-
-    // 4. Save final state snapshot
     {
-        use crate::ports::repository::Repository;
-        let final_state = crate::ports::repository::BotStateSnapshot {
-            version: env!("CARGO_PKG_VERSION").to_string(),
-            timestamp_ms: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis() as u64,
-            open_orders: Vec::new(),
-            positions: Vec::new(),
-            cumulative_pnl: 0.0,
-            daily_loss: 0.0,
-        };
-        if let Err(e) = repo.save_state(&final_state).await {
-            warn!(error = %e, "Failed to save final state");
-        } else {
-            info!("Final state snapshot saved");
+        use crate::ports::execution::OrderExecution;
+        info!("Cancelling all open orders...");
+        match executor.cancel_all_orders().await {
+            Ok(n) => info!(cancelled = n, "Open orders cancelled"),
+            Err(e) => warn!(error = %e, "Failed to cancel some orders"),
         }
     }
 
+    // 4. Save final state snapshot (orders left after cancel-all,
+    //    risk counters, ledger)
+    match checkpointer.checkpoint(now_ms()).await {
+        Ok(state) => info!(
+            open_orders = state.open_orders.len(),
+            positions = state.positions.len(),
+            "Final state snapshot saved"
+        ),
+        Err(e) => warn!(error = %e, "Failed to save final state"),
+    }
+
     // 5. Wait for engine (up to 30s)
     info!("Waiting for engine shutdown...");
     let _ = tokio::time::timeout(
@@ -257,6 +513,15 @@ async fn main() -> Result<()> {
     .await;
 
     // 7. Stop auxiliary tasks
+    gate_handle.abort();
+    risk_handle.abort();
+    ledger_handle.abort();
+    fee_handle.abort();
+    checkpoint_handle.abort();
+    for handle in onchain_handles {
+        handle.abort();
+    }
+    kill_handle.abort();
     reload_handle.abort();
     health_handle.abort();
 
@@ -266,16 +531,27 @@ async fn main() -> Result<()> {
 
 /// Serve health and metrics endpoints on :9090.
 ///
-/// - `/live`  — Liveness probe: 200 if process is running
-/// - `/ready` — Readiness probe: 503 during graceful shutdown
+/// - `/live`    — Liveness probe: 200 if process is running
+/// - `/ready`   — Readiness probe: 503 during graceful shutdown
+/// - `/metrics` — Prometheus text exposition
+/// - `/admin/*` — Operator controls (only when `ADMIN_TOKEN` is set)
 async fn serve_health(
     health_rx: watch::Receiver<bool>,
+    metrics: Arc<MetricsRegistry>,
+    admin: Option<axum::Router>,
     _config: config::AppConfig,
 ) -> Result<()> {
     use axum::{extract::State, http::StatusCode, routing::get, Router};
 
     let app = Router::new()
         .route("/live", get(|| async { StatusCode::OK }))
+        .route(
+            "/metrics",
+            get(move || {
+                let metrics = Arc::clone(&metrics);
+                async move { metrics.render() }
+            }),
+        )
         .route(
             "/ready",
             get(
@@ -289,6 +565,10 @@ async fn serve_health(
             ),
         )
         .with_state(health_rx);
+    let app = match admin {
+        Some(admin) => app.merge(admin),
+        None => app,
+    };
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
     info!("Health server listening on :9090");
@@ -32,6 +32,86 @@ pub struct RedemptionResult {
   pub gas_cost_matic: f64,
 }
 
+/// Result of merging complementary YES + NO positions into USDC.
+#[derive(Debug, Clone)]
+pub struct MergeResult {
+  /// Transaction hash.
+  pub tx_hash: String,
+  /// Complete sets merged (whole tokens).
+  pub sets_merged: f64,
+  /// USDC returned (1 per complete set).
+  pub usdc_recovered: f64,
+  /// Gas cost in MATIC.
+  pub gas_cost_matic: f64,
+}
+
+/// Result of splitting USDC into complete YES + NO sets.
+#[derive(Debug, Clone)]
+pub struct SplitResult {
+  /// Transaction hash.
+  pub tx_hash: String,
+  /// Complete sets minted (whole tokens).
+  pub sets_split: f64,
+  /// USDC locked (1 per complete set).
+  pub usdc_spent: f64,
+  /// Gas cost in MATIC.
+  pub gas_cost_matic: f64,
+}
+
+/// Result of converting NO positions through the NegRiskAdapter.
+#[derive(Debug, Clone)]
+pub struct ConvertResult {
+  /// Transaction hash.
+  pub tx_hash: String,
+  /// NO tokens converted per outcome (whole tokens).
+  pub amount: f64,
+  /// YES minted per other outcome: `amount` less the adapter fee.
+  pub minted: f64,
+  /// USDC released: (outcomes converted − 1) × `minted`.
+  pub usdc_recovered: f64,
+  /// Gas cost in MATIC.
+  pub gas_cost_matic: f64,
+}
+
+/// Result of a USDC transfer from the bot's wallet.
+#[derive(Debug, Clone)]
+pub struct TransferResult {
+  /// Transaction hash.
+  pub tx_hash: String,
+  /// USDC transferred.
+  pub amount: f64,
+  /// Gas cost in MATIC.
+  pub gas_cost_matic: f64,
+}
+
+/// Payout vector of a CTF condition.
+///
+/// Mirrors `payoutNumerators` / `payoutDenominator` on the
+/// ConditionalTokens contract. A zero denominator means the oracle
+/// has not reported yet. Binary markets use index 0 = YES, 1 = NO.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConditionPayouts {
+  /// Payout numerator per outcome slot.
+  pub numerators: Vec<u128>,
+  /// Sum of numerators once reported; zero while unresolved.
+  pub denominator: u128,
+}
+
+impl ConditionPayouts {
+  /// Whether the oracle has reported a payout.
+  pub fn is_resolved(&self) -> bool {
+    self.denominator > 0
+  }
+
+  /// Fraction of 1 USDC paid per token of outcome `index`.
+  pub fn fraction(&self, index: usize) -> f64 {
+    if self.denominator == 0 {
+      return 0.0;
+    }
+    self.numerators.get(index).copied().unwrap_or(0) as f64 / self.denominator as f64
+  }
+}
+
 /// Trait for on-chain interactions via alloy-rs.
 ///
 /// Handles CTF contract calls for position management
@@ -41,9 +121,24 @@ pub trait ChainClient: Send + Sync + 'static {
   /// Get the USDC balance of the bot's wallet.
   async fn usdc_balance(&self) -> anyhow::Result<f64>;
 
+  /// Get the USDC balance of another address (e.g. the cold wallet).
+  async fn usdc_balance_of(&self, address: &str) -> anyhow::Result<f64>;
+
+  /// Get the native gas token (MATIC) balance of the bot's wallet.
+  async fn native_balance(&self) -> anyhow::Result<f64>;
+
+  /// Transfer `amount_raw` USDC (atomic units, 6 decimals) from the
+  /// bot's wallet to `to`.
+  async fn transfer_usdc(&self, to: &str, amount_raw: u128) -> anyhow::Result<TransferResult>;
+
   /// Get the CTF token balance for a specific outcome token.
   async fn token_balance(&self, token_id: &TokenId) -> anyhow::Result<TokenBalance>;
 
+  /// Get CTF token balances for several outcome tokens in one call.
+  ///
+  /// Results are in the same order as `token_ids`.
+  async fn token_balances(&self, token_ids: &[TokenId]) -> anyhow::Result<Vec<TokenBalance>>;
+
   /// Batch redeem resolved positions for USDC.
   ///
   /// Automatically detects resolved markets and redeems
@@ -51,9 +146,32 @@ pub trait ChainClient: Send + Sync + 'static {
   /// batching multiple redemptions into a single transaction.
   async fn batch_redeem(&self, token_ids: &[TokenId]) -> anyhow::Result<RedemptionResult>;
 
+  /// Merge `amount_raw` complete YES + NO sets of a condition back
+  /// into USDC (atomic units, 6 decimals).
+  async fn merge_positions(&self, condition_id: &str, amount_raw: u128) -> anyhow::Result<MergeResult>;
+
+  /// Split `amount_raw` USDC (atomic units) into complete YES + NO
+  /// sets of a condition.
+  async fn split_position(&self, condition_id: &str, amount_raw: u128) -> anyhow::Result<SplitResult>;
+
+  /// Convert `amount_raw` NO tokens of each listed outcome of a neg-risk
+  /// event into USDC plus YES of the remaining outcomes
+  /// (NegRiskAdapter `convertPositions`), net of the adapter's fee.
+  async fn convert_positions(
+    &self,
+    neg_risk_market_id: &str,
+    question_indices: &[u32],
+    amount_raw: u128,
+  ) -> anyhow::Result<ConvertResult>;
+
   /// Check if a market's condition has been resolved.
   async fn is_condition_resolved(&self, condition_id: &str) -> anyhow::Result<bool>;
 
+  /// Read the payout vector of a condition.
+  ///
+  /// Returns an empty, zero-denominator vector while unresolved.
+  async fn condition_payouts(&self, condition_id: &str) -> anyhow::Result<ConditionPayouts>;
+
   /// Get the current gas price on Polygon.
   async fn gas_price_gwei(&self) -> anyhow::Result<f64>;
 
@@ -116,4 +116,10 @@ pub trait OrderExecution: Send + Sync + 'static {
   ///
   /// Returns (remaining_requests, reset_time_ms).
   async fn rate_limit_status(&self) -> (u32, u64);
+
+  /// Credit tokens minted (`Buy`) or burned (`Sell`) on-chain, outside
+  /// the CLOB, at `price` per token.
+  ///
+  /// Only executors that track positions (the `RiskGate`) use this.
+  async fn record_transfer(&self, _token_id: &TokenId, _side: TradeSide, _price: f64, _size: f64) {}
 }
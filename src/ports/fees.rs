@@ -0,0 +1,44 @@
+//! Fee Source Port - Fee Rates and Trade History
+//!
+//! The CLOB is the authority on what a market charges and what was
+//! actually charged:
+//! - `fee_rate_bps`: the taker `base_fee` currently applied to a token
+//! - `trades`: the account's matched fills, with the rate each one paid
+//!
+//! `FeeSchedules` refreshes from the first, `FeeReconciler` checks the
+//! second against what the bot expected to pay.
+
+use async_trait::async_trait;
+
+use crate::domain::trade::{TokenId, TradeSide};
+
+/// One of the account's fills as reported by the CLOB.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeFill {
+  /// CLOB trade ID.
+  pub trade_id: String,
+  /// Outcome token traded.
+  pub token_id: TokenId,
+  /// Our side of the trade.
+  pub side: TradeSide,
+  /// Execution price.
+  pub price: f64,
+  /// Filled size in tokens.
+  pub size: f64,
+  /// Whether our order was resting (maker) or crossing (taker).
+  pub is_maker: bool,
+  /// Taker rate the match was charged at, in basis points.
+  pub fee_rate_bps: u32,
+  /// Match time (Unix ms).
+  pub timestamp_ms: u64,
+}
+
+/// Trait for fee rate and trade history providers.
+#[async_trait]
+pub trait FeeSource: Send + Sync + 'static {
+  /// Current taker fee rate of a token, in basis points.
+  async fn fee_rate_bps(&self, token_id: &str) -> anyhow::Result<u32>;
+
+  /// The account's fills matched in `[from_ms, to_ms)`.
+  async fn trades(&self, from_ms: u64, to_ms: u64) -> anyhow::Result<Vec<TradeFill>>;
+}
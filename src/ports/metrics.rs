@@ -0,0 +1,24 @@
+//! Metrics Port - Trading Observability Interface
+//!
+//! Defines the trait the usecases layer uses to report trading
+//! events (rejections, PnL, breaker state) without depending on
+//! Prometheus. `MetricsRegistry` is the production implementation.
+
+/// Sink for trading metrics emitted by use cases.
+pub trait MetricsSink: Send + Sync + 'static {
+  /// Count an order rejected before reaching, or by, the CLOB.
+  fn order_rejected(&self, asset: &str, reason: &str);
+
+  /// Publish cumulative realized and current unrealized PnL of an asset.
+  fn pnl_updated(&self, asset: &str, realized: f64, unrealized: f64);
+}
+
+/// Metrics sink that discards everything (tests, CLI tools).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetrics;
+
+impl MetricsSink for NoopMetrics {
+  fn order_rejected(&self, _asset: &str, _reason: &str) {}
+
+  fn pnl_updated(&self, _asset: &str, _realized: f64, _unrealized: f64) {}
+}
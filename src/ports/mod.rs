@@ -7,11 +7,21 @@
 //! - `MarketFeed`: Real-time market data streaming
 //! - `OrderExecution`: Order placement and management via CLOB
 //! - `ChainClient`: On-chain CTF operations (batch redeem)
-//! - `Repository`: State persistence (JSONL-based)
+//! - `FeeSource`: CLOB fee rates and account trade history
+//! - `Repository`: State persistence (JSONL or SQLite)
+//! - `OrderJournal`: Write-ahead log of order intents
+//! - `MetricsSink`: Trading observability (Prometheus-agnostic)
 //! - `OrderExecutor`: High-level quoting orchestration
+//! - `Strategy`: Pluggable per-market signal generation
+//! - `SecretProvider`: Credential lookup (env, file, Vault)
 
 pub mod chain_client;
 pub mod execution;
+pub mod fees;
 pub mod market_feed;
+pub mod metrics;
+pub mod order_journal;
 pub mod order_executor;
 pub mod repository;
+pub mod secrets;
+pub mod strategy;
@@ -33,7 +33,7 @@ pub struct Quote {
 impl Quote {
   /// Calculate the mid-price of this quote.
   pub fn mid_price(&self) -> f64 {
-    (self.bid_price + self.ask_price) / 2.0
+    f64::midpoint(self.bid_price, self.ask_price)
   }
 
   /// Check if the quote has a positive spread.
@@ -0,0 +1,64 @@
+//! Order Journal Port - Write-Ahead Log of Order Intents
+//!
+//! Every order is journaled under its client-generated salt before it
+//! is sent, and its outcome once the CLOB answers. An intent without an
+//! outcome means the process died mid-send: the order may be resting
+//! on the CLOB without us knowing its ID, but under the same salt.
+//!
+//! On startup `StateCheckpointer` replays the journal against the CLOB
+//! open orders: journaled orders are adopted, unknown ones cancelled.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::trade::{Order, OrderId};
+
+/// What the CLOB answered to a journaled intent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum IntentOutcome {
+  /// Accepted; resting (or filled) under this CLOB order ID.
+  Placed { order_id: OrderId },
+  /// Rejected by the CLOB, or not found after a restart.
+  Rejected { reason: String },
+}
+
+/// A journaled intent and its outcome, if recorded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournaledOrder {
+  /// Client-generated order salt, unique per send.
+  pub client_id: String,
+  /// The order as sent.
+  pub order: Order,
+  /// When the intent was journaled (Unix ms).
+  pub timestamp_ms: u64,
+  /// Outcome; `None` while unresolved.
+  pub outcome: Option<IntentOutcome>,
+}
+
+impl JournaledOrder {
+  /// CLOB order ID, once placed.
+  pub fn order_id(&self) -> Option<&str> {
+    match &self.outcome {
+      Some(IntentOutcome::Placed { order_id }) => Some(order_id),
+      _ => None,
+    }
+  }
+}
+
+/// Trait for durable order intent journals.
+#[async_trait]
+pub trait OrderJournal: Send + Sync + 'static {
+  /// Record an intent; must be durable (fsync'd) before returning.
+  async fn record_intent(&self, client_id: &str, order: &Order) -> anyhow::Result<()>;
+
+  /// Record the outcome of an intent.
+  async fn record_outcome(&self, client_id: &str, outcome: IntentOutcome) -> anyhow::Result<()>;
+
+  /// All intents still in the journal, oldest first.
+  async fn entries(&self) -> anyhow::Result<Vec<JournaledOrder>>;
+
+  /// Drop intents resolved before `before_ms`; returns how many.
+  /// Unresolved intents are always kept.
+  async fn compact(&self, before_ms: u64) -> anyhow::Result<usize>;
+}
@@ -1,12 +1,13 @@
 //! Repository Port - State Persistence Interface
 //!
-//! Defines traits for persisting bot state using JSONL files.
-//! No database dependency - lightweight append-only log format
-//! optimized for audit trails and crash recovery.
+//! Defines traits for persisting bot state. Implemented over JSONL
+//! files (append-only, the default) and an embedded SQLite database
+//! with indexed tables; `[storage]` selects one.
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::portfolio::Portfolio;
 use crate::domain::trade::{MarketId, Order, OrderId};
 
 /// A single trade record for persistence and auditing.
@@ -51,30 +52,229 @@ pub struct DailyPnl {
   pub volume: f64,
   /// Maximum drawdown during the day.
   pub max_drawdown: f64,
+  /// Fees booked during the day.
+  #[serde(default)]
+  pub fees: f64,
+  /// Maker rebates accrued during the day.
+  #[serde(default)]
+  pub rebates: f64,
+}
+
+/// One position's outcome in a persisted settlement sweep.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementEntry {
+  /// Market condition ID.
+  pub market_id: MarketId,
+  /// Resolution outcome (e.g. "ResolvedYes", "Pending").
+  pub resolution: String,
+  /// Payout numerators reported on-chain (empty while pending).
+  pub payouts: Vec<u128>,
+  /// USDC recovered.
+  pub usdc_recovered: f64,
+  /// Realized PnL, when the token's outcome is known.
+  pub realized_pnl: Option<f64>,
+  /// Redemption transaction hash(es).
+  pub tx_hash: Option<String>,
+  /// Whether settlement succeeded.
+  pub success: bool,
+  /// Error message if settlement failed.
+  pub error: Option<String>,
+}
+
+/// A persisted settlement sweep.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementRecord {
+  /// When the sweep ran (Unix ms).
+  pub timestamp_ms: u64,
+  /// Per-position outcomes.
+  pub entries: Vec<SettlementEntry>,
+  /// Total USDC recovered.
+  pub total_usdc_recovered: f64,
+  /// Realized PnL booked by this sweep.
+  pub realized_pnl: f64,
+  /// Markets settled successfully.
+  pub markets_settled: usize,
+  /// Markets that failed settlement.
+  pub markets_failed: usize,
+}
+
+/// Expected vs charged fees of one market over a day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketFeeReconciliation {
+  /// Market condition ID (the token ID for unregistered tokens).
+  pub market_id: MarketId,
+  /// Fills in the trade history.
+  pub trades: u64,
+  /// Taker fees the market's schedule predicted.
+  pub expected_fees: f64,
+  /// Taker fees at the rates the CLOB actually charged.
+  pub actual_fees: f64,
+  /// Maker rebates accrued on the market's maker fills.
+  pub rebates: f64,
+  /// Fills charged at a rate other than the schedule's.
+  pub rate_mismatches: u64,
+  /// Whether the fee gap exceeds `fees.reconcile_tolerance`.
+  pub flagged: bool,
+}
+
+/// A persisted daily fee reconciliation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeReconciliation {
+  /// UTC day reconciled (YYYY-MM-DD).
+  pub date: String,
+  /// When the reconciliation ran (Unix ms).
+  pub timestamp_ms: u64,
+  /// Per-market results, sorted by market ID.
+  pub markets: Vec<MarketFeeReconciliation>,
+  /// Total expected taker fees.
+  pub expected_fees: f64,
+  /// Total fees actually charged.
+  pub actual_fees: f64,
+  /// Total maker rebates accrued.
+  pub rebates: f64,
+  /// Fees the portfolio ledger booked for the day, if it closed it.
+  pub booked_fees: Option<f64>,
+  /// Rebates the portfolio ledger booked for the day, if it closed it.
+  pub booked_rebates: Option<f64>,
+}
+
+impl FeeReconciliation {
+  /// Charged minus expected fees.
+  pub fn fee_gap(&self) -> f64 {
+    self.actual_fees - self.expected_fees
+  }
+
+  /// Markets whose gap exceeded the tolerance.
+  pub fn flagged(&self) -> impl Iterator<Item = &MarketFeeReconciliation> {
+    self.markets.iter().filter(|m| m.flagged)
+  }
+}
+
+/// Circuit breaker state machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BreakerState {
+  /// Normal trading.
+  #[default]
+  Closed,
+  /// Tripped; no trading until the cooldown elapses.
+  Open,
+  /// Cooldown elapsed; reduced-size trial trading. One loss
+  /// re-opens the breaker, one win closes it.
+  HalfOpen,
+}
+
+/// Who or what halted trading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HaltSource {
+  /// Admin HTTP endpoint.
+  Admin,
+  /// Kill switch file on disk.
+  KillSwitch,
+  /// Wallet monitor: gas balance below `wallet.min_matic_balance`.
+  LowGas,
+  /// Startup recovery: restored state disagrees with the CLOB or chain.
+  Recovery,
+  /// Engine: a failed multi-leg batch could not be flattened.
+  Unwind,
+}
+
+/// An operator-initiated trading halt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HaltRecord {
+  /// Free-form reason given by the operator.
+  pub reason: String,
+  /// Where the halt came from.
+  pub source: HaltSource,
+  /// When trading was halted (Unix ms).
+  pub halted_at_ms: u64,
+}
+
+/// Persisted risk counters so restarts cannot reset them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskStateSnapshot {
+  /// UTC trading day the counters belong to (YYYY-MM-DD).
+  pub day: String,
+  /// Realized loss so far on `day`.
+  pub daily_loss: f64,
+  /// Consecutive losing trades.
+  pub consecutive_losses: u32,
+  /// Circuit breaker state.
+  pub breaker: BreakerState,
+  /// When the breaker last tripped (Unix ms).
+  pub tripped_at_ms: Option<u64>,
+  /// Active trading halt; survives restarts until resumed.
+  #[serde(default)]
+  pub halt: Option<HaltRecord>,
+}
+
+/// Schema of `BotStateSnapshot` written by this build.
+///
+/// 1: pre-0.6 (orders, positions, PnL); 2: + `risk`, `portfolio`;
+/// 3: + `schema_version`, open orders carry their credited fills.
+pub const STATE_SCHEMA_VERSION: u32 = 3;
+
+/// A tracked resting order and the size already credited from it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenOrderState {
+  /// The order as submitted (CLOB ID, original size).
+  pub order: Order,
+  /// Filled size already booked as a position.
+  pub filled: f64,
 }
 
 /// Bot state snapshot for crash recovery.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BotStateSnapshot {
-  /// Version of the state format.
+  /// Schema of the snapshot; older ones are migrated on load.
+  #[serde(default)]
+  pub schema_version: u32,
+  /// Bot version that wrote the snapshot.
   pub version: String,
   /// Timestamp of snapshot (Unix ms).
   pub timestamp_ms: u64,
-  /// Currently open orders.
-  pub open_orders: Vec<Order>,
+  /// Orders resting on the CLOB when the snapshot was taken.
+  pub open_orders: Vec<OpenOrderState>,
   /// Current position sizes per token.
   pub positions: Vec<(String, f64)>,
   /// Cumulative P&L.
   pub cumulative_pnl: f64,
   /// Daily loss so far.
   pub daily_loss: f64,
+  /// Risk manager counters (absent in pre-0.6 snapshots).
+  #[serde(default)]
+  pub risk: Option<RiskStateSnapshot>,
+  /// Portfolio ledger lots and PnL (absent in pre-0.6 snapshots).
+  #[serde(default)]
+  pub portfolio: Option<Portfolio>,
+}
+
+impl BotStateSnapshot {
+  /// Empty snapshot of this build (current schema).
+  pub fn new() -> Self {
+    Self {
+      schema_version: STATE_SCHEMA_VERSION,
+      version: env!("CARGO_PKG_VERSION").to_string(),
+      timestamp_ms: 0,
+      open_orders: Vec::new(),
+      positions: Vec::new(),
+      cumulative_pnl: 0.0,
+      daily_loss: 0.0,
+      risk: None,
+      portfolio: None,
+    }
+  }
+}
+
+impl Default for BotStateSnapshot {
+  fn default() -> Self {
+    Self::new()
+  }
 }
 
 /// Trait for state persistence providers.
 ///
-/// Uses JSONL (JSON Lines) format for append-only logging.
-/// Each line is a self-contained JSON record, making it easy
-/// to parse, stream, and recover from partial writes.
+/// Records are append-only: trades and settlement reports are never
+/// rewritten, and the latest state snapshot replaces the previous one.
 #[async_trait]
 pub trait Repository: Send + Sync + 'static {
   /// Append a trade record to the trade log.
@@ -102,6 +302,18 @@ pub trait Repository: Send + Sync + 'static {
   /// Load daily P&L history.
   async fn load_daily_pnl(&self) -> anyhow::Result<Vec<DailyPnl>>;
 
+  /// Append a settlement sweep report.
+  async fn save_settlement_report(&self, record: &SettlementRecord) -> anyhow::Result<()>;
+
+  /// Load all settlement sweep reports.
+  async fn load_settlement_reports(&self) -> anyhow::Result<Vec<SettlementRecord>>;
+
+  /// Append a daily fee reconciliation.
+  async fn save_fee_reconciliation(&self, record: &FeeReconciliation) -> anyhow::Result<()>;
+
+  /// Load all daily fee reconciliations.
+  async fn load_fee_reconciliations(&self) -> anyhow::Result<Vec<FeeReconciliation>>;
+
   /// Check if the repository is healthy (disk space, permissions).
   async fn is_healthy(&self) -> bool;
 }
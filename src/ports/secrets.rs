@@ -0,0 +1,107 @@
+//! Secrets Port - Credential Lookup Interface
+//!
+//! Credentials are looked up by name through a `SecretProvider`
+//! (environment, local file, Vault) and handed around as `Secret`:
+//! - the buffer is zeroized when the value is dropped
+//! - `Debug` / `Display` print `<redacted>`, so a secret in a struct
+//!   or a tracing field never reaches the logs
+//! - the plaintext is only reachable through `expose()`
+
+use std::fmt;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use zeroize::Zeroize;
+
+/// CLOB API key.
+pub const POLY_API_KEY: &str = "poly_api_key";
+/// CLOB API secret (HMAC key).
+pub const POLY_API_SECRET: &str = "poly_api_secret";
+/// CLOB passphrase.
+pub const POLY_PASSPHRASE: &str = "poly_passphrase";
+/// Wallet signing key, hex (when no keystore is configured).
+pub const PRIVATE_KEY: &str = "private_key";
+/// Passphrase of the encrypted wallet keystore.
+pub const KEYSTORE_PASSWORD: &str = "keystore_password";
+/// Bearer token for `/admin`.
+pub const ADMIN_TOKEN: &str = "admin_token";
+
+/// A credential: zeroized on drop, redacted when formatted.
+#[derive(Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+  /// Wrap a plaintext value (takes ownership, no copy).
+  pub fn new(value: String) -> Self {
+    Self(value)
+  }
+
+  /// The plaintext; keep the borrow short and never log it.
+  pub fn expose(&self) -> &str {
+    &self.0
+  }
+
+  /// Whether the value is empty.
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+impl From<String> for Secret {
+  fn from(value: String) -> Self {
+    Self::new(value)
+  }
+}
+
+impl Drop for Secret {
+  fn drop(&mut self) {
+    self.0.zeroize();
+  }
+}
+
+impl fmt::Debug for Secret {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("<redacted>")
+  }
+}
+
+impl fmt::Display for Secret {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("<redacted>")
+  }
+}
+
+/// Source of credentials by name (see the key constants above).
+#[async_trait]
+pub trait SecretProvider: Send + Sync {
+  /// Backend name for logs (`env`, `file`, `vault`).
+  fn name(&self) -> &'static str;
+
+  /// Look up `key`; `Ok(None)` when the backend has no such secret.
+  async fn get(&self, key: &str) -> Result<Option<Secret>>;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_secret_is_redacted() {
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Holder {
+      token: Option<Secret>,
+    }
+
+    let secret = Secret::new("hunter2".to_string());
+    assert_eq!(format!("{secret:?} {secret}"), "<redacted> <redacted>");
+    assert_eq!(secret.expose(), "hunter2");
+
+    let holder = Holder {
+      token: Some(secret),
+    };
+    assert!(!format!("{holder:?}").contains("hunter2"));
+  }
+}
@@ -0,0 +1,247 @@
+//! Strategy Port - Pluggable Signal Generation
+//!
+//! A `Strategy` turns market events for one configured market into
+//! intents. The `ArbitrageEngine` owns the pipeline that acts on them
+//! (risk check → Kelly sizing → `OrderManager` → `RiskGate` →
+//! execution), so every strategy shares the same risk and order
+//! management. Strategies never call adapters directly.
+//!
+//! Strategies are selected per market by name (`strategies = [...]` in
+//! `[[markets]]`) and built by the `StrategyRegistry`.
+
+use crate::config::AppConfig;
+use crate::domain::fees::FeeSchedule;
+use crate::domain::trade::{MarketId, Order, OrderType, TokenId, TradeSide};
+use crate::ports::market_feed::PriceUpdate;
+
+/// Outcome token decimals (atomic units per whole token).
+pub const TOKEN_SCALE: f64 = 1e6;
+
+/// Most marketable sell limit (lowest CLOB tick).
+pub const FLATTEN_SELL_PRICE: f64 = 0.01;
+
+/// Most marketable buy limit (highest CLOB tick).
+const FLATTEN_BUY_PRICE: f64 = 0.99;
+
+/// Token deltas below this are treated as flat.
+const DUST: f64 = 1e-6;
+
+/// The market a strategy instance is bound to.
+#[derive(Debug, Clone)]
+pub struct StrategyMarket {
+  /// Condition ID.
+  pub condition_id: MarketId,
+  /// YES outcome token.
+  pub yes_token_id: TokenId,
+  /// NO outcome token.
+  pub no_token_id: TokenId,
+  /// Fee schedule of the market, kept current by the engine.
+  pub fees: FeeSchedule,
+}
+
+/// Directional view on a token; the engine sizes it with Kelly and
+/// quotes it as a maker order at `fair_value`.
+#[derive(Debug, Clone)]
+pub struct Signal {
+  /// Token to quote.
+  pub token_id: TokenId,
+  /// Model fair value of the token.
+  pub fair_value: f64,
+  /// Edge after fees (positive = buy, negative = sell).
+  pub edge: f64,
+  /// Price the edge was measured against (best ask, else fair value).
+  pub market_price: f64,
+  /// Standard error of the probability estimate (0 = no shrinkage).
+  pub prob_std_error: f64,
+}
+
+/// Something a strategy wants done.
+#[derive(Debug, Clone)]
+pub enum StrategyIntent {
+  /// Kelly-sized maker quote.
+  Signal(Signal),
+  /// Explicitly sized order, sent as-is (e.g. a FOK arbitrage leg).
+  Order(Order),
+  /// Cancel resting orders on a token.
+  CancelToken(TokenId),
+  /// Split USDC into complete YES + NO sets (atomic units).
+  Split {
+    /// Condition ID.
+    condition_id: MarketId,
+    /// Amount in atomic units (6 decimals).
+    amount_raw: u128,
+  },
+  /// Merge complete YES + NO sets back into USDC (atomic units).
+  Merge {
+    /// Condition ID.
+    condition_id: MarketId,
+    /// Amount in atomic units (6 decimals).
+    amount_raw: u128,
+  },
+}
+
+impl StrategyIntent {
+  /// Whether the intent needs an on-chain transaction.
+  pub fn is_onchain(&self) -> bool {
+    matches!(self, Self::Split { .. } | Self::Merge { .. })
+  }
+
+  /// Intents that flatten what the `executed` part of a failed batch
+  /// left in `market`.
+  ///
+  /// Nets the token deltas of the filled (FOK) orders, splits and
+  /// merges: complete sets are merged back into USDC, any other long
+  /// is FOK-sold and any short FOK-bought at the most marketable price.
+  /// Resting maker orders are left alone.
+  pub fn unwind(market: &StrategyMarket, executed: &[Self]) -> Vec<Self> {
+    let mut yes = 0.0;
+    let mut no = 0.0;
+    let mut others: Vec<(TokenId, f64)> = Vec::new();
+    for intent in executed {
+      let (token_id, delta) = match intent {
+        Self::Order(order) if order.order_type == OrderType::Fok => {
+          let delta = match order.side {
+            TradeSide::Buy => order.size,
+            TradeSide::Sell => -order.size,
+          };
+          (&order.token_id, delta)
+        }
+        Self::Split { condition_id, amount_raw } if *condition_id == market.condition_id => {
+          let sets = *amount_raw as f64 / TOKEN_SCALE;
+          yes += sets;
+          no += sets;
+          continue;
+        }
+        Self::Merge { condition_id, amount_raw } if *condition_id == market.condition_id => {
+          let sets = *amount_raw as f64 / TOKEN_SCALE;
+          yes -= sets;
+          no -= sets;
+          continue;
+        }
+        _ => continue,
+      };
+      if *token_id == market.yes_token_id {
+        yes += delta;
+      } else if *token_id == market.no_token_id {
+        no += delta;
+      } else if let Some(entry) = others.iter_mut().find(|(t, _)| t == token_id) {
+        entry.1 += delta;
+      } else {
+        others.push((token_id.clone(), delta));
+      }
+    }
+
+    let mut unwinds = Vec::new();
+    let sets = yes.min(no);
+    if sets > DUST {
+      let amount_raw = (sets * TOKEN_SCALE).floor() as u128;
+      unwinds.push(Self::Merge {
+        condition_id: market.condition_id.clone(),
+        amount_raw,
+      });
+      let merged = amount_raw as f64 / TOKEN_SCALE;
+      yes -= merged;
+      no -= merged;
+    }
+    let residuals = [(market.yes_token_id.clone(), yes), (market.no_token_id.clone(), no)];
+    for (token_id, delta) in residuals.into_iter().chain(others) {
+      if delta > DUST {
+        unwinds.push(Self::Order(Order::new_taker(token_id, TradeSide::Sell, FLATTEN_SELL_PRICE, delta)));
+      } else if delta < -DUST {
+        unwinds.push(Self::Order(Order::new_taker(token_id, TradeSide::Buy, FLATTEN_BUY_PRICE, -delta)));
+      }
+    }
+    unwinds
+  }
+}
+
+/// A trading strategy bound to one market.
+///
+/// `on_update` is synchronous and must not block: all I/O happens in
+/// the engine when it executes the returned intents. Intents of one
+/// call run in order, and the rest of the batch is dropped as soon as
+/// one fails, so multi-leg trades can rely on earlier legs. The legs
+/// that did execute are then flattened (`StrategyIntent::unwind`).
+pub trait Strategy: Send + 'static {
+  /// Registry name (e.g. `lmsr_mm`).
+  fn name(&self) -> &'static str;
+
+  /// Tokens this strategy needs updates for (default: YES only).
+  fn tokens(&self, market: &StrategyMarket) -> Vec<TokenId> {
+    vec![market.yes_token_id.clone()]
+  }
+
+  /// React to a book update for one of `tokens`.
+  fn on_update(&mut self, market: &StrategyMarket, update: &PriceUpdate) -> Vec<StrategyIntent>;
+
+  /// Apply a hot-reloaded config, keeping accumulated state (default:
+  /// ignore). Only called while the market itself is unchanged.
+  fn reconfigure(&mut self, _config: &AppConfig) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn market() -> StrategyMarket {
+    StrategyMarket {
+      condition_id: "0xcond".to_string(),
+      yes_token_id: "yes".to_string(),
+      no_token_id: "no".to_string(),
+      fees: FeeSchedule::new(crate::domain::fees::FeeClass::Standard, 0.0),
+    }
+  }
+
+  fn order(intent: &StrategyIntent) -> (&str, TradeSide, f64, f64) {
+    match intent {
+      StrategyIntent::Order(o) => {
+        assert_eq!(o.order_type, OrderType::Fok);
+        (o.token_id.as_str(), o.side, o.price, o.size)
+      }
+      other => panic!("expected an order, got {other:?}"),
+    }
+  }
+
+  #[test]
+  fn test_unwind_sells_single_filled_buy_leg() {
+    let executed = [StrategyIntent::Order(Order::new_taker("yes".to_string(), TradeSide::Buy, 0.45, 10.0))];
+    let unwinds = StrategyIntent::unwind(&market(), &executed);
+    assert_eq!(unwinds.len(), 1);
+    assert_eq!(order(&unwinds[0]), ("yes", TradeSide::Sell, FLATTEN_SELL_PRICE, 10.0));
+  }
+
+  #[test]
+  fn test_unwind_sells_unsold_half_of_split() {
+    let executed = [
+      StrategyIntent::Split { condition_id: "0xcond".to_string(), amount_raw: 10_000_000 },
+      StrategyIntent::Order(Order::new_taker("yes".to_string(), TradeSide::Sell, 0.55, 10.0)),
+    ];
+    let unwinds = StrategyIntent::unwind(&market(), &executed);
+    assert_eq!(unwinds.len(), 1);
+    assert_eq!(order(&unwinds[0]), ("no", TradeSide::Sell, FLATTEN_SELL_PRICE, 10.0));
+  }
+
+  #[test]
+  fn test_unwind_merges_split_sets() {
+    let executed = [StrategyIntent::Split { condition_id: "0xcond".to_string(), amount_raw: 10_000_000 }];
+    let unwinds = StrategyIntent::unwind(&market(), &executed);
+    assert_eq!(unwinds.len(), 1);
+    match &unwinds[0] {
+      StrategyIntent::Merge { condition_id, amount_raw } => {
+        assert_eq!(condition_id, "0xcond");
+        assert_eq!(*amount_raw, 10_000_000);
+      }
+      other => panic!("expected a merge, got {other:?}"),
+    }
+  }
+
+  #[test]
+  fn test_unwind_completed_batch_is_flat() {
+    let executed = [
+      StrategyIntent::Order(Order::new_taker("yes".to_string(), TradeSide::Buy, 0.45, 10.0)),
+      StrategyIntent::Order(Order::new_taker("no".to_string(), TradeSide::Buy, 0.5, 10.0)),
+      StrategyIntent::Merge { condition_id: "0xcond".to_string(), amount_raw: 10_000_000 },
+    ];
+    assert!(StrategyIntent::unwind(&market(), &executed).is_empty());
+  }
+}
@@ -1,36 +1,65 @@
-//! Arbitrage Engine — Core Pricing and Quoting Loop
+//! Arbitrage Engine — Strategy Runner and Shared Order Pipeline
 //!
-//! The main market-making use case that:
+//! The main trading use case that:
 //! 1. Receives price updates via `MarketFeed` broadcast channels
-//! 2. Computes LMSR fair values
-//! 3. Detects edge after fees (maker = 0%)
-//! 4. Sizes positions via quarter-Kelly
-//! 5. Places maker-only orders via `OrderExecution` port
+//! 2. Routes each update to the strategies configured for its market
+//!    (built by the `StrategyRegistry`, default `lmsr_mm`), with the
+//!    market's current `FeeSchedules` entry
+//! 3. Executes the returned intents through one shared pipeline:
+//!    - `Signal`: risk check → quarter-Kelly sizing, scaled down by
+//!      drawdown and by the estimator's uncertainty; when several
+//!      markets signal within the debounce window, sized jointly with
+//!      `PortfolioKelly` → maker order
+//!    - `Order`: risk check → `OrderManager` as-is
+//!    - `Split` / `Merge`: CTF transaction via the `ChainClient` port;
+//!      the sets minted or burned are credited to the gate's positions
+//!      and booked in the `PortfolioLedger`
+//!
+//!    A failing intent drops the rest of its batch; the legs already
+//!    executed are flattened (`StrategyIntent::unwind`), and trading is
+//!    halted if that fails too
+//! 4. Every order goes through the `OrderExecution` port, i.e. the
+//!    `RiskGate`, whichever strategy produced it
+//! 5. Applies hot-reloaded `[lmsr]`, `[risk]`, `[rate_limits]`,
+//!    `[strategy]`, `[complete_set]` and `[[markets]]` changes from
+//!    the `ConfigWatcher`, (un)subscribing markets as they are
+//!    (de)activated, and acks each reload so a rejected one is rolled
+//!    back everywhere
 //!
 //! Architecture: event-driven via `tokio::select!` over broadcast
 //! receivers. NEVER polls on interval, NEVER uses `try_recv()`.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::task::Poll;
-use std::time::Instant;
-
-use anyhow::Result;
-use tokio::sync::broadcast;
-use tracing::{debug, info, instrument, warn};
-
-use crate::config::AppConfig;
-use crate::domain::bayesian::BayesianEstimator;
-use crate::domain::fees::FeeCalculator;
-use crate::domain::kelly::KellySizer;
-use crate::domain::lmsr::LmsrPricer;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::config::hot_reload::{ConfigUpdate, ReloadSubscriber};
+use crate::config::{AppConfig, MarketConfig};
+use crate::domain::kelly::{
+    DrawdownScaler, DrawdownTracker, KellySizer, SizingContext,
+};
+use crate::domain::portfolio_kelly::{Opportunity, PortfolioKelly};
+use crate::domain::time::{now_ms, utc_day};
+use crate::domain::trade::{Asset, TokenId, TradeSide};
+use crate::ports::chain_client::ChainClient;
 use crate::ports::execution::OrderExecution;
 use crate::ports::market_feed::{MarketFeed, PriceUpdate};
+use crate::ports::repository::HaltSource;
+use crate::ports::strategy::{Signal, StrategyIntent, StrategyMarket};
 
+use super::fee_schedule::FeeSchedules;
 use super::order_manager::OrderManager;
+use super::portfolio_ledger::PortfolioLedger;
 use super::risk_manager::RiskManager;
+use super::strategy_registry::{MarketStrategies, StrategyRegistry};
 
-/// Internal event type for the engine select loop.
-enum FeedEvent {
+/// Event type for the feed select loops (shared with `NegRiskArbitrage`).
+pub(super) enum FeedEvent {
     /// A price update from any subscribed market.
     Update(PriceUpdate),
     /// Shutdown signal received.
@@ -39,24 +68,55 @@ enum FeedEvent {
     Lagged(u64),
 }
 
-/// Arbitrage engine orchestrating the full market-making loop.
+/// A recent signal kept for joint portfolio sizing.
+#[derive(Debug, Clone, Copy)]
+struct LiveSignal {
+    /// The bet as sized (win probability, contract price).
+    opportunity: Opportunity,
+    /// Whether the bet is long YES (false = long NO side).
+    long_yes: bool,
+    /// Underlying asset, if the token is configured.
+    asset: Option<Asset>,
+    /// When the signal was last seen.
+    seen_at: Instant,
+}
+
+/// Arbitrage engine running every market's strategies.
 pub struct ArbitrageEngine<F: MarketFeed, E: OrderExecution> {
     /// Market data feed (port).
     feed: Arc<F>,
     /// Order execution adapter (port).
     execution: Arc<E>,
-    /// LMSR pricing model.
-    pricer: LmsrPricer,
+    /// Chain client for split / merge intents (live mode only).
+    chain: Option<Arc<dyn ChainClient>>,
+    /// Ledger booking splits and merges, if attached.
+    ledger: Option<Arc<PortfolioLedger>>,
+    /// Shared fee schedules (configured ones until attached).
+    fees: Option<Arc<FeeSchedules>>,
     /// Kelly position sizer.
     sizer: KellySizer,
-    /// Fee calculator (maker = 0%).
-    fees: FeeCalculator,
-    /// Bayesian probability estimator.
-    estimator: BayesianEstimator,
+    /// Kelly multiplier as a function of drawdown.
+    drawdown_scaler: DrawdownScaler,
+    /// Equity peaks for intraday / peak-to-trough drawdown.
+    drawdown: DrawdownTracker,
+    /// Joint allocator for concurrent signals.
+    portfolio: PortfolioKelly,
+    /// Signals seen within the debounce window, by token.
+    signals: HashMap<TokenId, LiveSignal>,
+    /// Token ID → asset (for correlation lookup).
+    token_assets: HashMap<TokenId, Asset>,
+    /// Strategies of every active market.
+    slots: Vec<MarketStrategies>,
+    /// Token ID → (slot, strategy) pairs that receive its updates.
+    routes: HashMap<TokenId, Vec<(usize, usize)>>,
+    /// Factories for markets activated by a reload.
+    registry: StrategyRegistry,
+    /// Reloaded configs from the `ConfigWatcher`, if attached.
+    config_updates: Option<ReloadSubscriber>,
     /// Order manager for lifecycle.
     order_manager: OrderManager<E>,
-    /// Risk manager for limits and circuit breakers.
-    risk_manager: RiskManager,
+    /// Risk manager shared with the `RiskGate` on the order path.
+    risk_manager: Arc<RwLock<RiskManager>>,
     /// Bot configuration.
     config: AppConfig,
     /// Shutdown signal receiver.
@@ -65,65 +125,104 @@ pub struct ArbitrageEngine<F: MarketFeed, E: OrderExecution> {
 
 impl<F: MarketFeed, E: OrderExecution> ArbitrageEngine<F, E> {
     /// Create a new arbitrage engine with all domain components wired.
+    ///
+    /// `risk_manager` is the same instance the execution `RiskGate`
+    /// checks against, so the engine's early exit and the gate agree.
+    /// Fails if a market names a strategy `registry` does not know.
     pub fn new(
         feed: Arc<F>,
         execution: Arc<E>,
+        risk_manager: Arc<RwLock<RiskManager>>,
         config: AppConfig,
         shutdown_rx: broadcast::Receiver<()>,
-    ) -> Self {
-        let pricer = LmsrPricer::new(config.lmsr.liquidity_parameter);
-        let sizer = KellySizer::new(config.lmsr.kelly_fraction);
-        let fees = FeeCalculator::new_maker();
-        let estimator = BayesianEstimator::new(config.lmsr.prior_weight);
+        registry: &StrategyRegistry,
+    ) -> Result<Self> {
+        let sizer = KellySizer::with_max_position(
+            config.lmsr.kelly_fraction,
+            config.lmsr.max_position_fraction,
+        );
+        let drawdown_scaler = DrawdownScaler::new(
+            config.lmsr.max_drawdown_fraction,
+            config.lmsr.min_drawdown_scale,
+        );
+        let portfolio = PortfolioKelly::new(
+            config.lmsr.kelly_fraction,
+            config.lmsr.max_position_fraction,
+        );
+        let token_assets = token_assets(&config);
+        let slots = registry.build(&config)?;
+        let routes = routes(&slots);
         let order_manager = OrderManager::new(Arc::clone(&execution), &config);
-        let risk_manager = RiskManager::new(&config.risk);
 
-        Self {
+        Ok(Self {
             feed,
             execution,
-            pricer,
+            chain: None,
+            ledger: None,
+            fees: None,
             sizer,
-            fees,
-            estimator,
+            drawdown_scaler,
+            drawdown: DrawdownTracker::default(),
+            portfolio,
+            signals: HashMap::new(),
+            token_assets,
+            slots,
+            routes,
+            registry: registry.clone(),
+            config_updates: None,
             order_manager,
             risk_manager,
             config,
             shutdown_rx,
-        }
+        })
+    }
+
+    /// Attach a chain client so strategies can split and merge.
+    ///
+    /// Without one, batches containing on-chain intents are skipped.
+    pub fn with_chain(mut self, chain: Arc<dyn ChainClient>) -> Self {
+        self.chain = Some(chain);
+        self
+    }
+
+    /// Book on-chain splits and merges in `ledger` (fills reach it
+    /// through the `RiskGate`).
+    pub fn with_ledger(mut self, ledger: Arc<PortfolioLedger>) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
+    /// Price taker legs at the rates in `fees`, refreshed from the CLOB.
+    pub fn with_fees(mut self, fees: Arc<FeeSchedules>) -> Self {
+        self.fees = Some(fees);
+        self
+    }
+
+    /// Apply configs published by the `ConfigWatcher` while running.
+    pub fn with_config_updates(mut self, updates: ReloadSubscriber) -> Self {
+        self.config_updates = Some(updates);
+        self
     }
 
     /// Run the main event loop.
     ///
-    /// Subscribes to all configured markets and processes price updates
-    /// as they arrive via `tokio::select!` — pure event-driven, NEVER polling.
-    /// Exits cleanly on shutdown signal.
+    /// Subscribes to every token a strategy asked for and processes
+    /// price updates as they arrive via `tokio::select!` — pure
+    /// event-driven, NEVER polling. Exits cleanly on shutdown signal.
     #[instrument(skip(self), name = "arbitrage_loop")]
     pub async fn run(&mut self) -> Result<()> {
         info!(
-            markets = self.config.markets.len(),
+            markets = self.slots.len(),
+            strategies = self.slots.iter().map(|s| s.strategies.len()).sum::<usize>(),
             "Starting arbitrage engine"
         );
 
-        // Subscribe to all active market tokens
-        let active_markets: Vec<_> = self
-            .config
-            .markets
-            .iter()
-            .filter(|m| m.active)
-            .collect();
-
-        if active_markets.is_empty() {
+        if self.routes.is_empty() {
             warn!("No active markets configured, engine idle");
-            return Ok(());
         }
 
-        // Create subscriptions for YES tokens via MarketFeed port
-        let token_ids: Vec<_> = active_markets
-            .iter()
-            .map(|m| m.yes_token_id.clone())
-            .collect();
-
-        let mut receivers = self.feed.subscribe_many(&token_ids);
+        // Subscribe (in config order) via MarketFeed port
+        let mut receivers = self.feed.subscribe_many(&self.token_ids());
 
         info!(
             subscriptions = receivers.len(),
@@ -132,11 +231,30 @@ impl<F: MarketFeed, E: OrderExecution> ArbitrageEngine<F, E> {
 
         // Main event loop — tokio::select! with biased shutdown priority
         loop {
-            let event = recv_first_event(
-                &mut receivers,
-                &mut self.shutdown_rx,
-            )
-            .await;
+            let event = tokio::select! {
+                biased;
+                update = next_config(&mut self.config_updates) => {
+                    let result = self.apply_config(update.config).await;
+                    if let Some(updates) = &self.config_updates {
+                        updates.ack(update.generation, &result);
+                    }
+                    match result {
+                        Ok(true) => {
+                            // Dropping a receiver unsubscribes its token
+                            receivers = self.feed.subscribe_many(&self.token_ids());
+                            info!(subscriptions = receivers.len(), "Market subscriptions updated");
+                        }
+                        Ok(false) => {}
+                        Err(e) => warn!(
+                            error = %e,
+                            generation = update.generation,
+                            "Config reload rejected — keeping current"
+                        ),
+                    }
+                    continue;
+                }
+                event = recv_first_event(&mut receivers, &mut self.shutdown_rx) => event,
+            };
 
             match event {
                 FeedEvent::Shutdown => {
@@ -144,13 +262,7 @@ impl<F: MarketFeed, E: OrderExecution> ArbitrageEngine<F, E> {
                     break;
                 }
                 FeedEvent::Update(price_update) => {
-                    if let Err(e) = self.process_update(&price_update).await {
-                        warn!(
-                            error = %e,
-                            token = %price_update.token_id,
-                            "Error processing price update"
-                        );
-                    }
+                    self.process_update(&price_update).await;
                 }
                 FeedEvent::Lagged(count) => {
                     warn!(
@@ -166,81 +278,327 @@ impl<F: MarketFeed, E: OrderExecution> ArbitrageEngine<F, E> {
 
     /// Process a single price update.
     ///
-    /// Core pipeline: mid-price → Bayesian estimate → LMSR fair value
-    /// → edge detection → risk check → Kelly sizing → maker order.
+    /// Hands the update to every strategy routed to its token, in
+    /// config order, and executes each strategy's intents. A failing
+    /// batch is logged and does not affect the other strategies.
     #[instrument(skip(self, update), fields(token = %update.token_id))]
-    async fn process_update(&mut self, update: &PriceUpdate) -> Result<()> {
-        let start = Instant::now();
-
-        // 1. Extract mid-price (must be valid probability range)
-        let mid = match update.mid_price {
-            Some(p) if p > 0.0 && p < 1.0 => p,
-            _ => {
-                debug!("Skipping update: no valid mid-price");
-                return Ok(());
-            }
+    pub async fn process_update(&mut self, update: &PriceUpdate) {
+        let Some(routes) = self.routes.get(&update.token_id).cloned() else {
+            return;
         };
 
-        // 2. Update Bayesian EWMA estimate
-        let estimated_prob = self.estimator.update(mid);
+        for (slot, index) in routes {
+            let start = Instant::now();
+            if let Some(fees) = &self.fees {
+                let market = &mut self.slots[slot].market;
+                market.fees = fees.for_market(&market.condition_id).await;
+            }
+            let MarketStrategies { market, strategies } = &mut self.slots[slot];
+            let strategy = &mut strategies[index];
+            let name = strategy.name();
+            let intents = strategy.on_update(market, update);
+            if intents.is_empty() {
+                continue;
+            }
+            let market = market.clone();
+            if let Err(e) = self.execute(name, &market, intents, start).await {
+                warn!(
+                    error = %e,
+                    strategy = name,
+                    token = %update.token_id,
+                    "Strategy intents failed, rest of batch dropped"
+                );
+            }
+        }
+    }
 
-        // 3. Compute LMSR fair value
-        let fair_value = self.pricer.price(estimated_prob);
+    /// Apply a hot-reloaded config (reloadable sections only).
+    ///
+    /// Kelly sizing, rate limits and risk limits are re-derived.
+    /// Markets that were deactivated, removed or edited have their
+    /// resting orders cancelled and their strategies dropped; new or
+    /// edited active markets get fresh strategies; untouched markets
+    /// keep theirs (and their state) via `Strategy::reconfigure`.
+    ///
+    /// Returns whether the subscribed token set changed. Nothing is
+    /// applied if a market names an unknown strategy.
+    pub async fn apply_config(&mut self, new: AppConfig) -> Result<bool> {
+        let old_tokens = self.token_ids();
+        let old_markets: HashMap<&str, &MarketConfig> = self
+            .config
+            .markets
+            .iter()
+            .filter(|m| m.active)
+            .map(|m| (m.condition_id.as_str(), m))
+            .collect();
 
-        // 4. Calculate edge after fees (maker fee = 0)
-        let edge = if let Some(best_ask) = update.best_ask {
-            self.fees.net_edge(fair_value, best_ask, true)
-        } else {
-            0.0
-        };
+        // Build first so a bad market leaves everything untouched
+        let started = new
+            .markets
+            .iter()
+            .filter(|m| m.active && old_markets.get(m.condition_id.as_str()).copied() != Some(*m))
+            .map(|m| self.registry.build_market(&new, m))
+            .collect::<Result<Vec<_>>>()?;
+        let stopped: Vec<MarketConfig> = old_markets
+            .values()
+            .filter(|m| !new.markets.iter().any(|n| n.active && n == **m))
+            .map(|m| (*m).clone())
+            .collect();
 
-        // 5. Check minimum edge threshold
-        if edge.abs() < self.config.lmsr.min_edge {
-            debug!(
-                edge = edge,
-                min = self.config.lmsr.min_edge,
-                "Edge below threshold, skipping"
+        for market in &stopped {
+            for token_id in [&market.yes_token_id, &market.no_token_id] {
+                if let Err(e) = self.order_manager.cancel_token(token_id).await {
+                    warn!(error = %e, token = %token_id, "Failed to cancel orders of stopped market");
+                }
+            }
+            self.slots
+                .retain(|s| s.market.condition_id != market.condition_id);
+            info!(market = %market.condition_id, "Market stopped");
+        }
+        for slot in &mut self.slots {
+            for strategy in &mut slot.strategies {
+                strategy.reconfigure(&new);
+            }
+        }
+        for slot in started {
+            info!(
+                market = %slot.market.condition_id,
+                strategies = ?slot.strategies.iter().map(|s| s.name()).collect::<Vec<_>>(),
+                "Market started"
             );
+            self.slots.push(slot);
+        }
+        self.routes = routes(&self.slots);
+
+        self.sizer = KellySizer::with_max_position(
+            new.lmsr.kelly_fraction,
+            new.lmsr.max_position_fraction,
+        );
+        self.drawdown_scaler = DrawdownScaler::new(
+            new.lmsr.max_drawdown_fraction,
+            new.lmsr.min_drawdown_scale,
+        );
+        self.portfolio = PortfolioKelly::new(
+            new.lmsr.kelly_fraction,
+            new.lmsr.max_position_fraction,
+        );
+        self.token_assets = token_assets(&new);
+        self.order_manager.set_rate_limits(&new.rate_limits);
+        self.risk_manager
+            .write()
+            .await
+            .apply_config(&new.risk, &new.markets);
+        self.config = new;
+
+        info!(markets = self.slots.len(), "Config applied");
+        Ok(self.token_ids() != old_tokens)
+    }
+
+    /// Tokens the strategies need, in config order, deduplicated.
+    fn token_ids(&self) -> Vec<TokenId> {
+        let mut token_ids: Vec<TokenId> = Vec::with_capacity(self.routes.len());
+        for entry in &self.slots {
+            for strategy in &entry.strategies {
+                for token_id in strategy.tokens(&entry.market) {
+                    if !token_ids.contains(&token_id) {
+                        token_ids.push(token_id);
+                    }
+                }
+            }
+        }
+        token_ids
+    }
+
+    /// Execute one strategy's intents in order, stopping at the first
+    /// failure and flattening the legs already executed.
+    async fn execute(
+        &mut self,
+        strategy: &'static str,
+        market: &StrategyMarket,
+        intents: Vec<StrategyIntent>,
+        start: Instant,
+    ) -> Result<()> {
+        if self.chain.is_none() && intents.iter().any(StrategyIntent::is_onchain) {
+            debug!(strategy, "No chain client — skipping on-chain batch");
             return Ok(());
         }
 
-        // 6. Risk check (circuit breaker, daily loss, exposure)
-        if !self.risk_manager.can_trade() {
-            warn!("Risk limits reached, trade blocked");
+        // Cheap circuit-breaker check; full limits run in the RiskGate
+        let trades = intents
+            .iter()
+            .any(|i| !matches!(i, StrategyIntent::CancelToken(_)));
+        if trades && !self.risk_manager.read().await.can_trade() {
+            warn!(strategy, "Risk limits reached, trade blocked");
             return Ok(());
         }
 
-        // 7. Kelly sizing against current bankroll
+        let mut executed = Vec::with_capacity(intents.len());
+        for intent in intents {
+            if let Err(e) = self.run_intent(strategy, market, intent.clone(), start).await {
+                self.unwind(strategy, market, &executed, start).await;
+                return Err(e);
+            }
+            executed.push(intent);
+        }
+        Ok(())
+    }
+
+    /// Flatten the executed legs of a failed batch.
+    ///
+    /// If flattening fails too, trading is halted
+    /// (`HaltSource::Unwind`) for an operator to clean up.
+    async fn unwind(
+        &mut self,
+        strategy: &'static str,
+        market: &StrategyMarket,
+        executed: &[StrategyIntent],
+        start: Instant,
+    ) {
+        let unwinds = StrategyIntent::unwind(market, executed);
+        if unwinds.is_empty() {
+            return;
+        }
+        warn!(strategy, market = %market.condition_id, legs = unwinds.len(), "Batch failed, unwinding executed legs");
+        for intent in unwinds {
+            if let Err(e) = self.run_intent(strategy, market, intent, start).await {
+                let reason = format!("{strategy} unwind failed on {}: {e:#}", market.condition_id);
+                error!(strategy, market = %market.condition_id, error = %e, "Unwind failed, position left open");
+                self.risk_manager
+                    .write()
+                    .await
+                    .halt(&reason, HaltSource::Unwind, now_ms());
+                return;
+            }
+        }
+    }
+
+    /// Execute a single intent.
+    async fn run_intent(
+        &mut self,
+        strategy: &'static str,
+        market: &StrategyMarket,
+        intent: StrategyIntent,
+        start: Instant,
+    ) -> Result<()> {
+        match intent {
+            StrategyIntent::Signal(signal) => self.place_signal(strategy, signal, start).await?,
+            StrategyIntent::Order(order) => {
+                let token_id = order.token_id.clone();
+                match self.order_manager.place_order(order).await? {
+                    Some(placement) if placement.accepted => {}
+                    Some(placement) => bail!(
+                        "Order on {token_id} rejected: {}",
+                        placement.rejection_reason.unwrap_or_default()
+                    ),
+                    None => bail!("Order on {token_id} throttled by rate limit"),
+                }
+            }
+            StrategyIntent::CancelToken(token_id) => {
+                self.order_manager.cancel_token(&token_id).await?;
+            }
+            StrategyIntent::Split { condition_id, amount_raw } => {
+                let chain = self.chain.as_ref().context("No chain client")?;
+                let split = chain.split_position(&condition_id, amount_raw).await?;
+                info!(strategy, market = %condition_id, tx = %split.tx_hash, sets = split.sets_split, "Split complete sets");
+                let ledger = self.ledger.as_deref();
+                Self::book_sets(&self.execution, ledger, market, TradeSide::Buy, split.sets_split).await;
+            }
+            StrategyIntent::Merge { condition_id, amount_raw } => {
+                let chain = self.chain.as_ref().context("No chain client")?;
+                let merge = chain.merge_positions(&condition_id, amount_raw).await?;
+                info!(strategy, market = %condition_id, tx = %merge.tx_hash, usdc = merge.usdc_recovered, "Merged complete sets");
+                let ledger = self.ledger.as_deref();
+                Self::book_sets(&self.execution, ledger, market, TradeSide::Sell, merge.sets_merged).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Credit complete sets minted (`Buy`) or burned (`Sell`) to the
+    /// gate's positions at half a set per outcome, and book them in
+    /// the ledger.
+    async fn book_sets(
+        execution: &E,
+        ledger: Option<&PortfolioLedger>,
+        market: &StrategyMarket,
+        side: TradeSide,
+        sets: f64,
+    ) {
+        for token_id in [&market.yes_token_id, &market.no_token_id] {
+            execution.record_transfer(token_id, side, 0.5, sets).await;
+        }
+        if let Some(ledger) = ledger {
+            match side {
+                TradeSide::Buy => ledger.record_split(&market.condition_id, sets).await,
+                TradeSide::Sell => ledger.record_merge(&market.condition_id, sets).await,
+            };
+        }
+    }
+
+    /// Size a directional signal and quote it as a maker order.
+    ///
+    /// Kelly sizing against current bankroll, scaled by drawdown
+    /// (equity = free USDC + capital in positions/resting orders) and
+    /// shrunk by estimate uncertainty.
+    async fn place_signal(&mut self, strategy: &'static str, signal: Signal, start: Instant) -> Result<()> {
         let bankroll = self
             .execution
-            .available_balance(crate::domain::trade::TradeSide::Buy)
+            .available_balance(TradeSide::Buy)
             .await?;
+        let equity = bankroll + self.risk_manager.read().await.total_exposure();
+        self.drawdown.observe(equity, &utc_day(now_ms()));
+
+        let ctx = SizingContext {
+            drawdown_scale: self.drawdown_scaler.scale(self.drawdown.drawdown()),
+            prob_std_error: signal.prob_std_error,
+        };
+
+        // Buying YES at the ask, or (negative edge) the NO side at 1 − ask
+        let long_yes = signal.edge > 0.0;
+        let (win_prob, price) = if long_yes {
+            (signal.fair_value, signal.market_price)
+        } else {
+            (1.0 - signal.fair_value, 1.0 - signal.market_price)
+        };
+        let single_size = self.sizer.sized(win_prob, price, bankroll, &ctx);
+        let opportunity = Opportunity {
+            probability: win_prob,
+            price,
+        };
 
+        // Several markets signalling together share one Kelly budget;
+        // never size above what this market would get on its own.
         let kelly_size = self
-            .sizer
-            .optimal_size(estimated_prob, fair_value, bankroll);
+            .portfolio_fraction(&signal.token_id, opportunity, long_yes, bankroll)
+            .map_or(single_size, |fraction| {
+                self.sizer
+                    .apply_context(fraction, win_prob - price, bankroll, &ctx)
+                    .min(single_size)
+            });
 
         if kelly_size < 1.0 {
             debug!(size = kelly_size, "Kelly size too small, skipping");
             return Ok(());
         }
 
-        // 8. Place maker order
         let latency = start.elapsed();
         info!(
-            fair_value = fair_value,
-            edge = edge,
+            strategy,
+            fair_value = signal.fair_value,
+            edge = signal.edge,
             size = kelly_size,
+            drawdown_scale = ctx.drawdown_scale,
+            std_error = ctx.prob_std_error,
             latency_us = latency.as_micros(),
             "Signal detected — placing maker order"
         );
 
         self.order_manager
             .place_maker_order(
-                &update.token_id,
-                fair_value,
+                &signal.token_id,
+                signal.fair_value,
                 kelly_size,
-                edge > 0.0,
+                long_yes,
             )
             .await?;
 
@@ -248,6 +606,104 @@ impl<F: MarketFeed, E: OrderExecution> ArbitrageEngine<F, E> {
     }
 }
 
+impl<F: MarketFeed, E: OrderExecution> ArbitrageEngine<F, E> {
+    /// Joint Kelly fraction for `token_id` given other live signals.
+    ///
+    /// Records the signal, drops signals older than the debounce
+    /// window, and returns `None` when this is the only live signal.
+    fn portfolio_fraction(
+        &mut self,
+        token_id: &TokenId,
+        opportunity: Opportunity,
+        long_yes: bool,
+        bankroll: f64,
+    ) -> Option<f64> {
+        let now = Instant::now();
+        let window = Duration::from_millis(self.config.strategy.debounce_ms);
+        self.signals.insert(
+            token_id.clone(),
+            LiveSignal {
+                opportunity,
+                long_yes,
+                asset: self.token_assets.get(token_id).copied(),
+                seen_at: now,
+            },
+        );
+        self.signals
+            .retain(|_, s| now.duration_since(s.seen_at) <= window);
+
+        if self.signals.len() < 2 || bankroll <= 0.0 {
+            return None;
+        }
+
+        let tokens: Vec<&TokenId> = self.signals.keys().collect();
+        let live: Vec<&LiveSignal> = tokens.iter().map(|t| &self.signals[*t]).collect();
+        let index = tokens.iter().position(|t| *t == token_id)?;
+
+        let strategy = &self.config.strategy;
+        let correlation: Vec<Vec<f64>> = live
+            .iter()
+            .map(|a| {
+                live.iter()
+                    .map(|b| {
+                        let base = match (a.asset, b.asset) {
+                            (Some(x), Some(y)) if x == y => strategy.same_asset_correlation,
+                            (Some(_), Some(_)) => strategy.cross_asset_correlation,
+                            _ => 0.0,
+                        };
+                        // Long NO is short the underlying direction
+                        if a.long_yes == b.long_yes { base } else { -base }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let opportunities: Vec<Opportunity> = live.iter().map(|s| s.opportunity).collect();
+        let budget = (self.config.risk.max_total_exposure / bankroll).clamp(0.0, 1.0);
+        let allocation = self.portfolio.allocate(&opportunities, &correlation, budget);
+
+        debug!(
+            signals = opportunities.len(),
+            fraction = allocation[index],
+            "Portfolio Kelly allocation"
+        );
+        Some(allocation[index])
+    }
+}
+
+/// Token ID → asset (for correlation lookup).
+fn token_assets(config: &AppConfig) -> HashMap<TokenId, Asset> {
+    config
+        .markets
+        .iter()
+        .flat_map(|m| [(m.yes_token_id.clone(), m.asset), (m.no_token_id.clone(), m.asset)])
+        .collect()
+}
+
+/// Token ID → (slot, strategy) routing table.
+fn routes(slots: &[MarketStrategies]) -> HashMap<TokenId, Vec<(usize, usize)>> {
+    let mut routes: HashMap<TokenId, Vec<(usize, usize)>> = HashMap::new();
+    for (slot, entry) in slots.iter().enumerate() {
+        for (index, strategy) in entry.strategies.iter().enumerate() {
+            for token_id in strategy.tokens(&entry.market) {
+                routes.entry(token_id).or_default().push((slot, index));
+            }
+        }
+    }
+    routes
+}
+
+/// Wait for the next reloaded config; pending forever without a
+/// watcher (or once it is gone).
+pub(super) async fn next_config(updates: &mut Option<ReloadSubscriber>) -> ConfigUpdate {
+    if let Some(updates) = updates {
+        if let Some(update) = updates.next().await {
+            return update;
+        }
+    }
+    std::future::pending().await
+}
+
 /// Receive the first available event from any market feed receiver OR shutdown.
 ///
 /// Uses `tokio::select!` with biased shutdown priority and a `poll_fn` that
@@ -257,7 +713,7 @@ impl<F: MarketFeed, E: OrderExecution> ArbitrageEngine<F, E> {
 /// The `poll_fn` approach is idiomatic for a dynamic number of futures and
 /// has zero overhead — each receiver's waker is registered with the tokio
 /// runtime and only woken when data arrives.
-async fn recv_first_event(
+pub(super) async fn recv_first_event(
     receivers: &mut [broadcast::Receiver<PriceUpdate>],
     shutdown_rx: &mut broadcast::Receiver<()>,
 ) -> FeedEvent {
@@ -294,14 +750,9 @@ async fn recv_first_event(
                     Poll::Ready(Err(RecvError::Lagged(n))) => {
                         return Poll::Ready(FeedEvent::Lagged(n));
                     }
-                    Poll::Ready(Err(RecvError::Closed)) => {
-                        // Channel closed — skip this receiver, try next
-                        continue;
-                    }
-                    Poll::Pending => {
-                        // Waker registered — will be notified when data arrives
-                        continue;
-                    }
+                    // Channel closed — skip this receiver, try next.
+                    // Pending: waker registered, notified when data arrives
+                    Poll::Ready(Err(RecvError::Closed)) | Poll::Pending => {}
                 }
             }
             // All receivers are Pending — we'll be woken by any of them
@@ -0,0 +1,199 @@
+//! Fee Reconciler - Daily Expected vs Charged Fees
+//!
+//! Every `fees.refresh_interval_secs`:
+//! - market fee rates are refreshed from the CLOB (`FeeSchedules::refresh`)
+//! - once a UTC day has ended, its trade history is pulled and every
+//!   taker fill is priced twice: at the market's schedule (expected) and
+//!   at the rate the CLOB charged (actual); maker fills accrue the
+//!   market's rebate share of the fee the taker paid
+//! - markets whose gap exceeds `fees.reconcile_tolerance` are flagged,
+//!   and the result is saved as a `FeeReconciliation` next to the fees
+//!   and rebates the `PortfolioLedger` booked for the day
+//!
+//! Days already saved are not reconciled again, so restarts do not
+//! duplicate records.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use tokio::sync::broadcast;
+use tracing::{info, instrument, warn};
+
+use crate::config::AppConfig;
+use crate::domain::fees::FeeSchedule;
+use crate::domain::time::{now_ms, utc_day};
+use crate::ports::fees::FeeSource;
+use crate::ports::repository::{FeeReconciliation, MarketFeeReconciliation, Repository};
+
+use super::fee_schedule::FeeSchedules;
+
+/// One UTC day in milliseconds.
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Daily driver for fee rate refreshes and fee reconciliation.
+pub struct FeeReconciler<S: FeeSource, R: Repository> {
+  /// CLOB fee rates and trade history.
+  source: Arc<S>,
+  /// Where reconciliations are saved and daily PnL is read.
+  repo: Arc<R>,
+  /// Schedules shared with the `PortfolioLedger`.
+  schedules: Arc<FeeSchedules>,
+  /// Largest tolerated daily fee gap per market (USDC).
+  tolerance: f64,
+  /// Tick interval.
+  interval: Duration,
+  /// Last UTC day reconciled (or found already saved).
+  last_reconciled: Option<String>,
+}
+
+impl<S: FeeSource, R: Repository> FeeReconciler<S, R> {
+  /// Create a reconciler from `[fees]` config.
+  pub fn new(source: Arc<S>, repo: Arc<R>, schedules: Arc<FeeSchedules>, config: &AppConfig) -> Self {
+    Self {
+      source,
+      repo,
+      schedules,
+      tolerance: config.fees.reconcile_tolerance,
+      interval: Duration::from_secs(config.fees.refresh_interval_secs),
+      last_reconciled: None,
+    }
+  }
+
+  /// Reconcile the fills of `date` (YYYY-MM-DD) and save the result.
+  #[instrument(skip(self))]
+  pub async fn reconcile(&self, date: &str, now_ms: u64) -> Result<FeeReconciliation> {
+    let start_ms = day_start_ms(date)?;
+    let fills = self.source.trades(start_ms, start_ms + DAY_MS).await?;
+
+    let mut markets: BTreeMap<String, MarketFeeReconciliation> = BTreeMap::new();
+    for fill in &fills {
+      let market_id = self.schedules.market_of(&fill.token_id);
+      let schedule = self.schedules.for_market(&market_id).await;
+      let charged = FeeSchedule::from_bps(fill.fee_rate_bps, schedule.maker_rebate_share);
+
+      let row = markets
+        .entry(market_id.clone())
+        .or_insert_with(|| MarketFeeReconciliation {
+          market_id,
+          trades: 0,
+          expected_fees: 0.0,
+          actual_fees: 0.0,
+          rebates: 0.0,
+          rate_mismatches: 0,
+          flagged: false,
+        });
+      row.trades += 1;
+      if fill.fee_rate_bps != schedule.fee_rate_bps() {
+        row.rate_mismatches += 1;
+      }
+      if fill.is_maker {
+        row.rebates += charged.maker_rebate(fill.price, fill.size);
+      } else {
+        row.expected_fees += schedule.taker_fee(fill.price, fill.size);
+        row.actual_fees += charged.taker_fee(fill.price, fill.size);
+      }
+    }
+
+    let mut rows: Vec<MarketFeeReconciliation> = markets.into_values().collect();
+    for row in &mut rows {
+      row.flagged = (row.actual_fees - row.expected_fees).abs() > self.tolerance;
+      if row.flagged {
+        warn!(
+          date,
+          market_id = %row.market_id,
+          expected = row.expected_fees,
+          actual = row.actual_fees,
+          rate_mismatches = row.rate_mismatches,
+          "Fees charged differ from schedule"
+        );
+      }
+    }
+
+    let booked = self
+      .repo
+      .load_daily_pnl()
+      .await?
+      .into_iter()
+      .rfind(|d| d.date == date);
+    let record = FeeReconciliation {
+      date: date.to_string(),
+      timestamp_ms: now_ms,
+      expected_fees: rows.iter().map(|r| r.expected_fees).sum(),
+      actual_fees: rows.iter().map(|r| r.actual_fees).sum(),
+      rebates: rows.iter().map(|r| r.rebates).sum(),
+      booked_fees: booked.as_ref().map(|d| d.fees),
+      booked_rebates: booked.as_ref().map(|d| d.rebates),
+      markets: rows,
+    };
+
+    info!(
+      date,
+      trades = fills.len(),
+      expected = record.expected_fees,
+      actual = record.actual_fees,
+      rebates = record.rebates,
+      booked_fees = ?record.booked_fees,
+      flagged = record.flagged().count(),
+      "Fees reconciled"
+    );
+    self.repo.save_fee_reconciliation(&record).await?;
+    Ok(record)
+  }
+
+  /// Refresh rates and reconcile the previous day if not done yet.
+  pub async fn tick(&mut self, now_ms: u64) -> Result<Option<FeeReconciliation>> {
+    self.schedules.refresh(self.source.as_ref()).await;
+
+    let yesterday = utc_day(now_ms.saturating_sub(DAY_MS));
+    if self.last_reconciled.as_deref() == Some(yesterday.as_str()) {
+      return Ok(None);
+    }
+    if self.last_reconciled.is_none()
+      && self
+        .repo
+        .load_fee_reconciliations()
+        .await?
+        .iter()
+        .any(|r| r.date == yesterday)
+    {
+      self.last_reconciled = Some(yesterday);
+      return Ok(None);
+    }
+
+    let record = self.reconcile(&yesterday, now_ms).await?;
+    self.last_reconciled = Some(yesterday);
+    Ok(Some(record))
+  }
+
+  /// Run `tick` on the configured interval until shutdown.
+  pub async fn run(mut self, mut shutdown_rx: broadcast::Receiver<()>) {
+    info!(
+      interval_secs = self.interval.as_secs(),
+      tolerance = self.tolerance,
+      "Fee reconciler started"
+    );
+    let mut ticker = tokio::time::interval(self.interval);
+    loop {
+      tokio::select! {
+        biased;
+        _ = shutdown_rx.recv() => break,
+        _ = ticker.tick() => {
+          if let Err(e) = self.tick(now_ms()).await {
+            warn!(error = %e, "Fee reconciliation failed");
+          }
+        }
+      }
+    }
+    info!("Fee reconciler stopped");
+  }
+}
+
+/// Midnight UTC of a YYYY-MM-DD day, in Unix ms.
+fn day_start_ms(date: &str) -> Result<u64> {
+  let day = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+    .with_context(|| format!("Invalid date {date} (expected YYYY-MM-DD)"))?;
+  Ok(day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp_millis() as u64)
+}
@@ -0,0 +1,165 @@
+//! Fee Schedules - Per-Market Taker Rates and Maker Rebates
+//!
+//! Every configured market starts on the schedule of its `fee_class`
+//! and `maker_rebate_share` (falling back to `fees.maker_rebate_share`).
+//! With `fees.fetch_rates`, `refresh` asks the CLOB for each market's
+//! current rate and replaces the configured one; a failed lookup keeps
+//! the last known rate. Tokens of unknown markets use the standard tier.
+
+use std::collections::HashMap;
+
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::config::AppConfig;
+use crate::domain::fees::{FeeClass, FeeSchedule};
+use crate::domain::trade::{MarketId, TokenId};
+use crate::ports::fees::FeeSource;
+
+/// Shared per-market fee schedules.
+pub struct FeeSchedules {
+  /// Current schedule per market.
+  schedules: RwLock<HashMap<MarketId, FeeSchedule>>,
+  /// Token → market.
+  token_markets: HashMap<TokenId, MarketId>,
+  /// Token whose rate is queried for each market (its YES token).
+  rate_tokens: Vec<(MarketId, TokenId)>,
+  /// Schedule for tokens of unregistered markets.
+  fallback: FeeSchedule,
+  /// Whether `refresh` queries the CLOB.
+  fetch_rates: bool,
+}
+
+impl FeeSchedules {
+  /// Configured schedules of `[[markets]]`.
+  pub fn new(config: &AppConfig) -> Self {
+    let mut schedules = HashMap::new();
+    let mut token_markets = HashMap::new();
+    let mut rate_tokens = Vec::new();
+    for m in &config.markets {
+      schedules.insert(m.condition_id.clone(), config.fees.schedule_for(m));
+      token_markets.insert(m.yes_token_id.clone(), m.condition_id.clone());
+      token_markets.insert(m.no_token_id.clone(), m.condition_id.clone());
+      rate_tokens.push((m.condition_id.clone(), m.yes_token_id.clone()));
+    }
+    Self {
+      schedules: RwLock::new(schedules),
+      token_markets,
+      rate_tokens,
+      fallback: FeeSchedule::new(FeeClass::Standard, config.fees.maker_rebate_share),
+      fetch_rates: config.fees.fetch_rates,
+    }
+  }
+
+  /// Market a token belongs to (itself when unregistered).
+  pub fn market_of(&self, token_id: &str) -> MarketId {
+    self
+      .token_markets
+      .get(token_id)
+      .cloned()
+      .unwrap_or_else(|| token_id.to_string())
+  }
+
+  /// Schedule of a market.
+  pub async fn for_market(&self, market_id: &str) -> FeeSchedule {
+    self
+      .schedules
+      .read()
+      .await
+      .get(market_id)
+      .copied()
+      .unwrap_or(self.fallback)
+  }
+
+  /// Schedule of the market a token belongs to.
+  pub async fn for_token(&self, token_id: &str) -> FeeSchedule {
+    self.for_market(&self.market_of(token_id)).await
+  }
+
+  /// All market schedules, sorted by market ID.
+  pub async fn snapshot(&self) -> Vec<(MarketId, FeeSchedule)> {
+    let mut rows: Vec<(MarketId, FeeSchedule)> = self
+      .schedules
+      .read()
+      .await
+      .iter()
+      .map(|(id, s)| (id.clone(), *s))
+      .collect();
+    rows.sort_by(|a, b| a.0.cmp(&b.0));
+    rows
+  }
+
+  /// Take each market's taker rate from the CLOB; returns how many
+  /// changed. Does nothing when `fees.fetch_rates` is off.
+  pub async fn refresh<S: FeeSource>(&self, source: &S) -> usize {
+    if !self.fetch_rates {
+      return 0;
+    }
+    let mut fetched = Vec::with_capacity(self.rate_tokens.len());
+    for (market_id, token_id) in &self.rate_tokens {
+      match source.fee_rate_bps(token_id).await {
+        Ok(bps) => fetched.push((market_id, bps)),
+        Err(e) => warn!(market_id = %market_id, error = %e, "Fee rate lookup failed — keeping last rate"),
+      }
+    }
+
+    let mut schedules = self.schedules.write().await;
+    let mut changed = 0;
+    for (market_id, bps) in fetched {
+      let current = schedules.get(market_id).copied().unwrap_or(self.fallback);
+      let updated = FeeSchedule::from_bps(bps, current.maker_rebate_share);
+      if updated != current {
+        info!(
+          market_id = %market_id,
+          from_bps = current.fee_rate_bps(),
+          to_bps = bps,
+          "Market fee rate updated"
+        );
+        schedules.insert(market_id.clone(), updated);
+        changed += 1;
+      }
+    }
+    debug!(markets = self.rate_tokens.len(), changed, "Fee rates refreshed");
+    changed
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use anyhow::Result;
+  use async_trait::async_trait;
+
+  use crate::ports::fees::TradeFill;
+
+  struct FixedRate(u32);
+
+  #[async_trait]
+  impl FeeSource for FixedRate {
+    async fn fee_rate_bps(&self, _token_id: &str) -> Result<u32> {
+      Ok(self.0)
+    }
+
+    async fn trades(&self, _from_ms: u64, _to_ms: u64) -> Result<Vec<TradeFill>> {
+      Ok(Vec::new())
+    }
+  }
+
+  #[tokio::test]
+  async fn test_refresh_replaces_configured_rate_and_keeps_rebate() {
+    let mut config = crate::config::loader::load_config("config.toml.example").unwrap();
+    config.fees.maker_rebate_share = 0.2;
+    let market = config.markets[0].clone();
+    let schedules = FeeSchedules::new(&config);
+
+    let configured = schedules.for_token(&market.no_token_id).await;
+    assert_eq!(configured, FeeSchedule::new(market.fee_class, 0.2));
+
+    assert_eq!(schedules.refresh(&FixedRate(250)).await, config.markets.len());
+    let fetched = schedules.for_market(&market.condition_id).await;
+    assert_eq!(fetched.fee_rate_bps(), 250);
+    assert_eq!(fetched.maker_rebate_share, 0.2);
+    assert_eq!(schedules.refresh(&FixedRate(250)).await, 0);
+  }
+}
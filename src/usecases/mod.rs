@@ -5,14 +5,41 @@
 //! business operation.
 //!
 //! Use cases:
-//! - `ArbitrageEngine`: Main pricing + quoting loop
+//! - `ArbitrageEngine`: Runs per-market strategies through the shared
+//!   risk + sizing + order pipeline
+//! - `FeeSchedules`: Per-market taker rates (CLOB-refreshed) and rebates
+//! - `FeeReconciler`: Daily expected vs charged fees from trade history
+//! - `NegRiskArbitrage`: Neg-risk event baskets via split / convert
 //! - `OrderManager`: Order lifecycle management
+//! - `PortfolioLedger`: Cost basis, mark-to-market and daily PnL
+//! - `PositionMerger`: Merge overlapping YES/NO holdings back to USDC
 //! - `RiskManager`: Position limits, circuit breakers, daily loss
+//! - `RiskGate`: Pre-trade risk checks wrapping `OrderExecution`
+//! - `RiskScheduler`: Day rollover, breaker recovery, risk persistence
 //! - `Settlement`: Batch redemption of resolved markets
+//! - `SettlementScheduler`: Daily sweep with gas deferral and PnL booking
+//! - `StateCheckpointer`: Full state snapshots and startup recovery checks
+//! - `StrategyRegistry`: Per-market strategies selected by config
+//! - `strategies`: Built-in `Strategy` implementations
+//! - `TradingControl`: Operator halt, resume and cancel-all
 //! - `WalletManager`: Balance tracking and USDC management
+//! - `WalletMonitor`: Gas guard and hot/cold rebalancing
 
 pub mod arbitrage_engine;
+pub mod fee_reconciler;
+pub mod fee_schedule;
+pub mod neg_risk_arb;
 pub mod order_manager;
+pub mod portfolio_ledger;
+pub mod position_merger;
+pub mod risk_gate;
 pub mod risk_manager;
+pub mod risk_scheduler;
 pub mod settlement;
+pub mod settlement_scheduler;
+pub mod state_checkpoint;
+pub mod strategies;
+pub mod strategy_registry;
+pub mod trading_control;
 pub mod wallet_manager;
+pub mod wallet_monitor;
@@ -0,0 +1,555 @@
+//! Neg-Risk Arbitrage - Multi-Outcome Event Baskets
+//!
+//! Watches every outcome book of each neg-risk event group (markets
+//! sharing a `neg_risk_market_id`) and trades the basket picked by
+//! `domain::neg_risk::evaluate`, each leg priced at the taker fee of
+//! its outcome's market (`FeeSchedules`):
+//! - `BuyYesBasket`: FOK-buy every YES, held to resolution and redeemed
+//!   by the settlement sweep
+//! - `SplitSellYes`: `splitPosition` on every outcome, FOK-sell every
+//!   YES, then `convertPositions` all NO back into USDC
+//! - `ConvertNo`: FOK-buy NO on the chosen outcomes, `convertPositions`
+//!   them through the NegRiskAdapter, then FOK-sell the YES received
+//!
+//! A basket that does not complete is unwound instead of held: tokens
+//! from filled legs are sold at market and unsold split sets merged
+//! back, so a partial basket never leaves directional exposure. Legs
+//! that cannot be flattened halt trading (`HaltSource::Unwind`) for an
+//! operator to clean up. No basket starts while the `RiskManager`
+//! blocks trading.
+//!
+//! Legs go out one by one through an `OrderManager` (rate limits),
+//! and a basket only starts when the per-minute budget covers its
+//! legs plus an unwind of each.
+//!
+//! Fills are booked by the `RiskGate`; every split, merge and
+//! conversion is credited to the gate's positions and booked in the
+//! `PortfolioLedger` here.
+//!
+//! Reloaded `[[markets]]` (`with_config_updates`) toggle events: one
+//! with an inactive outcome is not traded.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::config::hot_reload::ReloadSubscriber;
+use crate::config::{AppConfig, MarketConfig, NegRiskArbConfig};
+use crate::domain::complete_set::BookTop;
+use crate::domain::neg_risk::{self, BasketLeg, NegRiskKind, NegRiskOpportunity, OutcomeBook};
+use crate::domain::time::now_ms;
+use crate::domain::trade::{Order, TokenId, TradeSide};
+use crate::ports::chain_client::{ChainClient, ConvertResult};
+use crate::ports::execution::OrderExecution;
+use crate::ports::market_feed::{MarketFeed, PriceUpdate};
+use crate::ports::repository::HaltSource;
+use crate::ports::strategy::{FLATTEN_SELL_PRICE, TOKEN_SCALE};
+
+use super::arbitrage_engine::{next_config, recv_first_event, FeedEvent};
+use super::fee_schedule::FeeSchedules;
+use super::order_manager::OrderManager;
+use super::portfolio_ledger::PortfolioLedger;
+use super::risk_manager::RiskManager;
+
+/// A neg-risk basket trade attempt.
+#[derive(Debug, Clone)]
+pub struct NegRiskTrade {
+  /// NegRiskAdapter market ID of the event.
+  pub event_id: String,
+  /// Opportunity that was taken.
+  pub opportunity: NegRiskOpportunity,
+  /// Number of CLOB legs filled.
+  pub legs_filled: usize,
+  /// Split / convert / merge transaction hashes, in submission order.
+  pub chain_txs: Vec<String>,
+  /// The basket did not complete and was flattened.
+  pub unwound: bool,
+}
+
+/// Neg-risk event group arbitrage strategy.
+pub struct NegRiskArbitrage<F: MarketFeed, E: OrderExecution, C: ChainClient> {
+  /// Market data feed (port).
+  feed: Arc<F>,
+  /// Order execution (port), wrapped in the `RiskGate`.
+  execution: Arc<E>,
+  /// Rate-limited order path for the CLOB legs.
+  orders: OrderManager<E>,
+  /// Chain client for split / convert.
+  chain: Arc<C>,
+  /// Ledger booking the on-chain steps.
+  ledger: Arc<PortfolioLedger>,
+  /// Risk manager shared with the `RiskGate`, halted on a failed unwind.
+  risk_manager: Arc<RwLock<RiskManager>>,
+  /// Per-market fee schedules (configured ones until shared).
+  fees: Arc<FeeSchedules>,
+  /// Strategy parameters.
+  config: NegRiskArbConfig,
+  /// Event groups: (neg-risk market ID, outcomes by question index).
+  groups: Vec<(String, Vec<MarketConfig>)>,
+  /// Latest top of book per token.
+  books: HashMap<TokenId, BookTop>,
+  /// Last attempt per event (cooldown).
+  last_attempt: HashMap<String, Instant>,
+  /// Reloaded configs from the `ConfigWatcher`, if attached.
+  config_updates: Option<ReloadSubscriber>,
+}
+
+impl<F: MarketFeed, E: OrderExecution, C: ChainClient> NegRiskArbitrage<F, E, C> {
+  /// Create the strategy from `[neg_risk_arb]` and `[[markets]]` config.
+  pub fn new(
+    feed: Arc<F>,
+    execution: Arc<E>,
+    chain: Arc<C>,
+    ledger: Arc<PortfolioLedger>,
+    risk_manager: Arc<RwLock<RiskManager>>,
+    config: &AppConfig,
+  ) -> Self {
+    Self {
+      feed,
+      orders: OrderManager::new(Arc::clone(&execution), config),
+      execution,
+      chain,
+      ledger,
+      risk_manager,
+      fees: Arc::new(FeeSchedules::new(config)),
+      config: config.neg_risk_arb.clone(),
+      groups: event_groups(config),
+      books: HashMap::new(),
+      last_attempt: HashMap::new(),
+      config_updates: None,
+    }
+  }
+
+  /// Price legs at the shared schedules, refreshed from the CLOB.
+  pub fn with_fees(mut self, fees: Arc<FeeSchedules>) -> Self {
+    self.fees = fees;
+    self
+  }
+
+  /// Apply `[[markets]]` published by the `ConfigWatcher` while running.
+  pub fn with_config_updates(mut self, updates: ReloadSubscriber) -> Self {
+    self.config_updates = Some(updates);
+    self
+  }
+
+  /// Apply a reloaded config: events pause or resume with the
+  /// `active` flag of their outcomes and `[rate_limits]` apply to the
+  /// legs. The market set itself is fixed
+  /// until restart (`hot_reload::stage_reload`).
+  pub fn apply_config(&mut self, config: &AppConfig) {
+    self.groups = event_groups(config);
+    self.orders.set_rate_limits(&config.rate_limits);
+    let paused = self
+      .groups
+      .iter()
+      .filter(|(_, members)| !members.iter().all(|m| m.active))
+      .count();
+    info!(events = self.groups.len(), paused, "Neg-risk config applied");
+  }
+
+  /// Subscribe to both legs of every outcome and trade until shutdown.
+  pub async fn run(&mut self, mut shutdown_rx: broadcast::Receiver<()>) -> Result<()> {
+    let token_ids: Vec<TokenId> = self
+      .groups
+      .iter()
+      .flat_map(|(_, members)| members.iter())
+      .flat_map(|m| [m.yes_token_id.clone(), m.no_token_id.clone()])
+      .collect();
+    let mut receivers = self.feed.subscribe_many(&token_ids);
+    info!(
+      events = self.groups.len(),
+      min_edge = self.config.min_edge,
+      "Neg-risk arbitrage started"
+    );
+
+    loop {
+      let event = tokio::select! {
+        biased;
+        update = next_config(&mut self.config_updates) => {
+          self.apply_config(&update.config);
+          if let Some(updates) = &self.config_updates {
+            updates.ack(update.generation, &Ok(()));
+          }
+          continue;
+        }
+        event = recv_first_event(&mut receivers, &mut shutdown_rx) => event,
+      };
+      match event {
+        FeedEvent::Shutdown => break,
+        FeedEvent::Update(update) => {
+          if let Err(e) = self.on_update(&update).await {
+            warn!(error = %e, token = %update.token_id, "Neg-risk trade failed");
+          }
+        }
+        FeedEvent::Lagged(count) => {
+          warn!(dropped = count, "Neg-risk receiver lagged");
+        }
+      }
+    }
+    info!("Neg-risk arbitrage stopped");
+    Ok(())
+  }
+
+  /// Record a book update and trade the event's basket if mispriced.
+  #[instrument(skip(self, update), fields(token = %update.token_id))]
+  pub async fn on_update(&mut self, update: &PriceUpdate) -> Result<Option<NegRiskTrade>> {
+    self.books.insert(
+      update.token_id.clone(),
+      BookTop {
+        bid: update.best_bid,
+        bid_size: update.bid_size,
+        ask: update.best_ask,
+        ask_size: update.ask_size,
+      },
+    );
+
+    let Some((event_id, members)) = self
+      .groups
+      .iter()
+      .find(|(_, members)| {
+        members
+          .iter()
+          .any(|m| m.yes_token_id == update.token_id || m.no_token_id == update.token_id)
+      })
+      .cloned()
+    else {
+      return Ok(None);
+    };
+    if !members.iter().all(|m| m.active) {
+      return Ok(None);
+    }
+
+    let outcomes: Vec<OutcomeBook> = members
+      .iter()
+      .map(|m| OutcomeBook {
+        yes: self.books.get(&m.yes_token_id).copied().unwrap_or_default(),
+        no: self.books.get(&m.no_token_id).copied().unwrap_or_default(),
+      })
+      .collect();
+    let mut fees = Vec::with_capacity(members.len());
+    for m in &members {
+      fees.push(self.fees.for_market(&m.condition_id).await.calculator());
+    }
+    let Some(opportunity) =
+      neg_risk::evaluate(&outcomes, &fees, self.config.min_edge, self.config.max_size)
+    else {
+      return Ok(None);
+    };
+
+    // Splits and conversions go on-chain outside the RiskGate, and the
+    // YES sells would pass a halt as reducing: refuse the whole basket.
+    {
+      let risk = self.risk_manager.read().await;
+      if !risk.can_trade() {
+        warn!(
+          event = %event_id,
+          halt = ?risk.halt_record().map(|h| &h.reason),
+          "Risk limits reached, neg-risk basket blocked"
+        );
+        return Ok(None);
+      }
+    }
+
+    // A throttled leg would be unwound, and a throttled unwind halts
+    let budget = 2 * opportunity.legs.len();
+    if self.orders.remaining_orders() < budget {
+      debug!(event = %event_id, budget, "Order rate budget too low for neg-risk basket");
+      return Ok(None);
+    }
+
+    let cooldown = Duration::from_millis(self.config.cooldown_ms);
+    if self
+      .last_attempt
+      .get(&event_id)
+      .is_some_and(|t| t.elapsed() < cooldown)
+    {
+      debug!(event = %event_id, "Neg-risk opportunity in cooldown");
+      return Ok(None);
+    }
+    self.last_attempt.insert(event_id.clone(), Instant::now());
+
+    info!(
+      event = %event_id,
+      kind = ?opportunity.kind,
+      legs = opportunity.legs.len(),
+      converted = opportunity.convert.len(),
+      size = opportunity.size,
+      edge = opportunity.edge_per_basket,
+      "Neg-risk opportunity"
+    );
+
+    let trade = match opportunity.kind {
+      NegRiskKind::BuyYesBasket => self.buy_yes_basket(&event_id, &members, opportunity).await?,
+      NegRiskKind::SplitSellYes => self.split_sell_yes(&event_id, &members, opportunity).await?,
+      NegRiskKind::ConvertNo => self.convert_no(&event_id, &members, opportunity).await?,
+    };
+    Ok(Some(trade))
+  }
+
+  /// Buy every YES; the basket pays 1 at resolution.
+  async fn buy_yes_basket(
+    &mut self,
+    event_id: &str,
+    members: &[MarketConfig],
+    opportunity: NegRiskOpportunity,
+  ) -> Result<NegRiskTrade> {
+    let (filled, missed) = self.take_legs(members, &opportunity.legs, opportunity.size).await;
+    let unwound = !missed.is_empty();
+    if unwound {
+      warn!(event = %event_id, legs_filled = filled.len(), "Partial YES basket — selling filled legs");
+      self.flatten(event_id, members, &filled, opportunity.size).await?;
+    }
+    Ok(NegRiskTrade {
+      event_id: event_id.to_string(),
+      opportunity,
+      legs_filled: filled.len(),
+      chain_txs: Vec::new(),
+      unwound,
+    })
+  }
+
+  /// Split every outcome, sell every YES, convert every NO.
+  async fn split_sell_yes(
+    &mut self,
+    event_id: &str,
+    members: &[MarketConfig],
+    opportunity: NegRiskOpportunity,
+  ) -> Result<NegRiskTrade> {
+    let amount_raw = to_raw(opportunity.size);
+    let mut chain_txs = Vec::with_capacity(members.len() + 1);
+    for market in members {
+      // Sets split before a failure are left for the position merger
+      let split = self
+        .chain
+        .split_position(&market.condition_id, amount_raw)
+        .await
+        .with_context(|| format!("Split failed for {}", market.condition_id))?;
+      self.book_sets(market, TradeSide::Buy, split.sets_split).await;
+      chain_txs.push(split.tx_hash);
+    }
+
+    let (sold, unsold) = self.take_legs(members, &opportunity.legs, opportunity.size).await;
+    let unwound = !unsold.is_empty();
+    if unwound {
+      // Converting only some NO would mint YES on the unsold outcomes:
+      // merge those sets back and sell the NO left by the sold ones
+      warn!(event = %event_id, legs_filled = sold.len(), "Not all YES sold — unwinding split sets");
+      for leg in &unsold {
+        let condition_id = &members[leg.outcome].condition_id;
+        let merge = self
+          .chain
+          .merge_positions(condition_id, amount_raw)
+          .await
+          .with_context(|| format!("Unwind merge failed for {condition_id}"))?;
+        self.book_sets(&members[leg.outcome], TradeSide::Sell, merge.sets_merged).await;
+        chain_txs.push(merge.tx_hash);
+      }
+      let no_held: Vec<BasketLeg> = sold.iter().map(|leg| BasketLeg { yes: false, ..*leg }).collect();
+      self.flatten(event_id, members, &no_held, opportunity.size).await?;
+    } else {
+      // A full set of NO pays out the same whichever outcome wins, so
+      // a failed conversion leaves no directional exposure
+      match self
+        .chain
+        .convert_positions(event_id, &question_indices(members, &opportunity.convert), amount_raw)
+        .await
+      {
+        Ok(convert) => {
+          self.book_convert(members, &opportunity.convert, &convert).await;
+          chain_txs.push(convert.tx_hash);
+        }
+        Err(e) => warn!(error = %e, event = %event_id, "Conversion failed — NO tokens kept"),
+      }
+    }
+
+    Ok(NegRiskTrade {
+      event_id: event_id.to_string(),
+      opportunity,
+      legs_filled: sold.len(),
+      chain_txs,
+      unwound,
+    })
+  }
+
+  /// Buy NO on the chosen outcomes, convert them, sell the YES received.
+  async fn convert_no(
+    &mut self,
+    event_id: &str,
+    members: &[MarketConfig],
+    opportunity: NegRiskOpportunity,
+  ) -> Result<NegRiskTrade> {
+    let (buys, sells): (Vec<BasketLeg>, Vec<BasketLeg>) =
+      opportunity.legs.iter().copied().partition(|l| l.side == TradeSide::Buy);
+
+    let (bought, missed) = self.take_legs(members, &buys, opportunity.size).await;
+    let mut legs_filled = bought.len();
+    let mut chain_txs = Vec::new();
+    let mut unwound = true;
+    if missed.is_empty() {
+      match self
+        .chain
+        .convert_positions(
+          event_id,
+          &question_indices(members, &opportunity.convert),
+          to_raw(opportunity.size),
+        )
+        .await
+      {
+        Ok(convert) => {
+          self.book_convert(members, &opportunity.convert, &convert).await;
+          chain_txs.push(convert.tx_hash);
+          // The adapter fee comes out of the YES minted
+          let (sold, unsold) = self.take_legs(members, &sells, convert.minted).await;
+          legs_filled += sold.len();
+          unwound = !unsold.is_empty();
+          if unwound {
+            warn!(event = %event_id, unsold = unsold.len(), "Not all YES sold — selling at market");
+            self.flatten(event_id, members, &unsold, convert.minted).await?;
+          }
+        }
+        Err(e) => {
+          warn!(error = %e, event = %event_id, "Conversion failed — selling NO tokens");
+          self.flatten(event_id, members, &bought, opportunity.size).await?;
+        }
+      }
+    } else {
+      warn!(event = %event_id, legs_filled, "Partial NO fill — selling filled legs");
+      self.flatten(event_id, members, &bought, opportunity.size).await?;
+    }
+
+    Ok(NegRiskTrade {
+      event_id: event_id.to_string(),
+      opportunity,
+      legs_filled,
+      chain_txs,
+      unwound,
+    })
+  }
+
+  /// Sell the tokens of `held` legs at market.
+  ///
+  /// If any is left, trading is halted (`HaltSource::Unwind`) and an
+  /// error returned.
+  async fn flatten(
+    &mut self,
+    event_id: &str,
+    members: &[MarketConfig],
+    held: &[BasketLeg],
+    size: f64,
+  ) -> Result<()> {
+    let sells: Vec<BasketLeg> = held
+      .iter()
+      .map(|leg| BasketLeg {
+        side: TradeSide::Sell,
+        price: FLATTEN_SELL_PRICE,
+        ..*leg
+      })
+      .collect();
+    let (_, unsold) = self.take_legs(members, &sells, size).await;
+    if !unsold.is_empty() {
+      let reason = format!("Neg-risk event {event_id}: {} legs could not be flattened", unsold.len());
+      error!(event = %event_id, unsold = unsold.len(), "Unwind failed, position left open");
+      self
+        .risk_manager
+        .write()
+        .await
+        .halt(&reason, HaltSource::Unwind, now_ms());
+      bail!(reason);
+    }
+    Ok(())
+  }
+
+  /// Credit complete sets of `market` minted (`Buy`) or burned
+  /// (`Sell`) to the gate's positions, half a set per outcome, and
+  /// book them in the ledger.
+  async fn book_sets(&self, market: &MarketConfig, side: TradeSide, sets: f64) {
+    for token_id in [&market.yes_token_id, &market.no_token_id] {
+      self.execution.record_transfer(token_id, side, 0.5, sets).await;
+    }
+    match side {
+      TradeSide::Buy => self.ledger.record_split(&market.condition_id, sets).await,
+      TradeSide::Sell => self.ledger.record_merge(&market.condition_id, sets).await,
+    };
+  }
+
+  /// Book the NO of the `converted` outcomes burned and the YES minted
+  /// on the others; the gate carries both at the ledger's basis.
+  async fn book_convert(&self, members: &[MarketConfig], converted: &[usize], result: &ConvertResult) {
+    let burned: Vec<TokenId> = converted.iter().map(|&i| members[i].no_token_id.clone()).collect();
+    let minted: Vec<TokenId> = members
+      .iter()
+      .enumerate()
+      .filter(|(i, _)| !converted.contains(i))
+      .map(|(_, m)| m.yes_token_id.clone())
+      .collect();
+
+    for token_id in &burned {
+      let entry = self.ledger.avg_entry_price(token_id).await.unwrap_or(0.0);
+      self.execution.record_transfer(token_id, TradeSide::Sell, entry, result.amount).await;
+    }
+    self
+      .ledger
+      .record_convert(burned, minted.clone(), result.amount, result.minted, result.usdc_recovered)
+      .await;
+    for token_id in &minted {
+      let entry = self.ledger.avg_entry_price(token_id).await.unwrap_or(0.0);
+      self.execution.record_transfer(token_id, TradeSide::Buy, entry, result.minted).await;
+    }
+  }
+
+  /// Send FOK legs in order through the `OrderManager`; returns the
+  /// (filled, missed) legs. A throttled leg counts as missed.
+  async fn take_legs(
+    &mut self,
+    members: &[MarketConfig],
+    legs: &[BasketLeg],
+    size: f64,
+  ) -> (Vec<BasketLeg>, Vec<BasketLeg>) {
+    let mut filled = Vec::with_capacity(legs.len());
+    let mut missed = Vec::new();
+    for leg in legs {
+      let market = &members[leg.outcome];
+      let token_id = if leg.yes { &market.yes_token_id } else { &market.no_token_id };
+      let order = Order::new_taker(token_id.clone(), leg.side, leg.price, size);
+      match self.orders.place_order(order).await {
+        Ok(Some(placement)) if placement.accepted => filled.push(*leg),
+        Ok(Some(_)) => missed.push(*leg),
+        Ok(None) => {
+          debug!(token = %token_id, "Leg throttled");
+          missed.push(*leg);
+        }
+        Err(e) => {
+          debug!(error = %e, "Leg failed");
+          missed.push(*leg);
+        }
+      }
+    }
+    (filled, missed)
+  }
+}
+
+/// Tradable event groups (two or more outcomes) from `[[markets]]`.
+fn event_groups(config: &AppConfig) -> Vec<(String, Vec<MarketConfig>)> {
+  config
+    .neg_risk_groups()
+    .into_iter()
+    .filter(|(_, members)| members.len() >= 2)
+    .collect()
+}
+
+/// Question indices of the given outcome positions.
+fn question_indices(members: &[MarketConfig], outcomes: &[usize]) -> Vec<u32> {
+  outcomes
+    .iter()
+    .map(|&i| members[i].question_index.unwrap_or(i as u32))
+    .collect()
+}
+
+/// Whole tokens to atomic units.
+fn to_raw(size: f64) -> u128 {
+  (size * TOKEN_SCALE).round().max(0.0) as u128
+}
@@ -14,7 +14,7 @@ use std::time::Instant;
 use anyhow::Result;
 use tracing::{debug, info, instrument, warn};
 
-use crate::config::AppConfig;
+use crate::config::{AppConfig, RateLimitConfig};
 use crate::domain::trade::{Order, OrderId, OrderType, TradeSide, TokenId};
 use crate::ports::execution::{OrderExecution, OrderPlacement};
 
@@ -47,6 +47,12 @@ impl<E: OrderExecution> OrderManager<E> {
     }
   }
 
+  /// Apply reloaded `[rate_limits]`; the recent-order window is kept.
+  pub fn set_rate_limits(&mut self, config: &RateLimitConfig) {
+    self.max_orders_per_minute = config.max_orders_per_minute;
+    self.min_interval_ms = config.min_interval_ms;
+  }
+
   /// Place a maker-only GTC order.
   ///
   /// All orders are post-only to guarantee maker execution
@@ -59,25 +65,6 @@ impl<E: OrderExecution> OrderManager<E> {
     size: f64,
     is_buy: bool,
   ) -> Result<Option<OrderPlacement>> {
-    // Rate limit check
-    if !self.check_rate_limit() {
-      debug!("Rate limit reached, skipping order");
-      return Ok(None);
-    }
-
-    // Enforce minimum interval
-    if let Some(last) = self.last_order_time {
-      let elapsed = last.elapsed().as_millis() as u64;
-      if elapsed < self.min_interval_ms {
-        debug!(
-          elapsed_ms = elapsed,
-          min_ms = self.min_interval_ms,
-          "Minimum interval not met"
-        );
-        return Ok(None);
-      }
-    }
-
     let side = if is_buy {
       TradeSide::Buy
     } else {
@@ -96,19 +83,53 @@ impl<E: OrderExecution> OrderManager<E> {
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
         .as_millis() as u64,
+      salt: String::new(), // Generated by the executor
     };
 
+    self.place_order(order).await
+  }
+
+  /// Place an explicitly built order (e.g. a strategy's FOK leg).
+  ///
+  /// Every order counts against the per-minute budget; the minimum
+  /// interval only throttles maker quotes, so the legs of a multi-leg
+  /// trade go out back to back. Returns `None` when throttled.
+  #[instrument(skip(self, order), fields(token = %order.token_id, price = order.price, size = order.size))]
+  pub async fn place_order(&mut self, order: Order) -> Result<Option<OrderPlacement>> {
+    // Rate limit check
+    if !self.check_rate_limit() {
+      debug!("Rate limit reached, skipping order");
+      return Ok(None);
+    }
+
+    // Enforce minimum interval between quotes
+    if let Some(last) = self.last_order_time.filter(|_| order.post_only) {
+      let elapsed = last.elapsed().as_millis() as u64;
+      if elapsed < self.min_interval_ms {
+        debug!(
+          elapsed_ms = elapsed,
+          min_ms = self.min_interval_ms,
+          "Minimum interval not met"
+        );
+        return Ok(None);
+      }
+    }
+
     let result = self.execution.place_order(&order).await?;
 
     if result.accepted {
-      let mut tracked = order;
-      tracked.id = result.order_id.clone();
-      self.open_orders.insert(result.order_id.clone(), tracked);
       self.record_order();
       info!(
         order_id = %result.order_id,
-        "Maker order placed successfully"
+        post_only = order.post_only,
+        "Order placed successfully"
       );
+      // Only resting orders are tracked; FOK orders are done on return
+      if order.post_only {
+        let mut tracked = order;
+        tracked.id.clone_from(&result.order_id);
+        self.open_orders.insert(result.order_id.clone(), tracked);
+      }
     } else {
       warn!(
         reason = ?result.rejection_reason,
@@ -128,20 +149,34 @@ impl<E: OrderExecution> OrderManager<E> {
     Ok(count)
   }
 
+  /// Cancel all open orders on one token and stop tracking them.
+  #[instrument(skip(self))]
+  pub async fn cancel_token(&mut self, token_id: &TokenId) -> Result<usize> {
+    let results = self.execution.cancel_orders_for_token(token_id).await?;
+    self.open_orders.retain(|_, o| &o.token_id != token_id);
+    let count = results.iter().filter(|c| c.success).count();
+    debug!(token = %token_id, cancelled = count, "Token orders cancelled");
+    Ok(count)
+  }
+
   /// Get the number of currently tracked open orders.
   pub fn open_order_count(&self) -> usize {
     self.open_orders.len()
   }
 
-  /// Check if we're within rate limits.
-  fn check_rate_limit(&mut self) -> bool {
+  /// Orders still allowed within the current minute.
+  pub fn remaining_orders(&mut self) -> usize {
     let now = Instant::now();
     // Remove timestamps older than 1 minute
     self
       .order_timestamps
       .retain(|t| now.duration_since(*t).as_secs() < 60);
+    (self.max_orders_per_minute as usize).saturating_sub(self.order_timestamps.len())
+  }
 
-    self.order_timestamps.len() < self.max_orders_per_minute as usize
+  /// Check if we're within rate limits.
+  fn check_rate_limit(&mut self) -> bool {
+    self.remaining_orders() > 0
   }
 
   /// Record an order placement for rate limiting.
@@ -0,0 +1,488 @@
+//! Portfolio Ledger - Fills, Marks and Daily PnL
+//!
+//! Owns the `Portfolio` and feeds it from the rest of the bot:
+//! - `RiskGate` reports fills; taker fills pay the market's
+//!   `FeeSchedules` rate, maker fills pay nothing and accrue the
+//!   market's maker rebate as a separate `Fee` event
+//! - Strategies report the splits, merges and neg-risk conversions
+//!   they run on-chain, `PositionMerger` and `SettlementScheduler` report merges and
+//!   redemptions
+//! - The realized PnL of all of them is the cumulative PnL
+//!   (`BotStateSnapshot.cumulative_pnl`)
+//! - Every `portfolio.mark_interval_secs` held tokens are marked to the
+//!   feed's last mid, per-asset PnL gauges are published and, if events
+//!   were booked, a checkpoint is requested from the `StateCheckpointer`
+//! - At UTC midnight the day is closed into a `DailyPnl` record
+//!
+//! Realized PnL of every closing order, merge and redemption goes to
+//! `RiskManager::record_trade`, so the daily loss budget and the loss
+//! streak follow actual results. Sell fills booked against an order
+//! are summed and reported once, when the gate closes the order.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::sync::{broadcast, Mutex, Notify, RwLock};
+use tracing::{debug, info, instrument, warn};
+
+use crate::config::AppConfig;
+use crate::domain::portfolio::{AssetPnl, LedgerEvent, MarketPnl, Portfolio};
+use crate::domain::time::{now_ms, utc_day};
+use crate::domain::trade::{OrderId, Position, TokenId, TradeSide};
+use crate::ports::market_feed::MarketFeed;
+use crate::ports::metrics::MetricsSink;
+use crate::ports::repository::{DailyPnl, Repository};
+
+use super::fee_schedule::FeeSchedules;
+use super::risk_manager::RiskManager;
+
+/// Shared portfolio ledger.
+pub struct PortfolioLedger {
+  /// Lots, marks and PnL.
+  portfolio: RwLock<Portfolio>,
+  /// Receives realized PnL of closing events.
+  risk: Option<Arc<RwLock<RiskManager>>>,
+  /// PnL gauges.
+  metrics: Arc<dyn MetricsSink>,
+  /// Per-market taker rates and maker rebates.
+  fees: Arc<FeeSchedules>,
+  /// Realized PnL carried over from a snapshot without a saved ledger.
+  carried_pnl: RwLock<f64>,
+  /// Realized PnL of sell fills per order not yet closed.
+  open_trades: Mutex<HashMap<OrderId, f64>>,
+  /// Events applied since the last checkpoint request.
+  dirty: AtomicBool,
+  /// Checkpoint requests to the `StateCheckpointer`, if attached.
+  checkpoints: Option<Arc<Notify>>,
+}
+
+impl PortfolioLedger {
+  /// Create an empty ledger for the configured markets.
+  pub fn new(config: &AppConfig, metrics: Arc<dyn MetricsSink>) -> Self {
+    let mut portfolio = Portfolio::new(config.portfolio.cost_method);
+    for m in &config.markets {
+      portfolio.register_market(&m.condition_id, m.asset, &m.yes_token_id, &m.no_token_id);
+    }
+    Self {
+      portfolio: RwLock::new(portfolio),
+      risk: None,
+      metrics,
+      fees: Arc::new(FeeSchedules::new(config)),
+      carried_pnl: RwLock::new(0.0),
+      open_trades: Mutex::new(HashMap::new()),
+      dirty: AtomicBool::new(false),
+      checkpoints: None,
+    }
+  }
+
+  /// Report realized PnL to the risk manager.
+  pub fn with_risk(mut self, risk: Arc<RwLock<RiskManager>>) -> Self {
+    self.risk = Some(risk);
+    self
+  }
+
+  /// Price fills with shared (CLOB-refreshed) fee schedules.
+  pub fn with_fees(mut self, fees: Arc<FeeSchedules>) -> Self {
+    self.fees = fees;
+    self
+  }
+
+  /// Request a checkpoint through `checkpoints` after new events.
+  pub fn with_checkpoints(mut self, checkpoints: Arc<Notify>) -> Self {
+    self.checkpoints = Some(checkpoints);
+    self
+  }
+
+  /// Book a fill and any maker rebate; returns the PnL they realized.
+  ///
+  /// A closing fill is reported to the risk manager as a trade on its own.
+  pub async fn record_fill(
+    &self,
+    token_id: &str,
+    side: TradeSide,
+    price: f64,
+    size: f64,
+    is_maker: bool,
+  ) -> f64 {
+    self.fill(None, token_id, side, price, size, is_maker).await
+  }
+
+  /// Book a (partial) fill of `order_id`, like `record_fill`, but hold
+  /// its realized PnL until `close_order`.
+  pub async fn record_order_fill(
+    &self,
+    order_id: &str,
+    token_id: &str,
+    side: TradeSide,
+    price: f64,
+    size: f64,
+    is_maker: bool,
+  ) -> f64 {
+    self.fill(Some(order_id), token_id, side, price, size, is_maker).await
+  }
+
+  /// Report the PnL realized by all fills of `order_id` as one trade.
+  pub async fn close_order(&self, order_id: &str) {
+    let realized = self.open_trades.lock().await.remove(order_id);
+    if let Some(realized) = realized {
+      self.report(realized).await;
+    }
+  }
+
+  /// Book a fill, then report or accumulate its realized PnL.
+  async fn fill(
+    &self,
+    order_id: Option<&str>,
+    token_id: &str,
+    side: TradeSide,
+    price: f64,
+    size: f64,
+    is_maker: bool,
+  ) -> f64 {
+    let schedule = self.fees.for_token(token_id).await;
+    let fee = if is_maker { 0.0 } else { schedule.taker_fee(price, size) };
+    let realized = self
+      .book(&LedgerEvent::Fill {
+        token_id: token_id.to_string(),
+        side,
+        price,
+        size,
+        fee,
+        timestamp_ms: now_ms(),
+      })
+      .await;
+    if side == TradeSide::Sell {
+      match order_id {
+        Some(id) => {
+          *self.open_trades.lock().await.entry(id.to_string()).or_insert(0.0) += realized;
+        }
+        None => self.report(realized).await,
+      }
+    }
+
+    let rebate = if is_maker { schedule.maker_rebate(price, size) } else { 0.0 };
+    if rebate > 0.0 {
+      let market_id = self.fees.market_of(token_id);
+      return realized + self.record_fee(&market_id, -rebate).await;
+    }
+    realized
+  }
+
+  /// Book a fee (positive) or rebate (negative) for a market.
+  pub async fn record_fee(&self, market_id: &str, amount: f64) -> f64 {
+    self
+      .apply(LedgerEvent::Fee {
+        market_id: market_id.to_string(),
+        amount,
+      })
+      .await
+  }
+
+  /// Book USDC split into complete sets.
+  pub async fn record_split(&self, market_id: &str, sets: f64) -> f64 {
+    self
+      .apply(LedgerEvent::Split {
+        market_id: market_id.to_string(),
+        sets,
+        timestamp_ms: now_ms(),
+      })
+      .await
+  }
+
+  /// Book complete sets merged back into USDC.
+  pub async fn record_merge(&self, market_id: &str, sets: f64) -> f64 {
+    self
+      .apply(LedgerEvent::Merge {
+        market_id: market_id.to_string(),
+        sets,
+      })
+      .await
+  }
+
+  /// Book neg-risk NO (`burned`, `amount` each) converted into `usdc`
+  /// plus YES of the other outcomes (`minted`, `minted_amount` each).
+  pub async fn record_convert(
+    &self,
+    burned: Vec<TokenId>,
+    minted: Vec<TokenId>,
+    amount: f64,
+    minted_amount: f64,
+    usdc: f64,
+  ) -> f64 {
+    self
+      .apply(LedgerEvent::Convert {
+        burned,
+        minted,
+        amount,
+        minted_amount,
+        usdc,
+        timestamp_ms: now_ms(),
+      })
+      .await
+  }
+
+  /// Book the redemption of a resolved market.
+  pub async fn record_redemption(&self, market_id: &str, yes_payout: f64, no_payout: f64) -> f64 {
+    self
+      .apply(LedgerEvent::Redemption {
+        market_id: market_id.to_string(),
+        yes_payout,
+        no_payout,
+      })
+      .await
+  }
+
+  /// Realized PnL of every fill, merge, conversion and redemption
+  /// booked so far, net of fees.
+  pub async fn cumulative_pnl(&self) -> f64 {
+    self.portfolio.read().await.total_realized() + *self.carried_pnl.read().await
+  }
+
+  /// Apply an event and pass closing PnL on to the risk manager.
+  ///
+  /// Fills are reported by `fill`, per order.
+  async fn apply(&self, event: LedgerEvent) -> f64 {
+    let realized = self.book(&event).await;
+    let closes = match &event {
+      LedgerEvent::Fill { .. } | LedgerEvent::Fee { .. } | LedgerEvent::Split { .. } => false,
+      LedgerEvent::Merge { .. } | LedgerEvent::Convert { .. } | LedgerEvent::Redemption { .. } => true,
+    };
+    if closes {
+      self.report(realized).await;
+    }
+    realized
+  }
+
+  /// Apply an event to the portfolio; returns the PnL it realized.
+  async fn book(&self, event: &LedgerEvent) -> f64 {
+    let realized = self.portfolio.write().await.apply(event);
+    self.dirty.store(true, Ordering::Release);
+    debug!(event = ?event, realized, "Ledger event booked");
+    realized
+  }
+
+  /// Pass a closed trade's realized PnL on to the risk manager.
+  async fn report(&self, realized: f64) {
+    if realized == 0.0 {
+      return;
+    }
+    if let Some(risk) = &self.risk {
+      risk.write().await.record_trade(realized);
+    }
+  }
+
+  /// Mark held tokens to the feed's last mid; returns how many were marked.
+  pub async fn mark_from_feed<F: MarketFeed>(&self, feed: &F) -> usize {
+    let held = self.portfolio.read().await.held_tokens();
+    let mut marks = Vec::with_capacity(held.len());
+    for token_id in held {
+      if let Some(mid) = feed.last_price(&token_id).await.and_then(|u| u.mid_price) {
+        marks.push((token_id, mid));
+      }
+    }
+
+    let mut portfolio = self.portfolio.write().await;
+    for (token_id, mid) in &marks {
+      portfolio.mark(token_id, *mid);
+    }
+    marks.len()
+  }
+
+  /// Publish per-asset realized and unrealized PnL.
+  pub async fn publish_metrics(&self) {
+    for row in self.by_asset().await {
+      self.metrics.pnl_updated(&row.asset, row.realized, row.unrealized);
+    }
+  }
+
+  /// Switch to `date`, returning the closed day's summary.
+  pub async fn roll_day(&self, date: &str) -> Option<DailyPnl> {
+    let day = self.portfolio.write().await.roll_day(date)?;
+    self.dirty.store(true, Ordering::Release);
+    Some(DailyPnl {
+      date: day.date,
+      realized_pnl: day.realized,
+      unrealized_pnl: day.unrealized,
+      trade_count: day.trade_count,
+      volume: day.volume,
+      max_drawdown: day.max_drawdown,
+      fees: day.fees,
+      rebates: day.rebates,
+    })
+  }
+
+  /// Copy of the portfolio (for snapshots).
+  pub async fn snapshot(&self) -> Portfolio {
+    self.portfolio.read().await.clone()
+  }
+
+  /// Size held per token.
+  pub async fn sizes(&self) -> Vec<(TokenId, f64)> {
+    self.portfolio.read().await.sizes()
+  }
+
+  /// Cost basis held per token (USDC).
+  pub async fn costs(&self) -> Vec<(TokenId, f64)> {
+    self.portfolio.read().await.costs()
+  }
+
+  /// Average cost per token of an open position.
+  pub async fn avg_entry_price(&self, token_id: &str) -> Option<f64> {
+    self.portfolio.read().await.avg_entry_price(token_id)
+  }
+
+  /// Open positions, marked to market.
+  pub async fn positions(&self) -> Vec<Position> {
+    self.portfolio.read().await.positions()
+  }
+
+  /// PnL per market.
+  pub async fn by_market(&self) -> Vec<MarketPnl> {
+    self.portfolio.read().await.by_market()
+  }
+
+  /// PnL per asset.
+  pub async fn by_asset(&self) -> Vec<AssetPnl> {
+    self.portfolio.read().await.by_asset()
+  }
+
+  /// Load the ledger from the latest snapshot, if it has one; an
+  /// older snapshot without it only carries its cumulative PnL over.
+  #[instrument(skip(self, repo))]
+  pub async fn restore<R: Repository>(&self, repo: &R) -> Result<bool> {
+    let Some(state) = repo.load_latest_state().await? else {
+      return Ok(false);
+    };
+    let Some(saved) = state.portfolio else {
+      *self.carried_pnl.write().await = state.cumulative_pnl;
+      return Ok(false);
+    };
+    let mut portfolio = self.portfolio.write().await;
+    portfolio.restore(saved);
+    info!(
+      positions = portfolio.held_tokens().len(),
+      realized = portfolio.total_realized(),
+      day = portfolio.day(),
+      "Portfolio ledger restored"
+    );
+    Ok(true)
+  }
+
+  /// Mark, close the day if it changed, publish and request a
+  /// checkpoint if events were booked.
+  pub async fn tick<F: MarketFeed, R: Repository>(
+    &self,
+    feed: &F,
+    repo: &R,
+    now_ms: u64,
+  ) -> Result<()> {
+    self.mark_from_feed(feed).await;
+
+    if let Some(daily) = self.roll_day(&utc_day(now_ms)).await {
+      info!(
+        date = %daily.date,
+        realized = daily.realized_pnl,
+        unrealized = daily.unrealized_pnl,
+        trades = daily.trade_count,
+        max_drawdown = daily.max_drawdown,
+        "Trading day closed"
+      );
+      repo.save_daily_pnl(&daily).await?;
+    }
+    self.publish_metrics().await;
+
+    if let Some(checkpoints) = &self.checkpoints {
+      if self.dirty.swap(false, Ordering::AcqRel) {
+        checkpoints.notify_one();
+      }
+    }
+    Ok(())
+  }
+
+  /// Run `tick` on the configured interval until shutdown.
+  pub async fn run<F: MarketFeed, R: Repository>(
+    self: Arc<Self>,
+    feed: Arc<F>,
+    repo: Arc<R>,
+    interval: Duration,
+    mut shutdown_rx: broadcast::Receiver<()>,
+  ) {
+    info!(interval_secs = interval.as_secs(), "Portfolio ledger started");
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+      tokio::select! {
+        biased;
+        _ = shutdown_rx.recv() => break,
+        _ = ticker.tick() => {
+          if let Err(e) = self.tick(feed.as_ref(), repo.as_ref(), now_ms()).await {
+            warn!(error = %e, "Portfolio ledger tick failed");
+          }
+        }
+      }
+    }
+    info!("Portfolio ledger stopped");
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use crate::ports::metrics::NoopMetrics;
+
+  #[tokio::test]
+  async fn test_maker_fill_accrues_rebate_outside_the_basis() {
+    let mut config = crate::config::loader::load_config("config.toml.example").unwrap();
+    config.fees.maker_rebate_share = 0.5;
+    let token = config.markets[0].yes_token_id.clone();
+    let ledger = PortfolioLedger::new(&config, Arc::new(NoopMetrics));
+
+    let rebate = ledger.record_fill(&token, TradeSide::Buy, 0.50, 100.0, true).await;
+    let expected = ledger.fees.for_token(&token).await.maker_rebate(0.50, 100.0);
+    assert!(rebate > 0.0 && (rebate - expected).abs() < 1e-12);
+    assert_eq!(ledger.avg_entry_price(&token).await, Some(0.50));
+
+    let portfolio = ledger.snapshot().await;
+    assert!((portfolio.rebates() - expected).abs() < 1e-12);
+    assert_eq!(portfolio.fees_paid(), 0.0);
+  }
+
+  #[tokio::test]
+  async fn test_taker_fill_pays_fee_and_sale_reaches_risk() {
+    let config = crate::config::loader::load_config("config.toml.example").unwrap();
+    let risk = Arc::new(RwLock::new(RiskManager::new(&config.risk)));
+    let ledger = PortfolioLedger::new(&config, Arc::new(NoopMetrics)).with_risk(Arc::clone(&risk));
+
+    ledger.record_fill("tok", TradeSide::Buy, 0.50, 100.0, false).await;
+    let entry = ledger.avg_entry_price("tok").await.unwrap();
+    assert!(entry > 0.50, "taker fee capitalized into the basis");
+
+    let realized = ledger.record_fill("tok", TradeSide::Sell, 0.40, 100.0, true).await;
+    assert!((realized - (40.0 - entry * 100.0)).abs() < 1e-9);
+    assert!((risk.read().await.snapshot().daily_loss + realized).abs() < 1e-9);
+  }
+
+  #[tokio::test]
+  async fn test_partial_fills_of_one_order_count_as_one_trade() {
+    let config = crate::config::loader::load_config("config.toml.example").unwrap();
+    let risk = Arc::new(RwLock::new(RiskManager::new(&config.risk)));
+    let ledger = PortfolioLedger::new(&config, Arc::new(NoopMetrics)).with_risk(Arc::clone(&risk));
+
+    ledger.record_fill("tok", TradeSide::Buy, 0.50, 90.0, true).await;
+    let mut realized = 0.0;
+    for _ in 0..3 {
+      realized += ledger
+        .record_order_fill("ord_1", "tok", TradeSide::Sell, 0.40, 30.0, true)
+        .await;
+    }
+    assert_eq!(risk.read().await.snapshot().consecutive_losses, 0);
+
+    ledger.close_order("ord_1").await;
+    let state = risk.read().await.snapshot();
+    assert_eq!(state.consecutive_losses, 1);
+    assert!((state.daily_loss + realized).abs() < 1e-9);
+  }
+}
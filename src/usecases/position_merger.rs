@@ -0,0 +1,210 @@
+//! Position Merger - Recover USDC from Complete Sets
+//!
+//! Holding both YES and NO of the same condition locks capital that
+//! can be released immediately: `min(yes, no)` complete sets are merged
+//! back into USDC via ConditionalTokens `mergePositions` (or the
+//! NegRiskAdapter for neg-risk markets).
+//! - Balances come from on-chain ERC-1155 queries of configured markets
+//! - Skipped while gas is above `settlement.max_gas_gwei`
+//! - Each merge is appended to the trade log with side `Merge` and
+//!   booked in the `PortfolioLedger`, when one is attached
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use tokio::sync::broadcast;
+use tracing::{info, instrument, warn};
+use uuid::Uuid;
+
+use crate::config::{AppConfig, MarketConfig};
+use crate::ports::chain_client::{ChainClient, MergeResult};
+use crate::ports::repository::{Repository, TradeRecord};
+
+use super::portfolio_ledger::PortfolioLedger;
+
+/// Outcome token decimals (atomic units per whole token).
+const TOKEN_SCALE: f64 = 1e6;
+
+/// Trade-log side recorded for merges.
+pub const MERGE_SIDE: &str = "Merge";
+
+/// Complete sets (atomic units) mergeable from a YES/NO pair, or `None`
+/// when below `min_raw`.
+pub fn mergeable_sets(yes_raw: u128, no_raw: u128, min_raw: u128) -> Option<u128> {
+  let sets = yes_raw.min(no_raw);
+  (sets > 0 && sets >= min_raw).then_some(sets)
+}
+
+/// A merge executed for one market.
+#[derive(Debug, Clone)]
+pub struct MergeOutcome {
+  /// Condition ID.
+  pub market_id: String,
+  /// On-chain result.
+  pub result: MergeResult,
+}
+
+/// Detects overlapping YES/NO holdings and merges them into USDC.
+pub struct PositionMerger<C: ChainClient, R: Repository> {
+  /// Chain client for balances, gas and merges.
+  chain: Arc<C>,
+  /// Repository for the trade log.
+  repo: Arc<R>,
+  /// Configured markets to scan.
+  markets: Vec<MarketConfig>,
+  /// Gas ceiling (gwei); above it merges are skipped.
+  max_gas_gwei: f64,
+  /// Minimum complete sets worth a transaction (atomic units).
+  min_merge_raw: u128,
+  /// Scan interval.
+  interval: Duration,
+  /// Ledger the merges are booked in, if attached.
+  ledger: Option<Arc<PortfolioLedger>>,
+}
+
+impl<C: ChainClient, R: Repository> PositionMerger<C, R> {
+  /// Create a merger from `[settlement]` and `[[markets]]` config.
+  pub fn new(chain: Arc<C>, repo: Arc<R>, config: &AppConfig) -> Self {
+    Self {
+      chain,
+      repo,
+      markets: config.markets.clone(),
+      max_gas_gwei: config.settlement.max_gas_gwei,
+      min_merge_raw: (config.settlement.min_merge_size.max(0.0) * TOKEN_SCALE) as u128,
+      interval: Duration::from_secs(config.settlement.merge_interval_secs),
+      ledger: None,
+    }
+  }
+
+  /// Book merges in `ledger`.
+  pub fn with_ledger(mut self, ledger: Arc<PortfolioLedger>) -> Self {
+    self.ledger = Some(ledger);
+    self
+  }
+
+  /// Scan balances once and merge every overlapping pair.
+  ///
+  /// A failed merge is logged and does not stop the others.
+  #[instrument(skip(self))]
+  pub async fn run_once(&self) -> Result<Vec<MergeOutcome>> {
+    if self.markets.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    let gas_gwei = self.chain.gas_price_gwei().await.context("Gas price query failed")?;
+    if gas_gwei > self.max_gas_gwei {
+      info!(gas_gwei, max_gas_gwei = self.max_gas_gwei, "Gas above limit — skipping merges");
+      return Ok(Vec::new());
+    }
+
+    let token_ids: Vec<String> = self
+      .markets
+      .iter()
+      .flat_map(|m| [m.yes_token_id.clone(), m.no_token_id.clone()])
+      .collect();
+    let balances = self
+      .chain
+      .token_balances(&token_ids)
+      .await
+      .context("Failed to query outcome token balances")?;
+    let raw_of = |token_id: &str| {
+      balances
+        .iter()
+        .find(|b| b.token_id == token_id)
+        .map_or(0, |b| b.balance_raw)
+    };
+
+    let mut outcomes = Vec::new();
+    for market in &self.markets {
+      let Some(sets) = mergeable_sets(
+        raw_of(&market.yes_token_id),
+        raw_of(&market.no_token_id),
+        self.min_merge_raw,
+      ) else {
+        continue;
+      };
+
+      match self.chain.merge_positions(&market.condition_id, sets).await {
+        Ok(result) => {
+          info!(
+            market = %market.condition_id,
+            sets = result.sets_merged,
+            usdc = result.usdc_recovered,
+            tx = %result.tx_hash,
+            "Merged complete sets"
+          );
+          if let Err(e) = self.record(&market.condition_id, &result).await {
+            // The merge already happened on-chain; only the log entry is lost
+            warn!(error = %e, market = %market.condition_id, "Failed to record merge");
+          }
+          if let Some(ledger) = &self.ledger {
+            ledger.record_merge(&market.condition_id, result.sets_merged).await;
+          }
+          outcomes.push(MergeOutcome {
+            market_id: market.condition_id.clone(),
+            result,
+          });
+        }
+        Err(e) => {
+          warn!(error = %e, market = %market.condition_id, "Merge failed");
+        }
+      }
+    }
+    Ok(outcomes)
+  }
+
+  /// Run `run_once` on the configured interval until shutdown.
+  pub async fn run(self, mut shutdown_rx: broadcast::Receiver<()>) {
+    info!(interval_secs = self.interval.as_secs(), "Position merger started");
+    let mut ticker = tokio::time::interval(self.interval);
+    loop {
+      tokio::select! {
+        biased;
+        _ = shutdown_rx.recv() => break,
+        _ = ticker.tick() => {
+          if let Err(e) = self.run_once().await {
+            warn!(error = %e, "Merge scan failed");
+          }
+        }
+      }
+    }
+    info!("Position merger stopped");
+  }
+
+  /// Append a merge to the trade log.
+  async fn record(&self, market_id: &str, result: &MergeResult) -> Result<()> {
+    let record = TradeRecord {
+      id: Uuid::new_v4().to_string(),
+      order_id: result.tx_hash.clone(),
+      market_id: market_id.to_string(),
+      side: MERGE_SIDE.to_string(),
+      // One complete set always redeems for exactly 1 USDC
+      price: 1.0,
+      size: result.sets_merged,
+      lmsr_fair_value: 1.0,
+      edge: 0.0,
+      kelly_fraction: 0.0,
+      fees: 0.0,
+      timestamp_ms: Utc::now().timestamp_millis().max(0) as u64,
+    };
+    self.repo.save_trade(&record).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_mergeable_sets_takes_smaller_leg() {
+    assert_eq!(mergeable_sets(5_000_000, 3_000_000, 1_000_000), Some(3_000_000));
+  }
+
+  #[test]
+  fn test_mergeable_sets_respects_minimum() {
+    assert_eq!(mergeable_sets(5_000_000, 500_000, 1_000_000), None);
+    assert_eq!(mergeable_sets(5_000_000, 0, 0), None);
+  }
+}
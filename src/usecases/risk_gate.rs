@@ -0,0 +1,536 @@
+//! Risk Gate - Pre-Trade Checks on Every Order
+//!
+//! `RiskGate` wraps any `OrderExecution` adapter and runs
+//! `RiskManager::check_order` before an order is forwarded to the
+//! CLOB. Exposure is computed from live state, not from what the
+//! engine believes it placed:
+//! - Resting orders tracked by the gate (reconciled with the CLOB)
+//! - Filled positions carried at cost, credited from order status;
+//!   accepted FOK orders are credited as soon as they are placed
+//!
+//! The sizes held let sells of held tokens through a halt (see
+//! `RiskManager::check_order`), so failed batches can still unwind.
+//!
+//! Every credited fill is also booked in the
+//! `PortfolioLedger`, when one is attached; an order's realized PnL
+//! reaches the risk manager as one trade once the order is settled.
+//!
+//! The tracked orders are checkpointed in `BotStateSnapshot` and
+//! restored on startup; `adopt_untracked` picks up CLOB orders the
+//! snapshot missed (placed after the last checkpoint).
+//!
+//! Rejections are returned as `OrderPlacement { accepted: false }`
+//! and counted per reason via the `MetricsSink` port.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tracing::{debug, info, instrument, warn};
+
+use crate::config::AppConfig;
+use crate::domain::time::now_ms;
+use crate::domain::trade::{Order, OrderId, OrderType, TokenId, TradeSide};
+use crate::ports::execution::{
+  OrderCancellation, OrderExecution, OrderPlacement, OrderStatus,
+};
+use crate::ports::metrics::MetricsSink;
+use crate::ports::repository::OpenOrderState;
+
+use super::portfolio_ledger::PortfolioLedger;
+use super::risk_manager::{Exposure, RiskManager};
+
+/// Reason label used when the CLOB itself rejects an order.
+const CLOB_REJECTION: &str = "clob";
+
+/// A resting order as seen by the gate.
+#[derive(Debug, Clone)]
+struct RestingOrder {
+  /// The order as submitted (original size).
+  order: Order,
+  /// Size already credited to positions.
+  filled: f64,
+}
+
+impl RestingOrder {
+  /// Unfilled size still on the book.
+  fn remaining(&self) -> f64 {
+    (self.order.size - self.filled).max(0.0)
+  }
+}
+
+/// Live order book and inventory used to compute exposure.
+#[derive(Debug, Default)]
+struct GateBook {
+  /// Resting orders keyed by CLOB order ID.
+  resting: HashMap<OrderId, RestingOrder>,
+  /// Cost basis of filled positions per token (USDC).
+  positions: HashMap<TokenId, f64>,
+  /// Size of filled positions per token.
+  held: HashMap<TokenId, f64>,
+}
+
+impl GateBook {
+  /// Snapshot exposure from positions and the unfilled part of orders.
+  ///
+  /// Sizes held are net of resting sells, which already claim them.
+  fn exposure(&self) -> Exposure {
+    let remaining: Vec<Order> = self
+      .resting
+      .values()
+      .map(|r| Order {
+        size: r.remaining(),
+        ..r.order.clone()
+      })
+      .collect();
+    let mut held = self.held.clone();
+    for o in remaining.iter().filter(|o| o.side == TradeSide::Sell) {
+      *held.entry(o.token_id.clone()).or_insert(0.0) -= o.size;
+    }
+    Exposure::from_book(&self.positions, &remaining).with_held(held)
+  }
+
+  /// Credit a fill against the position for its token.
+  fn apply_fill(&mut self, token_id: &str, side: TradeSide, price: f64, size: f64) {
+    let cost = price * size;
+    let entry = self.positions.entry(token_id.to_string()).or_insert(0.0);
+    match side {
+      TradeSide::Buy => *entry += cost,
+      TradeSide::Sell => *entry = (*entry - cost).max(0.0),
+    }
+    if *entry <= f64::EPSILON {
+      self.positions.remove(token_id);
+    }
+
+    let held = self.held.entry(token_id.to_string()).or_insert(0.0);
+    match side {
+      TradeSide::Buy => *held += size,
+      TradeSide::Sell => *held = (*held - size).max(0.0),
+    }
+    if *held <= f64::EPSILON {
+      self.held.remove(token_id);
+    }
+  }
+}
+
+/// Order execution decorator that enforces pre-trade risk checks.
+pub struct RiskGate<E: OrderExecution + ?Sized> {
+  /// Wrapped execution adapter (the real CLOB client).
+  inner: Arc<E>,
+  /// Shared risk manager (limits + circuit breaker).
+  risk: Arc<RwLock<RiskManager>>,
+  /// Metrics sink for rejection counters.
+  metrics: Arc<dyn MetricsSink>,
+  /// Live exposure state. Held across placement so concurrent
+  /// orders cannot both pass against the same headroom.
+  book: Mutex<GateBook>,
+  /// Token ID → asset label for metrics.
+  assets: HashMap<TokenId, String>,
+  /// Ledger that books the fills, if attached.
+  ledger: Option<Arc<PortfolioLedger>>,
+}
+
+impl<E: OrderExecution + ?Sized> RiskGate<E> {
+  /// Wrap an execution adapter with the given risk manager.
+  pub fn new(
+    inner: Arc<E>,
+    risk: Arc<RwLock<RiskManager>>,
+    metrics: Arc<dyn MetricsSink>,
+    config: &AppConfig,
+  ) -> Self {
+    let assets = config
+      .markets
+      .iter()
+      .flat_map(|m| {
+        let asset = m.asset.to_string();
+        [
+          (m.yes_token_id.clone(), asset.clone()),
+          (m.no_token_id.clone(), asset),
+        ]
+      })
+      .collect();
+
+    Self {
+      inner,
+      risk,
+      metrics,
+      book: Mutex::new(GateBook::default()),
+      assets,
+      ledger: None,
+    }
+  }
+
+  /// Book every detected fill in `ledger`.
+  pub fn with_ledger(mut self, ledger: Arc<PortfolioLedger>) -> Self {
+    self.ledger = Some(ledger);
+    self
+  }
+
+  /// Shared handle to the risk manager.
+  pub fn risk(&self) -> Arc<RwLock<RiskManager>> {
+    Arc::clone(&self.risk)
+  }
+
+  /// Current exposure (positions + resting orders).
+  pub async fn exposure(&self) -> Exposure {
+    self.book.lock().await.exposure()
+  }
+
+  /// Tracked resting orders with the size credited from each.
+  pub async fn open_orders(&self) -> Vec<OpenOrderState> {
+    let mut orders: Vec<OpenOrderState> = self
+      .book
+      .lock()
+      .await
+      .resting
+      .values()
+      .map(|r| OpenOrderState {
+        order: r.order.clone(),
+        filled: r.filled,
+      })
+      .collect();
+    orders.sort_by(|a, b| a.order.id.cmp(&b.order.id));
+    orders
+  }
+
+  /// Load checkpointed orders, position costs (USDC per token) and
+  /// sizes held per token.
+  ///
+  /// Run `reconcile` afterwards to settle orders that changed while
+  /// the bot was down.
+  pub async fn restore(
+    &self,
+    orders: Vec<OpenOrderState>,
+    positions: Vec<(TokenId, f64)>,
+    held: Vec<(TokenId, f64)>,
+  ) {
+    let mut book = self.book.lock().await;
+    book.resting = orders
+      .into_iter()
+      .filter(|o| !o.order.id.is_empty())
+      .map(|o| {
+        (
+          o.order.id.clone(),
+          RestingOrder {
+            order: o.order,
+            filled: o.filled,
+          },
+        )
+      })
+      .collect();
+    book.positions = positions.into_iter().filter(|(_, cost)| *cost > 0.0).collect();
+    book.held = held.into_iter().filter(|(_, size)| *size > 0.0).collect();
+    self.sync_exposure(&book).await;
+    info!(
+      resting = book.resting.len(),
+      positions = book.positions.len(),
+      "Risk gate restored"
+    );
+  }
+
+  /// Track CLOB orders the gate does not know; returns their IDs.
+  ///
+  /// Their original size is unknown, so the live remaining size is
+  /// taken as the order size.
+  #[instrument(skip(self))]
+  pub async fn adopt_untracked(&self) -> Result<Vec<OrderId>> {
+    let mut adopted = Vec::new();
+    for order in self.untracked_orders().await? {
+      adopted.push(order.id.clone());
+      self.adopt(order).await;
+    }
+    Ok(adopted)
+  }
+
+  /// Open CLOB orders the gate does not track.
+  pub async fn untracked_orders(&self) -> Result<Vec<Order>> {
+    let open = self.inner.get_open_orders().await?;
+    let book = self.book.lock().await;
+    Ok(
+      open
+        .into_iter()
+        .filter(|o| !o.id.is_empty() && !book.resting.contains_key(&o.id))
+        .collect(),
+    )
+  }
+
+  /// Track one CLOB order with nothing credited yet; the next
+  /// `reconcile` credits whatever it has filled since `order.size`.
+  pub async fn adopt(&self, order: Order) {
+    warn!(order_id = %order.id, token = %order.token_id, "Adopting untracked CLOB order");
+    let mut book = self.book.lock().await;
+    book.resting.insert(order.id.clone(), RestingOrder { order, filled: 0.0 });
+    self.sync_exposure(&book).await;
+  }
+
+  /// Record a fill reported outside the reconciler (e.g. user WS feed).
+  pub async fn record_fill(
+    &self,
+    token_id: &str,
+    side: TradeSide,
+    price: f64,
+    size: f64,
+    is_maker: bool,
+  ) {
+    let mut book = self.book.lock().await;
+    book.apply_fill(token_id, side, price, size);
+    if let Some(ledger) = &self.ledger {
+      ledger.record_fill(token_id, side, price, size, is_maker).await;
+    }
+    self.sync_exposure(&book).await;
+  }
+
+  /// Reconcile tracked orders with the CLOB.
+  ///
+  /// Orders still open have their partial fills credited; orders
+  /// no longer open are settled (`settle`) and dropped.
+  /// Returns the number of tracked orders removed.
+  #[instrument(skip(self))]
+  pub async fn reconcile(&self) -> Result<usize> {
+    let open = self.inner.get_open_orders().await?;
+    let open_by_id: HashMap<&str, &Order> =
+      open.iter().map(|o| (o.id.as_str(), o)).collect();
+
+    let mut book = self.book.lock().await;
+    let tracked: Vec<OrderId> = book.resting.keys().cloned().collect();
+    let mut removed = 0;
+
+    for order_id in tracked {
+      let Some(resting) = book.resting.get(&order_id).cloned() else {
+        continue;
+      };
+
+      if let Some(live) = open_by_id.get(order_id.as_str()) {
+        // Still on the book: anything missing from its size has filled.
+        let filled = (resting.order.size - live.size).max(0.0);
+        let delta = filled - resting.filled;
+        if delta > 0.0 {
+          let o = &resting.order;
+          book.apply_fill(&o.token_id, o.side, o.price, delta);
+          self.book_fill(&order_id, o, o.price, delta).await;
+          if let Some(r) = book.resting.get_mut(&order_id) {
+            r.filled = filled;
+          }
+        }
+        continue;
+      }
+
+      if self.settle(&mut book, &order_id).await {
+        removed += 1;
+      }
+    }
+
+    self.sync_exposure(&book).await;
+    debug!(
+      resting = book.resting.len(),
+      positions = book.positions.len(),
+      removed = removed,
+      "Risk gate reconciled"
+    );
+    Ok(removed)
+  }
+
+  /// Run `reconcile` on a fixed interval until shutdown.
+  pub async fn run_reconciler(
+    self: Arc<Self>,
+    interval: Duration,
+    mut shutdown_rx: broadcast::Receiver<()>,
+  ) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+      tokio::select! {
+        biased;
+        _ = shutdown_rx.recv() => break,
+        _ = ticker.tick() => {
+          if let Err(e) = self.reconcile().await {
+            warn!(error = %e, "Risk gate reconciliation failed");
+          }
+        }
+      }
+    }
+    info!("Risk gate reconciler stopped");
+  }
+
+  /// Credit the fills of a tracked order that left the book, per
+  /// `get_order_status`, and stop tracking it.
+  ///
+  /// Returns false, keeping the order for the next `reconcile`, if
+  /// the CLOB still reports it open or the lookup fails.
+  async fn settle(&self, book: &mut GateBook, order_id: &OrderId) -> bool {
+    let Some(resting) = book.resting.get(order_id).cloned() else {
+      return true;
+    };
+    let (filled, price) = match self.inner.get_order_status(order_id).await {
+      Ok(OrderStatus::Filled { avg_price, filled_size }) => (filled_size, avg_price),
+      Ok(OrderStatus::PartiallyFilled { filled_size, avg_price, .. }) => {
+        (filled_size, avg_price)
+      }
+      Ok(OrderStatus::Open { .. }) => return false,
+      Ok(OrderStatus::Cancelled | OrderStatus::Unknown) => {
+        (resting.filled, resting.order.price)
+      }
+      Err(e) => {
+        warn!(order_id = %order_id, error = %e, "Order status lookup failed");
+        return false;
+      }
+    };
+
+    let delta = filled - resting.filled;
+    if delta > 0.0 {
+      let o = &resting.order;
+      book.apply_fill(&o.token_id, o.side, price, delta);
+      self.book_fill(order_id, o, price, delta).await;
+    }
+    book.resting.remove(order_id);
+    if let Some(ledger) = &self.ledger {
+      ledger.close_order(order_id).await;
+    }
+    true
+  }
+
+  /// Book a fill of a tracked order in the ledger, if attached.
+  async fn book_fill(&self, order_id: &str, order: &Order, price: f64, size: f64) {
+    if let Some(ledger) = &self.ledger {
+      ledger
+        .record_order_fill(order_id, &order.token_id, order.side, price, size, order.post_only)
+        .await;
+    }
+  }
+
+  /// Push the current total exposure into the risk manager.
+  async fn sync_exposure(&self, book: &GateBook) {
+    let total = book.exposure().total();
+    self.risk.write().await.update_exposure(total);
+  }
+
+  /// Asset label for a token (falls back to "unknown").
+  fn asset_label(&self, token_id: &str) -> &str {
+    self
+      .assets
+      .get(token_id)
+      .map_or("unknown", String::as_str)
+  }
+}
+
+#[async_trait]
+impl<E: OrderExecution + ?Sized> OrderExecution for RiskGate<E> {
+  async fn place_order(&self, order: &Order) -> Result<OrderPlacement> {
+    let mut book = self.book.lock().await;
+
+    let bankroll = self.inner.available_balance(TradeSide::Buy).await?;
+    let check = self
+      .risk
+      .read()
+      .await
+      .check_order(order, &book.exposure(), bankroll);
+
+    if let Err(rejection) = check {
+      warn!(
+        token = %order.token_id,
+        price = order.price,
+        size = order.size,
+        reason = rejection.label(),
+        detail = %rejection,
+        "Order rejected by risk gate"
+      );
+      self
+        .metrics
+        .order_rejected(self.asset_label(&order.token_id), rejection.label());
+      return Ok(OrderPlacement {
+        order_id: String::new(),
+        accepted: false,
+        rejection_reason: Some(format!("Risk: {rejection}")),
+        timestamp_ms: now_ms(),
+      });
+    }
+
+    let placement = self.inner.place_order(order).await?;
+    if placement.accepted {
+      let mut tracked = order.clone();
+      tracked.id = placement.order_id.clone();
+      let fok = tracked.order_type == OrderType::Fok;
+      book.resting.insert(
+        placement.order_id.clone(),
+        RestingOrder {
+          order: tracked,
+          filled: 0.0,
+        },
+      );
+      if fok {
+        // Accepted FOK orders have filled: credit them before the next
+        // order (e.g. an unwind) is checked against the book
+        self.settle(&mut book, &placement.order_id).await;
+      }
+      self.sync_exposure(&book).await;
+    } else {
+      self
+        .metrics
+        .order_rejected(self.asset_label(&order.token_id), CLOB_REJECTION);
+    }
+
+    Ok(placement)
+  }
+
+  async fn cancel_order(&self, order_id: &OrderId) -> Result<OrderCancellation> {
+    let result = self.inner.cancel_order(order_id).await?;
+    if result.success {
+      let mut book = self.book.lock().await;
+      self.settle(&mut book, order_id).await;
+      self.sync_exposure(&book).await;
+    }
+    Ok(result)
+  }
+
+  async fn cancel_all_orders(&self) -> Result<usize> {
+    let cancelled = self.inner.cancel_all_orders().await?;
+    let mut book = self.book.lock().await;
+    let tracked: Vec<OrderId> = book.resting.keys().cloned().collect();
+    for order_id in tracked {
+      self.settle(&mut book, &order_id).await;
+    }
+    self.sync_exposure(&book).await;
+    Ok(cancelled)
+  }
+
+  async fn cancel_orders_for_token(
+    &self,
+    token_id: &TokenId,
+  ) -> Result<Vec<OrderCancellation>> {
+    let results = self.inner.cancel_orders_for_token(token_id).await?;
+    let mut book = self.book.lock().await;
+    for result in results.iter().filter(|r| r.success) {
+      self.settle(&mut book, &result.order_id).await;
+    }
+    self.sync_exposure(&book).await;
+    Ok(results)
+  }
+
+  async fn get_order_status(&self, order_id: &OrderId) -> Result<OrderStatus> {
+    self.inner.get_order_status(order_id).await
+  }
+
+  async fn get_open_orders(&self) -> Result<Vec<Order>> {
+    self.inner.get_open_orders().await
+  }
+
+  async fn available_balance(&self, side: TradeSide) -> Result<f64> {
+    self.inner.available_balance(side).await
+  }
+
+  async fn is_healthy(&self) -> bool {
+    self.inner.is_healthy().await
+  }
+
+  async fn rate_limit_status(&self) -> (u32, u64) {
+    self.inner.rate_limit_status().await
+  }
+
+  async fn record_transfer(&self, token_id: &TokenId, side: TradeSide, price: f64, size: f64) {
+    let mut book = self.book.lock().await;
+    book.apply_fill(token_id, side, price, size);
+    self.sync_exposure(&book).await;
+  }
+}
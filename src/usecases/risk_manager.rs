@@ -5,11 +5,345 @@
 //! - Maximum position size per market
 //! - Maximum total exposure
 //! - Circuit breaker on consecutive losses
-//! - Cooldown period after circuit breaker trigger
+//! - Cooldown period, then half-open trial trading at reduced size
+//! - UTC day rollover of daily counters (driven by `tick`)
+//! - Operator trading halt (admin API / kill switch)
+//! - Per-market, per-asset and correlation-group limits on gross
+//!   exposure and net directional delta (YES cost − NO cost)
+//!
+//! `check_order` is the pre-trade entry point used by `RiskGate`;
+//! it returns a structured `RiskRejection` so callers can count
+//! rejections by reason.
+
+use std::collections::HashMap;
+use std::fmt;
 
 use tracing::{info, warn};
 
-use crate::config::RiskConfig;
+use crate::config::{MarketConfig, RiskConfig};
+use crate::domain::time::{now_ms, utc_day};
+
+use crate::domain::trade::{Asset, Order, TokenId, TradeSide};
+use crate::ports::repository::{BreakerState, HaltRecord, HaltSource, RiskStateSnapshot};
+
+/// Structured reason a pre-trade risk check rejected an order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RiskRejection {
+  /// Trading halted by an operator.
+  Halted {
+    /// Operator-supplied reason.
+    reason: String,
+  },
+  /// Circuit breaker is active and the cooldown has not elapsed.
+  CircuitBreaker,
+  /// Bankroll is below the configured minimum.
+  BelowMinBankroll {
+    /// Current bankroll (USDC).
+    bankroll: f64,
+    /// Configured minimum (USDC).
+    min: f64,
+  },
+  /// Order would push the token's exposure past `max_position_size`.
+  PositionLimit {
+    /// Token the order is for.
+    token_id: TokenId,
+    /// Exposure after the order (USDC).
+    projected: f64,
+    /// Configured limit (USDC).
+    limit: f64,
+  },
+  /// Order would push total exposure past `max_total_exposure`.
+  ExposureLimit {
+    /// Total exposure after the order (USDC).
+    projected: f64,
+    /// Configured limit (USDC).
+    limit: f64,
+  },
+  /// Order would push a market's gross exposure past its limit.
+  MarketLimit {
+    /// Condition ID of the market.
+    condition_id: String,
+    /// Exposure after the order (USDC).
+    projected: f64,
+    /// Configured limit (USDC).
+    limit: f64,
+  },
+  /// Order would push an asset's gross exposure past its limit.
+  AssetLimit {
+    /// Asset the limit applies to.
+    asset: Asset,
+    /// Exposure after the order (USDC).
+    projected: f64,
+    /// Configured limit (USDC).
+    limit: f64,
+  },
+  /// Order would push a correlation group's gross exposure past its limit.
+  GroupLimit {
+    /// Correlation group name.
+    group: String,
+    /// Exposure after the order (USDC).
+    projected: f64,
+    /// Configured limit (USDC).
+    limit: f64,
+  },
+  /// Order would push net delta (YES − NO) past a scope's limit.
+  NetDeltaLimit {
+    /// Market, asset or group the limit belongs to.
+    scope: String,
+    /// Signed net delta after the order (USDC).
+    projected: f64,
+    /// Configured absolute limit (USDC).
+    limit: f64,
+  },
+  /// Daily realized loss has reached the configured fraction.
+  DailyLossLimit {
+    /// Realized loss so far today (USDC).
+    daily_loss: f64,
+    /// Maximum allowed loss (USDC).
+    limit: f64,
+  },
+  /// Order parameters are malformed (price/size out of range).
+  InvalidOrder(String),
+}
+
+impl RiskRejection {
+  /// Short, stable label for the `orders_rejected{reason}` metric.
+  pub fn label(&self) -> &'static str {
+    match self {
+      Self::Halted { .. } => "halted",
+      Self::CircuitBreaker => "circuit_breaker",
+      Self::BelowMinBankroll { .. } => "min_bankroll",
+      Self::PositionLimit { .. } => "position_limit",
+      Self::ExposureLimit { .. } => "exposure_limit",
+      Self::MarketLimit { .. } => "market_limit",
+      Self::AssetLimit { .. } => "asset_limit",
+      Self::GroupLimit { .. } => "group_limit",
+      Self::NetDeltaLimit { .. } => "net_delta",
+      Self::DailyLossLimit { .. } => "daily_loss",
+      Self::InvalidOrder(_) => "invalid_order",
+    }
+  }
+}
+
+impl fmt::Display for RiskRejection {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Halted { reason } => write!(f, "trading halted: {reason}"),
+      Self::CircuitBreaker => write!(f, "circuit breaker active"),
+      Self::BelowMinBankroll { bankroll, min } => {
+        write!(f, "bankroll {bankroll:.2} below minimum {min:.2}")
+      }
+      Self::PositionLimit { token_id, projected, limit } => write!(
+        f,
+        "position in {token_id} would be {projected:.2} (limit {limit:.2})"
+      ),
+      Self::ExposureLimit { projected, limit } => {
+        write!(f, "total exposure would be {projected:.2} (limit {limit:.2})")
+      }
+      Self::MarketLimit { condition_id, projected, limit } => write!(
+        f,
+        "market {condition_id} exposure would be {projected:.2} (limit {limit:.2})"
+      ),
+      Self::AssetLimit { asset, projected, limit } => write!(
+        f,
+        "{asset} exposure would be {projected:.2} (limit {limit:.2})"
+      ),
+      Self::GroupLimit { group, projected, limit } => write!(
+        f,
+        "group {group} exposure would be {projected:.2} (limit {limit:.2})"
+      ),
+      Self::NetDeltaLimit { scope, projected, limit } => write!(
+        f,
+        "net delta in {scope} would be {projected:+.2} (limit ±{limit:.2})"
+      ),
+      Self::DailyLossLimit { daily_loss, limit } => {
+        write!(f, "daily loss {daily_loss:.2} reached limit {limit:.2}")
+      }
+      Self::InvalidOrder(msg) => write!(f, "invalid order: {msg}"),
+    }
+  }
+}
+
+/// Point-in-time exposure built from live positions and resting orders.
+///
+/// All values are USDC notional. Positions are carried at cost;
+/// resting orders at `price * size` for buys (sells only reduce
+/// inventory we already hold, so they add no exposure).
+#[derive(Debug, Clone, Default)]
+pub struct Exposure {
+  /// Cost basis of filled positions per token.
+  pub positions: HashMap<TokenId, f64>,
+  /// Notional of resting buy orders per token.
+  pub resting: HashMap<TokenId, f64>,
+  /// Tokens held per token, net of resting sells (size, not USDC).
+  pub held: HashMap<TokenId, f64>,
+}
+
+impl Exposure {
+  /// Build an exposure snapshot from positions and resting orders.
+  pub fn from_book<'a>(
+    positions: &HashMap<TokenId, f64>,
+    resting_orders: impl IntoIterator<Item = &'a Order>,
+  ) -> Self {
+    let mut resting: HashMap<TokenId, f64> = HashMap::new();
+    for order in resting_orders {
+      *resting.entry(order.token_id.clone()).or_insert(0.0) += order_notional(order);
+    }
+    Self {
+      positions: positions.clone(),
+      resting,
+      held: HashMap::new(),
+    }
+  }
+
+  /// Attach the sizes held per token (net of resting sells).
+  pub fn with_held(mut self, held: HashMap<TokenId, f64>) -> Self {
+    self.held = held;
+    self
+  }
+
+  /// Whether `order` only sells tokens already held.
+  pub fn reduces(&self, order: &Order) -> bool {
+    order.side == TradeSide::Sell
+      && order.size <= self.held.get(&order.token_id).copied().unwrap_or(0.0) + SIZE_TOLERANCE
+  }
+
+  /// Exposure in a single token (position + resting buys).
+  pub fn token(&self, token_id: &str) -> f64 {
+    self.positions.get(token_id).copied().unwrap_or(0.0)
+      + self.resting.get(token_id).copied().unwrap_or(0.0)
+  }
+
+  /// Total exposure across all tokens.
+  pub fn total(&self) -> f64 {
+    self.positions.values().sum::<f64>() + self.resting.values().sum::<f64>()
+  }
+}
+
+/// Slack when comparing a sell against the size held (float sums).
+const SIZE_TOLERANCE: f64 = 1e-9;
+
+/// USDC exposure an order adds if it rests or fills.
+pub fn order_notional(order: &Order) -> f64 {
+  match order.side {
+    TradeSide::Buy => order.price * order.size,
+    TradeSide::Sell => 0.0,
+  }
+}
+
+/// Resolve configured market/asset/group limits into scoped limits.
+fn scoped_limits(config: &RiskConfig) -> Vec<ScopedLimit> {
+  let markets = config.market_limits.iter().map(|l| ScopedLimit {
+    scope: LimitScope::Market(l.condition_id.clone()),
+    max_exposure: l.max_exposure,
+    max_net_delta: l.max_net_delta,
+  });
+  let assets = config.asset_limits.iter().map(|l| ScopedLimit {
+    scope: LimitScope::Asset(l.asset),
+    max_exposure: l.max_exposure,
+    max_net_delta: l.max_net_delta,
+  });
+  let groups = config.correlation_groups.iter().map(|g| ScopedLimit {
+    scope: LimitScope::Group {
+      name: g.name.clone(),
+      assets: g.assets.clone(),
+      markets: g.markets.clone(),
+    },
+    max_exposure: g.max_exposure,
+    max_net_delta: g.max_net_delta,
+  });
+  markets.chain(assets).chain(groups).collect()
+}
+
+/// Market membership of an outcome token.
+#[derive(Debug, Clone)]
+struct TokenInfo {
+  /// Condition ID of the market the token belongs to.
+  condition_id: String,
+  /// Underlying asset of the market.
+  asset: Asset,
+  /// Whether this is the YES outcome.
+  is_yes: bool,
+}
+
+impl TokenInfo {
+  /// Direction of the token: +1 for YES, -1 for NO.
+  fn sign(&self) -> f64 {
+    if self.is_yes { 1.0 } else { -1.0 }
+  }
+}
+
+/// Which markets a scoped limit covers.
+#[derive(Debug, Clone)]
+enum LimitScope {
+  /// A single market by condition ID.
+  Market(String),
+  /// Every market of one asset.
+  Asset(Asset),
+  /// A named correlation bucket.
+  Group {
+    /// Group name.
+    name: String,
+    /// Member assets.
+    assets: Vec<Asset>,
+    /// Member markets by condition ID.
+    markets: Vec<String>,
+  },
+}
+
+impl LimitScope {
+  /// Whether a token falls inside this scope.
+  fn contains(&self, info: &TokenInfo) -> bool {
+    match self {
+      Self::Market(condition_id) => &info.condition_id == condition_id,
+      Self::Asset(asset) => info.asset == *asset,
+      Self::Group { assets, markets, .. } => {
+        assets.contains(&info.asset) || markets.contains(&info.condition_id)
+      }
+    }
+  }
+
+  /// Human-readable scope name for rejection reasons.
+  fn name(&self) -> String {
+    match self {
+      Self::Market(condition_id) => condition_id.clone(),
+      Self::Asset(asset) => asset.to_string(),
+      Self::Group { name, .. } => name.clone(),
+    }
+  }
+
+  /// Gross-exposure rejection for this scope.
+  fn exposure_rejection(&self, projected: f64, limit: f64) -> RiskRejection {
+    match self {
+      Self::Market(condition_id) => RiskRejection::MarketLimit {
+        condition_id: condition_id.clone(),
+        projected,
+        limit,
+      },
+      Self::Asset(asset) => RiskRejection::AssetLimit {
+        asset: *asset,
+        projected,
+        limit,
+      },
+      Self::Group { name, .. } => RiskRejection::GroupLimit {
+        group: name.clone(),
+        projected,
+        limit,
+      },
+    }
+  }
+}
+
+/// Gross exposure and/or net delta cap for a scope.
+#[derive(Debug, Clone)]
+struct ScopedLimit {
+  /// Markets covered.
+  scope: LimitScope,
+  /// Maximum gross exposure (USDC).
+  max_exposure: Option<f64>,
+  /// Maximum absolute net delta (USDC).
+  max_net_delta: Option<f64>,
+}
 
 /// Risk manager enforcing trading limits and circuit breakers.
 pub struct RiskManager {
@@ -25,16 +359,26 @@ pub struct RiskManager {
   circuit_breaker_losses: u32,
   /// Cooldown period (seconds).
   cooldown_seconds: u64,
+  /// Position limit multiplier during half-open trials.
+  half_open_size_fraction: f64,
+  /// UTC day the daily counters belong to (YYYY-MM-DD).
+  day: String,
   /// Current daily realized loss.
   daily_loss: f64,
   /// Consecutive loss counter.
   consecutive_losses: u32,
-  /// Whether circuit breaker is active.
-  circuit_breaker_active: bool,
+  /// Circuit breaker state.
+  breaker: BreakerState,
   /// When circuit breaker was triggered (Unix ms).
   circuit_breaker_time: Option<u64>,
+  /// Active operator halt, if any.
+  halt: Option<HaltRecord>,
   /// Current total exposure.
   total_exposure: f64,
+  /// Market/asset/group limits from config.
+  scoped_limits: Vec<ScopedLimit>,
+  /// Token ID → market membership (filled by `with_markets`).
+  tokens: HashMap<TokenId, TokenInfo>,
 }
 
 impl RiskManager {
@@ -47,35 +391,312 @@ impl RiskManager {
       min_bankroll: config.min_bankroll,
       circuit_breaker_losses: config.circuit_breaker_losses,
       cooldown_seconds: config.cooldown_seconds,
+      half_open_size_fraction: config.half_open_size_fraction,
+      day: utc_day(now_ms()),
       daily_loss: 0.0,
       consecutive_losses: 0,
-      circuit_breaker_active: false,
+      breaker: BreakerState::Closed,
       circuit_breaker_time: None,
+      halt: None,
       total_exposure: 0.0,
+      scoped_limits: scoped_limits(config),
+      tokens: HashMap::new(),
+    }
+  }
+
+  /// Register market membership so scoped limits can be enforced.
+  ///
+  /// Without this, tokens are unknown and only global limits apply.
+  pub fn with_markets(mut self, markets: &[MarketConfig]) -> Self {
+    self.register_markets(markets);
+    self
+  }
+
+  /// Add token → market membership for `markets`.
+  fn register_markets(&mut self, markets: &[MarketConfig]) {
+    for m in markets {
+      for (token_id, is_yes) in [(&m.yes_token_id, true), (&m.no_token_id, false)] {
+        self.tokens.insert(
+          token_id.clone(),
+          TokenInfo {
+            condition_id: m.condition_id.clone(),
+            asset: m.asset,
+            is_yes,
+          },
+        );
+      }
     }
   }
 
+  /// Apply reloaded limits and market membership.
+  ///
+  /// Daily loss, breaker state, halt and exposure are kept; only the
+  /// limits they are checked against change.
+  pub fn apply_config(&mut self, config: &RiskConfig, markets: &[MarketConfig]) {
+    self.max_daily_loss_fraction = config.max_daily_loss_fraction;
+    self.max_position_size = config.max_position_size;
+    self.max_total_exposure = config.max_total_exposure;
+    self.min_bankroll = config.min_bankroll;
+    self.circuit_breaker_losses = config.circuit_breaker_losses;
+    self.cooldown_seconds = config.cooldown_seconds;
+    self.half_open_size_fraction = config.half_open_size_fraction;
+    self.scoped_limits = scoped_limits(config);
+    self.tokens.clear();
+    self.register_markets(markets);
+  }
+
   /// Check if trading is currently allowed.
+  ///
+  /// An open breaker whose cooldown has elapsed allows trading
+  /// (as a half-open trial) even before `tick` moves it over.
   pub fn can_trade(&self) -> bool {
-    if self.circuit_breaker_active {
-      if let Some(trigger_time) = self.circuit_breaker_time {
-        let now = std::time::SystemTime::now()
-          .duration_since(std::time::UNIX_EPOCH)
-          .unwrap_or_default()
-          .as_millis() as u64;
-        let elapsed_secs = (now - trigger_time) / 1000;
-        if elapsed_secs < self.cooldown_seconds {
-          return false;
-        }
-      }
+    if self.halt.is_some() {
+      return false;
+    }
+    match self.breaker {
+      BreakerState::Closed | BreakerState::HalfOpen => true,
+      BreakerState::Open => self.cooldown_elapsed(now_ms()),
     }
+  }
+
+  /// Advance time-driven state: UTC day rollover and Open → HalfOpen.
+  ///
+  /// Returns true if anything changed (so the caller can persist).
+  pub fn tick(&mut self, now_ms: u64) -> bool {
+    let mut changed = false;
+
+    let today = utc_day(now_ms);
+    if today != self.day {
+      info!(from = %self.day, to = %today, "UTC day rollover");
+      self.reset_daily();
+      self.day = today;
+      changed = true;
+    }
+
+    if self.breaker == BreakerState::Open && self.cooldown_elapsed(now_ms) {
+      self.breaker = BreakerState::HalfOpen;
+      info!(
+        size_fraction = self.half_open_size_fraction,
+        "Circuit breaker half-open, trial trading at reduced size"
+      );
+      changed = true;
+    }
+
+    changed
+  }
+
+  /// Export counters for persistence.
+  pub fn snapshot(&self) -> RiskStateSnapshot {
+    RiskStateSnapshot {
+      day: self.day.clone(),
+      daily_loss: self.daily_loss,
+      consecutive_losses: self.consecutive_losses,
+      breaker: self.breaker,
+      tripped_at_ms: self.circuit_breaker_time,
+      halt: self.halt.clone(),
+    }
+  }
+
+  /// Restore persisted counters, then roll over if the day changed.
+  pub fn restore(&mut self, state: &RiskStateSnapshot, now_ms: u64) {
+    self.day.clone_from(&state.day);
+    self.daily_loss = state.daily_loss;
+    self.consecutive_losses = state.consecutive_losses;
+    self.breaker = state.breaker;
+    self.circuit_breaker_time = state.tripped_at_ms;
+    self.halt.clone_from(&state.halt);
+    info!(
+      day = %self.day,
+      daily_loss = self.daily_loss,
+      consecutive_losses = self.consecutive_losses,
+      breaker = ?self.breaker,
+      halted = self.halt.is_some(),
+      "Risk state restored"
+    );
+    self.tick(now_ms);
+  }
+
+  /// Halt trading until `resume` is called.
+  ///
+  /// Returns false if trading was already halted (the original
+  /// record is kept so the first reason and timestamp survive).
+  pub fn halt(&mut self, reason: &str, source: HaltSource, now_ms: u64) -> bool {
+    if self.halt.is_some() {
+      return false;
+    }
+    warn!(reason = reason, source = ?source, "Trading halted");
+    self.halt = Some(HaltRecord {
+      reason: reason.to_string(),
+      source,
+      halted_at_ms: now_ms,
+    });
     true
   }
 
+  /// Lift an active halt, returning the record that was cleared.
+  pub fn resume(&mut self) -> Option<HaltRecord> {
+    let record = self.halt.take();
+    if let Some(r) = &record {
+      info!(reason = %r.reason, source = ?r.source, "Trading resumed");
+    }
+    record
+  }
+
+  /// Active halt, if any.
+  pub fn halt_record(&self) -> Option<&HaltRecord> {
+    self.halt.as_ref()
+  }
+
+  /// Current circuit breaker state.
+  pub fn breaker_state(&self) -> BreakerState {
+    self.breaker
+  }
+
+  /// Whether the cooldown since the last trip has elapsed.
+  fn cooldown_elapsed(&self, now_ms: u64) -> bool {
+    self.circuit_breaker_time.is_none_or(|tripped| {
+      now_ms.saturating_sub(tripped) / 1000 >= self.cooldown_seconds
+    })
+  }
+
+  /// Per-token position limit, reduced while on trial.
+  fn effective_max_position(&self) -> f64 {
+    match self.breaker {
+      BreakerState::Closed => self.max_position_size,
+      BreakerState::Open | BreakerState::HalfOpen => {
+        self.max_position_size * self.half_open_size_fraction
+      }
+    }
+  }
+
   /// Check if a new position of given size is allowed.
+  ///
+  /// Uses the last exposure fed via `update_exposure`; prefer
+  /// `check_order` on the order path.
   pub fn can_open_position(&self, size: f64, bankroll: f64) -> bool {
+    self
+      .check_limits("", size, size, self.total_exposure + size, bankroll)
+      .is_ok()
+  }
+
+  /// Pre-trade check for a concrete order against live exposure.
+  ///
+  /// Every order passes through here (via `RiskGate`) before it
+  /// reaches the CLOB. Sells of held tokens only face the scoped
+  /// limits, so positions can be unwound while trading is halted.
+  pub fn check_order(
+    &self,
+    order: &Order,
+    exposure: &Exposure,
+    bankroll: f64,
+  ) -> Result<(), RiskRejection> {
+    if !(order.price > 0.0 && order.price < 1.0) {
+      return Err(RiskRejection::InvalidOrder(format!(
+        "price {} outside (0, 1)",
+        order.price
+      )));
+    }
+    if order.size <= 0.0 || !order.size.is_finite() {
+      return Err(RiskRejection::InvalidOrder(format!(
+        "size {} must be positive",
+        order.size
+      )));
+    }
+
+    // Selling held tokens only reduces risk: halts, the breaker and the
+    // bankroll and loss floors must not trap an unwind
+    if exposure.reduces(order) {
+      return self.check_scoped_limits(order, exposure);
+    }
+
+    let notional = order_notional(order);
+    self.check_limits(
+      &order.token_id,
+      notional,
+      exposure.token(&order.token_id) + notional,
+      exposure.total() + notional,
+      bankroll,
+    )?;
+    self.check_scoped_limits(order, exposure)
+  }
+
+  /// Enforce market, asset and correlation-group limits.
+  ///
+  /// Gross limits only bind on buys. Net delta limits bind on any
+  /// order that moves delta further from zero past the limit, so
+  /// hedging the opposite outcome is always allowed.
+  fn check_scoped_limits(
+    &self,
+    order: &Order,
+    exposure: &Exposure,
+  ) -> Result<(), RiskRejection> {
+    let Some(info) = self.tokens.get(&order.token_id) else {
+      return Ok(());
+    };
+
+    let notional = order.price * order.size;
+    let (gross_added, delta_added) = match order.side {
+      TradeSide::Buy => (notional, info.sign() * notional),
+      TradeSide::Sell => (0.0, -info.sign() * notional),
+    };
+
+    for limit in self.scoped_limits.iter().filter(|l| l.scope.contains(info)) {
+      let (gross, delta) = self.scope_totals(exposure, &limit.scope);
+
+      if let Some(max) = limit.max_exposure {
+        let projected = gross + gross_added;
+        if gross_added > 0.0 && projected > max {
+          return Err(limit.scope.exposure_rejection(projected, max));
+        }
+      }
+
+      if let Some(max) = limit.max_net_delta {
+        let projected = delta + delta_added;
+        if projected.abs() > max && projected.abs() > delta.abs() {
+          return Err(RiskRejection::NetDeltaLimit {
+            scope: limit.scope.name(),
+            projected,
+            limit: max,
+          });
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Gross exposure and signed net delta of all tokens in a scope.
+  fn scope_totals(&self, exposure: &Exposure, scope: &LimitScope) -> (f64, f64) {
+    exposure
+      .positions
+      .iter()
+      .chain(exposure.resting.iter())
+      .filter_map(|(token_id, value)| {
+        self
+          .tokens
+          .get(token_id)
+          .filter(|info| scope.contains(info))
+          .map(|info| (*value, info.sign() * value))
+      })
+      .fold((0.0, 0.0), |(g, d), (v, dv)| (g + v, d + dv))
+  }
+
+  /// Shared limit evaluation for `can_open_position` and `check_order`.
+  fn check_limits(
+    &self,
+    token_id: &str,
+    added: f64,
+    token_projected: f64,
+    total_projected: f64,
+    bankroll: f64,
+  ) -> Result<(), RiskRejection> {
+    if let Some(halt) = &self.halt {
+      return Err(RiskRejection::Halted {
+        reason: halt.reason.clone(),
+      });
+    }
     if !self.can_trade() {
-      return false;
+      return Err(RiskRejection::CircuitBreaker);
     }
 
     // Check minimum bankroll
@@ -85,17 +706,28 @@ impl RiskManager {
         min = self.min_bankroll,
         "Bankroll below minimum"
       );
-      return false;
+      return Err(RiskRejection::BelowMinBankroll {
+        bankroll,
+        min: self.min_bankroll,
+      });
     }
 
-    // Check position size limit
-    if size > self.max_position_size {
-      return false;
+    // Check position size limit (risk-reducing orders always pass)
+    let max_position = self.effective_max_position();
+    if added > 0.0 && token_projected > max_position {
+      return Err(RiskRejection::PositionLimit {
+        token_id: token_id.to_string(),
+        projected: token_projected,
+        limit: max_position,
+      });
     }
 
     // Check total exposure
-    if self.total_exposure + size > self.max_total_exposure {
-      return false;
+    if added > 0.0 && total_projected > self.max_total_exposure {
+      return Err(RiskRejection::ExposureLimit {
+        projected: total_projected,
+        limit: self.max_total_exposure,
+      });
     }
 
     // Check daily loss limit
@@ -106,23 +738,35 @@ impl RiskManager {
         max = max_loss,
         "Daily loss limit reached"
       );
-      return false;
+      return Err(RiskRejection::DailyLossLimit {
+        daily_loss: self.daily_loss,
+        limit: max_loss,
+      });
     }
 
-    true
+    Ok(())
   }
 
   /// Record a trade result.
+  ///
+  /// While half-open, a loss re-trips the breaker and a win closes it.
   pub fn record_trade(&mut self, pnl: f64) {
+    let on_trial = self.breaker != BreakerState::Closed && self.can_trade();
+
     if pnl < 0.0 {
       self.daily_loss += pnl.abs();
       self.consecutive_losses += 1;
 
-      if self.consecutive_losses >= self.circuit_breaker_losses {
+      if on_trial || self.consecutive_losses >= self.circuit_breaker_losses {
         self.trigger_circuit_breaker();
       }
     } else {
       self.consecutive_losses = 0;
+      if on_trial {
+        self.breaker = BreakerState::Closed;
+        self.circuit_breaker_time = None;
+        info!("Circuit breaker closed after successful trial");
+      }
     }
   }
 
@@ -139,7 +783,7 @@ impl RiskManager {
     );
     self.daily_loss = 0.0;
     self.consecutive_losses = 0;
-    self.circuit_breaker_active = false;
+    self.breaker = BreakerState::Closed;
     self.circuit_breaker_time = None;
   }
 
@@ -148,20 +792,20 @@ impl RiskManager {
     self.daily_loss
   }
 
-  /// Check if circuit breaker is active.
+  /// Get the last total exposure fed via `update_exposure`.
+  pub fn total_exposure(&self) -> f64 {
+    self.total_exposure
+  }
+
+  /// Check if circuit breaker is active (open or half-open).
   pub fn is_circuit_breaker_active(&self) -> bool {
-    self.circuit_breaker_active
+    self.breaker != BreakerState::Closed
   }
 
   /// Trigger the circuit breaker.
   fn trigger_circuit_breaker(&mut self) {
-    let now = std::time::SystemTime::now()
-      .duration_since(std::time::UNIX_EPOCH)
-      .unwrap_or_default()
-      .as_millis() as u64;
-
-    self.circuit_breaker_active = true;
-    self.circuit_breaker_time = Some(now);
+    self.breaker = BreakerState::Open;
+    self.circuit_breaker_time = Some(now_ms());
 
     warn!(
       consecutive_losses = self.consecutive_losses,
@@ -183,9 +827,53 @@ mod tests {
       min_bankroll: 50.0,
       circuit_breaker_losses: 3,
       cooldown_seconds: 300,
+      half_open_size_fraction: 0.25,
+      market_limits: Vec::new(),
+      asset_limits: Vec::new(),
+      correlation_groups: Vec::new(),
     }
   }
 
+  fn market(id: &str, asset: Asset) -> MarketConfig {
+    MarketConfig {
+      condition_id: id.to_string(),
+      yes_token_id: format!("{id}_yes"),
+      no_token_id: format!("{id}_no"),
+      asset,
+      active: true,
+      neg_risk: false,
+      neg_risk_market_id: None,
+      question_index: None,
+      outcome_count: None,
+      strategies: vec!["lmsr_mm".to_string()],
+      fee_class: crate::domain::fees::FeeClass::default(),
+      maker_rebate_share: None,
+    }
+  }
+
+  fn correlated_manager() -> RiskManager {
+    let mut config = test_config();
+    config.max_total_exposure = 10_000.0;
+    config.max_position_size = 1_000.0;
+    config.asset_limits.push(crate::config::AssetLimit {
+      asset: Asset::BTC,
+      max_exposure: Some(250.0),
+      max_net_delta: None,
+    });
+    config.correlation_groups.push(crate::config::CorrelationGroup {
+      name: "crypto".to_string(),
+      assets: vec![Asset::BTC, Asset::ETH],
+      markets: Vec::new(),
+      max_exposure: None,
+      max_net_delta: Some(150.0),
+    });
+    RiskManager::new(&config).with_markets(&[
+      market("btc_5m", Asset::BTC),
+      market("btc_15m", Asset::BTC),
+      market("eth_5m", Asset::ETH),
+    ])
+  }
+
   #[test]
   fn test_can_trade_initially() {
     let rm = RiskManager::new(&test_config());
@@ -201,6 +889,225 @@ mod tests {
     assert!(rm.is_circuit_breaker_active());
   }
 
+  fn buy(token: &str, price: f64, size: f64) -> Order {
+    Order::new_maker(token.to_string(), TradeSide::Buy, price, size)
+  }
+
+  #[test]
+  fn test_check_order_position_limit() {
+    let rm = RiskManager::new(&test_config());
+    let mut positions = HashMap::new();
+    positions.insert("yes".to_string(), 80.0);
+    let exposure = Exposure::from_book(&positions, &[]);
+
+    // 0.5 * 20 = 10 → 90 total, within 100
+    assert!(rm.check_order(&buy("yes", 0.5, 20.0), &exposure, 1000.0).is_ok());
+
+    // 0.5 * 60 = 30 → 110, over the per-token limit
+    let err = rm
+      .check_order(&buy("yes", 0.5, 60.0), &exposure, 1000.0)
+      .unwrap_err();
+    assert_eq!(err.label(), "position_limit");
+  }
+
+  #[test]
+  fn test_check_order_counts_resting_orders() {
+    let rm = RiskManager::new(&test_config());
+    let resting: Vec<Order> = (0..5)
+      .map(|i| buy(&format!("tok_{i}"), 0.9, 100.0))
+      .collect();
+    let exposure = Exposure::from_book(&HashMap::new(), &resting);
+    assert!((exposure.total() - 450.0).abs() < 1e-9);
+
+    let err = rm
+      .check_order(&buy("other", 0.5, 120.0), &exposure, 1000.0)
+      .unwrap_err();
+    assert!(matches!(err, RiskRejection::ExposureLimit { .. }));
+  }
+
+  #[test]
+  fn test_check_order_sell_adds_no_exposure() {
+    let rm = RiskManager::new(&test_config());
+    let mut positions = HashMap::new();
+    positions.insert("yes".to_string(), 500.0);
+    let exposure = Exposure::from_book(&positions, &[]);
+    let sell = Order::new_maker("yes".to_string(), TradeSide::Sell, 0.6, 50.0);
+    assert!(rm.check_order(&sell, &exposure, 1000.0).is_ok());
+  }
+
+  #[test]
+  fn test_check_order_rejects_invalid_price() {
+    let rm = RiskManager::new(&test_config());
+    let err = rm
+      .check_order(&buy("yes", 1.2, 1.0), &Exposure::default(), 1000.0)
+      .unwrap_err();
+    assert_eq!(err.label(), "invalid_order");
+  }
+
+  #[test]
+  fn test_apply_config_keeps_counters() {
+    let mut rm = RiskManager::new(&test_config());
+    rm.record_trade(-10.0);
+    let exposure = Exposure::default();
+    assert!(rm.check_order(&buy("yes", 0.5, 300.0), &exposure, 1000.0).is_err());
+
+    let mut config = test_config();
+    config.max_position_size = 200.0;
+    rm.apply_config(&config, &[market("btc_5m", Asset::BTC)]);
+    assert!(rm.check_order(&buy("yes", 0.5, 300.0), &exposure, 1000.0).is_ok());
+    assert_eq!(rm.daily_loss(), 10.0);
+  }
+
+  #[test]
+  fn test_check_order_circuit_breaker() {
+    let mut rm = RiskManager::new(&test_config());
+    for _ in 0..3 {
+      rm.record_trade(-1.0);
+    }
+    let err = rm
+      .check_order(&buy("yes", 0.5, 1.0), &Exposure::default(), 1000.0)
+      .unwrap_err();
+    assert_eq!(err, RiskRejection::CircuitBreaker);
+  }
+
+  #[test]
+  fn test_halt_lets_sells_of_held_tokens_through() {
+    let mut rm = RiskManager::new(&test_config());
+    rm.halt("operator", HaltSource::Admin, 0);
+    let mut held = HashMap::new();
+    held.insert("yes".to_string(), 20.0);
+    let exposure = Exposure::from_book(&HashMap::new(), &[]).with_held(held);
+
+    let unwind = Order::new_maker("yes".to_string(), TradeSide::Sell, 0.01, 20.0);
+    assert!(rm.check_order(&unwind, &exposure, 0.0).is_ok());
+
+    let oversell = Order::new_maker("yes".to_string(), TradeSide::Sell, 0.01, 25.0);
+    let err = rm.check_order(&oversell, &exposure, 1000.0).unwrap_err();
+    assert_eq!(err.label(), "halted");
+    assert!(rm.check_order(&buy("yes", 0.5, 1.0), &exposure, 1000.0).is_err());
+  }
+
+  #[test]
+  fn test_correlated_yes_positions_share_net_delta() {
+    let rm = correlated_manager();
+    let mut positions = HashMap::new();
+    positions.insert("btc_5m_yes".to_string(), 80.0);
+    positions.insert("eth_5m_yes".to_string(), 60.0);
+    let exposure = Exposure::from_book(&positions, &[]);
+
+    // Each market is small, but together the bucket goes +140 → +160
+    let err = rm
+      .check_order(&buy("btc_15m_yes", 0.5, 40.0), &exposure, 1000.0)
+      .unwrap_err();
+    assert_eq!(err.label(), "net_delta");
+
+    // Buying NO in the same bucket reduces delta and passes
+    assert!(rm
+      .check_order(&buy("btc_15m_no", 0.5, 40.0), &exposure, 1000.0)
+      .is_ok());
+  }
+
+  #[test]
+  fn test_asset_limit_counts_both_outcomes() {
+    let rm = correlated_manager();
+    let mut positions = HashMap::new();
+    positions.insert("btc_5m_yes".to_string(), 100.0);
+    positions.insert("btc_15m_no".to_string(), 100.0);
+    let exposure = Exposure::from_book(&positions, &[]);
+
+    // Net delta is flat, but gross BTC exposure 200 + 60 > 250
+    let err = rm
+      .check_order(&buy("btc_5m_no", 0.6, 100.0), &exposure, 1000.0)
+      .unwrap_err();
+    assert!(matches!(err, RiskRejection::AssetLimit { asset: Asset::BTC, .. }));
+  }
+
+  fn tripped_with_elapsed_cooldown() -> RiskManager {
+    let mut config = test_config();
+    config.cooldown_seconds = 0;
+    let mut rm = RiskManager::new(&config);
+    for _ in 0..3 {
+      rm.record_trade(-1.0);
+    }
+    rm
+  }
+
+  #[test]
+  fn test_half_open_after_cooldown_limits_size() {
+    let mut rm = tripped_with_elapsed_cooldown();
+    assert_eq!(rm.breaker_state(), BreakerState::Open);
+
+    assert!(rm.tick(now_ms()));
+    assert_eq!(rm.breaker_state(), BreakerState::HalfOpen);
+    assert!(rm.can_trade());
+
+    // Trial limit is 25% of 100
+    let err = rm
+      .check_order(&buy("yes", 0.5, 60.0), &Exposure::default(), 1000.0)
+      .unwrap_err();
+    assert!(matches!(err, RiskRejection::PositionLimit { limit, .. } if limit == 25.0));
+  }
+
+  #[test]
+  fn test_half_open_trial_outcome() {
+    let mut rm = tripped_with_elapsed_cooldown();
+    rm.tick(now_ms());
+    rm.record_trade(2.0);
+    assert_eq!(rm.breaker_state(), BreakerState::Closed);
+
+    let mut rm = tripped_with_elapsed_cooldown();
+    rm.tick(now_ms());
+    rm.record_trade(-1.0);
+    assert_eq!(rm.breaker_state(), BreakerState::Open);
+  }
+
+  #[test]
+  fn test_tick_rolls_over_utc_day() {
+    let mut rm = RiskManager::new(&test_config());
+    rm.record_trade(-7.0);
+    let tomorrow = now_ms() + 86_400_000;
+    assert!(rm.tick(tomorrow));
+    assert_eq!(rm.daily_loss(), 0.0);
+    assert_eq!(rm.snapshot().day, utc_day(tomorrow));
+    assert!(!rm.tick(tomorrow));
+  }
+
+  #[test]
+  fn test_restore_keeps_same_day_counters() {
+    let mut rm = RiskManager::new(&test_config());
+    rm.record_trade(-5.0);
+    rm.record_trade(-5.0);
+    rm.record_trade(-5.0);
+    let saved = rm.snapshot();
+
+    // A restart must not hand back a fresh daily loss budget
+    let mut restarted = RiskManager::new(&test_config());
+    restarted.restore(&saved, now_ms());
+    assert_eq!(restarted.daily_loss(), 15.0);
+    assert!(restarted.is_circuit_breaker_active());
+    assert!(!restarted.can_trade());
+  }
+
+  #[test]
+  fn test_halt_blocks_orders_and_survives_rollover() {
+    let mut rm = RiskManager::new(&test_config());
+    assert!(rm.halt("FOMC", HaltSource::Admin, 1));
+    assert!(!rm.halt("again", HaltSource::KillSwitch, 2));
+    assert!(!rm.can_trade());
+
+    let err = rm
+      .check_order(&buy("yes", 0.5, 1.0), &Exposure::default(), 1000.0)
+      .unwrap_err();
+    assert_eq!(err.label(), "halted");
+
+    // Day rollover resets counters, not operator halts
+    rm.tick(now_ms() + 86_400_000);
+    assert_eq!(rm.snapshot().halt.unwrap().reason, "FOMC");
+
+    assert_eq!(rm.resume().unwrap().halted_at_ms, 1);
+    assert!(rm.can_trade());
+  }
+
   #[test]
   fn test_winning_trade_resets_counter() {
     let mut rm = RiskManager::new(&test_config());
@@ -0,0 +1,101 @@
+//! Risk Scheduler - Time-Driven Risk State and Persistence
+//!
+//! Drives the parts of `RiskManager` that depend on the clock
+//! rather than on trades:
+//! - UTC midnight rollover of daily counters
+//! - Open → HalfOpen once the breaker cooldown elapses
+//! - Requesting a checkpoint from the `StateCheckpointer` whenever the
+//!   counters change, so a crash-loop cannot reset the daily loss budget
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::sync::{broadcast, Notify, RwLock};
+use tracing::{info, instrument};
+
+use crate::domain::time::now_ms;
+use crate::ports::repository::{Repository, RiskStateSnapshot};
+
+use super::risk_manager::RiskManager;
+
+/// Periodic driver for risk rollover, breaker recovery and persistence.
+pub struct RiskScheduler<R: Repository> {
+  /// Shared risk manager.
+  risk: Arc<RwLock<RiskManager>>,
+  /// Repository holding the state snapshot (read on restore).
+  repo: Arc<R>,
+  /// Tick interval.
+  interval: Duration,
+  /// Risk state at the last checkpoint request (skip when unchanged).
+  last_saved: Option<RiskStateSnapshot>,
+  /// Checkpoint requests to the `StateCheckpointer`, if attached.
+  checkpoints: Option<Arc<Notify>>,
+}
+
+impl<R: Repository> RiskScheduler<R> {
+  /// Create a new scheduler.
+  pub fn new(risk: Arc<RwLock<RiskManager>>, repo: Arc<R>, interval: Duration) -> Self {
+    Self {
+      risk,
+      repo,
+      interval,
+      last_saved: None,
+      checkpoints: None,
+    }
+  }
+
+  /// Request a checkpoint through `checkpoints` when counters change.
+  pub fn with_checkpoints(mut self, checkpoints: Arc<Notify>) -> Self {
+    self.checkpoints = Some(checkpoints);
+    self
+  }
+
+  /// Restore risk counters from the latest snapshot, if any.
+  ///
+  /// Returns true if a persisted risk state was found.
+  #[instrument(skip(self))]
+  pub async fn restore(&mut self) -> Result<bool> {
+    let Some(state) = self.repo.load_latest_state().await? else {
+      return Ok(false);
+    };
+    let Some(risk_state) = state.risk else {
+      info!("Snapshot has no risk state — starting with fresh counters");
+      return Ok(false);
+    };
+
+    let mut risk = self.risk.write().await;
+    risk.restore(&risk_state, now_ms());
+    self.last_saved = Some(risk_state);
+    Ok(true)
+  }
+
+  /// Advance risk state and request a checkpoint if anything changed.
+  pub async fn tick(&mut self) {
+    let snapshot = {
+      let mut risk = self.risk.write().await;
+      risk.tick(now_ms());
+      risk.snapshot()
+    };
+
+    if self.last_saved.as_ref() != Some(&snapshot) {
+      if let Some(checkpoints) = &self.checkpoints {
+        checkpoints.notify_one();
+      }
+      self.last_saved = Some(snapshot);
+    }
+  }
+
+  /// Run `tick` on the configured interval until shutdown.
+  pub async fn run(mut self, mut shutdown_rx: broadcast::Receiver<()>) {
+    let mut ticker = tokio::time::interval(self.interval);
+    loop {
+      tokio::select! {
+        biased;
+        _ = shutdown_rx.recv() => break,
+        _ = ticker.tick() => self.tick().await,
+      }
+    }
+    info!("Risk scheduler stopped");
+  }
+}
@@ -6,31 +6,88 @@
 //!
 //! Settlement flow:
 //! 1. Scan open positions for resolved markets
-//! 2. Verify resolution on-chain
-//! 3. Batch redeem winning positions
-//! 4. Update local state and log results
+//! 2. Read the payout vector on-chain and classify the outcome
+//! 3. Batch redeem positions with a non-zero payout; losing
+//!    positions settle locally at zero
+//! 4. Update local state and log results, with realized PnL per
+//!    position from its payout fraction
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use chrono::Utc;
+use rust_decimal::prelude::ToPrimitive;
 use tracing::{error, info, warn};
 
+use crate::config::MarketConfig;
 use crate::domain::trade::{MarketId, Position, TokenId};
-use crate::ports::chain_client::{ChainClient, RedemptionResult};
-use crate::ports::repository::Repository;
+use crate::ports::chain_client::{ChainClient, ConditionPayouts, RedemptionResult};
+use crate::ports::repository::{Repository, SettlementEntry, SettlementRecord};
 
 /// Status of a market resolution check.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Resolved variants carry the on-chain payout vector
+/// (index 0 = YES, 1 = NO).
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ResolutionStatus {
   /// Market has not yet resolved.
   Pending,
   /// Market resolved; YES won.
-  ResolvedYes,
+  ResolvedYes(ConditionPayouts),
   /// Market resolved; NO won.
-  ResolvedNo,
-  /// Market was voided / cancelled.
-  Voided,
+  ResolvedNo(ConditionPayouts),
+  /// Market was voided / cancelled; every outcome pays equally.
+  Voided(ConditionPayouts),
+  /// Any other split of collateral across outcomes.
+  Split(ConditionPayouts),
+}
+
+impl ResolutionStatus {
+  /// Classify a payout vector.
+  pub fn from_payouts(payouts: ConditionPayouts) -> Self {
+    if !payouts.is_resolved() {
+      return Self::Pending;
+    }
+    let d = payouts.denominator;
+    match payouts.numerators.as_slice() {
+      [yes, 0] if *yes == d => Self::ResolvedYes(payouts),
+      [0, no] if *no == d => Self::ResolvedNo(payouts),
+      [first, rest @ ..] if !rest.is_empty() && rest.iter().all(|n| n == first) => {
+        Self::Voided(payouts)
+      }
+      _ => Self::Split(payouts),
+    }
+  }
+
+  /// Payout vector, if resolved.
+  pub fn payouts(&self) -> Option<&ConditionPayouts> {
+    match self {
+      Self::Pending => None,
+      Self::ResolvedYes(p) | Self::ResolvedNo(p) | Self::Voided(p) | Self::Split(p) => Some(p),
+    }
+  }
+
+  /// Variant name, for logs and persisted reports.
+  pub fn label(&self) -> &'static str {
+    match self {
+      Self::Pending => "Pending",
+      Self::ResolvedYes(_) => "ResolvedYes",
+      Self::ResolvedNo(_) => "ResolvedNo",
+      Self::Voided(_) => "Voided",
+      Self::Split(_) => "Split",
+    }
+  }
+
+  /// Whether the market has resolved.
+  pub fn is_resolved(&self) -> bool {
+    self.payouts().is_some()
+  }
+
+  /// USDC paid per token of outcome `index` (0.0 while pending).
+  pub fn payout_fraction(&self, index: usize) -> f64 {
+    self.payouts().map_or(0.0, |p| p.fraction(index))
+  }
 }
 
 /// Summary of a single settlement attempt.
@@ -44,6 +101,9 @@ pub struct SettlementResult {
   pub usdc_recovered: f64,
   /// Transaction hash (if redeemed on-chain).
   pub tx_hash: Option<String>,
+  /// Realized PnL (payout − cost basis), when the token's outcome and
+  /// cost basis are known.
+  pub realized_pnl: Option<f64>,
   /// Whether settlement succeeded.
   pub success: bool,
   /// Error message if settlement failed.
@@ -65,33 +125,92 @@ pub struct SettlementReport {
   pub timestamp: chrono::DateTime<Utc>,
 }
 
+impl SettlementReport {
+  /// Realized PnL booked by successful settlements.
+  ///
+  /// Results without a known cost basis contribute nothing; their
+  /// proceeds are reported by `unattributed_usdc`.
+  pub fn realized_pnl(&self) -> f64 {
+    self
+      .results
+      .iter()
+      .filter(|r| r.success)
+      .filter_map(|r| r.realized_pnl)
+      .sum()
+  }
+
+  /// USDC recovered by successful settlements whose PnL is unknown.
+  pub fn unattributed_usdc(&self) -> f64 {
+    self
+      .results
+      .iter()
+      .filter(|r| r.success && r.realized_pnl.is_none())
+      .map(|r| r.usdc_recovered)
+      .sum()
+  }
+
+  /// Convert to the persisted form.
+  pub fn to_record(&self) -> SettlementRecord {
+    SettlementRecord {
+      timestamp_ms: self.timestamp.timestamp_millis().max(0) as u64,
+      entries: self
+        .results
+        .iter()
+        .map(|r| SettlementEntry {
+          market_id: r.market_id.clone(),
+          resolution: r.resolution.label().to_string(),
+          payouts: r
+            .resolution
+            .payouts()
+            .map(|p| p.numerators.clone())
+            .unwrap_or_default(),
+          usdc_recovered: r.usdc_recovered,
+          realized_pnl: r.realized_pnl,
+          tx_hash: r.tx_hash.clone(),
+          success: r.success,
+          error: r.error.clone(),
+        })
+        .collect(),
+      total_usdc_recovered: self.total_usdc_recovered,
+      realized_pnl: self.realized_pnl(),
+      markets_settled: self.markets_settled,
+      markets_failed: self.markets_failed,
+    }
+  }
+}
+
 /// Settlement manager that handles batch redemption of resolved markets.
 pub struct Settlement<C: ChainClient, R: Repository> {
-  chain: C,
-  /// Reserved for future settlement persistence (trade log of redemptions).
-  #[allow(dead_code)]
-  repo: R,
+  chain: Arc<C>,
+  /// Repository for settlement reports and state.
+  repo: Arc<R>,
   /// Minimum USDC value to trigger on-chain redemption (avoid dust).
   min_redemption_value: f64,
   /// Maximum positions to redeem in a single batch.
   max_batch_size: usize,
+  /// Token ID → outcome slot index (0 = YES, 1 = NO).
+  outcomes: HashMap<TokenId, usize>,
+  /// Report what would be redeemed without sending transactions.
+  dry_run: bool,
 }
 
 impl<C: ChainClient, R: Repository> Settlement<C, R> {
   /// Create a new settlement manager.
-  pub fn new(chain: C, repo: R) -> Self {
+  pub fn new(chain: Arc<C>, repo: Arc<R>) -> Self {
     Self {
       chain,
       repo,
       min_redemption_value: 0.10,
       max_batch_size: 20,
+      outcomes: HashMap::new(),
+      dry_run: false,
     }
   }
 
   /// Create with custom thresholds.
   pub fn with_config(
-    chain: C,
-    repo: R,
+    chain: Arc<C>,
+    repo: Arc<R>,
     min_redemption_value: f64,
     max_batch_size: usize,
   ) -> Self {
@@ -100,13 +219,60 @@ impl<C: ChainClient, R: Repository> Settlement<C, R> {
       repo,
       min_redemption_value,
       max_batch_size,
+      outcomes: HashMap::new(),
+      dry_run: false,
     }
   }
 
+  /// Register configured markets so payouts can be attributed per token.
+  pub fn with_markets(mut self, markets: &[MarketConfig]) -> Self {
+    for market in markets {
+      self.outcomes.insert(market.yes_token_id.clone(), 0);
+      self.outcomes.insert(market.no_token_id.clone(), 1);
+    }
+    self
+  }
+
+  /// Check resolutions but never redeem; redeemable positions are
+  /// reported at their expected payout without a transaction.
+  pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+    self.dry_run = dry_run;
+    self
+  }
+
+  /// Whether sweeps are dry runs.
+  pub fn is_dry_run(&self) -> bool {
+    self.dry_run
+  }
+
+  /// Chain client used for resolution checks and redemption.
+  pub fn chain(&self) -> &C {
+    &self.chain
+  }
+
+  /// Repository settlement reports are persisted to.
+  pub fn repo(&self) -> &R {
+    &self.repo
+  }
+
+  /// Payout value and realized PnL of a position under `status`.
+  ///
+  /// `None` when the token's outcome slot is unknown; the PnL is `None`
+  /// when the position carries no entry price (cost basis unknown).
+  fn attribute(&self, position: &Position, status: &ResolutionStatus) -> Option<(f64, Option<f64>)> {
+    let index = *self.outcomes.get(&position.token_id)?;
+    let size = position.size.to_f64().unwrap_or(0.0);
+    let entry = position.avg_entry_price.to_f64().unwrap_or(0.0);
+    let payout = size * status.payout_fraction(index);
+    let pnl = (entry > 0.0).then_some(payout - size * entry);
+    Some((payout, pnl))
+  }
+
   /// Run a full settlement sweep across all open positions.
   ///
-  /// Checks each position's market for resolution, then batch-redeems
-  /// any winning positions via the CTF contract.
+  /// Reads each position's payout vector, settles losing positions
+  /// locally, leaves payouts below `min_redemption_value` on-chain, and
+  /// batch-redeems the rest via the CTF contract.
   pub async fn sweep(&self, positions: &[Position]) -> Result<SettlementReport> {
     info!(
       position_count = positions.len(),
@@ -114,33 +280,53 @@ impl<C: ChainClient, R: Repository> Settlement<C, R> {
     );
 
     let mut results = Vec::new();
-    let mut redeemable: Vec<&Position> = Vec::new();
+    let mut redeemable: Vec<(&Position, ResolutionStatus)> = Vec::new();
 
     // Phase 1: Check resolution status for each position's market
     for position in positions {
       match self.check_resolution(&position.condition_id).await {
-        Ok(status) => {
-          match status {
-            ResolutionStatus::ResolvedYes | ResolutionStatus::ResolvedNo => {
-              info!(
-                market_id = %position.condition_id,
-                resolution = ?status,
-                "Market resolved, queuing for redemption"
-              );
-              redeemable.push(position);
-            }
-            ResolutionStatus::Voided => {
-              info!(
-                market_id = %position.condition_id,
-                "Market voided, queuing for redemption"
-              );
-              redeemable.push(position);
-            }
-            ResolutionStatus::Pending => {
-              // Not yet resolved, skip
-            }
-          }
+        Ok(ResolutionStatus::Pending) => {
+          // Not yet resolved, skip
         }
+        Ok(status) => match self.attribute(position, &status) {
+          Some((payout, pnl)) if payout <= 0.0 => {
+            // Nothing to redeem: settle locally, no gas spent
+            info!(
+              market_id = %position.condition_id,
+              token_id = %position.token_id,
+              resolution = ?status,
+              realized_pnl = ?pnl,
+              "Position resolved worthless"
+            );
+            results.push(SettlementResult {
+              market_id: position.condition_id.clone(),
+              resolution: status,
+              usdc_recovered: 0.0,
+              tx_hash: None,
+              realized_pnl: pnl,
+              success: true,
+              error: None,
+            });
+          }
+          Some((payout, _)) if payout < self.min_redemption_value => {
+            // Not worth the gas: leave it for a later sweep
+            info!(
+              market_id = %position.condition_id,
+              token_id = %position.token_id,
+              payout,
+              min_redemption_value = self.min_redemption_value,
+              "Skipping dust redemption"
+            );
+          }
+          _ => {
+            info!(
+              market_id = %position.condition_id,
+              resolution = ?status,
+              "Market resolved, queuing for redemption"
+            );
+            redeemable.push((position, status));
+          }
+        },
         Err(e) => {
           warn!(
             market_id = %position.condition_id,
@@ -152,6 +338,7 @@ impl<C: ChainClient, R: Repository> Settlement<C, R> {
             resolution: ResolutionStatus::Pending,
             usdc_recovered: 0.0,
             tx_hash: None,
+            realized_pnl: None,
             success: false,
             error: Some(format!("Resolution check failed: {e}")),
           });
@@ -161,7 +348,11 @@ impl<C: ChainClient, R: Repository> Settlement<C, R> {
 
     // Phase 2: Batch redeem resolved positions
     if !redeemable.is_empty() {
-      let batch_results = self.batch_redeem(&redeemable).await;
+      let batch_results = if self.dry_run {
+        self.preview_redeem(&redeemable)
+      } else {
+        self.batch_redeem(&redeemable).await
+      };
       results.extend(batch_results);
     }
 
@@ -193,32 +384,57 @@ impl<C: ChainClient, R: Repository> Settlement<C, R> {
     Ok(report)
   }
 
-  /// Check if a market's condition has been resolved on-chain.
+  /// Read a market's payout vector on-chain and classify it.
   async fn check_resolution(&self, condition_id: &str) -> Result<ResolutionStatus> {
-    let resolved = self
+    let payouts = self
       .chain
-      .is_condition_resolved(condition_id)
+      .condition_payouts(condition_id)
       .await
-      .context("Failed to query condition resolution")?;
-
-    if resolved {
-      // For simplicity, return ResolvedYes; a full implementation
-      // would query the payout vector to determine the outcome.
-      Ok(ResolutionStatus::ResolvedYes)
-    } else {
-      Ok(ResolutionStatus::Pending)
-    }
+      .context("Failed to query condition payouts")?;
+
+    Ok(ResolutionStatus::from_payouts(payouts))
+  }
+
+  /// Dry run: expected results of redeeming `positions`, nothing sent.
+  fn preview_redeem(&self, positions: &[(&Position, ResolutionStatus)]) -> Vec<SettlementResult> {
+    positions
+      .iter()
+      .map(|(pos, status)| {
+        let attribution = self.attribute(pos, status);
+        info!(
+          market_id = %pos.condition_id,
+          token_id = %pos.token_id,
+          resolution = ?status,
+          "Dry run — would redeem"
+        );
+        SettlementResult {
+          market_id: pos.condition_id.clone(),
+          resolution: status.clone(),
+          usdc_recovered: attribution.map_or(0.0, |(payout, _)| payout),
+          tx_hash: None,
+          realized_pnl: attribution.and_then(|(_, pnl)| pnl),
+          success: true,
+          error: None,
+        }
+      })
+      .collect()
   }
 
   /// Batch redeem a set of positions, respecting batch size limits.
-  async fn batch_redeem(&self, positions: &[&Position]) -> Vec<SettlementResult> {
+  ///
+  /// Recovered USDC is attributed in proportion to each position's
+  /// expected payout, or evenly if any outcome slot is unknown.
+  async fn batch_redeem(&self, positions: &[(&Position, ResolutionStatus)]) -> Vec<SettlementResult> {
     let mut results = Vec::new();
 
     for chunk in positions.chunks(self.max_batch_size) {
-      let condition_ids: Vec<String> = chunk
-        .iter()
-        .map(|p| p.condition_id.clone())
-        .collect();
+      // YES and NO of one condition are redeemed by the same call
+      let mut condition_ids: Vec<String> = Vec::with_capacity(chunk.len());
+      for (p, _) in chunk {
+        if !condition_ids.contains(&p.condition_id) {
+          condition_ids.push(p.condition_id.clone());
+        }
+      }
 
       info!(
         batch_size = chunk.len(),
@@ -234,19 +450,23 @@ impl<C: ChainClient, R: Repository> Settlement<C, R> {
             "Batch redemption successful"
           );
 
-          // Distribute recovered USDC proportionally
-          let per_position = if redemption.positions_redeemed > 0 {
-            redemption.usdc_recovered / redemption.positions_redeemed as f64
-          } else {
-            0.0
-          };
-
-          for pos in chunk {
+          let attributed: Vec<Option<(f64, Option<f64>)>> = chunk
+            .iter()
+            .map(|(p, status)| self.attribute(p, status))
+            .collect();
+          let expected: Option<Vec<f64>> = attributed
+            .iter()
+            .map(|a| a.map(|(payout, _)| payout))
+            .collect();
+          let shares = split_recovered(&redemption, chunk.len(), expected.as_deref());
+
+          for (((pos, status), share), attribution) in chunk.iter().zip(shares).zip(attributed) {
             results.push(SettlementResult {
               market_id: pos.condition_id.clone(),
-              resolution: ResolutionStatus::ResolvedYes,
-              usdc_recovered: per_position,
+              resolution: status.clone(),
+              usdc_recovered: share,
               tx_hash: Some(redemption.tx_hash.clone()),
+              realized_pnl: attribution.and_then(|(_, pnl)| pnl),
               success: true,
               error: None,
             });
@@ -259,12 +479,13 @@ impl<C: ChainClient, R: Repository> Settlement<C, R> {
             "Batch redemption failed"
           );
 
-          for pos in chunk {
+          for (pos, status) in chunk {
             results.push(SettlementResult {
               market_id: pos.condition_id.clone(),
-              resolution: ResolutionStatus::ResolvedYes,
+              resolution: status.clone(),
               usdc_recovered: 0.0,
               tx_hash: None,
+              realized_pnl: None,
               success: false,
               error: Some(format!("Batch redemption failed: {e}")),
             });
@@ -280,70 +501,97 @@ impl<C: ChainClient, R: Repository> Settlement<C, R> {
   pub async fn settle_single(&self, position: &Position) -> Result<SettlementResult> {
     let status = self.check_resolution(&position.condition_id).await?;
 
-    match status {
-      ResolutionStatus::Pending => {
-        Ok(SettlementResult {
-          market_id: position.condition_id.clone(),
-          resolution: ResolutionStatus::Pending,
-          usdc_recovered: 0.0,
-          tx_hash: None,
-          success: false,
-          error: Some("Market not yet resolved".to_string()),
-        })
-      }
-      _ => {
-        let ids = vec![position.condition_id.clone()];
-        match self.chain.batch_redeem(&ids).await {
-          Ok(redemption) => Ok(SettlementResult {
-            market_id: position.condition_id.clone(),
-            resolution: status,
-            usdc_recovered: redemption.usdc_recovered,
-            tx_hash: Some(redemption.tx_hash),
-            success: true,
-            error: None,
-          }),
-          Err(e) => Ok(SettlementResult {
-            market_id: position.condition_id.clone(),
-            resolution: status,
-            usdc_recovered: 0.0,
-            tx_hash: None,
-            success: false,
-            error: Some(format!("Redemption failed: {e}")),
-          }),
-        }
-      }
+    if status == ResolutionStatus::Pending {
+      return Ok(SettlementResult {
+        market_id: position.condition_id.clone(),
+        resolution: ResolutionStatus::Pending,
+        usdc_recovered: 0.0,
+        tx_hash: None,
+        realized_pnl: None,
+        success: false,
+        error: Some("Market not yet resolved".to_string()),
+      });
+    }
+
+    let realized_pnl = self.attribute(position, &status).and_then(|(_, pnl)| pnl);
+    let ids = vec![position.condition_id.clone()];
+    match self.chain.batch_redeem(&ids).await {
+      Ok(redemption) => Ok(SettlementResult {
+        market_id: position.condition_id.clone(),
+        resolution: status,
+        usdc_recovered: redemption.usdc_recovered,
+        tx_hash: Some(redemption.tx_hash),
+        realized_pnl,
+        success: true,
+        error: None,
+      }),
+      Err(e) => Ok(SettlementResult {
+        market_id: position.condition_id.clone(),
+        resolution: status,
+        usdc_recovered: 0.0,
+        tx_hash: None,
+        realized_pnl: None,
+        success: false,
+        error: Some(format!("Redemption failed: {e}")),
+      }),
     }
   }
 }
 
+/// Split a redemption's recovered USDC across `count` positions.
+///
+/// Weighted by `expected` payouts when available and non-zero,
+/// otherwise evenly.
+fn split_recovered(redemption: &RedemptionResult, count: usize, expected: Option<&[f64]>) -> Vec<f64> {
+  let total = redemption.usdc_recovered;
+  if let Some(expected) = expected {
+    let sum: f64 = expected.iter().sum();
+    if sum > 0.0 {
+      return expected.iter().map(|e| total * e / sum).collect();
+    }
+  }
+  let per_position = if count > 0 { total / count as f64 } else { 0.0 };
+  vec![per_position; count]
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  fn payouts(numerators: &[u128]) -> ConditionPayouts {
+    ConditionPayouts {
+      numerators: numerators.to_vec(),
+      denominator: numerators.iter().sum(),
+    }
+  }
+
   #[test]
   fn test_settlement_report_aggregation() {
-    let results = vec![
+    let results = [
       SettlementResult {
         market_id: "market_1".to_string(),
-        resolution: ResolutionStatus::ResolvedYes,
+        resolution: ResolutionStatus::ResolvedYes(payouts(&[1, 0])),
         usdc_recovered: 50.0,
         tx_hash: Some("0xabc".to_string()),
+        realized_pnl: Some(25.0),
         success: true,
         error: None,
       },
       SettlementResult {
         market_id: "market_2".to_string(),
-        resolution: ResolutionStatus::ResolvedNo,
+        resolution: ResolutionStatus::ResolvedNo(payouts(&[0, 1])),
         usdc_recovered: 0.0,
         tx_hash: None,
+        realized_pnl: None,
         success: false,
         error: Some("Redemption failed".to_string()),
       },
       SettlementResult {
         market_id: "market_3".to_string(),
-        resolution: ResolutionStatus::ResolvedYes,
+        resolution: ResolutionStatus::ResolvedYes(payouts(&[1, 0])),
         usdc_recovered: 30.0,
         tx_hash: Some("0xdef".to_string()),
+        realized_pnl: Some(10.0),
         success: true,
         error: None,
       },
@@ -358,9 +606,92 @@ mod tests {
     assert_eq!(failed, 1);
   }
 
+  #[test]
+  fn test_unknown_basis_is_not_realized_pnl() {
+    let report = SettlementReport {
+      results: vec![
+        SettlementResult {
+          market_id: "market_1".to_string(),
+          resolution: ResolutionStatus::ResolvedYes(payouts(&[1, 0])),
+          usdc_recovered: 50.0,
+          tx_hash: Some("0xabc".to_string()),
+          realized_pnl: Some(25.0),
+          success: true,
+          error: None,
+        },
+        SettlementResult {
+          market_id: "market_2".to_string(),
+          resolution: ResolutionStatus::ResolvedYes(payouts(&[1, 0])),
+          usdc_recovered: 30.0,
+          tx_hash: Some("0xdef".to_string()),
+          realized_pnl: None,
+          success: true,
+          error: None,
+        },
+      ],
+      total_usdc_recovered: 80.0,
+      markets_settled: 2,
+      markets_failed: 0,
+      timestamp: Utc::now(),
+    };
+
+    assert_eq!(report.realized_pnl(), 25.0);
+    assert_eq!(report.unattributed_usdc(), 30.0);
+  }
+
   #[test]
   fn test_resolution_status_eq() {
     assert_eq!(ResolutionStatus::Pending, ResolutionStatus::Pending);
-    assert_ne!(ResolutionStatus::ResolvedYes, ResolutionStatus::ResolvedNo);
+    assert_ne!(
+      ResolutionStatus::ResolvedYes(payouts(&[1, 0])),
+      ResolutionStatus::ResolvedNo(payouts(&[0, 1]))
+    );
+  }
+
+  #[test]
+  fn test_classify_payout_vectors() {
+    assert_eq!(
+      ResolutionStatus::from_payouts(ConditionPayouts::default()),
+      ResolutionStatus::Pending
+    );
+    assert!(matches!(
+      ResolutionStatus::from_payouts(payouts(&[1, 0])),
+      ResolutionStatus::ResolvedYes(_)
+    ));
+    assert!(matches!(
+      ResolutionStatus::from_payouts(payouts(&[0, 1])),
+      ResolutionStatus::ResolvedNo(_)
+    ));
+    assert!(matches!(
+      ResolutionStatus::from_payouts(payouts(&[1, 1])),
+      ResolutionStatus::Voided(_)
+    ));
+    assert!(matches!(
+      ResolutionStatus::from_payouts(payouts(&[3, 7])),
+      ResolutionStatus::Split(_)
+    ));
+  }
+
+  #[test]
+  fn test_payout_fractions() {
+    let voided = ResolutionStatus::from_payouts(payouts(&[1, 1]));
+    assert_eq!(voided.payout_fraction(0), 0.5);
+    assert_eq!(voided.payout_fraction(1), 0.5);
+
+    let split = ResolutionStatus::from_payouts(payouts(&[3, 7]));
+    assert!((split.payout_fraction(1) - 0.7).abs() < 1e-12);
+    assert_eq!(ResolutionStatus::Pending.payout_fraction(0), 0.0);
+  }
+
+  #[test]
+  fn test_split_recovered_weights_by_expected_payout() {
+    let redemption = RedemptionResult {
+      tx_hash: "0x1".to_string(),
+      positions_redeemed: 2,
+      usdc_recovered: 90.0,
+      gas_cost_matic: 0.0,
+    };
+    assert_eq!(split_recovered(&redemption, 2, Some(&[60.0, 30.0])), vec![60.0, 30.0]);
+    assert_eq!(split_recovered(&redemption, 2, None), vec![45.0, 45.0]);
   }
 }
@@ -0,0 +1,296 @@
+//! Settlement Scheduler - Daily Redemption Sweep
+//!
+//! Runs `Settlement::sweep` once a day at
+//! `settlement.batch_redeem_hour_utc`:
+//! - Positions come from on-chain ERC-1155 balances of configured markets
+//! - Deferred with exponential backoff (5 min → 1 h) while gas is above
+//!   `settlement.max_gas_gwei` or when a redemption fails
+//! - Each report is persisted via `Repository`; its redemptions are
+//!   booked in the ledger, whose realized PnL the `StateCheckpointer`
+//!   writes to `BotStateSnapshot.cumulative_pnl`
+//!
+//! Cost basis is not known on-chain: with a `PortfolioLedger` attached
+//! positions carry the ledger's average entry price and redemptions are
+//! booked in it; tokens without a known basis report no realized PnL,
+//! only the USDC recovered. Without a ledger nothing is booked
+//! (the `settle` command only saves reports).
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use tokio::sync::broadcast;
+use tracing::{info, instrument, warn};
+
+use crate::config::{AppConfig, MarketConfig};
+use crate::domain::trade::Position;
+use crate::ports::chain_client::ChainClient;
+use crate::ports::repository::Repository;
+
+use super::portfolio_ledger::PortfolioLedger;
+use super::settlement::{Settlement, SettlementReport};
+
+/// First retry delay after a deferred sweep.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// Longest retry delay.
+const MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+/// Daily driver for settlement sweeps.
+pub struct SettlementScheduler<C: ChainClient, R: Repository> {
+  /// Settlement use case (owns the chain client and repository).
+  settlement: Settlement<C, R>,
+  /// Configured markets whose tokens are swept.
+  markets: Vec<MarketConfig>,
+  /// Hour (UTC) the daily sweep becomes due.
+  redeem_hour_utc: u32,
+  /// Gas ceiling (gwei); above it the sweep is deferred.
+  max_gas_gwei: f64,
+  /// Tick interval.
+  interval: Duration,
+  /// UTC day of the last completed sweep.
+  last_run_day: Option<NaiveDate>,
+  /// When a deferred sweep should be retried.
+  retry_at: Option<DateTime<Utc>>,
+  /// Delay applied on the next deferral.
+  backoff: Duration,
+  /// Ledger providing cost basis and booking redemptions, if attached.
+  ledger: Option<Arc<PortfolioLedger>>,
+}
+
+impl<C: ChainClient, R: Repository> SettlementScheduler<C, R> {
+  /// Create a scheduler from `[settlement]` and `[[markets]]` config.
+  pub fn new(chain: Arc<C>, repo: Arc<R>, config: &AppConfig, interval: Duration) -> Self {
+    Self {
+      settlement: Settlement::new(chain, repo).with_markets(&config.markets),
+      markets: config.markets.clone(),
+      redeem_hour_utc: config.settlement.batch_redeem_hour_utc,
+      max_gas_gwei: config.settlement.max_gas_gwei,
+      interval,
+      last_run_day: None,
+      retry_at: None,
+      backoff: INITIAL_BACKOFF,
+      ledger: None,
+    }
+  }
+
+  /// Take cost basis from `ledger` and book redemptions in it.
+  pub fn with_ledger(mut self, ledger: Arc<PortfolioLedger>) -> Self {
+    self.ledger = Some(ledger);
+    self
+  }
+
+  /// Whether a sweep should run at `now`.
+  pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+    self.retry_at.map_or_else(
+      || now.hour() >= self.redeem_hour_utc && self.last_run_day != Some(now.date_naive()),
+      |retry_at| now >= retry_at,
+    )
+  }
+
+  /// When a deferred sweep will be retried, if any.
+  pub fn retry_at(&self) -> Option<DateTime<Utc>> {
+    self.retry_at
+  }
+
+  /// Run the sweep if due. Returns the report when one ran.
+  #[instrument(skip(self))]
+  pub async fn tick(&mut self, now: DateTime<Utc>) -> Result<Option<SettlementReport>> {
+    if !self.is_due(now) {
+      return Ok(None);
+    }
+
+    let gas_gwei = match self.settlement.chain().gas_price_gwei().await {
+      Ok(gas) => gas,
+      Err(e) => {
+        self.defer(now);
+        return Err(e).context("Gas price query failed");
+      }
+    };
+    if gas_gwei > self.max_gas_gwei {
+      info!(
+        gas_gwei,
+        max_gas_gwei = self.max_gas_gwei,
+        retry_in_secs = self.backoff.as_secs(),
+        "Gas above limit — deferring settlement"
+      );
+      self.defer(now);
+      return Ok(None);
+    }
+
+    let positions = match self.held_positions(now).await {
+      Ok(positions) => positions,
+      Err(e) => {
+        self.defer(now);
+        return Err(e);
+      }
+    };
+    if positions.is_empty() {
+      info!("No outcome tokens held — nothing to settle");
+      self.complete(now);
+      return Ok(None);
+    }
+
+    let report = self.settlement.sweep(&positions).await?;
+    if let Err(e) = self.persist(&report).await {
+      // Redemptions already happened on-chain; do not re-run for this
+      warn!(error = %e, "Failed to persist settlement report");
+    }
+
+    if report.markets_failed > 0 {
+      warn!(
+        failed = report.markets_failed,
+        retry_in_secs = self.backoff.as_secs(),
+        "Some settlements failed — retrying later"
+      );
+      self.defer(now);
+    } else {
+      self.complete(now);
+    }
+    Ok(Some(report))
+  }
+
+  /// Sweep dry: report what would be redeemed without sending or
+  /// persisting anything.
+  pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+    self.settlement = self.settlement.with_dry_run(dry_run);
+    self
+  }
+
+  /// Sweep held tokens now, ignoring the schedule (operator command).
+  /// Redemptions still refuse gas above `settlement.max_gas_gwei`; real
+  /// sweeps are persisted like scheduled ones.
+  pub async fn sweep_now(&self) -> Result<SettlementReport> {
+    let positions = self.held_positions(Utc::now()).await?;
+    let report = self.settlement.sweep(&positions).await?;
+    if !self.settlement.is_dry_run() {
+      self.persist(&report).await?;
+    }
+    Ok(report)
+  }
+
+  /// Run `tick` on the configured interval until shutdown.
+  pub async fn run(mut self, mut shutdown_rx: broadcast::Receiver<()>) {
+    info!(
+      hour_utc = self.redeem_hour_utc,
+      max_gas_gwei = self.max_gas_gwei,
+      "Settlement scheduler started"
+    );
+    let mut ticker = tokio::time::interval(self.interval);
+    loop {
+      tokio::select! {
+        biased;
+        _ = shutdown_rx.recv() => break,
+        _ = ticker.tick() => {
+          if let Err(e) = self.tick(Utc::now()).await {
+            warn!(error = %e, "Settlement tick failed");
+          }
+        }
+      }
+    }
+    info!("Settlement scheduler stopped");
+  }
+
+  /// Push the next attempt out by the current backoff and double it.
+  fn defer(&mut self, now: DateTime<Utc>) {
+    let delay = chrono::Duration::from_std(self.backoff).unwrap_or(chrono::Duration::hours(1));
+    self.retry_at = Some(now + delay);
+    self.backoff = (self.backoff * 2).min(MAX_BACKOFF);
+  }
+
+  /// Mark today's sweep as done and reset the backoff.
+  fn complete(&mut self, now: DateTime<Utc>) {
+    self.last_run_day = Some(now.date_naive());
+    self.retry_at = None;
+    self.backoff = INITIAL_BACKOFF;
+  }
+
+  /// Outcome tokens currently held, as positions.
+  async fn held_positions(&self, now: DateTime<Utc>) -> Result<Vec<Position>> {
+    let token_ids: Vec<String> = self
+      .markets
+      .iter()
+      .flat_map(|m| [m.yes_token_id.clone(), m.no_token_id.clone()])
+      .collect();
+    if token_ids.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    let balances = self
+      .settlement
+      .chain()
+      .token_balances(&token_ids)
+      .await
+      .context("Failed to query outcome token balances")?;
+
+    let mut positions = Vec::new();
+    for b in balances.into_iter().filter(|b| b.balance_raw > 0) {
+      let Some(market) = self
+        .markets
+        .iter()
+        .find(|m| m.yes_token_id == b.token_id || m.no_token_id == b.token_id)
+      else {
+        continue;
+      };
+      let entry = match &self.ledger {
+        Some(ledger) => ledger.avg_entry_price(&b.token_id).await.unwrap_or(0.0),
+        None => 0.0,
+      };
+      positions.push(Position {
+        condition_id: market.condition_id.clone(),
+        token_id: b.token_id,
+        asset: market.asset,
+        size: Decimal::from_f64(b.balance).unwrap_or_default(),
+        avg_entry_price: Decimal::from_f64(entry).unwrap_or_default(),
+        unrealized_pnl: Decimal::ZERO,
+        opened_at: now,
+        resolved: false,
+      });
+    }
+    Ok(positions)
+  }
+
+  /// Book successful redemptions in the ledger (once per market).
+  async fn book_redemptions(&self, report: &SettlementReport) {
+    let Some(ledger) = &self.ledger else {
+      return;
+    };
+    if self.settlement.is_dry_run() {
+      return;
+    }
+    let mut booked = HashSet::new();
+    for r in report.results.iter().filter(|r| r.success && r.resolution.is_resolved()) {
+      if booked.insert(r.market_id.as_str()) {
+        ledger
+          .record_redemption(
+            &r.market_id,
+            r.resolution.payout_fraction(0),
+            r.resolution.payout_fraction(1),
+          )
+          .await;
+      }
+    }
+  }
+
+  /// Save the report and book its redemptions in the ledger.
+  async fn persist(&self, report: &SettlementReport) -> Result<()> {
+    self.book_redemptions(report).await;
+    let unattributed = report.unattributed_usdc();
+    if unattributed > 0.0 {
+      warn!(
+        usdc_recovered = unattributed,
+        "Settled tokens with unknown cost basis — proceeds not booked as PnL"
+      );
+    }
+    self
+      .settlement
+      .repo()
+      .save_settlement_report(&report.to_record())
+      .await
+      .context("Failed to save settlement report")
+  }
+}
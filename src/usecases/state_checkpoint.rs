@@ -0,0 +1,369 @@
+//! State Checkpointer - Full Snapshots and Startup Recovery
+//!
+//! Every `recovery.checkpoint_interval_secs`, whenever a component
+//! requests it (`RiskScheduler` on a counter change, `PortfolioLedger`
+//! after new events), and once more on shutdown, the live state of
+//! every component is written as one `BotStateSnapshot`:
+//! - `RiskGate`: tracked resting orders with the size credited from each
+//! - `RiskManager`: counters, breaker and halt
+//! - `PortfolioLedger`: lots, marks, PnL (positions = held sizes) and
+//!   the cumulative settlement PnL
+//!
+//! The checkpointer is the only writer of the snapshot, and every field
+//! comes from a live component, so concurrent tasks cannot overwrite
+//! each other's updates with a stale copy.
+//!
+//! On startup `restore` loads the orders and positions back into the
+//! gate, and `verify` checks them before trading resumes:
+//! - tracked orders are reconciled with the CLOB (fills credited,
+//!   closed orders dropped)
+//! - untracked CLOB orders are replayed against the order journal:
+//!   journaled ones are adopted, unknown ones cancelled
+//!   (`recovery.cancel_unknown_orders`); without a journal all are adopted
+//! - ledger sizes are compared with on-chain token balances; a gap
+//!   above `recovery.position_tolerance` halts trading
+//!   (`HaltSource::Recovery`) until an operator resumes it
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::sync::{broadcast, Mutex, Notify, RwLock};
+use tracing::{info, instrument, warn};
+
+use crate::config::AppConfig;
+use crate::domain::time::now_ms;
+use crate::domain::trade::{Order, OrderId, TokenId};
+use crate::ports::chain_client::ChainClient;
+use crate::ports::execution::OrderExecution;
+use crate::ports::order_journal::{IntentOutcome, OrderJournal};
+use crate::ports::repository::{BotStateSnapshot, HaltSource, Repository, STATE_SCHEMA_VERSION};
+
+use super::portfolio_ledger::PortfolioLedger;
+use super::risk_gate::RiskGate;
+use super::risk_manager::RiskManager;
+
+/// Resolved intents this recent survive compaction, covering orders
+/// placed between a checkpoint's capture and its save.
+const JOURNAL_RETENTION_MS: u64 = 60_000;
+
+/// A token whose ledger size disagrees with the chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionMismatch {
+  /// Outcome token.
+  pub token_id: TokenId,
+  /// Size the restored ledger holds.
+  pub ledger: f64,
+  /// Size held on-chain.
+  pub chain: f64,
+}
+
+/// Outcome of the startup recovery checks.
+#[derive(Debug, Clone, Default)]
+pub struct RecoveryReport {
+  /// Tracked orders closed on the CLOB while the bot was down.
+  pub closed_orders: usize,
+  /// CLOB orders missing from the snapshot, now tracked.
+  pub adopted_orders: Vec<OrderId>,
+  /// CLOB orders unknown to the order journal, cancelled.
+  pub cancelled_orders: Vec<OrderId>,
+  /// Journaled intents never answered and not open on the CLOB.
+  pub abandoned_intents: usize,
+  /// Whether balances were checked on-chain (live mode only).
+  pub chain_checked: bool,
+  /// Positions that disagree with the chain.
+  pub mismatches: Vec<PositionMismatch>,
+  /// Whether this check halted trading (false if already halted).
+  pub halted: bool,
+}
+
+/// Periodic writer of the full bot state, and its startup counterpart.
+pub struct StateCheckpointer<E: OrderExecution + ?Sized, R: Repository> {
+  /// Gate tracking resting orders and position costs.
+  gate: Arc<RiskGate<E>>,
+  /// Shared risk manager.
+  risk: Arc<RwLock<RiskManager>>,
+  /// Portfolio ledger.
+  ledger: Arc<PortfolioLedger>,
+  /// Repository holding the state snapshot.
+  repo: Arc<R>,
+  /// Configured outcome tokens (checked on-chain even when not held).
+  tokens: Vec<TokenId>,
+  /// Largest tolerated ledger/chain gap per token.
+  tolerance: f64,
+  /// Halt trading on a mismatch.
+  halt_on_mismatch: bool,
+  /// Checkpoint interval.
+  interval: Duration,
+  /// Order intent journal replayed on startup, if any.
+  journal: Option<Arc<dyn OrderJournal>>,
+  /// Cancel (rather than adopt) orders the journal does not know.
+  cancel_unknown: bool,
+  /// Out-of-interval checkpoint requests from other components.
+  requests: Arc<Notify>,
+  /// Serializes capture + save, so an older capture never lands last.
+  writing: Mutex<()>,
+}
+
+impl<E: OrderExecution + ?Sized, R: Repository> StateCheckpointer<E, R> {
+  /// Create a checkpointer from `[recovery]` config.
+  pub fn new(
+    gate: Arc<RiskGate<E>>,
+    risk: Arc<RwLock<RiskManager>>,
+    ledger: Arc<PortfolioLedger>,
+    repo: Arc<R>,
+    config: &AppConfig,
+  ) -> Self {
+    let tokens = config
+      .markets
+      .iter()
+      .flat_map(|m| [m.yes_token_id.clone(), m.no_token_id.clone()])
+      .collect();
+    Self {
+      gate,
+      risk,
+      ledger,
+      repo,
+      tokens,
+      tolerance: config.recovery.position_tolerance,
+      halt_on_mismatch: config.recovery.halt_on_mismatch,
+      interval: Duration::from_secs(config.recovery.checkpoint_interval_secs),
+      journal: None,
+      cancel_unknown: config.recovery.cancel_unknown_orders,
+      requests: Arc::new(Notify::new()),
+      writing: Mutex::new(()),
+    }
+  }
+
+  /// Also checkpoint whenever `requests` is notified.
+  pub fn with_requests(mut self, requests: Arc<Notify>) -> Self {
+    self.requests = requests;
+    self
+  }
+
+  /// Replay this order journal on startup and compact it on checkpoint.
+  pub fn with_journal(mut self, journal: Arc<dyn OrderJournal>) -> Self {
+    self.journal = Some(journal);
+    self
+  }
+
+  /// Current state of every component as one snapshot.
+  pub async fn capture(&self, now_ms: u64) -> BotStateSnapshot {
+    let open_orders = self.gate.open_orders().await;
+    let portfolio = self.ledger.snapshot().await;
+    let risk_state = self.risk.read().await.snapshot();
+
+    BotStateSnapshot {
+      schema_version: STATE_SCHEMA_VERSION,
+      version: env!("CARGO_PKG_VERSION").to_string(),
+      timestamp_ms: now_ms,
+      open_orders,
+      positions: portfolio.sizes(),
+      cumulative_pnl: self.ledger.cumulative_pnl().await,
+      daily_loss: risk_state.daily_loss,
+      risk: Some(risk_state),
+      portfolio: Some(portfolio),
+    }
+  }
+
+  /// Capture and save the full state, then drop journaled intents
+  /// the snapshot now covers.
+  pub async fn checkpoint(&self, now_ms: u64) -> Result<BotStateSnapshot> {
+    let _writing = self.writing.lock().await;
+    let state = self.capture(now_ms).await;
+    self.repo.save_state(&state).await?;
+    if let Some(journal) = &self.journal {
+      if let Err(e) = journal.compact(now_ms.saturating_sub(JOURNAL_RETENTION_MS)).await {
+        warn!(error = %e, "Order journal compaction failed");
+      }
+    }
+    Ok(state)
+  }
+
+  /// Load checkpointed orders into the gate, with position costs and
+  /// sizes from the (already restored) ledger. Returns the number of
+  /// orders.
+  #[instrument(skip(self))]
+  pub async fn restore(&self) -> Result<usize> {
+    let orders = self
+      .repo
+      .load_latest_state()
+      .await?
+      .map(|s| s.open_orders)
+      .unwrap_or_default();
+    let restored = orders.len();
+    self
+      .gate
+      .restore(orders, self.ledger.costs().await, self.ledger.sizes().await)
+      .await;
+    Ok(restored)
+  }
+
+  /// Check restored state against the CLOB and, when given, the chain.
+  ///
+  /// Must run before the engine starts trading.
+  #[instrument(skip(self, chain))]
+  pub async fn verify(&self, chain: Option<&dyn ChainClient>, now_ms: u64) -> Result<RecoveryReport> {
+    let mut report = RecoveryReport {
+      closed_orders: self.gate.reconcile().await?,
+      ..RecoveryReport::default()
+    };
+    match &self.journal {
+      Some(journal) => self.replay(journal.as_ref(), &mut report).await?,
+      None => report.adopted_orders = self.gate.adopt_untracked().await?,
+    }
+    if !report.adopted_orders.is_empty() {
+      // Credit what adopted orders filled while the bot was down
+      self.gate.reconcile().await?;
+    }
+
+    if let Some(chain) = chain {
+      let ledger: Vec<(TokenId, f64)> = self.ledger.sizes().await;
+      let tokens: Vec<TokenId> = self
+        .tokens
+        .iter()
+        .chain(ledger.iter().map(|(token_id, _)| token_id))
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+      let balances = chain.token_balances(&tokens).await?;
+      report.chain_checked = true;
+
+      for token_id in tokens {
+        let held = ledger
+          .iter()
+          .find(|(t, _)| *t == token_id)
+          .map_or(0.0, |(_, size)| *size);
+        let onchain = balances
+          .iter()
+          .find(|b| b.token_id == token_id)
+          .map_or(0.0, |b| b.balance);
+        if (held - onchain).abs() > self.tolerance {
+          warn!(token = %token_id, ledger = held, chain = onchain, "Restored position disagrees with chain");
+          report.mismatches.push(PositionMismatch {
+            token_id,
+            ledger: held,
+            chain: onchain,
+          });
+        }
+      }
+    }
+
+    if !report.mismatches.is_empty() && self.halt_on_mismatch {
+      let reason = format!(
+        "{} position(s) differ from on-chain balances after restart",
+        report.mismatches.len()
+      );
+      report.halted = self
+        .risk
+        .write()
+        .await
+        .halt(&reason, HaltSource::Recovery, now_ms);
+    }
+
+    info!(
+      closed_orders = report.closed_orders,
+      adopted_orders = report.adopted_orders.len(),
+      cancelled_orders = report.cancelled_orders.len(),
+      abandoned_intents = report.abandoned_intents,
+      chain_checked = report.chain_checked,
+      mismatches = report.mismatches.len(),
+      halted = report.halted,
+      "Recovery verified"
+    );
+    Ok(report)
+  }
+
+  /// Resolve untracked CLOB orders against the order journal.
+  ///
+  /// An order whose placement was journaled is adopted at its original
+  /// size. So is one carrying the salt of an unanswered intent, and the
+  /// intent is marked placed. Anything else is cancelled, or adopted
+  /// when `cancel_unknown` is off. Intents still unanswered are marked
+  /// rejected; a fill they got is caught by the chain balance check.
+  async fn replay(&self, journal: &dyn OrderJournal, report: &mut RecoveryReport) -> Result<()> {
+    let entries = journal.entries().await?;
+    let mut pending: Vec<_> = entries.iter().filter(|e| e.outcome.is_none()).collect();
+
+    for live in self.gate.untracked_orders().await? {
+      let placed = entries.iter().find(|e| e.order_id() == Some(live.id.as_str()));
+      let intent = match placed {
+        Some(entry) => Some(entry),
+        None => match pending.iter().position(|e| !live.salt.is_empty() && e.client_id == live.salt) {
+          Some(i) => {
+            let entry = pending.remove(i);
+            journal
+              .record_outcome(
+                &entry.client_id,
+                IntentOutcome::Placed {
+                  order_id: live.id.clone(),
+                },
+              )
+              .await?;
+            Some(entry)
+          }
+          None => None,
+        },
+      };
+
+      if let Some(entry) = intent {
+        report.adopted_orders.push(live.id.clone());
+        self
+          .gate
+          .adopt(Order {
+            id: live.id,
+            ..entry.order.clone()
+          })
+          .await;
+        continue;
+      }
+
+      if self.cancel_unknown {
+        warn!(order_id = %live.id, token = %live.token_id, "Cancelling CLOB order unknown to the journal");
+        let result = self.gate.cancel_order(&live.id).await?;
+        if result.success {
+          report.cancelled_orders.push(live.id);
+          continue;
+        }
+        warn!(order_id = %live.id, error = ?result.error, "Cancel failed, adopting instead");
+      }
+      report.adopted_orders.push(live.id.clone());
+      self.gate.adopt(live).await;
+    }
+
+    for entry in pending {
+      warn!(client_id = %entry.client_id, token = %entry.order.token_id, "Journaled order not found on the CLOB");
+      journal
+        .record_outcome(
+          &entry.client_id,
+          IntentOutcome::Rejected {
+            reason: "Not open on the CLOB after restart".to_string(),
+          },
+        )
+        .await?;
+      report.abandoned_intents += 1;
+    }
+    Ok(())
+  }
+
+  /// Checkpoint on the configured interval, and on request, until
+  /// shutdown.
+  pub async fn run(self: Arc<Self>, mut shutdown_rx: broadcast::Receiver<()>) {
+    info!(interval_secs = self.interval.as_secs(), "State checkpointer started");
+    let mut ticker = tokio::time::interval(self.interval);
+    loop {
+      tokio::select! {
+        biased;
+        _ = shutdown_rx.recv() => break,
+        () = self.requests.notified() => {}
+        _ = ticker.tick() => {}
+      }
+      if let Err(e) = self.checkpoint(now_ms()).await {
+        warn!(error = %e, "State checkpoint failed");
+      }
+    }
+    info!("State checkpointer stopped");
+  }
+}
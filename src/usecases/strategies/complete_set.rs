@@ -0,0 +1,205 @@
+//! Complete-Set Arbitrage - Trade YES + NO Bundles Against USDC
+//!
+//! Watches both legs' books and takes the bundle when it is mispriced
+//! after the market's taker fees (see `domain::complete_set`):
+//! - `BuyMerge`: FOK-buy YES and NO at the asks, then merge the
+//!   complete sets back into USDC
+//! - `SplitSell`: split USDC into complete sets, then FOK-sell YES and
+//!   NO at the bids
+//!
+//! The engine drops the rest of the batch when a leg fails and
+//! flattens the legs that did execute (`StrategyIntent::unwind`): a
+//! lone filled buy is sold back, split sets are merged back or the
+//! leg left over after one sell is sold at market.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tracing::info;
+
+use crate::config::{AppConfig, CompleteSetConfig};
+use crate::domain::complete_set::{self, BookTop, CompleteSetSide};
+use crate::domain::trade::{Order, TokenId, TradeSide};
+use crate::ports::market_feed::PriceUpdate;
+use crate::ports::strategy::{Strategy, StrategyIntent, StrategyMarket, TOKEN_SCALE};
+
+/// Registry name.
+pub const NAME: &str = "complete_set_arb";
+
+/// Complete-set arbitrage strategy for one market.
+pub struct CompleteSetStrategy {
+  /// Strategy parameters.
+  config: CompleteSetConfig,
+  /// Latest top of book per token.
+  books: HashMap<TokenId, BookTop>,
+  /// Last time a bundle was taken (cooldown).
+  last_attempt: Option<Instant>,
+}
+
+impl CompleteSetStrategy {
+  /// Create the strategy from `[complete_set]` config.
+  pub fn new(config: &AppConfig) -> Self {
+    Self {
+      config: config.complete_set.clone(),
+      books: HashMap::new(),
+      last_attempt: None,
+    }
+  }
+}
+
+impl Strategy for CompleteSetStrategy {
+  fn name(&self) -> &'static str {
+    NAME
+  }
+
+  fn tokens(&self, market: &StrategyMarket) -> Vec<TokenId> {
+    vec![market.yes_token_id.clone(), market.no_token_id.clone()]
+  }
+
+  fn on_update(&mut self, market: &StrategyMarket, update: &PriceUpdate) -> Vec<StrategyIntent> {
+    self.books.insert(
+      update.token_id.clone(),
+      BookTop {
+        bid: update.best_bid,
+        bid_size: update.bid_size,
+        ask: update.best_ask,
+        ask_size: update.ask_size,
+      },
+    );
+    let (Some(yes), Some(no)) = (
+      self.books.get(&market.yes_token_id),
+      self.books.get(&market.no_token_id),
+    ) else {
+      return Vec::new();
+    };
+    let fees = market.fees.calculator();
+    let Some(opportunity) =
+      complete_set::evaluate(yes, no, &fees, self.config.min_edge, self.config.max_size)
+    else {
+      return Vec::new();
+    };
+
+    let cooldown = Duration::from_millis(self.config.cooldown_ms);
+    if self.last_attempt.is_some_and(|t| t.elapsed() < cooldown) {
+      return Vec::new();
+    }
+    self.last_attempt = Some(Instant::now());
+
+    info!(
+      market = %market.condition_id,
+      side = ?opportunity.side,
+      yes_price = opportunity.yes_price,
+      no_price = opportunity.no_price,
+      size = opportunity.size,
+      edge = opportunity.edge_per_set,
+      "Complete-set opportunity"
+    );
+
+    let amount_raw = (opportunity.size * TOKEN_SCALE).round() as u128;
+    let leg = |token_id: &TokenId, side, price| {
+      StrategyIntent::Order(Order::new_taker(token_id.clone(), side, price, opportunity.size))
+    };
+    match opportunity.side {
+      CompleteSetSide::BuyMerge => vec![
+        leg(&market.yes_token_id, TradeSide::Buy, opportunity.yes_price),
+        leg(&market.no_token_id, TradeSide::Buy, opportunity.no_price),
+        StrategyIntent::Merge {
+          condition_id: market.condition_id.clone(),
+          amount_raw,
+        },
+      ],
+      CompleteSetSide::SplitSell => vec![
+        StrategyIntent::Split {
+          condition_id: market.condition_id.clone(),
+          amount_raw,
+        },
+        leg(&market.yes_token_id, TradeSide::Sell, opportunity.yes_price),
+        leg(&market.no_token_id, TradeSide::Sell, opportunity.no_price),
+      ],
+    }
+  }
+
+  fn reconfigure(&mut self, config: &AppConfig) {
+    self.config = config.complete_set.clone();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use crate::domain::fees::{FeeClass, FeeSchedule};
+
+  fn market() -> StrategyMarket {
+    StrategyMarket {
+      condition_id: "0xcond".to_string(),
+      yes_token_id: "yes".to_string(),
+      no_token_id: "no".to_string(),
+      fees: FeeSchedule::new(FeeClass::Standard, 0.0),
+    }
+  }
+
+  fn update(token_id: &str, bid: f64, ask: f64) -> PriceUpdate {
+    PriceUpdate {
+      market_id: "0xcond".to_string(),
+      token_id: token_id.to_string(),
+      best_bid: Some(bid),
+      best_ask: Some(ask),
+      mid_price: Some(f64::midpoint(bid, ask)),
+      timestamp_ms: 0,
+      bid_size: Some(10.0),
+      ask_size: Some(10.0),
+    }
+  }
+
+  fn strategy() -> CompleteSetStrategy {
+    CompleteSetStrategy {
+      config: CompleteSetConfig::default(),
+      books: HashMap::new(),
+      last_attempt: None,
+    }
+  }
+
+  #[test]
+  fn test_buy_merge_intents_end_with_merge() {
+    let mut s = strategy();
+    let m = market();
+    assert!(s.on_update(&m, &update("yes", 0.44, 0.45)).is_empty());
+
+    let intents = s.on_update(&m, &update("no", 0.49, 0.50));
+    assert_eq!(intents.len(), 3);
+    assert!(matches!(&intents[0], StrategyIntent::Order(o) if o.token_id == "yes" && o.side == TradeSide::Buy));
+    assert!(matches!(
+      &intents[2],
+      StrategyIntent::Merge { amount_raw: 10_000_000, .. }
+    ));
+
+    // Cooldown blocks an immediate repeat
+    assert!(s.on_update(&m, &update("no", 0.49, 0.50)).is_empty());
+  }
+
+  #[test]
+  fn test_split_sell_intents_start_with_split() {
+    let mut s = strategy();
+    let m = market();
+    s.on_update(&m, &update("yes", 0.56, 0.57));
+    let intents = s.on_update(&m, &update("no", 0.47, 0.48));
+    assert!(intents[0].is_onchain());
+    assert!(intents[1..]
+      .iter()
+      .all(|i| matches!(i, StrategyIntent::Order(o) if o.side == TradeSide::Sell)));
+  }
+
+  #[test]
+  fn test_legs_pay_the_market_fee_tier() {
+    // 0.6 ¢ raw edge: clears standard fees, not crypto ones (~0.3 ¢)
+    let mut s = strategy();
+    let mut m = market();
+    m.fees = FeeSchedule::new(FeeClass::CryptoShortDuration, 0.0);
+    s.on_update(&m, &update("yes", 0.40, 0.497));
+    assert!(s.on_update(&m, &update("no", 0.40, 0.497)).is_empty());
+
+    m.fees = FeeSchedule::new(FeeClass::Standard, 0.0);
+    assert_eq!(s.on_update(&m, &update("no", 0.40, 0.497)).len(), 3);
+  }
+}
@@ -0,0 +1,98 @@
+//! LMSR Market Maker - Default Quoting Strategy
+//!
+//! The signal pipeline the engine used to hard-wire:
+//! 1. Mid-price → Bayesian EWMA probability estimate
+//! 2. LMSR fair value
+//! 3. Edge after fees against the best ask (maker = 0%)
+//! 4. Signal when |edge| ≥ `lmsr.min_edge`; the engine sizes it
+//!
+//! Each market gets its own estimator; it survives `[lmsr]` reloads
+//! unless `prior_weight` changes.
+
+use rust_decimal::Decimal;
+
+use crate::config::AppConfig;
+use crate::domain::bayesian::BayesianEstimator;
+use crate::domain::fees::FeeCalculator;
+use crate::domain::lmsr::LmsrPricer;
+use crate::ports::market_feed::PriceUpdate;
+use crate::ports::strategy::{Signal, Strategy, StrategyIntent, StrategyMarket};
+
+/// Registry name.
+pub const NAME: &str = "lmsr_mm";
+
+/// LMSR fair-value maker strategy.
+pub struct LmsrMarketMaker {
+  /// LMSR pricing model.
+  pricer: LmsrPricer,
+  /// Bayesian probability estimator.
+  estimator: BayesianEstimator,
+  /// Prior weight the estimator was built with.
+  prior_weight: Decimal,
+  /// Fee calculator (maker = 0%).
+  fees: FeeCalculator,
+  /// Minimum |edge| to signal.
+  min_edge: f64,
+  /// Report the estimate's std error for Kelly shrinkage.
+  uncertainty_shrinkage: bool,
+}
+
+impl LmsrMarketMaker {
+  /// Create the strategy from `[lmsr]` config.
+  pub fn new(config: &AppConfig) -> Self {
+    Self {
+      pricer: LmsrPricer::new(config.lmsr.liquidity_parameter),
+      estimator: BayesianEstimator::new(config.lmsr.prior_weight),
+      prior_weight: config.lmsr.prior_weight,
+      fees: FeeCalculator::new_maker(),
+      min_edge: config.lmsr.min_edge,
+      uncertainty_shrinkage: config.lmsr.uncertainty_shrinkage,
+    }
+  }
+}
+
+impl Strategy for LmsrMarketMaker {
+  fn name(&self) -> &'static str {
+    NAME
+  }
+
+  fn on_update(&mut self, _market: &StrategyMarket, update: &PriceUpdate) -> Vec<StrategyIntent> {
+    // Mid-price must be a valid probability
+    let mid = match update.mid_price {
+      Some(p) if p > 0.0 && p < 1.0 => p,
+      _ => return Vec::new(),
+    };
+
+    let estimated_prob = self.estimator.update(mid);
+    let fair_value = self.pricer.price(estimated_prob);
+    let edge = match update.best_ask {
+      Some(best_ask) => self.fees.net_edge(fair_value, best_ask, true),
+      None => 0.0,
+    };
+    if edge.abs() < self.min_edge {
+      return Vec::new();
+    }
+
+    vec![StrategyIntent::Signal(Signal {
+      token_id: update.token_id.clone(),
+      fair_value,
+      edge,
+      market_price: update.best_ask.unwrap_or(fair_value),
+      prob_std_error: if self.uncertainty_shrinkage {
+        self.estimator.std_error()
+      } else {
+        0.0
+      },
+    })]
+  }
+
+  fn reconfigure(&mut self, config: &AppConfig) {
+    self.pricer = LmsrPricer::new(config.lmsr.liquidity_parameter);
+    self.min_edge = config.lmsr.min_edge;
+    self.uncertainty_shrinkage = config.lmsr.uncertainty_shrinkage;
+    if config.lmsr.prior_weight != self.prior_weight {
+      self.estimator = BayesianEstimator::new(config.lmsr.prior_weight);
+      self.prior_weight = config.lmsr.prior_weight;
+    }
+  }
+}
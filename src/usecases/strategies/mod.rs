@@ -0,0 +1,11 @@
+//! Built-in Strategies
+//!
+//! Implementations of the `Strategy` port, selected per market by name:
+//! - `lmsr_mm`: EWMA → LMSR fair value → maker edge (Kelly-sized quotes)
+//! - `complete_set_arb`: YES + NO bundle arbitrage via split / merge
+
+pub mod complete_set;
+pub mod lmsr_mm;
+
+pub use complete_set::CompleteSetStrategy;
+pub use lmsr_mm::LmsrMarketMaker;
@@ -0,0 +1,124 @@
+//! Strategy Registry - Name → Strategy Factories
+//!
+//! Maps the names used in `[[markets]] strategies = [...]` to factories
+//! and builds one instance per (market, strategy) for active markets.
+//! Built-ins: `lmsr_mm` (default) and `complete_set_arb`. New ideas
+//! register a factory instead of forking the engine.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+
+use crate::config::{AppConfig, MarketConfig};
+use crate::ports::strategy::{Strategy, StrategyMarket};
+
+use super::strategies::{complete_set, lmsr_mm, CompleteSetStrategy, LmsrMarketMaker};
+
+/// Builds a strategy instance for one market.
+pub type StrategyFactory = fn(&AppConfig, &MarketConfig) -> Box<dyn Strategy>;
+
+/// Strategies running on one market.
+pub struct MarketStrategies {
+  /// The market.
+  pub market: StrategyMarket,
+  /// Strategy instances, in config order.
+  pub strategies: Vec<Box<dyn Strategy>>,
+}
+
+/// Registry of strategy factories by name.
+#[derive(Clone)]
+pub struct StrategyRegistry {
+  /// Factories by registry name.
+  factories: HashMap<&'static str, StrategyFactory>,
+}
+
+impl Default for StrategyRegistry {
+  /// Registry with the built-in strategies.
+  fn default() -> Self {
+    let mut registry = Self::empty();
+    registry.register(lmsr_mm::NAME, |config, _| Box::new(LmsrMarketMaker::new(config)));
+    registry.register(complete_set::NAME, |config, _| {
+      Box::new(CompleteSetStrategy::new(config))
+    });
+    registry
+  }
+}
+
+impl StrategyRegistry {
+  /// Registry without any strategies.
+  pub fn empty() -> Self {
+    Self {
+      factories: HashMap::new(),
+    }
+  }
+
+  /// Register (or replace) a factory under `name`.
+  pub fn register(&mut self, name: &'static str, factory: StrategyFactory) {
+    self.factories.insert(name, factory);
+  }
+
+  /// Registered names, sorted.
+  pub fn names(&self) -> Vec<&'static str> {
+    let mut names: Vec<_> = self.factories.keys().copied().collect();
+    names.sort_unstable();
+    names
+  }
+
+  /// Build the configured strategies of every active market.
+  ///
+  /// Fails on an unknown strategy name.
+  pub fn build(&self, config: &AppConfig) -> Result<Vec<MarketStrategies>> {
+    config
+      .markets
+      .iter()
+      .filter(|m| m.active)
+      .map(|market| self.build_market(config, market))
+      .collect()
+  }
+
+  /// Build the configured strategies of one market.
+  pub fn build_market(&self, config: &AppConfig, market: &MarketConfig) -> Result<MarketStrategies> {
+    let strategies = market
+      .strategies
+      .iter()
+      .map(|name| match self.factories.get(name.as_str()) {
+        Some(factory) => Ok(factory(config, market)),
+        None => bail!(
+          "Unknown strategy '{name}' for market {} (known: {})",
+          market.condition_id,
+          self.names().join(", ")
+        ),
+      })
+      .collect::<Result<Vec<_>>>()?;
+    Ok(MarketStrategies {
+      market: StrategyMarket {
+        condition_id: market.condition_id.clone(),
+        yes_token_id: market.yes_token_id.clone(),
+        no_token_id: market.no_token_id.clone(),
+        fees: config.fees.schedule_for(market),
+      },
+      strategies,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_default_registry_has_builtins() {
+    assert_eq!(
+      StrategyRegistry::default().names(),
+      vec!["complete_set_arb", "lmsr_mm"]
+    );
+  }
+
+  #[test]
+  fn test_build_rejects_unknown_strategy() {
+    let mut config = crate::config::loader::load_config("config.toml.example").unwrap();
+    config.markets[0].strategies = vec!["moon_shot".to_string()];
+    let err = StrategyRegistry::default().build(&config).err().unwrap();
+    assert!(err.to_string().contains("moon_shot"));
+  }
+}
@@ -0,0 +1,97 @@
+//! Trading Control - Operator Halt, Resume and Cancel-All
+//!
+//! Single entry point for operator actions, shared by the admin
+//! HTTP endpoints and the kill switch file. Halting flips the
+//! `RiskManager` into a state where `RiskGate` rejects every order
+//! and then cancels everything resting; the process, feeds and
+//! caches keep running so trading can resume without a restart.
+//!
+//! The halt record lives in `RiskManager` and is persisted by
+//! `RiskScheduler`, so a halted bot stays halted across restarts.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::Serialize;
+use tokio::sync::RwLock;
+use tracing::{info, instrument, warn};
+
+use crate::domain::time::now_ms;
+use crate::ports::execution::OrderExecution;
+use crate::ports::repository::{BreakerState, HaltRecord, HaltSource};
+
+use super::risk_manager::RiskManager;
+
+/// Snapshot of trading state returned to operators.
+#[derive(Debug, Clone, Serialize)]
+pub struct TradingStatus {
+  /// Whether new orders are accepted.
+  pub trading_enabled: bool,
+  /// Active halt, if any.
+  pub halt: Option<HaltRecord>,
+  /// Circuit breaker state.
+  pub breaker: BreakerState,
+  /// Realized loss so far today (USDC).
+  pub daily_loss: f64,
+}
+
+/// Operator controls over a running bot.
+pub struct TradingControl<E: OrderExecution> {
+  /// Shared risk manager (holds the halt record).
+  risk: Arc<RwLock<RiskManager>>,
+  /// Execution port used to cancel resting orders.
+  execution: Arc<E>,
+}
+
+impl<E: OrderExecution> TradingControl<E> {
+  /// Create a new controller.
+  pub fn new(risk: Arc<RwLock<RiskManager>>, execution: Arc<E>) -> Self {
+    Self { risk, execution }
+  }
+
+  /// Halt quoting and cancel all resting orders.
+  ///
+  /// Returns the number of orders cancelled. The halt is set
+  /// before cancelling so no new order can slip in between.
+  #[instrument(skip(self))]
+  pub async fn halt(&self, reason: &str, source: HaltSource) -> Result<usize> {
+    let newly = self.risk.write().await.halt(reason, source, now_ms());
+    if !newly {
+      info!("Halt requested while already halted");
+    }
+    self.cancel_all().await
+  }
+
+  /// Resume trading. Returns the halt that was lifted, if any.
+  #[instrument(skip(self))]
+  pub async fn resume(&self) -> Option<HaltRecord> {
+    self.risk.write().await.resume()
+  }
+
+  /// Cancel all resting orders without halting.
+  #[instrument(skip(self))]
+  pub async fn cancel_all(&self) -> Result<usize> {
+    let cancelled = self.execution.cancel_all_orders().await.map_err(|e| {
+      warn!(error = %e, "Cancel-all failed");
+      e
+    })?;
+    info!(cancelled = cancelled, "Operator cancel-all complete");
+    Ok(cancelled)
+  }
+
+  /// Current trading status.
+  pub async fn status(&self) -> TradingStatus {
+    let risk = self.risk.read().await;
+    TradingStatus {
+      trading_enabled: risk.can_trade(),
+      halt: risk.halt_record().cloned(),
+      breaker: risk.breaker_state(),
+      daily_loss: risk.daily_loss(),
+    }
+  }
+
+  /// Source of the active halt, if any.
+  pub async fn halt_source(&self) -> Option<HaltSource> {
+    self.risk.read().await.halt_record().map(|h| h.source)
+  }
+}
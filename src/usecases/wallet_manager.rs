@@ -2,7 +2,12 @@
 //!
 //! Tracks the bot's USDC balance, token positions, and provides
 //! bankroll management for the risk manager. Queries on-chain
-//! balances via the ChainClient port and caches locally.
+//! balances via the ChainClient port and caches locally; outcome
+//! token balances for tracked tokens are refreshed in one batch.
+//!
+//! With a cold wallet configured, the hot (bot) wallet is measured
+//! against it (`domain::wallet_allocation`) and excess USDC can be
+//! swept to it; gas (MATIC) is read uncached.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -12,7 +17,8 @@ use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 
-use crate::ports::chain_client::ChainClient;
+use crate::domain::wallet_allocation::{self, Allocation, AllocationPolicy};
+use crate::ports::chain_client::{ChainClient, TransferResult};
 
 /// Snapshot of the wallet state at a point in time.
 #[derive(Debug, Clone)]
@@ -48,10 +54,14 @@ pub struct WalletManager<C: ChainClient> {
   usdc_cache: RwLock<Option<CachedBalance>>,
   /// Cached token balances.
   token_cache: RwLock<HashMap<String, CachedBalance>>,
+  /// Outcome tokens always included in snapshots.
+  tracked_tokens: Vec<String>,
   /// Maximum cache age in seconds before refresh.
   cache_ttl_secs: i64,
   /// Initial bankroll recorded at startup.
   initial_bankroll: RwLock<Option<f64>>,
+  /// Watch-only cold wallet address.
+  cold_address: Option<String>,
 }
 
 impl<C: ChainClient> WalletManager<C> {
@@ -61,8 +71,10 @@ impl<C: ChainClient> WalletManager<C> {
       chain,
       usdc_cache: RwLock::new(None),
       token_cache: RwLock::new(HashMap::new()),
+      tracked_tokens: Vec::new(),
       cache_ttl_secs: 30,
       initial_bankroll: RwLock::new(None),
+      cold_address: None,
     }
   }
 
@@ -72,11 +84,75 @@ impl<C: ChainClient> WalletManager<C> {
       chain,
       usdc_cache: RwLock::new(None),
       token_cache: RwLock::new(HashMap::new()),
+      tracked_tokens: Vec::new(),
       cache_ttl_secs,
       initial_bankroll: RwLock::new(None),
+      cold_address: None,
     }
   }
 
+  /// Track outcome tokens (e.g. every configured YES/NO token).
+  pub fn with_tokens(mut self, token_ids: impl IntoIterator<Item = String>) -> Self {
+    self.tracked_tokens.extend(token_ids);
+    self.tracked_tokens.sort();
+    self.tracked_tokens.dedup();
+    self
+  }
+
+  /// Track the cold wallet at `address` (`wallet.cold_address`).
+  pub fn with_cold_wallet(mut self, address: Option<String>) -> Self {
+    self.cold_address = address;
+    self
+  }
+
+  /// Cold wallet address, if configured.
+  pub fn cold_address(&self) -> Option<&str> {
+    self.cold_address.as_deref()
+  }
+
+  /// Native gas (MATIC) balance of the hot wallet, uncached.
+  pub async fn native_balance(&self) -> Result<f64> {
+    self
+      .chain
+      .native_balance()
+      .await
+      .context("Failed to query MATIC balance")
+  }
+
+  /// Hot wallet USDC against the cold wallet; `None` without a cold wallet.
+  pub async fn allocation(&self, policy: &AllocationPolicy) -> Result<Option<Allocation>> {
+    let Some(cold) = &self.cold_address else {
+      return Ok(None);
+    };
+    let hot = self.usdc_balance().await?;
+    let cold_balance = self
+      .chain
+      .usdc_balance_of(cold)
+      .await
+      .context("Failed to query cold wallet USDC balance")?;
+    Ok(Some(wallet_allocation::evaluate(hot, cold_balance, policy)))
+  }
+
+  /// Send `amount` USDC from the hot wallet to the cold wallet.
+  pub async fn sweep_to_cold(&self, amount: f64) -> Result<TransferResult> {
+    let cold = self
+      .cold_address
+      .as_deref()
+      .context("No cold wallet configured")?;
+    let amount_raw = (amount * 1_000_000.0).floor() as u128;
+    anyhow::ensure!(amount_raw > 0, "Sweep amount must be positive");
+
+    let result = self.chain.transfer_usdc(cold, amount_raw).await?;
+    // Balance changed; refetch on next access
+    *self.usdc_cache.write().await = None;
+    info!(
+      amount = result.amount,
+      tx_hash = %result.tx_hash,
+      "Swept USDC to cold wallet"
+    );
+    Ok(result)
+  }
+
   /// Get the current USDC balance, using cache if fresh.
   pub async fn usdc_balance(&self) -> Result<f64> {
     // Check cache first
@@ -123,7 +199,7 @@ impl<C: ChainClient> WalletManager<C> {
     // Query on-chain
     let tb = self
       .chain
-      .token_balance(token_id)
+      .token_balance(&token_id.to_string())
       .await
       .context("Failed to query token balance")?;
 
@@ -144,17 +220,42 @@ impl<C: ChainClient> WalletManager<C> {
     Ok(balance)
   }
 
-  /// Get a full wallet snapshot (refreshes all balances).
-  pub async fn snapshot(&self) -> Result<WalletSnapshot> {
-    let usdc = self.usdc_balance().await?;
-
-    let token_balances = {
+  /// Balances of all tracked and previously queried tokens.
+  ///
+  /// Stale or missing entries are fetched in a single batch query.
+  pub async fn token_balances(&self) -> Result<HashMap<String, f64>> {
+    let stale: Vec<String> = {
       let cache = self.token_cache.read().await;
+      let mut ids: Vec<String> = self
+        .tracked_tokens
+        .iter()
+        .filter(|id| !cache.contains_key(*id))
+        .cloned()
+        .collect();
+      ids.extend(
+        cache
+          .iter()
+          .filter(|(_, v)| v.is_stale(self.cache_ttl_secs))
+          .map(|(k, _)| k.clone()),
+      );
+      ids
+    };
+
+    self.fetch_tokens(stale).await?;
+
+    let cache = self.token_cache.read().await;
+    Ok(
       cache
         .iter()
         .map(|(k, v)| (k.clone(), v.value))
-        .collect::<HashMap<_, _>>()
-    };
+        .collect(),
+    )
+  }
+
+  /// Get a full wallet snapshot (refreshes all balances).
+  pub async fn snapshot(&self) -> Result<WalletSnapshot> {
+    let usdc = self.usdc_balance().await?;
+    let token_balances = self.token_balances().await?;
 
     // Estimate total value as USDC + sum of token balances
     // (a real implementation would price tokens at market value)
@@ -188,7 +289,8 @@ impl<C: ChainClient> WalletManager<C> {
     *guard
   }
 
-  /// Calculate the current daily PnL relative to initial bankroll.
+  /// USDC balance change since the initial bankroll (cash only; open
+  /// positions and PnL attribution live in `PortfolioLedger`).
   pub async fn daily_pnl(&self) -> Result<f64> {
     let current = self.usdc_balance().await?;
     let initial = self.initial_bankroll().await.unwrap_or(current);
@@ -206,19 +308,19 @@ impl<C: ChainClient> WalletManager<C> {
     // Refresh USDC
     let _ = self.usdc_balance().await?;
 
-    // Refresh known token balances
-    let token_ids: Vec<String> = {
-      let cache = self.token_cache.read().await;
-      cache.keys().cloned().collect()
-    };
-
-    for token_id in &token_ids {
-      if let Err(e) = self.token_balance(token_id).await {
-        warn!(
-          token_id = %token_id,
-          error = %e,
-          "Failed to refresh token balance"
-        );
+    // Refresh known token balances in one batch; on failure the
+    // previous values are kept and refetched on next access
+    let previous = std::mem::take(&mut *self.token_cache.write().await);
+    let ids = previous
+      .keys()
+      .chain(self.tracked_tokens.iter())
+      .cloned()
+      .collect();
+    if let Err(e) = self.fetch_tokens(ids).await {
+      warn!(error = %e, "Failed to refresh token balances");
+      let mut cache = self.token_cache.write().await;
+      for (token_id, cached) in previous {
+        cache.entry(token_id).or_insert(cached);
       }
     }
 
@@ -226,6 +328,34 @@ impl<C: ChainClient> WalletManager<C> {
     Ok(())
   }
 
+  /// Fetch `ids` in one batch query and update the cache.
+  async fn fetch_tokens(&self, mut ids: Vec<String>) -> Result<()> {
+    ids.sort();
+    ids.dedup();
+    if ids.is_empty() {
+      return Ok(());
+    }
+
+    let balances = self
+      .chain
+      .token_balances(&ids)
+      .await
+      .context("Failed to query token balances")?;
+
+    let now = Utc::now();
+    let mut cache = self.token_cache.write().await;
+    for tb in balances {
+      cache.insert(
+        tb.token_id,
+        CachedBalance {
+          value: tb.balance,
+          updated_at: now,
+        },
+      );
+    }
+    Ok(())
+  }
+
   /// Check if the bankroll is above the minimum threshold.
   pub async fn is_above_minimum(&self, min_bankroll: f64) -> Result<bool> {
     let balance = self.usdc_balance().await?;
@@ -0,0 +1,274 @@
+//! Wallet Monitor - Gas Guard and Hot/Cold Rebalancing
+//!
+//! Runs every `wallet.check_interval_secs` in live mode:
+//! - MATIC below twice `wallet.min_matic_balance` warns; below the
+//!   minimum trading is halted (`HaltSource::LowGas`, orders
+//!   cancelled) while gas is still left to cancel and settle. The
+//!   halt lifts once the balance is back above 1.2× the minimum.
+//! - With `wallet.cold_address`, the hot USDC share is compared to
+//!   `hot_fraction` / `hot_alert_threshold` and drift is reported.
+//!   Top-ups are always proposals (the cold key is offline); sweeps
+//!   follow `wallet.transfer_mode`: `propose` logs them, `approve`
+//!   queues them for `POST /admin/wallet/approve`, `auto` sends them.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tracing::{error, info, instrument, warn};
+
+use crate::config::{TransferMode, WalletConfig};
+use crate::domain::wallet_allocation::{Allocation, Rebalance, TransferDirection};
+use crate::ports::chain_client::{ChainClient, TransferResult};
+use crate::ports::execution::OrderExecution;
+use crate::ports::repository::HaltSource;
+
+use super::trading_control::TradingControl;
+use super::wallet_manager::WalletManager;
+
+/// MATIC multiple of the minimum below which a warning is logged.
+const GAS_WARN_MULTIPLE: f64 = 2.0;
+
+/// MATIC multiple of the minimum required to lift a low-gas halt.
+const GAS_RESUME_MULTIPLE: f64 = 1.2;
+
+/// Gas balance classification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasState {
+  /// Comfortably above the minimum.
+  Ok,
+  /// Below twice the minimum: top up soon.
+  Low,
+  /// Below the minimum: trading halted.
+  Critical,
+}
+
+/// Classify a MATIC balance against the configured minimum.
+pub fn gas_state(matic: f64, min_matic: f64) -> GasState {
+  if matic < min_matic {
+    GasState::Critical
+  } else if matic < min_matic * GAS_WARN_MULTIPLE {
+    GasState::Low
+  } else {
+    GasState::Ok
+  }
+}
+
+/// Result of the last wallet check (served on `GET /admin/wallet`).
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletStatus {
+  /// Hot wallet MATIC balance.
+  pub matic_balance: f64,
+  /// Gas classification.
+  pub gas: GasState,
+  /// Hot/cold allocation; `None` without a cold wallet.
+  pub allocation: Option<Allocation>,
+  /// Sweep waiting for operator approval (`approve` mode).
+  pub pending_sweep: Option<Rebalance>,
+  /// Sweep mode in effect.
+  pub transfer_mode: TransferMode,
+}
+
+/// Periodic gas and allocation checks for the hot wallet.
+pub struct WalletMonitor<C: ChainClient, E: OrderExecution> {
+  /// Balances and transfers.
+  wallet: WalletManager<C>,
+  /// Halts trading on low gas.
+  control: Arc<TradingControl<E>>,
+  /// `[wallet]` settings.
+  config: WalletConfig,
+  /// Sweep awaiting approval.
+  pending: Mutex<Option<Rebalance>>,
+  /// Last check result.
+  status: RwLock<Option<WalletStatus>>,
+}
+
+impl<C: ChainClient, E: OrderExecution> WalletMonitor<C, E> {
+  /// Create a monitor from `[wallet]` config; `wallet` should carry
+  /// the cold address (`WalletManager::with_cold_wallet`).
+  pub fn new(
+    wallet: WalletManager<C>,
+    control: Arc<TradingControl<E>>,
+    config: &WalletConfig,
+  ) -> Self {
+    Self {
+      wallet,
+      control,
+      config: config.clone(),
+      pending: Mutex::new(None),
+      status: RwLock::new(None),
+    }
+  }
+
+  /// Check gas and allocation once and act on the result.
+  #[instrument(skip(self))]
+  pub async fn check(&self) -> Result<WalletStatus> {
+    let matic = self.wallet.native_balance().await?;
+    let gas = self.guard_gas(matic).await;
+
+    let allocation = self.wallet.allocation(&self.config.allocation_policy()).await?;
+    let mut pending = self.pending.lock().await;
+    *pending = None;
+    if let Some(a) = &allocation {
+      if a.drifted {
+        warn!(
+          alert = "hot_wallet_drift",
+          hot = a.hot_balance,
+          cold = a.cold_balance,
+          hot_share = a.hot_share,
+          target_hot = a.target_hot,
+          threshold = self.config.hot_alert_threshold,
+          "Hot wallet share outside the allocation band"
+        );
+      }
+      if let Some(rebalance) = a.rebalance {
+        *pending = self.handle_rebalance(rebalance, gas).await;
+      }
+    }
+
+    let status = WalletStatus {
+      matic_balance: matic,
+      gas,
+      allocation,
+      pending_sweep: *pending,
+      transfer_mode: self.config.transfer_mode,
+    };
+    *self.status.write().await = Some(status.clone());
+    Ok(status)
+  }
+
+  /// Halt below the gas minimum; lift our own halt once refilled.
+  async fn guard_gas(&self, matic: f64) -> GasState {
+    let min = self.config.min_matic_balance;
+    let state = gas_state(matic, min);
+    let source = self.control.halt_source().await;
+    match state {
+      GasState::Critical => {
+        error!(alert = "low_gas", matic, min, "MATIC below minimum — halting trading");
+        if source.is_none() {
+          let reason = format!("MATIC balance {matic:.3} below minimum {min}");
+          if let Err(e) = self.control.halt(&reason, HaltSource::LowGas).await {
+            error!(error = %e, "Low-gas halt could not cancel all orders");
+          }
+        }
+      }
+      GasState::Low => {
+        warn!(alert = "low_gas", matic, min, "MATIC running low — top up the hot wallet");
+      }
+      GasState::Ok => {}
+    }
+    if source == Some(HaltSource::LowGas) && matic >= min * GAS_RESUME_MULTIPLE {
+      info!(matic, "MATIC refilled — resuming trading");
+      self.control.resume().await;
+    }
+    state
+  }
+
+  /// Propose, queue or send a rebalancing transfer; returns the
+  /// sweep left awaiting approval.
+  async fn handle_rebalance(&self, rebalance: Rebalance, gas: GasState) -> Option<Rebalance> {
+    let cold = self.wallet.cold_address().unwrap_or_default();
+    if rebalance.direction == TransferDirection::TopUp {
+      warn!(
+        alert = "hot_wallet_top_up",
+        amount = rebalance.amount,
+        from = %cold,
+        "Top-up proposed: send USDC from the cold wallet to the hot wallet"
+      );
+      return None;
+    }
+
+    match self.config.transfer_mode {
+      TransferMode::Propose => {
+        warn!(
+          alert = "hot_wallet_sweep",
+          amount = rebalance.amount,
+          to = %cold,
+          "Sweep proposed: move excess USDC to the cold wallet"
+        );
+        None
+      }
+      TransferMode::Approve => {
+        warn!(
+          alert = "hot_wallet_sweep",
+          amount = rebalance.amount,
+          to = %cold,
+          "Sweep awaiting approval (POST /admin/wallet/approve)"
+        );
+        Some(rebalance)
+      }
+      TransferMode::Auto if gas == GasState::Critical => {
+        warn!(amount = rebalance.amount, "Sweep skipped: not enough gas");
+        None
+      }
+      TransferMode::Auto => {
+        if let Err(e) = self.wallet.sweep_to_cold(rebalance.amount).await {
+          error!(error = %e, amount = rebalance.amount, "Automatic sweep failed");
+        }
+        None
+      }
+    }
+  }
+
+  /// Send the queued sweep, re-sized against current balances.
+  ///
+  /// Fails when nothing is queued or the sweep is no longer needed.
+  #[instrument(skip(self))]
+  pub async fn approve(&self) -> Result<TransferResult> {
+    let mut pending = self.pending.lock().await;
+    if pending.is_none() {
+      bail!("No sweep awaiting approval");
+    }
+    *pending = None;
+
+    let current = self.wallet.allocation(&self.config.allocation_policy()).await?;
+    let Some(Rebalance {
+      direction: TransferDirection::Sweep,
+      amount,
+    }) = current.and_then(|a| a.rebalance)
+    else {
+      bail!("Sweep no longer needed at current balances");
+    };
+    info!(amount, "Operator approved sweep");
+    self.wallet.sweep_to_cold(amount).await
+  }
+
+  /// Last check result, if any.
+  pub async fn status(&self) -> Option<WalletStatus> {
+    self.status.read().await.clone()
+  }
+
+  /// Run `check` on the configured interval until shutdown.
+  pub async fn run(self: Arc<Self>, mut shutdown_rx: broadcast::Receiver<()>) {
+    let interval = Duration::from_secs(self.config.check_interval_secs);
+    info!(interval_secs = interval.as_secs(), "Wallet monitor started");
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+      tokio::select! {
+        biased;
+        _ = shutdown_rx.recv() => break,
+        _ = ticker.tick() => {
+          if let Err(e) = self.check().await {
+            warn!(error = %e, "Wallet check failed");
+          }
+        }
+      }
+    }
+    info!("Wallet monitor stopped");
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_gas_state_thresholds() {
+    assert_eq!(gas_state(0.4, 0.5), GasState::Critical);
+    assert_eq!(gas_state(0.9, 0.5), GasState::Low);
+    assert_eq!(gas_state(1.0, 0.5), GasState::Ok);
+  }
+}
@@ -0,0 +1,66 @@
+//! Signer tests against a local dev chain.
+//!
+//! Run with an anvil node (`anvil`) listening on `ANVIL_RPC_URL`
+//! (default `http://127.0.0.1:8545`):
+//!
+//!   cargo test --test anvil_test -- --ignored
+
+use std::sync::Arc;
+
+use alloy::primitives::Bytes;
+use alloy::signers::local::PrivateKeySigner;
+
+use polymarket_lmsr_bot::adapters::chain::{GasOracle, PolygonProvider, TxSender};
+use polymarket_lmsr_bot::config::{ApiConfig, SettlementConfig};
+
+/// Anvil's first pre-funded dev account (public, test-only key).
+const ANVIL_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+async fn anvil_sender() -> (Arc<PolygonProvider>, TxSender) {
+    let rpc_url = std::env::var("ANVIL_RPC_URL")
+        .unwrap_or_else(|_| "http://127.0.0.1:8545".to_string());
+    let api = ApiConfig {
+        clob_base_url: String::new(),
+        clob_ws_url: String::new(),
+        rpc_url,
+        chain_id: 31337,
+        timeout_ms: 5000,
+    };
+    let provider = Arc::new(PolygonProvider::connect(&api).await.unwrap());
+    let gas_oracle = Arc::new(GasOracle::new(Arc::clone(&provider)));
+    let signer: PrivateKeySigner = ANVIL_KEY.parse().unwrap();
+    let sender = TxSender::new(
+        Arc::clone(&provider),
+        gas_oracle,
+        signer,
+        api.chain_id,
+        &SettlementConfig::default(),
+    );
+    (provider, sender)
+}
+
+#[tokio::test]
+#[ignore = "requires a local anvil node"]
+async fn test_sender_signs_and_tracks_nonces() {
+    let (provider, sender) = anvil_sender().await;
+    let me = sender.address();
+    let before = provider
+        .inner()
+        .get_transaction_count(me)
+        .await
+        .unwrap();
+
+    // Two back-to-back sends must use consecutive nonces
+    let first = sender.send(me, Bytes::new()).await.unwrap();
+    let second = sender.send(me, Bytes::new()).await.unwrap();
+    assert!(first.status());
+    assert!(second.status());
+    assert_ne!(first.transaction_hash, second.transaction_hash);
+
+    let after = provider
+        .inner()
+        .get_transaction_count(me)
+        .await
+        .unwrap();
+    assert_eq!(after, before + 2);
+}
@@ -10,9 +10,12 @@ use polymarket_lmsr_bot::domain::fees::FeeCalculator;
 use polymarket_lmsr_bot::domain::kelly::KellySizer;
 use polymarket_lmsr_bot::domain::lmsr::LmsrPricer;
 use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 
 /// A single historical price point for backtesting.
 #[derive(Debug, Clone)]
+#[allow(dead_code)]
 struct HistoricalTick {
     /// Simulated timestamp (Unix ms).
     timestamp_ms: u64,
@@ -55,7 +58,7 @@ struct BacktestResult {
 /// to validate the strategy deterministically.
 fn generate_synthetic_data() -> Vec<HistoricalTick> {
     let mut ticks = Vec::new();
-    let base_time = 1700000000000u64;
+    let base_time = 1_700_000_000_000u64;
 
     // Scenario 1: Clear edge — spot at 50000, PM YES underpriced at 0.40
     // (fair value should be ~0.50, edge = 0.10)
@@ -99,7 +102,7 @@ fn run_backtest(ticks: &[HistoricalTick]) -> BacktestResult {
     let pricer = LmsrPricer::new(100.0);
     let sizer = KellySizer::new(0.25);
     let fees = FeeCalculator::new_maker();
-    let mut estimator = BayesianEstimator::new(0.5);
+    let mut estimator = BayesianEstimator::new(dec!(0.5));
 
     let risk_config = RiskConfig {
         max_daily_loss_fraction: 0.30, // 30% daily limit per checklist
@@ -108,6 +111,10 @@ fn run_backtest(ticks: &[HistoricalTick]) -> BacktestResult {
         min_bankroll: 50.0,
         circuit_breaker_losses: 5,
         cooldown_seconds: 1800,
+        half_open_size_fraction: 0.25,
+        market_limits: Vec::new(),
+        asset_limits: Vec::new(),
+        correlation_groups: Vec::new(),
     };
     let mut risk_manager = RiskManager::new(&risk_config);
 
@@ -129,7 +136,7 @@ fn run_backtest(ticks: &[HistoricalTick]) -> BacktestResult {
         }
 
         // Update Bayesian estimate with PM mid price
-        let pm_mid = (tick.pm_best_ask + tick.pm_best_bid) / 2.0;
+        let pm_mid = f64::midpoint(tick.pm_best_ask, tick.pm_best_bid);
         let estimated_prob = estimator.update(pm_mid);
 
         // Compute LMSR fair value
@@ -154,7 +161,7 @@ fn run_backtest(ticks: &[HistoricalTick]) -> BacktestResult {
 
         // Kelly sizing
         let kelly_size = sizer.optimal_size(estimated_prob, fair_value, bankroll);
-        let trade_size = kelly_size.min(100.0).max(1.0);
+        let trade_size = kelly_size.clamp(1.0, 100.0);
 
         // Simulate trade
         total_trades += 1;
@@ -271,21 +278,26 @@ fn test_backtest_respects_daily_loss_limit() {
         min_bankroll: 50.0,
         circuit_breaker_losses: 3,
         cooldown_seconds: 1800,
+        half_open_size_fraction: 0.25,
+        market_limits: Vec::new(),
+        asset_limits: Vec::new(),
+        correlation_groups: Vec::new(),
     };
 
     let mut rm = RiskManager::new(&risk_config);
     let bankroll = 1000.0;
 
-    // Simulate losses up to the daily limit
-    rm.record_trade(-5.0);
+    // Simulate losses below the daily limit
     rm.record_trade(-5.0);
     rm.record_trade(-5.0);
 
-    // Should still allow (15 < 20 = 2% of 1000)
+    // Should still allow (10 < 20 = 2% of 1000)
     assert!(rm.can_open_position(10.0, bankroll));
 
-    // But circuit breaker should have triggered (3 consecutive)
+    // A third consecutive loss trips the circuit breaker (15 < 20)
+    rm.record_trade(-5.0);
     assert!(rm.is_circuit_breaker_active());
+    assert!(!rm.can_open_position(10.0, bankroll));
 }
 
 #[test]
@@ -309,4 +321,16 @@ fn test_backtest_kelly_sizing_bounds() {
 fn test_backtest_fee_curve_at_probability_extremes() {
     let fees = FeeCalculator::new_maker();
 
-    // At p=0.50 (max fee region): fee
+    // At p=0.50 (max fee region): fee = 0.0025 * 0.25^2 per unit
+    let mid = FeeCalculator::standard().taker_fee(dec!(0.50), dec!(100.0));
+    assert!(mid > Decimal::ZERO, "Taker fee should be positive at p=0.50");
+
+    // Near the extremes the fee vanishes (p^2 * (1-p)^2 -> 0)
+    let low = FeeCalculator::standard().taker_fee(dec!(0.02), dec!(100.0));
+    let high = FeeCalculator::standard().taker_fee(dec!(0.98), dec!(100.0));
+    assert!(low < mid && high < mid, "Fees must peak at p=0.50");
+    assert!((low - high).abs() < dec!(0.000001), "Fee curve is symmetric");
+
+    // Maker orders never pay
+    assert_eq!(fees.maker_fee(dec!(0.50), dec!(100.0)), Decimal::ZERO);
+}
@@ -8,6 +8,9 @@ use std::time::Duration;
 
 use mockall::predicate::*;
 use mockall::mock;
+use polymarket_lmsr_bot::ports::chain_client::ChainClient;
+use polymarket_lmsr_bot::ports::execution::OrderExecution;
+use polymarket_lmsr_bot::ports::repository::Repository;
 use tokio::sync::broadcast;
 
 // ---- Mock Definitions ----
@@ -24,19 +27,19 @@ mock! {
 
         async fn cancel_order(
             &self,
-            order_id: &str,
+            order_id: &polymarket_lmsr_bot::domain::trade::OrderId,
         ) -> anyhow::Result<polymarket_lmsr_bot::ports::execution::OrderCancellation>;
 
         async fn cancel_all_orders(&self) -> anyhow::Result<usize>;
 
         async fn cancel_orders_for_token(
             &self,
-            token_id: &str,
+            token_id: &polymarket_lmsr_bot::domain::trade::TokenId,
         ) -> anyhow::Result<Vec<polymarket_lmsr_bot::ports::execution::OrderCancellation>>;
 
         async fn get_order_status(
             &self,
-            order_id: &str,
+            order_id: &polymarket_lmsr_bot::domain::trade::OrderId,
         ) -> anyhow::Result<polymarket_lmsr_bot::ports::execution::OrderStatus>;
 
         async fn get_open_orders(
@@ -60,16 +63,47 @@ mock! {
     #[async_trait::async_trait]
     impl polymarket_lmsr_bot::ports::chain_client::ChainClient for ChainCli {
         async fn usdc_balance(&self) -> anyhow::Result<f64>;
-        async fn token_balance(&self, token_id: &str)
+        async fn usdc_balance_of(&self, address: &str) -> anyhow::Result<f64>;
+        async fn native_balance(&self) -> anyhow::Result<f64>;
+        async fn transfer_usdc(&self, to: &str, amount_raw: u128)
+            -> anyhow::Result<polymarket_lmsr_bot::ports::chain_client::TransferResult>;
+        async fn token_balance(&self, token_id: &polymarket_lmsr_bot::domain::trade::TokenId)
             -> anyhow::Result<polymarket_lmsr_bot::ports::chain_client::TokenBalance>;
+        async fn token_balances(&self, token_ids: &[String])
+            -> anyhow::Result<Vec<polymarket_lmsr_bot::ports::chain_client::TokenBalance>>;
         async fn batch_redeem(&self, token_ids: &[String])
             -> anyhow::Result<polymarket_lmsr_bot::ports::chain_client::RedemptionResult>;
+        async fn merge_positions(&self, condition_id: &str, amount_raw: u128)
+            -> anyhow::Result<polymarket_lmsr_bot::ports::chain_client::MergeResult>;
+        async fn split_position(&self, condition_id: &str, amount_raw: u128)
+            -> anyhow::Result<polymarket_lmsr_bot::ports::chain_client::SplitResult>;
+        async fn convert_positions(&self, neg_risk_market_id: &str, question_indices: &[u32], amount_raw: u128)
+            -> anyhow::Result<polymarket_lmsr_bot::ports::chain_client::ConvertResult>;
         async fn is_condition_resolved(&self, condition_id: &str) -> anyhow::Result<bool>;
+        async fn condition_payouts(&self, condition_id: &str)
+            -> anyhow::Result<polymarket_lmsr_bot::ports::chain_client::ConditionPayouts>;
         async fn gas_price_gwei(&self) -> anyhow::Result<f64>;
         async fn is_healthy(&self) -> bool;
     }
 }
 
+mock! {
+    pub Feed {}
+
+    #[async_trait::async_trait]
+    impl polymarket_lmsr_bot::ports::market_feed::MarketFeed for Feed {
+        fn subscribe(&self, token_id: &polymarket_lmsr_bot::domain::trade::TokenId)
+            -> broadcast::Receiver<polymarket_lmsr_bot::ports::market_feed::PriceUpdate>;
+        async fn get_order_book(&self, token_id: &polymarket_lmsr_bot::domain::trade::TokenId)
+            -> anyhow::Result<polymarket_lmsr_bot::ports::market_feed::OrderBookSnapshot>;
+        fn subscribe_many(&self, token_ids: &[polymarket_lmsr_bot::domain::trade::TokenId])
+            -> Vec<broadcast::Receiver<polymarket_lmsr_bot::ports::market_feed::PriceUpdate>>;
+        async fn is_healthy(&self) -> bool;
+        async fn last_price(&self, token_id: &polymarket_lmsr_bot::domain::trade::TokenId)
+            -> Option<polymarket_lmsr_bot::ports::market_feed::PriceUpdate>;
+    }
+}
+
 mock! {
     pub Repo {}
 
@@ -88,10 +122,29 @@ mock! {
             -> anyhow::Result<()>;
         async fn load_daily_pnl(&self)
             -> anyhow::Result<Vec<polymarket_lmsr_bot::ports::repository::DailyPnl>>;
+        async fn save_settlement_report(&self, record: &polymarket_lmsr_bot::ports::repository::SettlementRecord)
+            -> anyhow::Result<()>;
+        async fn load_settlement_reports(&self)
+            -> anyhow::Result<Vec<polymarket_lmsr_bot::ports::repository::SettlementRecord>>;
+        async fn save_fee_reconciliation(&self, record: &polymarket_lmsr_bot::ports::repository::FeeReconciliation)
+            -> anyhow::Result<()>;
+        async fn load_fee_reconciliations(&self)
+            -> anyhow::Result<Vec<polymarket_lmsr_bot::ports::repository::FeeReconciliation>>;
         async fn is_healthy(&self) -> bool;
     }
 }
 
+mock! {
+    pub FeeSrc {}
+
+    #[async_trait::async_trait]
+    impl polymarket_lmsr_bot::ports::fees::FeeSource for FeeSrc {
+        async fn fee_rate_bps(&self, token_id: &str) -> anyhow::Result<u32>;
+        async fn trades(&self, from_ms: u64, to_ms: u64)
+            -> anyhow::Result<Vec<polymarket_lmsr_bot::ports::fees::TradeFill>>;
+    }
+}
+
 // ---- Integration Tests ----
 
 #[tokio::test]
@@ -106,17 +159,17 @@ async fn test_order_placement_and_cancellation_lifecycle() {
                 order_id: "ord_123".to_string(),
                 accepted: true,
                 rejection_reason: None,
-                timestamp_ms: 1700000000000,
+                timestamp_ms: 1_700_000_000_000,
             })
         });
 
     // Expect cancellation
     mock_exec
         .expect_cancel_order()
-        .with(eq("ord_123"))
+        .with(eq("ord_123".to_string()))
         .returning(|oid| {
             Ok(polymarket_lmsr_bot::ports::execution::OrderCancellation {
-                order_id: oid.to_string(),
+                order_id: oid.clone(),
                 success: true,
                 error: None,
             })
@@ -134,6 +187,7 @@ async fn test_order_placement_and_cancellation_lifecycle() {
         order_type: polymarket_lmsr_bot::domain::trade::OrderType::Gtc,
         post_only: true,
         timestamp_ms: 0,
+        salt: String::new(),
     };
 
     let result = exec.place_order(&order).await.unwrap();
@@ -141,7 +195,7 @@ async fn test_order_placement_and_cancellation_lifecycle() {
     assert_eq!(result.order_id, "ord_123");
 
     // Cancel order
-    let cancel = exec.cancel_order("ord_123").await.unwrap();
+    let cancel = exec.cancel_order(&"ord_123".to_string()).await.unwrap();
     assert!(cancel.success);
 }
 
@@ -180,105 +234,2217 @@ async fn test_settlement_with_resolved_market() {
 }
 
 #[tokio::test]
-async fn test_risk_manager_circuit_breaker_integration() {
-    use polymarket_lmsr_bot::config::RiskConfig;
-    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+async fn test_settlement_sweep_attributes_payouts_per_outcome() {
+    use polymarket_lmsr_bot::domain::trade::Position;
+    use polymarket_lmsr_bot::ports::chain_client::{ConditionPayouts, RedemptionResult};
+    use polymarket_lmsr_bot::usecases::settlement::{ResolutionStatus, Settlement};
+    use rust_decimal_macros::dec;
 
-    let config = RiskConfig {
-        max_daily_loss_fraction: 0.02,
-        max_position_size: 100.0,
-        max_total_exposure: 500.0,
-        min_bankroll: 50.0,
-        circuit_breaker_losses: 5,
-        cooldown_seconds: 1800,
-    };
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let market = config.markets[0].clone();
 
-    let mut rm = RiskManager::new(&config);
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_condition_payouts().returning(|_| {
+        Ok(ConditionPayouts {
+            numerators: vec![0, 1],
+            denominator: 1,
+        })
+    });
+    // Only the winning NO position is sent on-chain
+    mock_chain
+        .expect_batch_redeem()
+        .times(1)
+        .returning(|ids| {
+            Ok(RedemptionResult {
+                tx_hash: "0xfeed".to_string(),
+                positions_redeemed: ids.len(),
+                usdc_recovered: 40.0,
+                gas_cost_matic: 0.01,
+            })
+        });
 
-    // Should allow trading initially
-    assert!(rm.can_trade());
-    assert!(rm.can_open_position(50.0, 1000.0));
+    let position = |token_id: &str, entry| Position {
+        condition_id: market.condition_id.clone(),
+        token_id: token_id.to_string(),
+        asset: market.asset,
+        size: dec!(40),
+        avg_entry_price: entry,
+        unrealized_pnl: dec!(0),
+        opened_at: chrono::Utc::now(),
+        resolved: false,
+    };
+    let positions = vec![
+        position(&market.yes_token_id, dec!(0.55)),
+        position(&market.no_token_id, dec!(0.40)),
+    ];
 
-    // Simulate 5 consecutive losses
-    for _ in 0..5 {
-        rm.record_trade(-10.0);
-    }
+    let settlement = Settlement::new(Arc::new(mock_chain), Arc::new(MockRepo::new()))
+        .with_markets(&config.markets);
+    let report = settlement.sweep(&positions).await.unwrap();
 
-    // Circuit breaker should now block trading
-    assert!(rm.is_circuit_breaker_active());
-    assert!(!rm.can_trade());
+    assert_eq!(report.markets_settled, 2);
+    assert_eq!(report.total_usdc_recovered, 40.0);
 
-    // Reset daily counters
-    rm.reset_daily();
-    assert!(!rm.is_circuit_breaker_active());
-    assert!(rm.can_trade());
+    let yes = &report.results[0];
+    assert!(matches!(yes.resolution, ResolutionStatus::ResolvedNo(_)));
+    assert!(yes.tx_hash.is_none());
+    assert!((yes.realized_pnl.unwrap() + 22.0).abs() < 1e-9);
+
+    let no = &report.results[1];
+    assert_eq!(no.tx_hash.as_deref(), Some("0xfeed"));
+    assert!((no.realized_pnl.unwrap() - 24.0).abs() < 1e-9);
 }
 
 #[tokio::test]
-async fn test_repository_save_and_load_trade() {
+async fn test_settlement_scheduler_defers_on_gas_then_books_pnl() {
+    use chrono::TimeZone;
+    use polymarket_lmsr_bot::domain::trade::TradeSide;
+    use polymarket_lmsr_bot::ports::chain_client::{
+        ConditionPayouts, RedemptionResult, TokenBalance,
+    };
+    use polymarket_lmsr_bot::ports::metrics::NoopMetrics;
+    use polymarket_lmsr_bot::ports::repository::SettlementRecord;
+    use polymarket_lmsr_bot::usecases::portfolio_ledger::PortfolioLedger;
+    use polymarket_lmsr_bot::usecases::settlement_scheduler::SettlementScheduler;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let yes_token = config.markets[0].yes_token_id.clone();
+    let ledger = Arc::new(PortfolioLedger::new(&config, Arc::new(NoopMetrics)));
+    ledger.record_fill(&yes_token, TradeSide::Buy, 0.25, 40.0, true).await;
+
+    let mut mock_chain = MockChainCli::new();
+    // First gas reading is above the 35 gwei limit, the retry is not
+    let gas_calls = AtomicUsize::new(0);
+    mock_chain.expect_gas_price_gwei().returning(move || {
+        Ok(if gas_calls.fetch_add(1, Ordering::SeqCst) == 0 { 80.0 } else { 20.0 })
+    });
+    mock_chain.expect_token_balances().times(1).returning(move |ids| {
+        Ok(ids
+            .iter()
+            .map(|id| {
+                let raw = if *id == yes_token { 40_000_000 } else { 0 };
+                TokenBalance {
+                    token_id: id.clone(),
+                    balance_raw: raw,
+                    balance: raw as f64 / 1_000_000.0,
+                }
+            })
+            .collect())
+    });
+    mock_chain.expect_condition_payouts().returning(|_| {
+        Ok(ConditionPayouts {
+            numerators: vec![1, 0],
+            denominator: 1,
+        })
+    });
+    mock_chain.expect_batch_redeem().times(1).returning(|ids| {
+        Ok(RedemptionResult {
+            tx_hash: "0xbeef".to_string(),
+            positions_redeemed: ids.len(),
+            usdc_recovered: 40.0,
+            gas_cost_matic: 0.01,
+        })
+    });
+
+    let reports = Arc::new(std::sync::Mutex::new(Vec::<SettlementRecord>::new()));
+    let reports_ref = Arc::clone(&reports);
     let mut mock_repo = MockRepo::new();
+    mock_repo.expect_save_settlement_report().returning(move |r| {
+        reports_ref.lock().unwrap().push(r.clone());
+        Ok(())
+    });
+    // The snapshot is left to the state checkpointer
+    mock_repo.expect_save_state().never();
 
-    let record = polymarket_lmsr_bot::ports::repository::TradeRecord {
-        id: "trade_001".to_string(),
-        order_id: "ord_001".to_string(),
-        market_id: "market_btc_up".to_string(),
-        side: "BUY".to_string(),
-        price: 0.55,
-        size: 20.0,
-        lmsr_fair_value: 0.52,
-        edge: 0.03,
-        kelly_fraction: 0.25,
-        fees: 0.0,
-        timestamp_ms: 1700000000000,
-    };
+    let mut scheduler = SettlementScheduler::new(
+        Arc::new(mock_chain),
+        Arc::new(mock_repo),
+        &config,
+        Duration::from_secs(60),
+    )
+    .with_ledger(Arc::clone(&ledger));
 
-    let record_clone = record.clone();
+    // Before the configured hour nothing runs
+    let early = chrono::Utc.with_ymd_and_hms(2026, 3, 1, 3, 0, 0).unwrap();
+    assert!(!scheduler.is_due(early));
 
-    mock_repo
-        .expect_save_trade()
-        .returning(|_| Ok(()));
+    // At the hour, high gas defers the sweep by the backoff
+    let at_hour = chrono::Utc.with_ymd_and_hms(2026, 3, 1, 4, 0, 0).unwrap();
+    assert!(scheduler.tick(at_hour).await.unwrap().is_none());
+    let retry_at = scheduler.retry_at().unwrap();
+    assert_eq!(retry_at, at_hour + chrono::Duration::minutes(5));
+    assert!(!scheduler.is_due(at_hour + chrono::Duration::minutes(1)));
 
-    mock_repo
-        .expect_load_trades()
-        .returning(move || Ok(vec![record_clone.clone()]));
+    // Retry succeeds: report persisted and realized PnL booked
+    let report = scheduler.tick(retry_at).await.unwrap().unwrap();
+    assert_eq!(report.total_usdc_recovered, 40.0);
+    assert!(scheduler.retry_at().is_none());
+    assert!(!scheduler.is_due(retry_at + chrono::Duration::hours(1)));
 
-    // Save
-    mock_repo.save_trade(&record).await.unwrap();
+    assert_eq!(reports.lock().unwrap().len(), 1);
+    assert_eq!(reports.lock().unwrap()[0].entries[0].resolution, "ResolvedYes");
+    // 40 redeemed at 1.00 against a 0.25 basis
+    assert_eq!(ledger.cumulative_pnl().await, 30.0);
+    assert!(ledger.sizes().await.is_empty());
+}
 
-    // Load
-    let trades = mock_repo.load_trades().await.unwrap();
-    assert_eq!(trades.len(), 1);
-    assert_eq!(trades[0].id, "trade_001");
-    assert_eq!(trades[0].edge, 0.03);
-    assert_eq!(trades[0].fees, 0.0); // Maker = 0% fees
+#[tokio::test]
+async fn test_settle_dry_run_never_redeems_or_persists() {
+    use polymarket_lmsr_bot::ports::chain_client::{ConditionPayouts, TokenBalance};
+    use polymarket_lmsr_bot::usecases::settlement_scheduler::SettlementScheduler;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let no_token = config.markets[0].no_token_id.clone();
+
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_token_balances().returning(move |ids| {
+        Ok(ids
+            .iter()
+            .map(|id| {
+                let raw = if *id == no_token { 25_000_000 } else { 0 };
+                TokenBalance {
+                    token_id: id.clone(),
+                    balance_raw: raw,
+                    balance: raw as f64 / 1_000_000.0,
+                }
+            })
+            .collect())
+    });
+    mock_chain.expect_condition_payouts().returning(|_| {
+        Ok(ConditionPayouts {
+            numerators: vec![0, 1],
+            denominator: 1,
+        })
+    });
+    mock_chain.expect_batch_redeem().never();
+    // No repository expectations: any save would panic
+
+    let scheduler = SettlementScheduler::new(
+        Arc::new(mock_chain),
+        Arc::new(MockRepo::new()),
+        &config,
+        Duration::from_secs(60),
+    )
+    .with_dry_run(true);
+
+    let report = scheduler.sweep_now().await.unwrap();
+    assert_eq!(report.markets_settled, 1);
+    assert_eq!(report.total_usdc_recovered, 25.0);
+    assert!(report.results[0].tx_hash.is_none());
 }
 
 #[tokio::test]
-async fn test_wallet_balance_query() {
+async fn test_position_merger_merges_overlap_and_logs_trade() {
+    use polymarket_lmsr_bot::ports::chain_client::{MergeResult, TokenBalance};
+    use polymarket_lmsr_bot::ports::repository::TradeRecord;
+    use polymarket_lmsr_bot::usecases::position_merger::{PositionMerger, MERGE_SIDE};
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let yes_token = config.markets[0].yes_token_id.clone();
+    let no_token = config.markets[0].no_token_id.clone();
+    let condition = config.markets[0].condition_id.clone();
+
     let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_gas_price_gwei().returning(|| Ok(20.0));
+    mock_chain.expect_token_balances().returning(move |ids| {
+        Ok(ids
+            .iter()
+            .map(|id| {
+                // 12 YES vs 7.5 NO on the first market, nothing elsewhere
+                let raw = if *id == yes_token {
+                    12_000_000
+                } else if *id == no_token {
+                    7_500_000
+                } else {
+                    0
+                };
+                TokenBalance {
+                    token_id: id.clone(),
+                    balance_raw: raw,
+                    balance: raw as f64 / 1_000_000.0,
+                }
+            })
+            .collect())
+    });
+    let expected_condition = condition.clone();
+    mock_chain
+        .expect_merge_positions()
+        .times(1)
+        .withf(move |cid, amount| cid == expected_condition && *amount == 7_500_000)
+        .returning(|_, amount| {
+            Ok(MergeResult {
+                tx_hash: "0xmerge".to_string(),
+                sets_merged: amount as f64 / 1_000_000.0,
+                usdc_recovered: amount as f64 / 1_000_000.0,
+                gas_cost_matic: 0.01,
+            })
+        });
+
+    let trades = Arc::new(std::sync::Mutex::new(Vec::<TradeRecord>::new()));
+    let trades_ref = Arc::clone(&trades);
+    let mut mock_repo = MockRepo::new();
+    mock_repo.expect_save_trade().returning(move |t| {
+        trades_ref.lock().unwrap().push(t.clone());
+        Ok(())
+    });
+
+    let merger = PositionMerger::new(Arc::new(mock_chain), Arc::new(mock_repo), &config);
+    let outcomes = merger.run_once().await.unwrap();
+
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(outcomes[0].result.usdc_recovered, 7.5);
+
+    let trades = trades.lock().unwrap();
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].side, MERGE_SIDE);
+    assert_eq!(trades[0].market_id, condition);
+    assert_eq!(trades[0].order_id, "0xmerge");
+    assert_eq!(trades[0].size, 7.5);
+}
+
+/// Top-of-book update for one token.
+fn book_update(
+    token_id: &str,
+    bid: f64,
+    ask: f64,
+    size: f64,
+) -> polymarket_lmsr_bot::ports::market_feed::PriceUpdate {
+    polymarket_lmsr_bot::ports::market_feed::PriceUpdate {
+        market_id: "0x5f65177b394277fd294cd75650044e32ba009a95022d88a0c1d565897d72f8f1".to_string(),
+        token_id: token_id.to_string(),
+        best_bid: Some(bid),
+        best_ask: Some(ask),
+        mid_price: Some(f64::midpoint(bid, ask)),
+        timestamp_ms: 1_700_000_000_000,
+        bid_size: Some(size),
+        ask_size: Some(size),
+    }
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn filled_placement() -> anyhow::Result<polymarket_lmsr_bot::ports::execution::OrderPlacement> {
+    Ok(polymarket_lmsr_bot::ports::execution::OrderPlacement {
+        order_id: "ord_fok".to_string(),
+        accepted: true,
+        rejection_reason: None,
+        timestamp_ms: 1_700_000_000_000,
+    })
+}
+
+/// Engine over `config` with the built-in strategies.
+fn engine_with(
+    config: polymarket_lmsr_bot::config::AppConfig,
+    exec: MockOrderExec,
+) -> polymarket_lmsr_bot::usecases::arbitrage_engine::ArbitrageEngine<MockFeed, MockOrderExec> {
+    use polymarket_lmsr_bot::usecases::arbitrage_engine::ArbitrageEngine;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+    use polymarket_lmsr_bot::usecases::strategy_registry::StrategyRegistry;
+
+    let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+    let risk_manager = Arc::new(tokio::sync::RwLock::new(RiskManager::new(&config.risk)));
+    ArbitrageEngine::new(
+        Arc::new(MockFeed::new()),
+        Arc::new(exec),
+        risk_manager,
+        config,
+        shutdown_rx,
+        &StrategyRegistry::default(),
+    )
+    .unwrap()
+}
+
+/// Engine running only `complete_set_arb` on the BTC market.
+fn complete_set_engine(
+    exec: MockOrderExec,
+    chain: Option<MockChainCli>,
+) -> polymarket_lmsr_bot::usecases::arbitrage_engine::ArbitrageEngine<MockFeed, MockOrderExec> {
+    let mut config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    config.markets[0].strategies = vec!["complete_set_arb".to_string()];
+    let engine = engine_with(config, exec);
+    match chain {
+        Some(chain) => engine.with_chain(Arc::new(chain)),
+        None => engine,
+    }
+}
+
+#[tokio::test]
+async fn test_complete_set_strategy_buys_cheap_bundle_and_merges() {
+    use polymarket_lmsr_bot::domain::trade::{OrderType, TradeSide};
+    use polymarket_lmsr_bot::ports::chain_client::MergeResult;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let yes_token = config.markets[0].yes_token_id.clone();
+    let no_token = config.markets[0].no_token_id.clone();
+
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec
+        .expect_place_order()
+        .times(2)
+        .withf(|o| o.order_type == OrderType::Fok && o.side == TradeSide::Buy && o.size == 20.0)
+        .returning(|_| filled_placement());
 
+    let mut mock_chain = MockChainCli::new();
     mock_chain
-        .expect_usdc_balance()
-        .returning(|| Ok(1500.0));
+        .expect_merge_positions()
+        .times(1)
+        .withf(|_, amount| *amount == 20_000_000)
+        .returning(|_, amount| {
+            Ok(MergeResult {
+                tx_hash: "0xmerge".to_string(),
+                sets_merged: amount as f64 / 1_000_000.0,
+                usdc_recovered: amount as f64 / 1_000_000.0,
+                gas_cost_matic: 0.01,
+            })
+        });
+    mock_chain.expect_split_position().never();
+
+    let mut engine = complete_set_engine(mock_exec, Some(mock_chain));
+
+    // One leg alone is not enough to evaluate the bundle
+    let yes = book_update(&yes_token, 0.44, 0.45, 20.0);
+    engine.process_update(&yes).await;
+
+    // YES ask 0.45 + NO ask 0.50 = 0.95 → ~5 ¢ per set before fees
+    engine
+        .process_update(&book_update(&no_token, 0.49, 0.50, 35.0))
+        .await;
+
+    // Same market within the cooldown is not traded again
+    engine.process_update(&yes).await;
+}
+
+#[tokio::test]
+async fn test_complete_set_strategy_splits_then_sells_rich_bundle() {
+    use polymarket_lmsr_bot::domain::trade::TradeSide;
+    use polymarket_lmsr_bot::ports::chain_client::SplitResult;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let yes_token = config.markets[0].yes_token_id.clone();
+    let no_token = config.markets[0].no_token_id.clone();
 
+    let mut mock_chain = MockChainCli::new();
     mock_chain
-        .expect_is_healthy()
-        .returning(|| true);
+        .expect_split_position()
+        .times(1)
+        .withf(|_, amount| *amount == 10_000_000)
+        .returning(|_, amount| {
+            Ok(SplitResult {
+                tx_hash: "0xsplit".to_string(),
+                sets_split: amount as f64 / 1_000_000.0,
+                usdc_spent: amount as f64 / 1_000_000.0,
+                gas_cost_matic: 0.01,
+            })
+        });
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec
+        .expect_place_order()
+        .times(2)
+        .withf(|o| o.side == TradeSide::Sell && !o.post_only)
+        .returning(|_| filled_placement());
 
-    let balance = mock_chain.usdc_balance().await.unwrap();
-    assert_eq!(balance, 1500.0);
-    assert!(mock_chain.is_healthy().await);
+    let mut engine = complete_set_engine(mock_exec, Some(mock_chain));
+
+    // YES bid 0.56 + NO bid 0.47 = 1.03
+    engine
+        .process_update(&book_update(&yes_token, 0.56, 0.57, 10.0))
+        .await;
+    engine
+        .process_update(&book_update(&no_token, 0.47, 0.48, 10.0))
+        .await;
+}
+
+/// Placement the CLOB killed (FOK could not fill).
+#[allow(clippy::unnecessary_wraps)]
+fn killed_placement() -> anyhow::Result<polymarket_lmsr_bot::ports::execution::OrderPlacement> {
+    Ok(polymarket_lmsr_bot::ports::execution::OrderPlacement {
+        order_id: String::new(),
+        accepted: false,
+        rejection_reason: Some("FOK not filled".to_string()),
+        timestamp_ms: 1_700_000_000_000,
+    })
 }
 
 #[tokio::test]
-async fn test_graceful_shutdown_cancels_orders() {
+async fn test_complete_set_strategy_sells_filled_leg_when_other_leg_is_killed() {
+    use polymarket_lmsr_bot::domain::trade::{OrderType, TradeSide};
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let yes_token = config.markets[0].yes_token_id.clone();
+    let no_token = config.markets[0].no_token_id.clone();
+
     let mut mock_exec = MockOrderExec::new();
+    let yes = yes_token.clone();
+    mock_exec
+        .expect_place_order()
+        .times(1)
+        .withf(move |o| o.token_id == yes && o.side == TradeSide::Buy)
+        .returning(|_| filled_placement());
+    let no = no_token.clone();
+    mock_exec
+        .expect_place_order()
+        .times(1)
+        .withf(move |o| o.token_id == no && o.side == TradeSide::Buy)
+        .returning(|_| killed_placement());
+    // The YES leg is flattened at market instead of held
+    let yes = yes_token.clone();
+    mock_exec
+        .expect_place_order()
+        .times(1)
+        .withf(move |o| {
+            o.token_id == yes
+                && o.side == TradeSide::Sell
+                && o.order_type == OrderType::Fok
+                && o.price == 0.01
+                && o.size == 20.0
+        })
+        .returning(|_| filled_placement());
+
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_merge_positions().never();
+    mock_chain.expect_split_position().never();
+
+    let mut engine = complete_set_engine(mock_exec, Some(mock_chain));
+    engine
+        .process_update(&book_update(&yes_token, 0.44, 0.45, 20.0))
+        .await;
+    engine
+        .process_update(&book_update(&no_token, 0.49, 0.50, 35.0))
+        .await;
+}
 
+#[tokio::test]
+async fn test_complete_set_strategy_sells_unsold_half_of_split() {
+    use polymarket_lmsr_bot::domain::trade::TradeSide;
+    use polymarket_lmsr_bot::ports::chain_client::SplitResult;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let yes_token = config.markets[0].yes_token_id.clone();
+    let no_token = config.markets[0].no_token_id.clone();
+
+    let mut mock_chain = MockChainCli::new();
+    mock_chain
+        .expect_split_position()
+        .times(1)
+        .returning(|_, amount| {
+            Ok(SplitResult {
+                tx_hash: "0xsplit".to_string(),
+                sets_split: amount as f64 / 1_000_000.0,
+                usdc_spent: amount as f64 / 1_000_000.0,
+                gas_cost_matic: 0.01,
+            })
+        });
+    mock_chain.expect_merge_positions().never();
+
+    let mut mock_exec = MockOrderExec::new();
+    let yes = yes_token.clone();
     mock_exec
-        .expect_cancel_all_orders()
+        .expect_place_order()
         .times(1)
-        .returning(|| Ok(5));
+        .withf(move |o| o.token_id == yes && o.side == TradeSide::Sell)
+        .returning(|_| filled_placement());
+    let no = no_token.clone();
+    mock_exec
+        .expect_place_order()
+        .times(1)
+        .withf(move |o| o.token_id == no && o.price > 0.01)
+        .returning(|_| killed_placement());
+    // The NO half of the split sets is sold at market
+    let no = no_token.clone();
+    mock_exec
+        .expect_place_order()
+        .times(1)
+        .withf(move |o| {
+            o.token_id == no && o.side == TradeSide::Sell && o.price == 0.01 && o.size == 10.0
+        })
+        .returning(|_| filled_placement());
 
-    let cancelled = mock_exec.cancel_all_orders().await.unwrap();
-    assert_eq!(cancelled, 5);
+    let mut engine = complete_set_engine(mock_exec, Some(mock_chain));
+    engine
+        .process_update(&book_update(&yes_token, 0.56, 0.57, 10.0))
+        .await;
+    engine
+        .process_update(&book_update(&no_token, 0.47, 0.48, 10.0))
+        .await;
+}
+
+/// Executor whose orders all fill in full at their limit price.
+fn filling_exec() -> MockOrderExec {
+    use polymarket_lmsr_bot::ports::execution::{OrderPlacement, OrderStatus};
+
+    let fills = Arc::new(std::sync::Mutex::new(Vec::<(f64, f64)>::new()));
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_available_balance().returning(|_| Ok(1000.0));
+    let placed = Arc::clone(&fills);
+    mock_exec.expect_place_order().returning(move |o| {
+        let mut placed = placed.lock().unwrap();
+        placed.push((o.price, o.size));
+        Ok(OrderPlacement {
+            order_id: format!("ord_{}", placed.len() - 1),
+            accepted: true,
+            rejection_reason: None,
+            timestamp_ms: 1_700_000_000_000,
+        })
+    });
+    mock_exec.expect_get_order_status().returning(move |id| {
+        let index: usize = id.trim_start_matches("ord_").parse().unwrap();
+        let (avg_price, filled_size) = fills.lock().unwrap()[index];
+        Ok(OrderStatus::Filled {
+            avg_price,
+            filled_size,
+        })
+    });
+    mock_exec
+}
+
+type GatedExec = polymarket_lmsr_bot::usecases::risk_gate::RiskGate<MockOrderExec>;
+type GatedEngine = polymarket_lmsr_bot::usecases::arbitrage_engine::ArbitrageEngine<MockFeed, GatedExec>;
+
+/// `complete_set_arb` engine trading through a `RiskGate` that books
+/// into a ledger, as wired in `main`.
+fn gated_complete_set_engine(
+    exec: MockOrderExec,
+    chain: MockChainCli,
+) -> (
+    GatedEngine,
+    Arc<GatedExec>,
+    Arc<polymarket_lmsr_bot::usecases::portfolio_ledger::PortfolioLedger>,
+) {
+    use polymarket_lmsr_bot::ports::metrics::NoopMetrics;
+    use polymarket_lmsr_bot::usecases::arbitrage_engine::ArbitrageEngine;
+    use polymarket_lmsr_bot::usecases::portfolio_ledger::PortfolioLedger;
+    use polymarket_lmsr_bot::usecases::risk_gate::RiskGate;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+    use polymarket_lmsr_bot::usecases::strategy_registry::StrategyRegistry;
+
+    let mut config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    config.markets[0].strategies = vec!["complete_set_arb".to_string()];
+    let risk = Arc::new(tokio::sync::RwLock::new(RiskManager::new(&config.risk)));
+    let ledger = Arc::new(PortfolioLedger::new(&config, Arc::new(NoopMetrics)));
+    let gate = Arc::new(
+        RiskGate::new(
+            Arc::new(exec),
+            Arc::clone(&risk),
+            Arc::new(NoopMetrics),
+            &config,
+        )
+        .with_ledger(Arc::clone(&ledger)),
+    );
+    let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+    let engine = ArbitrageEngine::new(
+        Arc::new(MockFeed::new()),
+        Arc::clone(&gate),
+        risk,
+        config,
+        shutdown_rx,
+        &StrategyRegistry::default(),
+    )
+    .unwrap()
+    .with_chain(Arc::new(chain))
+    .with_ledger(Arc::clone(&ledger));
+    (engine, gate, ledger)
+}
+
+#[tokio::test]
+async fn test_complete_set_merge_leaves_no_lots_in_ledger_or_gate() {
+    use polymarket_lmsr_bot::ports::chain_client::MergeResult;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let yes_token = config.markets[0].yes_token_id.clone();
+    let no_token = config.markets[0].no_token_id.clone();
+
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_merge_positions().times(1).returning(|_, amount| {
+        Ok(MergeResult {
+            tx_hash: "0xmerge".to_string(),
+            sets_merged: amount as f64 / 1_000_000.0,
+            usdc_recovered: amount as f64 / 1_000_000.0,
+            gas_cost_matic: 0.01,
+        })
+    });
+    let (mut engine, gate, ledger) = gated_complete_set_engine(filling_exec(), mock_chain);
+
+    engine
+        .process_update(&book_update(&yes_token, 0.44, 0.45, 20.0))
+        .await;
+    engine
+        .process_update(&book_update(&no_token, 0.49, 0.50, 35.0))
+        .await;
+
+    // Both legs were bought, then burned by the merge
+    assert!(ledger.sizes().await.is_empty());
+    assert!(gate.exposure().await.held.is_empty());
+    let portfolio = ledger.snapshot().await;
+    let expected = 20.0 * (1.0 - 0.45 - 0.50) - portfolio.fees_paid();
+    assert!((portfolio.total_realized() - expected).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_complete_set_split_sale_realizes_against_half_a_set() {
+    use polymarket_lmsr_bot::ports::chain_client::SplitResult;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let yes_token = config.markets[0].yes_token_id.clone();
+    let no_token = config.markets[0].no_token_id.clone();
+
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_split_position().times(1).returning(|_, amount| {
+        Ok(SplitResult {
+            tx_hash: "0xsplit".to_string(),
+            sets_split: amount as f64 / 1_000_000.0,
+            usdc_spent: amount as f64 / 1_000_000.0,
+            gas_cost_matic: 0.01,
+        })
+    });
+    let (mut engine, gate, ledger) = gated_complete_set_engine(filling_exec(), mock_chain);
+
+    engine
+        .process_update(&book_update(&yes_token, 0.56, 0.57, 10.0))
+        .await;
+    engine
+        .process_update(&book_update(&no_token, 0.47, 0.48, 10.0))
+        .await;
+
+    // Sold 0.56 + 0.47 against a 0.50 basis per token, not zero
+    assert!(ledger.sizes().await.is_empty());
+    assert!(gate.exposure().await.held.is_empty());
+    let portfolio = ledger.snapshot().await;
+    let expected = 10.0 * (0.56 + 0.47 - 1.0) - portfolio.fees_paid();
+    assert!((portfolio.total_realized() - expected).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_complete_set_strategy_needs_chain_client() {
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    // `strategy = "..."` is accepted as a one-element list
+    assert_eq!(config.markets[1].strategies, vec!["lmsr_mm".to_string()]);
+
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_place_order().never();
+    let mut engine = complete_set_engine(mock_exec, None);
+
+    // Mispriced bundle, but the merge leg cannot run in paper mode
+    engine
+        .process_update(&book_update(&config.markets[0].yes_token_id, 0.44, 0.45, 20.0))
+        .await;
+    engine
+        .process_update(&book_update(&config.markets[0].no_token_id, 0.49, 0.50, 20.0))
+        .await;
+}
+
+#[tokio::test]
+async fn test_engine_reload_stops_deactivated_market() {
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let btc = config.markets[0].clone();
+
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec
+        .expect_cancel_orders_for_token()
+        .times(2)
+        .withf(move |token| *token == btc.yes_token_id || *token == btc.no_token_id)
+        .returning(|_| Ok(Vec::new()));
+    // A stopped market never reaches sizing or the order path
+    mock_exec.expect_available_balance().never();
+    mock_exec.expect_place_order().never();
+    let mut engine = engine_with(config.clone(), mock_exec);
+
+    // Unknown strategy: rejected, nothing applied
+    let mut bad = config.clone();
+    bad.markets[0].active = false;
+    bad.markets[1].strategies = vec!["moon_shot".to_string()];
+    assert!(engine.apply_config(bad).await.is_err());
+
+    let mut reloaded = config.clone();
+    reloaded.markets[0].active = false;
+    reloaded.rate_limits.max_orders_per_minute = 10;
+    assert!(engine.apply_config(reloaded.clone()).await.unwrap());
+
+    engine
+        .process_update(&book_update(&config.markets[0].yes_token_id, 0.30, 0.31, 50.0))
+        .await;
+
+    // Same config again: no subscription change
+    assert!(!engine.apply_config(reloaded).await.unwrap());
+}
+
+/// Example config plus a fee-free three-outcome neg-risk event.
+fn neg_risk_config() -> polymarket_lmsr_bot::config::AppConfig {
+    use polymarket_lmsr_bot::config::MarketConfig;
+    use polymarket_lmsr_bot::domain::trade::Asset;
+
+    let mut config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    for index in 0..3u32 {
+        config.markets.push(MarketConfig {
+            condition_id: format!("0x_event_q{index}"),
+            yes_token_id: format!("q{index}_yes"),
+            no_token_id: format!("q{index}_no"),
+            asset: Asset::BTC,
+            active: true,
+            neg_risk: true,
+            neg_risk_market_id: Some("0x_event".to_string()),
+            question_index: Some(index),
+            outcome_count: Some(3),
+            strategies: Vec::new(),
+            fee_class: polymarket_lmsr_bot::domain::fees::FeeClass::default(),
+            maker_rebate_share: None,
+        });
+    }
+    config
+}
+
+type NegRiskArb = polymarket_lmsr_bot::usecases::neg_risk_arb::NegRiskArbitrage<
+    MockFeed,
+    MockOrderExec,
+    MockChainCli,
+>;
+type SharedRisk =
+    Arc<tokio::sync::RwLock<polymarket_lmsr_bot::usecases::risk_manager::RiskManager>>;
+
+/// Neg-risk strategy over raw mocks, with the risk manager it halts.
+fn neg_risk_arb(
+    exec: MockOrderExec,
+    chain: MockChainCli,
+    config: &polymarket_lmsr_bot::config::AppConfig,
+) -> (NegRiskArb, SharedRisk) {
+    use polymarket_lmsr_bot::ports::metrics::NoopMetrics;
+    use polymarket_lmsr_bot::usecases::neg_risk_arb::NegRiskArbitrage;
+    use polymarket_lmsr_bot::usecases::portfolio_ledger::PortfolioLedger;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+
+    let risk = Arc::new(tokio::sync::RwLock::new(RiskManager::new(&config.risk)));
+    let arb = NegRiskArbitrage::new(
+        Arc::new(MockFeed::new()),
+        Arc::new(exec),
+        Arc::new(chain),
+        Arc::new(PortfolioLedger::new(config, Arc::new(NoopMetrics))),
+        Arc::clone(&risk),
+        config,
+    );
+    (arb, risk)
+}
+
+#[tokio::test]
+async fn test_neg_risk_converts_cheap_no_and_sells_yes() {
+    use polymarket_lmsr_bot::domain::neg_risk::NegRiskKind;
+    use polymarket_lmsr_bot::domain::trade::TradeSide;
+    use polymarket_lmsr_bot::ports::chain_client::ConvertResult;
+    let config = neg_risk_config();
+
+    // Buy NO on q0, then sell the YES of q1 and q2 received on conversion
+    let placed = Arc::new(std::sync::Mutex::new(Vec::<(String, TradeSide)>::new()));
+    let placed_ref = Arc::clone(&placed);
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_place_order().times(3).returning(move |o| {
+        placed_ref.lock().unwrap().push((o.token_id.clone(), o.side));
+        filled_placement()
+    });
+
+    let mut mock_chain = MockChainCli::new();
+    mock_chain
+        .expect_convert_positions()
+        .times(1)
+        .withf(|event, indices, amount| {
+            event == "0x_event" && indices == [0] && *amount == 50_000_000
+        })
+        .returning(|_, _, amount| {
+            Ok(ConvertResult {
+                tx_hash: "0xconvert".to_string(),
+                amount: amount as f64 / 1_000_000.0,
+                minted: amount as f64 / 1_000_000.0,
+                usdc_recovered: 0.0,
+                gas_cost_matic: 0.01,
+            })
+        });
+    mock_chain.expect_split_position().never();
+
+    let (mut arb, _) = neg_risk_arb(mock_exec, mock_chain, &config);
+
+    // NO q0 at 0.50 vs YES q1 + YES q2 bidding 0.30 + 0.28; nothing
+    // trades until the last book completes the picture
+    let books = [
+        ("q0_no", 0.45, 0.50),
+        ("q1_yes", 0.30, 0.35),
+        ("q1_no", 0.60, 0.72),
+        ("q2_no", 0.90, 0.95),
+        ("q0_yes", 0.40, 0.55),
+    ];
+    for (token, bid, ask) in books {
+        assert!(arb.on_update(&book_update(token, bid, ask, 100.0)).await.unwrap().is_none());
+    }
+    let trade = arb
+        .on_update(&book_update("q2_yes", 0.28, 0.33, 100.0))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(trade.opportunity.kind, NegRiskKind::ConvertNo);
+    assert_eq!(trade.opportunity.size, 50.0);
+    assert_eq!(trade.legs_filled, 3);
+    assert_eq!(trade.chain_txs, vec!["0xconvert".to_string()]);
+    assert!(!trade.unwound);
+    let placed = placed.lock().unwrap();
+    assert_eq!(placed[0], ("q0_no".to_string(), TradeSide::Buy));
+    assert!(placed[1..].iter().all(|(_, side)| *side == TradeSide::Sell));
+}
+
+#[tokio::test]
+async fn test_neg_risk_conversion_is_booked_in_ledger_and_gate() {
+    use polymarket_lmsr_bot::ports::chain_client::ConvertResult;
+    use polymarket_lmsr_bot::ports::metrics::NoopMetrics;
+    use polymarket_lmsr_bot::usecases::neg_risk_arb::NegRiskArbitrage;
+    use polymarket_lmsr_bot::usecases::portfolio_ledger::PortfolioLedger;
+    use polymarket_lmsr_bot::usecases::risk_gate::RiskGate;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+
+    let config = neg_risk_config();
+    let mut mock_chain = MockChainCli::new();
+    mock_chain
+        .expect_convert_positions()
+        .times(1)
+        .returning(|_, _, amount| {
+            Ok(ConvertResult {
+                tx_hash: "0xconvert".to_string(),
+                amount: amount as f64 / 1_000_000.0,
+                minted: amount as f64 / 1_000_000.0,
+                usdc_recovered: 0.0,
+                gas_cost_matic: 0.01,
+            })
+        });
+
+    let risk = Arc::new(tokio::sync::RwLock::new(RiskManager::new(&config.risk)));
+    let ledger = Arc::new(PortfolioLedger::new(&config, Arc::new(NoopMetrics)));
+    let gate = Arc::new(
+        RiskGate::new(
+            Arc::new(filling_exec()),
+            Arc::clone(&risk),
+            Arc::new(NoopMetrics),
+            &config,
+        )
+        .with_ledger(Arc::clone(&ledger)),
+    );
+    let mut arb = NegRiskArbitrage::new(
+        Arc::new(MockFeed::new()),
+        Arc::clone(&gate),
+        Arc::new(mock_chain),
+        Arc::clone(&ledger),
+        risk,
+        &config,
+    );
+
+    // Same books as above: NO q0 at 0.50 → YES q1 + q2 sold at 0.30 + 0.28
+    let books = [
+        ("q0_no", 0.45, 0.50),
+        ("q1_yes", 0.30, 0.35),
+        ("q1_no", 0.60, 0.72),
+        ("q2_no", 0.90, 0.95),
+        ("q0_yes", 0.40, 0.55),
+        ("q2_yes", 0.28, 0.33),
+    ];
+    for (token, bid, ask) in books {
+        arb.on_update(&book_update(token, bid, ask, 100.0)).await.unwrap();
+    }
+
+    // The minted YES carried the NO basis and were sold against it
+    assert!(ledger.sizes().await.is_empty());
+    assert!(gate.exposure().await.held.is_empty());
+    let portfolio = ledger.snapshot().await;
+    let expected = 50.0 * (0.30 + 0.28 - 0.50) - portfolio.fees_paid();
+    assert!((portfolio.total_realized() - expected).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_neg_risk_sells_filled_legs_of_partial_yes_basket() {
+    use polymarket_lmsr_bot::domain::neg_risk::NegRiskKind;
+    use polymarket_lmsr_bot::domain::trade::TradeSide;
+
+    let config = neg_risk_config();
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec
+        .expect_place_order()
+        .times(2)
+        .withf(|o| o.side == TradeSide::Buy && o.token_id != "q2_yes")
+        .returning(|_| filled_placement());
+    mock_exec
+        .expect_place_order()
+        .times(1)
+        .withf(|o| o.side == TradeSide::Buy && o.token_id == "q2_yes")
+        .returning(|_| killed_placement());
+    // The two YES bought are sold back at market, not held
+    mock_exec
+        .expect_place_order()
+        .times(2)
+        .withf(|o| {
+            o.side == TradeSide::Sell
+                && o.token_id != "q2_yes"
+                && o.price == 0.01
+                && o.size == 50.0
+        })
+        .returning(|_| filled_placement());
+
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_split_position().never();
+    mock_chain.expect_convert_positions().never();
+
+    let (mut arb, _) = neg_risk_arb(mock_exec, mock_chain, &config);
+
+    // YES asks sum to 0.90; no other basket pays
+    let books = [
+        ("q0_no", 0.70, 0.75),
+        ("q1_no", 0.70, 0.75),
+        ("q2_no", 0.70, 0.75),
+        ("q0_yes", 0.25, 0.30),
+        ("q1_yes", 0.25, 0.30),
+    ];
+    for (token, bid, ask) in books {
+        assert!(arb.on_update(&book_update(token, bid, ask, 100.0)).await.unwrap().is_none());
+    }
+    let trade = arb
+        .on_update(&book_update("q2_yes", 0.25, 0.30, 100.0))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(trade.opportunity.kind, NegRiskKind::BuyYesBasket);
+    assert_eq!(trade.legs_filled, 2);
+    assert!(trade.unwound);
+}
+
+#[tokio::test]
+async fn test_neg_risk_halts_when_partial_basket_cannot_be_flattened() {
+    use polymarket_lmsr_bot::domain::trade::TradeSide;
+    use polymarket_lmsr_bot::ports::repository::HaltSource;
+
+    let config = neg_risk_config();
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec
+        .expect_place_order()
+        .times(2)
+        .withf(|o| o.side == TradeSide::Buy && o.token_id != "q2_yes")
+        .returning(|_| filled_placement());
+    mock_exec
+        .expect_place_order()
+        .withf(|o| o.side == TradeSide::Buy && o.token_id == "q2_yes")
+        .returning(|_| killed_placement());
+    // No bid takes the YES bought back
+    mock_exec
+        .expect_place_order()
+        .withf(|o| o.side == TradeSide::Sell)
+        .returning(|_| killed_placement());
+
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_split_position().never();
+    mock_chain.expect_convert_positions().never();
+
+    let (mut arb, risk) = neg_risk_arb(mock_exec, mock_chain, &config);
+
+    let books = [
+        ("q0_no", 0.70, 0.75),
+        ("q1_no", 0.70, 0.75),
+        ("q2_no", 0.70, 0.75),
+        ("q0_yes", 0.25, 0.30),
+        ("q1_yes", 0.25, 0.30),
+    ];
+    for (token, bid, ask) in books {
+        assert!(arb.on_update(&book_update(token, bid, ask, 100.0)).await.unwrap().is_none());
+    }
+    assert!(arb.on_update(&book_update("q2_yes", 0.25, 0.30, 100.0)).await.is_err());
+
+    let risk = risk.read().await;
+    assert_eq!(risk.halt_record().unwrap().source, HaltSource::Unwind);
+}
+
+#[tokio::test]
+async fn test_neg_risk_event_pauses_while_an_outcome_is_inactive() {
+    let mut config = neg_risk_config();
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_place_order().times(3).returning(|_| filled_placement());
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_split_position().never();
+
+    let q2 = config.markets.len() - 1;
+    config.markets[q2].active = false;
+    let (mut arb, _) = neg_risk_arb(mock_exec, mock_chain, &config);
+
+    // YES asks sum to 0.90, but q2 is disabled
+    let books = [
+        ("q0_no", 0.70, 0.75),
+        ("q1_no", 0.70, 0.75),
+        ("q2_no", 0.70, 0.75),
+        ("q0_yes", 0.25, 0.30),
+        ("q1_yes", 0.25, 0.30),
+        ("q2_yes", 0.25, 0.30),
+    ];
+    for (token, bid, ask) in books {
+        assert!(arb.on_update(&book_update(token, bid, ask, 100.0)).await.unwrap().is_none());
+    }
+
+    // Re-enabled by a hot reload: the basket trades
+    config.markets[q2].active = true;
+    arb.apply_config(&config);
+    let trade = arb
+        .on_update(&book_update("q2_yes", 0.25, 0.30, 100.0))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(trade.legs_filled, 3);
+}
+
+#[tokio::test]
+async fn test_neg_risk_basket_waits_for_order_rate_budget() {
+    let mut config = neg_risk_config();
+    // Three legs plus their unwind need six orders
+    config.rate_limits.max_orders_per_minute = 5;
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_place_order().never();
+    let (mut arb, _) = neg_risk_arb(mock_exec, MockChainCli::new(), &config);
+
+    let books = [
+        ("q0_no", 0.70, 0.75),
+        ("q1_no", 0.70, 0.75),
+        ("q2_no", 0.70, 0.75),
+        ("q0_yes", 0.25, 0.30),
+        ("q1_yes", 0.25, 0.30),
+        ("q2_yes", 0.25, 0.30),
+    ];
+    for (token, bid, ask) in books {
+        assert!(arb.on_update(&book_update(token, bid, ask, 100.0)).await.unwrap().is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_neg_risk_refuses_split_while_halted() {
+    use polymarket_lmsr_bot::ports::repository::HaltSource;
+
+    let config = neg_risk_config();
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_place_order().never();
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_split_position().never();
+    mock_chain.expect_convert_positions().never();
+
+    let (mut arb, risk) = neg_risk_arb(mock_exec, mock_chain, &config);
+    risk.write().await.halt("operator", HaltSource::Admin, 0);
+
+    // YES bids sum to 1.35: a split-and-sell basket, refused before
+    // anything goes on-chain
+    let books = [
+        ("q0_no", 0.50, 0.55),
+        ("q1_no", 0.50, 0.55),
+        ("q2_no", 0.50, 0.55),
+        ("q0_yes", 0.45, 0.50),
+        ("q1_yes", 0.45, 0.50),
+        ("q2_yes", 0.45, 0.50),
+    ];
+    for (token, bid, ask) in books {
+        assert!(arb.on_update(&book_update(token, bid, ask, 100.0)).await.unwrap().is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_position_merger_skips_when_gas_too_high() {
+    use polymarket_lmsr_bot::usecases::position_merger::PositionMerger;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_gas_price_gwei().returning(|| Ok(90.0));
+    mock_chain.expect_token_balances().never();
+    mock_chain.expect_merge_positions().never();
+
+    let merger = PositionMerger::new(Arc::new(mock_chain), Arc::new(MockRepo::new()), &config);
+    assert!(merger.run_once().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_risk_manager_circuit_breaker_integration() {
+    use polymarket_lmsr_bot::config::RiskConfig;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+
+    let config = RiskConfig {
+        max_daily_loss_fraction: 0.02,
+        max_position_size: 100.0,
+        max_total_exposure: 500.0,
+        min_bankroll: 50.0,
+        circuit_breaker_losses: 5,
+        cooldown_seconds: 1800,
+        half_open_size_fraction: 0.25,
+        market_limits: Vec::new(),
+        asset_limits: Vec::new(),
+        correlation_groups: Vec::new(),
+    };
+
+    let mut rm = RiskManager::new(&config);
+
+    // Should allow trading initially
+    assert!(rm.can_trade());
+    assert!(rm.can_open_position(50.0, 1000.0));
+
+    // Simulate 5 consecutive losses
+    for _ in 0..5 {
+        rm.record_trade(-10.0);
+    }
+
+    // Circuit breaker should now block trading
+    assert!(rm.is_circuit_breaker_active());
+    assert!(!rm.can_trade());
+
+    // Reset daily counters
+    rm.reset_daily();
+    assert!(!rm.is_circuit_breaker_active());
+    assert!(rm.can_trade());
+}
+
+/// Metrics sink that records rejections for assertions.
+#[derive(Default)]
+struct RecordingMetrics {
+    rejected: std::sync::Mutex<Vec<(String, String)>>,
+}
+
+impl polymarket_lmsr_bot::ports::metrics::MetricsSink for RecordingMetrics {
+    fn order_rejected(&self, asset: &str, reason: &str) {
+        self.rejected
+            .lock()
+            .unwrap()
+            .push((asset.to_string(), reason.to_string()));
+    }
+
+    fn pnl_updated(&self, _asset: &str, _realized: f64, _unrealized: f64) {}
+}
+
+fn gated_executor(
+    mock_exec: MockOrderExec,
+    metrics: Arc<RecordingMetrics>,
+) -> polymarket_lmsr_bot::usecases::risk_gate::RiskGate<MockOrderExec> {
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let risk = Arc::new(tokio::sync::RwLock::new(
+        RiskManager::new(&config.risk).with_markets(&config.markets),
+    ));
+    polymarket_lmsr_bot::usecases::risk_gate::RiskGate::new(
+        Arc::new(mock_exec),
+        risk,
+        metrics,
+        &config,
+    )
+}
+
+#[tokio::test]
+async fn test_risk_gate_blocks_oversized_order() {
+    use polymarket_lmsr_bot::ports::execution::OrderExecution;
+
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_available_balance().returning(|_| Ok(1000.0));
+    // The CLOB must never see an order that fails the risk check
+    mock_exec.expect_place_order().times(0);
+
+    let metrics = Arc::new(RecordingMetrics::default());
+    let gate = gated_executor(mock_exec, Arc::clone(&metrics));
+
+    // 0.50 × 300 = 150 USDC > max_position_size (100)
+    let order = polymarket_lmsr_bot::domain::trade::Order::new_maker(
+        "21742633143463906290569050155826241533067272736897614950488156847949938836455".to_string(),
+        polymarket_lmsr_bot::domain::trade::TradeSide::Buy,
+        0.50,
+        300.0,
+    );
+
+    let result = gate.place_order(&order).await.unwrap();
+    assert!(!result.accepted);
+    assert!(result.rejection_reason.unwrap().starts_with("Risk:"));
+
+    let rejected = metrics.rejected.lock().unwrap();
+    assert_eq!(
+        rejected.as_slice(),
+        &[("BTC".to_string(), "position_limit".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn test_risk_gate_counts_resting_orders_as_exposure() {
+    use polymarket_lmsr_bot::ports::execution::OrderExecution;
+
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_available_balance().returning(|_| Ok(1000.0));
+    mock_exec.expect_place_order().times(1).returning(|_| {
+        Ok(polymarket_lmsr_bot::ports::execution::OrderPlacement {
+            order_id: "ord_1".to_string(),
+            accepted: true,
+            rejection_reason: None,
+            timestamp_ms: 1_700_000_000_000,
+        })
+    });
+
+    let metrics = Arc::new(RecordingMetrics::default());
+    let gate = gated_executor(mock_exec, Arc::clone(&metrics));
+
+    // 0.60 × 100 = 60 USDC each; the second pushes the token to 120
+    let order = polymarket_lmsr_bot::domain::trade::Order::new_maker(
+        "31012354326112874556791216440823478398231853090214958218931066543127830226108".to_string(),
+        polymarket_lmsr_bot::domain::trade::TradeSide::Buy,
+        0.60,
+        100.0,
+    );
+
+    assert!(gate.place_order(&order).await.unwrap().accepted);
+    assert!(!gate.place_order(&order).await.unwrap().accepted);
+    assert!((gate.exposure().await.total() - 60.0).abs() < 1e-9);
+
+    let rejected = metrics.rejected.lock().unwrap();
+    assert_eq!(rejected[0].0, "ETH");
+    assert_eq!(rejected[0].1, "position_limit");
+}
+
+#[tokio::test]
+async fn test_risk_gate_credits_fills_of_cancelled_order() {
+    use polymarket_lmsr_bot::ports::execution::{OrderCancellation, OrderExecution, OrderStatus};
+
+    let token =
+        "21742633143463906290569050155826241533067272736897614950488156847949938836455".to_string();
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_available_balance().returning(|_| Ok(1000.0));
+    mock_exec.expect_place_order().times(1).returning(|_| {
+        Ok(polymarket_lmsr_bot::ports::execution::OrderPlacement {
+            order_id: "ord_1".to_string(),
+            accepted: true,
+            rejection_reason: None,
+            timestamp_ms: 1_700_000_000_000,
+        })
+    });
+    mock_exec.expect_cancel_order().times(1).returning(|id| {
+        Ok(OrderCancellation {
+            order_id: id.clone(),
+            success: true,
+            error: None,
+        })
+    });
+    // 40 of 100 filled between the last reconcile and the cancel
+    mock_exec.expect_get_order_status().times(1).returning(|_| {
+        Ok(OrderStatus::PartiallyFilled {
+            filled_size: 40.0,
+            remaining_size: 0.0,
+            avg_price: 0.40,
+        })
+    });
+
+    let gate = gated_executor(mock_exec, Arc::new(RecordingMetrics::default()));
+    let order = polymarket_lmsr_bot::domain::trade::Order::new_maker(
+        token.clone(),
+        polymarket_lmsr_bot::domain::trade::TradeSide::Buy,
+        0.40,
+        100.0,
+    );
+    assert!(gate.place_order(&order).await.unwrap().accepted);
+    assert!(gate.cancel_order(&"ord_1".to_string()).await.unwrap().success);
+
+    let exposure = gate.exposure().await;
+    assert!(exposure.resting.is_empty());
+    assert!((exposure.token(&token) - 16.0).abs() < 1e-9);
+    assert!(gate.open_orders().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_halt_cancels_orders_and_blocks_gate_until_resume() {
+    use polymarket_lmsr_bot::ports::execution::OrderExecution;
+    use polymarket_lmsr_bot::ports::repository::HaltSource;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+    use polymarket_lmsr_bot::usecases::trading_control::TradingControl;
+
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_cancel_all_orders().times(1).returning(|| Ok(3));
+    mock_exec.expect_available_balance().returning(|_| Ok(1000.0));
+    mock_exec.expect_place_order().times(1).returning(|_| {
+        Ok(polymarket_lmsr_bot::ports::execution::OrderPlacement {
+            order_id: "ord_after_resume".to_string(),
+            accepted: true,
+            rejection_reason: None,
+            timestamp_ms: 1_700_000_000_000,
+        })
+    });
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let risk = Arc::new(tokio::sync::RwLock::new(RiskManager::new(&config.risk)));
+    let metrics = Arc::new(RecordingMetrics::default());
+    let gate = Arc::new(polymarket_lmsr_bot::usecases::risk_gate::RiskGate::new(
+        Arc::new(mock_exec),
+        Arc::clone(&risk),
+        Arc::clone(&metrics) as Arc<dyn polymarket_lmsr_bot::ports::metrics::MetricsSink>,
+        &config,
+    ));
+    let control = TradingControl::new(Arc::clone(&risk), Arc::clone(&gate));
+
+    let cancelled = control.halt("CPI print", HaltSource::Admin).await.unwrap();
+    assert_eq!(cancelled, 3);
+    let status = control.status().await;
+    assert!(!status.trading_enabled);
+    assert_eq!(status.halt.unwrap().reason, "CPI print");
+
+    let order = polymarket_lmsr_bot::domain::trade::Order::new_maker(
+        "21742633143463906290569050155826241533067272736897614950488156847949938836455".to_string(),
+        polymarket_lmsr_bot::domain::trade::TradeSide::Buy,
+        0.40,
+        10.0,
+    );
+    assert!(!gate.place_order(&order).await.unwrap().accepted);
+    assert_eq!(metrics.rejected.lock().unwrap()[0].1, "halted");
+
+    assert!(control.resume().await.is_some());
+    assert!(gate.place_order(&order).await.unwrap().accepted);
+}
+
+#[tokio::test]
+async fn test_engine_unwinds_filled_leg_through_halted_gate() {
+    use polymarket_lmsr_bot::domain::trade::{OrderType, TradeSide};
+    use polymarket_lmsr_bot::ports::execution::{OrderPlacement, OrderStatus};
+    use polymarket_lmsr_bot::ports::repository::HaltSource;
+    use polymarket_lmsr_bot::usecases::arbitrage_engine::ArbitrageEngine;
+    use polymarket_lmsr_bot::usecases::risk_gate::RiskGate;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+    use polymarket_lmsr_bot::usecases::strategy_registry::StrategyRegistry;
+
+    let mut config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    config.markets[0].strategies = vec!["complete_set_arb".to_string()];
+    let yes_token = config.markets[0].yes_token_id.clone();
+    let no_token = config.markets[0].no_token_id.clone();
+    let risk = Arc::new(tokio::sync::RwLock::new(RiskManager::new(&config.risk)));
+    let placed = |id: &str| {
+        Ok(OrderPlacement {
+            order_id: id.to_string(),
+            accepted: true,
+            rejection_reason: None,
+            timestamp_ms: 1_700_000_000_000,
+        })
+    };
+
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_available_balance().returning(|_| Ok(1000.0));
+    let yes = yes_token.clone();
+    mock_exec
+        .expect_place_order()
+        .times(1)
+        .withf(move |o| o.token_id == yes && o.side == TradeSide::Buy)
+        .returning(move |_| placed("ord_yes"));
+    // Trading is halted while the second leg is in flight
+    let no = no_token.clone();
+    let halting = Arc::clone(&risk);
+    mock_exec
+        .expect_place_order()
+        .times(1)
+        .withf(move |o| o.token_id == no && o.side == TradeSide::Buy)
+        .returning(move |_| {
+            halting
+                .try_write()
+                .unwrap()
+                .halt("operator", HaltSource::Admin, 0);
+            killed_placement()
+        });
+    let yes = yes_token.clone();
+    mock_exec
+        .expect_place_order()
+        .times(1)
+        .withf(move |o| {
+            o.token_id == yes
+                && o.side == TradeSide::Sell
+                && o.order_type == OrderType::Fok
+                && o.size == 20.0
+        })
+        .returning(move |_| placed("ord_unwind"));
+    mock_exec
+        .expect_get_order_status()
+        .returning(|id| {
+            let avg_price = if id == "ord_yes" { 0.45 } else { 0.01 };
+            Ok(OrderStatus::Filled {
+                avg_price,
+                filled_size: 20.0,
+            })
+        });
+
+    let gate = Arc::new(RiskGate::new(
+        Arc::new(mock_exec),
+        Arc::clone(&risk),
+        Arc::new(RecordingMetrics::default()),
+        &config,
+    ));
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_merge_positions().never();
+    let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+    let mut engine = ArbitrageEngine::new(
+        Arc::new(MockFeed::new()),
+        Arc::clone(&gate),
+        Arc::clone(&risk),
+        config,
+        shutdown_rx,
+        &StrategyRegistry::default(),
+    )
+    .unwrap()
+    .with_chain(Arc::new(mock_chain));
+
+    engine
+        .process_update(&book_update(&yes_token, 0.44, 0.45, 20.0))
+        .await;
+    engine
+        .process_update(&book_update(&no_token, 0.49, 0.50, 35.0))
+        .await;
+
+    // The flatten sell passed the halted gate: nothing is left held
+    assert!(gate.exposure().await.held.is_empty());
+    assert_eq!(
+        risk.read().await.halt_record().unwrap().source,
+        HaltSource::Admin
+    );
+}
+
+#[tokio::test]
+async fn test_risk_scheduler_restores_and_checkpoints_daily_loss() {
+    use polymarket_lmsr_bot::ports::repository::{
+        BotStateSnapshot, BreakerState, RiskStateSnapshot,
+    };
+    use polymarket_lmsr_bot::domain::time::utc_day;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+    use polymarket_lmsr_bot::usecases::risk_scheduler::RiskScheduler;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let today = utc_day(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64,
+    );
+    let previous = BotStateSnapshot {
+        schema_version: 2,
+        version: "0.5.0".to_string(),
+        timestamp_ms: 1_700_000_000_000,
+        open_orders: Vec::new(),
+        positions: Vec::new(),
+        cumulative_pnl: 0.0,
+        daily_loss: 18.0,
+        risk: Some(RiskStateSnapshot {
+            day: today,
+            daily_loss: 18.0,
+            consecutive_losses: 2,
+            breaker: BreakerState::Closed,
+            tripped_at_ms: None,
+            halt: None,
+        }),
+        portfolio: None,
+    };
+
+    let mut mock_repo = MockRepo::new();
+    mock_repo
+        .expect_load_latest_state()
+        .returning(move || Ok(Some(previous.clone())));
+    // Only the checkpointer writes the snapshot
+    mock_repo.expect_save_state().never();
+
+    let risk = Arc::new(tokio::sync::RwLock::new(RiskManager::new(&config.risk)));
+    let checkpoints = Arc::new(tokio::sync::Notify::new());
+    let mut scheduler = RiskScheduler::new(
+        Arc::clone(&risk),
+        Arc::new(mock_repo),
+        Duration::from_secs(5),
+    )
+    .with_checkpoints(Arc::clone(&checkpoints));
+    let requested = || async {
+        tokio::time::timeout(Duration::from_millis(10), checkpoints.notified())
+            .await
+            .is_ok()
+    };
+
+    // Restart must keep the loss already taken today
+    assert!(scheduler.restore().await.unwrap());
+    assert_eq!(risk.read().await.daily_loss(), 18.0);
+
+    // Unchanged state needs no checkpoint
+    scheduler.tick().await;
+    assert!(!requested().await);
+
+    // A new loss is checkpointed right away
+    risk.write().await.record_trade(-2.0);
+    scheduler.tick().await;
+    assert!(requested().await);
+    scheduler.tick().await;
+    assert!(!requested().await);
+}
+
+#[tokio::test]
+async fn test_repository_save_and_load_trade() {
+    let mut mock_repo = MockRepo::new();
+
+    let record = polymarket_lmsr_bot::ports::repository::TradeRecord {
+        id: "trade_001".to_string(),
+        order_id: "ord_001".to_string(),
+        market_id: "market_btc_up".to_string(),
+        side: "BUY".to_string(),
+        price: 0.55,
+        size: 20.0,
+        lmsr_fair_value: 0.52,
+        edge: 0.03,
+        kelly_fraction: 0.25,
+        fees: 0.0,
+        timestamp_ms: 1_700_000_000_000,
+    };
+
+    let record_clone = record.clone();
+
+    mock_repo
+        .expect_save_trade()
+        .returning(|_| Ok(()));
+
+    mock_repo
+        .expect_load_trades()
+        .returning(move || Ok(vec![record_clone.clone()]));
+
+    // Save
+    mock_repo.save_trade(&record).await.unwrap();
+
+    // Load
+    let trades = mock_repo.load_trades().await.unwrap();
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].id, "trade_001");
+    assert_eq!(trades[0].edge, 0.03);
+    assert_eq!(trades[0].fees, 0.0); // Maker = 0% fees
+}
+
+#[tokio::test]
+async fn test_wallet_balance_query() {
+    let mut mock_chain = MockChainCli::new();
+
+    mock_chain
+        .expect_usdc_balance()
+        .returning(|| Ok(1500.0));
+
+    mock_chain
+        .expect_is_healthy()
+        .returning(|| true);
+
+    let balance = mock_chain.usdc_balance().await.unwrap();
+    assert_eq!(balance, 1500.0);
+    assert!(mock_chain.is_healthy().await);
+}
+
+#[tokio::test]
+async fn test_wallet_snapshot_includes_tracked_token_balances() {
+    use polymarket_lmsr_bot::ports::chain_client::TokenBalance;
+    use polymarket_lmsr_bot::usecases::wallet_manager::WalletManager;
+
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_usdc_balance().returning(|| Ok(100.0));
+    // Both tracked tokens fetched in a single batch, once per TTL
+    mock_chain
+        .expect_token_balances()
+        .times(1)
+        .returning(|ids| {
+            Ok(ids
+                .iter()
+                .map(|id| TokenBalance {
+                    token_id: id.clone(),
+                    balance_raw: 25_000_000,
+                    balance: 25.0,
+                })
+                .collect())
+        });
+
+    let wallet = WalletManager::new(Arc::new(mock_chain))
+        .with_tokens(["yes_token".to_string(), "no_token".to_string()]);
+
+    let snapshot = wallet.snapshot().await.unwrap();
+    assert_eq!(snapshot.token_balances.len(), 2);
+    assert_eq!(snapshot.total_value, 150.0);
+
+    // Second snapshot is served from cache
+    let again = wallet.snapshot().await.unwrap();
+    assert_eq!(again.total_value, 150.0);
+}
+
+#[tokio::test]
+async fn test_wallet_monitor_halts_on_low_gas_and_resumes_after_refill() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use polymarket_lmsr_bot::ports::repository::HaltSource;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+    use polymarket_lmsr_bot::usecases::trading_control::TradingControl;
+    use polymarket_lmsr_bot::usecases::wallet_manager::WalletManager;
+    use polymarket_lmsr_bot::usecases::wallet_monitor::{GasState, WalletMonitor};
+
+    // 0.3 MATIC (below the 0.5 minimum), then 0.55 (not enough to
+    // resume), then 2.0
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut mock_chain = MockChainCli::new();
+    let counter = Arc::clone(&calls);
+    mock_chain.expect_native_balance().returning(move || {
+        Ok([0.3, 0.55, 2.0][counter.fetch_add(1, Ordering::SeqCst).min(2)])
+    });
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_cancel_all_orders().times(1).returning(|| Ok(2));
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let risk = Arc::new(tokio::sync::RwLock::new(RiskManager::new(&config.risk)));
+    let control = Arc::new(TradingControl::new(Arc::clone(&risk), Arc::new(mock_exec)));
+    let monitor = WalletMonitor::new(
+        WalletManager::new(Arc::new(mock_chain)),
+        Arc::clone(&control),
+        &config.wallet,
+    );
+
+    assert_eq!(monitor.check().await.unwrap().gas, GasState::Critical);
+    assert_eq!(control.halt_source().await, Some(HaltSource::LowGas));
+
+    assert_eq!(monitor.check().await.unwrap().gas, GasState::Low);
+    assert!(!control.status().await.trading_enabled);
+
+    assert_eq!(monitor.check().await.unwrap().gas, GasState::Ok);
+    assert!(control.status().await.trading_enabled);
+}
+
+#[tokio::test]
+async fn test_wallet_monitor_sweeps_only_after_approval() {
+    use polymarket_lmsr_bot::config::TransferMode;
+    use polymarket_lmsr_bot::domain::wallet_allocation::TransferDirection;
+    use polymarket_lmsr_bot::ports::chain_client::TransferResult;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+    use polymarket_lmsr_bot::usecases::trading_control::TradingControl;
+    use polymarket_lmsr_bot::usecases::wallet_manager::WalletManager;
+    use polymarket_lmsr_bot::usecases::wallet_monitor::WalletMonitor;
+
+    const COLD: &str = "0x008AeEda4D805471dF9b2A5B0f38A0C3bCBA786b";
+
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_native_balance().returning(|| Ok(5.0));
+    // 40% of 1000 USDC is hot: 20 points above target
+    mock_chain.expect_usdc_balance().returning(|| Ok(400.0));
+    mock_chain
+        .expect_usdc_balance_of()
+        .with(eq(COLD))
+        .returning(|_| Ok(600.0));
+    mock_chain
+        .expect_transfer_usdc()
+        .with(eq(COLD), eq(200_000_000u128))
+        .times(1)
+        .returning(|_, _| {
+            Ok(TransferResult {
+                tx_hash: "0xsweep".to_string(),
+                amount: 200.0,
+                gas_cost_matic: 0.01,
+            })
+        });
+
+    let mut config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    config.wallet.cold_address = Some(COLD.to_string());
+    config.wallet.transfer_mode = TransferMode::Approve;
+    let risk = Arc::new(tokio::sync::RwLock::new(RiskManager::new(&config.risk)));
+    let control = Arc::new(TradingControl::new(risk, Arc::new(MockOrderExec::new())));
+    let monitor = WalletMonitor::new(
+        WalletManager::new(Arc::new(mock_chain)).with_cold_wallet(Some(COLD.to_string())),
+        control,
+        &config.wallet,
+    );
+
+    let status = monitor.check().await.unwrap();
+    assert!(status.allocation.unwrap().drifted);
+    let pending = status.pending_sweep.unwrap();
+    assert_eq!(pending.direction, TransferDirection::Sweep);
+    assert_eq!(pending.amount, 200.0);
+
+    let transfer = monitor.approve().await.unwrap();
+    assert_eq!(transfer.tx_hash, "0xsweep");
+    // Nothing left to approve
+    assert!(monitor.approve().await.is_err());
+}
+
+#[tokio::test]
+async fn test_ledger_books_gate_fills_marks_and_closes_day() {
+    use polymarket_lmsr_bot::domain::trade::{Order, TradeSide};
+    use polymarket_lmsr_bot::ports::execution::{OrderExecution, OrderPlacement, OrderStatus};
+    use polymarket_lmsr_bot::ports::market_feed::PriceUpdate;
+    use polymarket_lmsr_bot::ports::metrics::NoopMetrics;
+    use polymarket_lmsr_bot::ports::repository::DailyPnl;
+    use polymarket_lmsr_bot::usecases::portfolio_ledger::PortfolioLedger;
+    use polymarket_lmsr_bot::usecases::risk_gate::RiskGate;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let yes_token = config.markets[0].yes_token_id.clone();
+
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_available_balance().returning(|_| Ok(1000.0));
+    mock_exec.expect_place_order().times(1).returning(|_| {
+        Ok(OrderPlacement {
+            order_id: "ord_1".to_string(),
+            accepted: true,
+            rejection_reason: None,
+            timestamp_ms: 1_700_000_000_000,
+        })
+    });
+    mock_exec.expect_get_open_orders().returning(|| Ok(Vec::new()));
+    mock_exec.expect_get_order_status().with(eq("ord_1".to_string())).returning(|_| {
+        Ok(OrderStatus::Filled {
+            avg_price: 0.40,
+            filled_size: 50.0,
+        })
+    });
+
+    let mut mock_feed = MockFeed::new();
+    mock_feed.expect_last_price().returning(|token_id| {
+        Some(PriceUpdate {
+            market_id: String::new(),
+            token_id: token_id.clone(),
+            best_bid: Some(0.49),
+            best_ask: Some(0.51),
+            mid_price: Some(0.50),
+            timestamp_ms: 1_700_000_000_000,
+            bid_size: None,
+            ask_size: None,
+        })
+    });
+
+    let days = Arc::new(std::sync::Mutex::new(Vec::<DailyPnl>::new()));
+    let days_ref = Arc::clone(&days);
+    let mut mock_repo = MockRepo::new();
+    // The ledger asks the state checkpointer to save it
+    mock_repo.expect_save_state().never();
+    mock_repo.expect_save_daily_pnl().times(1).returning(move |d| {
+        days_ref.lock().unwrap().push(d.clone());
+        Ok(())
+    });
+
+    let risk = Arc::new(tokio::sync::RwLock::new(
+        RiskManager::new(&config.risk).with_markets(&config.markets),
+    ));
+    let checkpoints = Arc::new(tokio::sync::Notify::new());
+    let ledger = Arc::new(
+        PortfolioLedger::new(&config, Arc::new(NoopMetrics))
+            .with_risk(Arc::clone(&risk))
+            .with_checkpoints(Arc::clone(&checkpoints)),
+    );
+    let gate = RiskGate::new(Arc::new(mock_exec), risk, Arc::new(NoopMetrics), &config)
+        .with_ledger(Arc::clone(&ledger));
+    let requested = || async {
+        tokio::time::timeout(Duration::from_millis(10), checkpoints.notified())
+            .await
+            .is_ok()
+    };
+
+    // Maker buy fills on reconcile: 50 @ 0.40, no fee
+    let order = Order::new_maker(yes_token.clone(), TradeSide::Buy, 0.40, 50.0);
+    assert!(gate.place_order(&order).await.unwrap().accepted);
+    gate.reconcile().await.unwrap();
+    assert!((ledger.avg_entry_price(&yes_token).await.unwrap() - 0.40).abs() < 1e-9);
+
+    // Day 1: marked at the 0.50 mid, checkpoint requested for the fill
+    let day1_ms = 1_772_366_400_000; // 2026-03-01 12:00 UTC
+    ledger.tick(&mock_feed, &mock_repo, day1_ms).await.unwrap();
+    let positions = ledger.positions().await;
+    assert_eq!(positions[0].token_id, yes_token);
+    assert!((ledger.by_asset().await[0].unrealized - 5.0).abs() < 1e-9);
+    assert!(requested().await);
+    assert_eq!(ledger.snapshot().await.sizes(), vec![(yes_token.clone(), 50.0)]);
+
+    // Sold at 0.55 the same day; the next day closes it
+    let realized = ledger.record_fill(&yes_token, TradeSide::Sell, 0.55, 50.0, true).await;
+    assert!((realized - 7.5).abs() < 1e-9);
+    ledger.tick(&mock_feed, &mock_repo, day1_ms + 86_400_000).await.unwrap();
+    assert!(requested().await);
+    assert!(ledger.snapshot().await.sizes().is_empty());
+
+    let days = days.lock().unwrap();
+    assert_eq!(days[0].date, "2026-03-01");
+    assert!((days[0].realized_pnl - 7.5).abs() < 1e-9);
+    assert_eq!(days[0].unrealized_pnl, 0.0);
+    assert_eq!(days[0].trade_count, 2);
+    assert!((days[0].volume - 47.5).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_graceful_shutdown_cancels_orders() {
+    let mut mock_exec = MockOrderExec::new();
+
+    mock_exec
+        .expect_cancel_all_orders()
+        .times(1)
+        .returning(|| Ok(5));
+
+    let cancelled = mock_exec.cancel_all_orders().await.unwrap();
+    assert_eq!(cancelled, 5);
+}
+
+#[tokio::test]
+async fn test_fee_reconciler_flags_rate_gap_and_accrues_rebates() {
+    use polymarket_lmsr_bot::domain::trade::TradeSide;
+    use polymarket_lmsr_bot::ports::fees::TradeFill;
+    use polymarket_lmsr_bot::ports::repository::{DailyPnl, FeeReconciliation};
+    use polymarket_lmsr_bot::usecases::fee_reconciler::FeeReconciler;
+    use polymarket_lmsr_bot::usecases::fee_schedule::FeeSchedules;
+
+    let mut config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    config.fees.maker_rebate_share = 0.5;
+    let (m0, m1) = (config.markets[0].clone(), config.markets[1].clone());
+    let day_start_ms = 1_772_323_200_000; // 2026-03-01 00:00 UTC
+
+    // Taker fill charged the crypto rate on a standard market; maker fill as scheduled
+    let fills = vec![
+        TradeFill {
+            trade_id: "t1".to_string(),
+            token_id: m0.yes_token_id.clone(),
+            side: TradeSide::Buy,
+            price: 0.50,
+            size: 1000.0,
+            is_maker: false,
+            fee_rate_bps: 250,
+            timestamp_ms: day_start_ms + 1_000,
+        },
+        TradeFill {
+            trade_id: "t2:o1".to_string(),
+            token_id: m1.no_token_id.clone(),
+            side: TradeSide::Sell,
+            price: 0.40,
+            size: 100.0,
+            is_maker: true,
+            fee_rate_bps: 25,
+            timestamp_ms: day_start_ms + 2_000,
+        },
+    ];
+    let mut mock_source = MockFeeSrc::new();
+    mock_source.expect_fee_rate_bps().returning(|_| Ok(25));
+    mock_source
+        .expect_trades()
+        .with(eq(day_start_ms), eq(day_start_ms + 86_400_000))
+        .times(1)
+        .returning(move |_, _| Ok(fills.clone()));
+
+    let saved = Arc::new(std::sync::Mutex::new(Vec::<FeeReconciliation>::new()));
+    let saved_ref = Arc::clone(&saved);
+    let mut mock_repo = MockRepo::new();
+    mock_repo.expect_load_fee_reconciliations().times(1).returning(|| Ok(Vec::new()));
+    mock_repo.expect_load_daily_pnl().returning(|| {
+        Ok(vec![DailyPnl {
+            date: "2026-03-01".to_string(),
+            realized_pnl: 0.0,
+            unrealized_pnl: 0.0,
+            trade_count: 2,
+            volume: 540.0,
+            max_drawdown: 0.0,
+            fees: 0.15625,
+            rebates: 0.0072,
+        }])
+    });
+    mock_repo.expect_save_fee_reconciliation().times(1).returning(move |r| {
+        saved_ref.lock().unwrap().push(r.clone());
+        Ok(())
+    });
+
+    let schedules = Arc::new(FeeSchedules::new(&config));
+    let mut reconciler =
+        FeeReconciler::new(Arc::new(mock_source), Arc::new(mock_repo), schedules, &config);
+
+    let now_ms = day_start_ms + 86_400_000 + 3_600_000; // 2026-03-02 01:00 UTC
+    let record = reconciler.tick(now_ms).await.unwrap().unwrap();
+    assert!(reconciler.tick(now_ms + 3_600_000).await.unwrap().is_none());
+
+    assert_eq!(record.date, "2026-03-01");
+    assert!((record.expected_fees - 0.15625).abs() < 1e-9);
+    assert!((record.actual_fees - 1.5625).abs() < 1e-9);
+    assert!((record.rebates - 0.0072).abs() < 1e-9);
+    assert_eq!(record.booked_fees, Some(0.15625));
+
+    let flagged: Vec<_> = record.flagged().collect();
+    assert_eq!(flagged.len(), 1);
+    assert_eq!(flagged[0].market_id, m0.condition_id);
+    assert_eq!(flagged[0].rate_mismatches, 1);
+    assert_eq!(saved.lock().unwrap()[0], record);
+}
+
+#[tokio::test]
+async fn test_checkpointer_restores_orders_and_halts_on_chain_mismatch() {
+    use polymarket_lmsr_bot::domain::trade::{Order, TradeSide};
+    use polymarket_lmsr_bot::ports::chain_client::{ChainClient, TokenBalance};
+    use polymarket_lmsr_bot::ports::metrics::NoopMetrics;
+    use polymarket_lmsr_bot::ports::repository::{
+        BotStateSnapshot, HaltSource, OpenOrderState, STATE_SCHEMA_VERSION,
+    };
+    use polymarket_lmsr_bot::usecases::portfolio_ledger::PortfolioLedger;
+    use polymarket_lmsr_bot::usecases::risk_gate::RiskGate;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+    use polymarket_lmsr_bot::usecases::state_checkpoint::StateCheckpointer;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let (yes_token, no_token) = (
+        config.markets[0].yes_token_id.clone(),
+        config.markets[0].no_token_id.clone(),
+    );
+    let now_ms = 1_772_366_400_000; // 2026-03-01 12:00 UTC
+
+    // Last run: 10 of a 50-token bid filled and booked before the crash
+    let mut tracked = Order::new_maker(yes_token.clone(), TradeSide::Buy, 0.40, 50.0);
+    tracked.id = "ord_1".to_string();
+    let previous = PortfolioLedger::new(&config, Arc::new(NoopMetrics));
+    previous.record_fill(&yes_token, TradeSide::Buy, 0.40, 10.0, true).await;
+    let mut saved = BotStateSnapshot::new();
+    saved.open_orders = vec![OpenOrderState { order: tracked.clone(), filled: 10.0 }];
+    saved.portfolio = Some(previous.snapshot().await);
+
+    // While down: 20 more filled, and an order was placed after the last checkpoint
+    let mut untracked = Order::new_maker(no_token.clone(), TradeSide::Buy, 0.30, 5.0);
+    untracked.id = "ord_2".to_string();
+    let live = vec![Order { size: 20.0, ..tracked }, untracked];
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_get_open_orders().returning(move || Ok(live.clone()));
+
+    let states = Arc::new(std::sync::Mutex::new(vec![saved]));
+    let (load_ref, save_ref) = (Arc::clone(&states), Arc::clone(&states));
+    let mut mock_repo = MockRepo::new();
+    mock_repo
+        .expect_load_latest_state()
+        .returning(move || Ok(load_ref.lock().unwrap().last().cloned()));
+    mock_repo.expect_save_state().returning(move |s| {
+        save_ref.lock().unwrap().push(s.clone());
+        Ok(())
+    });
+
+    // The chain holds 25 YES where the ledger books 30
+    let chain_yes = yes_token.clone();
+    let mut mock_chain = MockChainCli::new();
+    mock_chain.expect_token_balances().returning(move |ids| {
+        Ok(ids
+            .iter()
+            .map(|id| TokenBalance {
+                token_id: id.clone(),
+                balance_raw: 0,
+                balance: if *id == chain_yes { 25.0 } else { 0.0 },
+            })
+            .collect())
+    });
+
+    let repo = Arc::new(mock_repo);
+    let risk = Arc::new(tokio::sync::RwLock::new(
+        RiskManager::new(&config.risk).with_markets(&config.markets),
+    ));
+    let ledger = Arc::new(
+        PortfolioLedger::new(&config, Arc::new(NoopMetrics)).with_risk(Arc::clone(&risk)),
+    );
+    assert!(ledger.restore(repo.as_ref()).await.unwrap());
+    let gate = Arc::new(
+        RiskGate::new(Arc::new(mock_exec), Arc::clone(&risk), Arc::new(NoopMetrics), &config)
+            .with_ledger(Arc::clone(&ledger)),
+    );
+    let checkpointer = StateCheckpointer::new(
+        Arc::clone(&gate),
+        Arc::clone(&risk),
+        Arc::clone(&ledger),
+        Arc::clone(&repo),
+        &config,
+    );
+
+    assert_eq!(checkpointer.restore().await.unwrap(), 1);
+    let chain: &dyn ChainClient = &mock_chain;
+    let report = checkpointer.verify(Some(chain), now_ms).await.unwrap();
+
+    // Fills made while down are credited once; the new order is adopted
+    assert_eq!(report.closed_orders, 0);
+    assert_eq!(report.adopted_orders, vec!["ord_2".to_string()]);
+    assert!((ledger.sizes().await[0].1 - 30.0).abs() < 1e-9);
+    assert_eq!(report.mismatches.len(), 1);
+    assert_eq!(report.mismatches[0].token_id, yes_token);
+    assert!(report.halted);
+    assert!(!risk.read().await.can_trade());
+    assert_eq!(
+        risk.read().await.halt_record().unwrap().source,
+        HaltSource::Recovery
+    );
+
+    // The checkpoint carries the orders, the ledger and the halt
+    let state = checkpointer.checkpoint(now_ms).await.unwrap();
+    assert_eq!(state.schema_version, STATE_SCHEMA_VERSION);
+    assert_eq!(state.open_orders.len(), 2);
+    assert_eq!(state.open_orders[0].order.id, "ord_1");
+    assert!((state.open_orders[0].filled - 30.0).abs() < 1e-9);
+    assert_eq!(state.positions, vec![(yes_token, 30.0)]);
+    assert!(state.risk.unwrap().halt.is_some());
+    assert_eq!(states.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_checkpoint_restores_realized_pnl_of_fills_and_merges() {
+    use polymarket_lmsr_bot::domain::trade::TradeSide;
+    use polymarket_lmsr_bot::ports::metrics::NoopMetrics;
+    use polymarket_lmsr_bot::usecases::portfolio_ledger::PortfolioLedger;
+    use polymarket_lmsr_bot::usecases::risk_gate::RiskGate;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+    use polymarket_lmsr_bot::usecases::state_checkpoint::StateCheckpointer;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let market = &config.markets[0];
+
+    let states = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let (load_ref, save_ref) = (Arc::clone(&states), Arc::clone(&states));
+    let mut mock_repo = MockRepo::new();
+    mock_repo
+        .expect_load_latest_state()
+        .returning(move || Ok(load_ref.lock().unwrap().last().cloned()));
+    mock_repo.expect_save_state().returning(move |s| {
+        save_ref.lock().unwrap().push(s.clone());
+        Ok(())
+    });
+    let repo = Arc::new(mock_repo);
+
+    // 20 sets bought at 0.40 + 0.50; 5 YES sold at 0.60, 10 sets merged
+    let risk = Arc::new(tokio::sync::RwLock::new(RiskManager::new(&config.risk)));
+    let ledger = Arc::new(PortfolioLedger::new(&config, Arc::new(NoopMetrics)));
+    ledger.record_fill(&market.yes_token_id, TradeSide::Buy, 0.40, 20.0, true).await;
+    ledger.record_fill(&market.no_token_id, TradeSide::Buy, 0.50, 20.0, true).await;
+    ledger.record_fill(&market.yes_token_id, TradeSide::Sell, 0.60, 5.0, true).await;
+    ledger.record_merge(&market.condition_id, 10.0).await;
+    let realized = ledger.cumulative_pnl().await;
+    assert!(realized > 1.0);
+
+    let gate = Arc::new(RiskGate::new(
+        Arc::new(MockOrderExec::new()),
+        Arc::clone(&risk),
+        Arc::new(NoopMetrics),
+        &config,
+    ));
+    let checkpointer =
+        StateCheckpointer::new(gate, risk, Arc::clone(&ledger), Arc::clone(&repo), &config);
+    let state = checkpointer.checkpoint(1_772_366_400_000).await.unwrap();
+    assert!((state.cumulative_pnl - realized).abs() < 1e-9);
+
+    let restarted = PortfolioLedger::new(&config, Arc::new(NoopMetrics));
+    assert!(restarted.restore(repo.as_ref()).await.unwrap());
+    assert!((restarted.cumulative_pnl().await - realized).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_checkpointer_replays_order_journal_after_crash() {
+    use polymarket_lmsr_bot::adapters::persistence::FileOrderJournal;
+    use polymarket_lmsr_bot::domain::trade::{Order, TradeSide};
+    use polymarket_lmsr_bot::ports::metrics::NoopMetrics;
+    use polymarket_lmsr_bot::ports::order_journal::{IntentOutcome, OrderJournal};
+    use polymarket_lmsr_bot::usecases::portfolio_ledger::PortfolioLedger;
+    use polymarket_lmsr_bot::usecases::risk_gate::RiskGate;
+    use polymarket_lmsr_bot::usecases::risk_manager::RiskManager;
+    use polymarket_lmsr_bot::usecases::state_checkpoint::StateCheckpointer;
+
+    let config =
+        polymarket_lmsr_bot::config::loader::load_config("config.toml.example").unwrap();
+    let (yes_token, no_token) = (
+        config.markets[0].yes_token_id.clone(),
+        config.markets[0].no_token_id.clone(),
+    );
+    let dir = std::env::temp_dir().join(format!("polybot-replay-{}", std::process::id()));
+    let journal = Arc::new(FileOrderJournal::new(dir.to_str().unwrap()).await.unwrap());
+
+    // Last run, no checkpoint since: one bid answered, two sends cut off
+    let salted = |token: &String, side, price, size, salt: &str| Order {
+        salt: salt.to_string(),
+        ..Order::new_maker(token.clone(), side, price, size)
+    };
+    let placed = salted(&yes_token, TradeSide::Buy, 0.40, 50.0, "c1");
+    let in_flight = salted(&no_token, TradeSide::Buy, 0.30, 5.0, "c2");
+    let lost = salted(&yes_token, TradeSide::Sell, 0.60, 8.0, "c3");
+    journal.record_intent("c1", &placed).await.unwrap();
+    journal
+        .record_outcome("c1", IntentOutcome::Placed { order_id: "ord_1".to_string() })
+        .await
+        .unwrap();
+    journal.record_intent("c2", &in_flight).await.unwrap();
+    journal.record_intent("c3", &lost).await.unwrap();
+
+    // The CLOB: ord_1 10 filled, c2 landed as ord_2, and a stray ord_3
+    // that looks like c3 but carries another salt
+    let stray = Order {
+        id: "ord_3".to_string(),
+        salt: "not-ours".to_string(),
+        ..lost.clone()
+    };
+    let live = vec![
+        Order { id: "ord_1".to_string(), size: 40.0, ..placed },
+        Order { id: "ord_2".to_string(), ..in_flight },
+        stray,
+    ];
+    let mut mock_exec = MockOrderExec::new();
+    mock_exec.expect_get_open_orders().returning(move || Ok(live.clone()));
+    mock_exec
+        .expect_cancel_order()
+        .with(eq("ord_3".to_string()))
+        .times(1)
+        .returning(|oid| {
+            Ok(polymarket_lmsr_bot::ports::execution::OrderCancellation {
+                order_id: oid.clone(),
+                success: true,
+                error: None,
+            })
+        });
+
+    let mut mock_repo = MockRepo::new();
+    mock_repo.expect_load_latest_state().returning(|| Ok(None));
+    mock_repo.expect_save_state().returning(|_| Ok(()));
+
+    let risk = Arc::new(tokio::sync::RwLock::new(
+        RiskManager::new(&config.risk).with_markets(&config.markets),
+    ));
+    let ledger = Arc::new(
+        PortfolioLedger::new(&config, Arc::new(NoopMetrics)).with_risk(Arc::clone(&risk)),
+    );
+    let gate = Arc::new(
+        RiskGate::new(Arc::new(mock_exec), Arc::clone(&risk), Arc::new(NoopMetrics), &config)
+            .with_ledger(Arc::clone(&ledger)),
+    );
+    let checkpointer = StateCheckpointer::new(
+        Arc::clone(&gate),
+        Arc::clone(&risk),
+        Arc::clone(&ledger),
+        Arc::new(mock_repo),
+        &config,
+    )
+    .with_journal(journal.clone());
+
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    let report = checkpointer.verify(None, now_ms).await.unwrap();
+
+    // Journaled orders adopted at their original size, fills credited
+    assert_eq!(report.adopted_orders, vec!["ord_1".to_string(), "ord_2".to_string()]);
+    assert_eq!(report.cancelled_orders, vec!["ord_3".to_string()]);
+    assert_eq!(report.abandoned_intents, 1);
+    assert_eq!(ledger.sizes().await, vec![(yes_token, 10.0)]);
+    let tracked = gate.open_orders().await;
+    assert_eq!(tracked.len(), 2);
+    assert!((tracked[0].order.size - 50.0).abs() < 1e-9);
+    assert!((tracked[0].filled - 10.0).abs() < 1e-9);
+
+    // Every intent is resolved; a later checkpoint compacts them away
+    let entries = journal.entries().await.unwrap();
+    assert_eq!(entries[1].order_id(), Some("ord_2"));
+    assert!(matches!(entries[2].outcome, Some(IntentOutcome::Rejected { .. })));
+    checkpointer.checkpoint(now_ms + 120_000).await.unwrap();
+    assert!(journal.entries().await.unwrap().is_empty());
+
+    let _ = std::fs::remove_dir_all(&dir);
 }
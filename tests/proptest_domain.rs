@@ -4,6 +4,7 @@
 //! mathematical invariants across random inputs.
 
 use proptest::prelude::*;
+use rust_decimal::prelude::*;
 
 use polymarket_lmsr_bot::domain::fees::FeeCalculator;
 use polymarket_lmsr_bot::domain::kelly::KellySizer;
@@ -49,9 +50,10 @@ proptest! {
     #[test]
     fn maker_fee_always_zero(p in 0.01f64..0.99) {
         let fees = FeeCalculator::new_maker();
-        let fee = fees.maker_fee(p);
+        let price = Decimal::from_f64(p).unwrap();
+        let fee = fees.maker_fee(price, Decimal::ONE);
         prop_assert!(
-            fee.abs() < f64::EPSILON,
+            fee.is_zero(),
             "Maker fee should be 0, got {fee}"
         );
     }
@@ -59,8 +61,8 @@ proptest! {
     /// Taker fee must be non-negative and <= 1.56% (max at p=0.50).
     #[test]
     fn taker_fee_bounded(p in 0.01f64..0.99) {
-        let fees = FeeCalculator::new_taker();
-        let fee = fees.taker_fee(p);
+        let fees = FeeCalculator::standard();
+        let fee = fees.taker_fee_f64(p, 1.0);
         prop_assert!(fee >= 0.0, "Taker fee must be >= 0, got {fee}");
         // Max fee = 0.25 × 0.5² × 0.5² = 0.015625 ≈ 1.56%
         prop_assert!(